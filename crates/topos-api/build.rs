@@ -81,6 +81,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             ".topos.uci.v1.Certificate",
             "#[derive(Eq, Hash, serde::Deserialize, serde::Serialize)]",
         )
+        .type_attribute(
+            ".topos.uci.v1.BlockRange",
+            "#[derive(Eq, Hash, serde::Deserialize, serde::Serialize)]",
+        )
         .out_dir("src/grpc/generated")
         .compile(
             &[
@@ -92,6 +96,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/topos/tce/v1/synchronization.proto",
                 "proto/topos/tce/v1/double_echo.proto",
                 "proto/topos/tce/v1/gossipsub.proto",
+                "proto/topos/tce/v1/admin.proto",
                 "proto/topos/uci/v1/certification.proto",
                 "proto/topos/p2p/info.proto",
             ],
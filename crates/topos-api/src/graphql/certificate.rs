@@ -20,6 +20,7 @@ pub struct Certificate {
     pub target_subnets: Vec<SubnetId>,
     pub tx_root_hash: String,
     pub receipts_root_hash: String,
+    pub messages_root: String,
     pub verifier: u32,
 }
 
@@ -35,6 +36,7 @@ impl From<&topos_uci::Certificate> for Certificate {
             target_subnets: uci_cert.target_subnets.iter().map(SubnetId::from).collect(),
             tx_root_hash: hex::encode(uci_cert.tx_root_hash),
             receipts_root_hash: format!("0x{}", hex::encode(uci_cert.receipts_root_hash)),
+            messages_root: format!("0x{}", hex::encode(uci_cert.messages_root)),
             verifier: uci_cert.verifier,
         }
     }
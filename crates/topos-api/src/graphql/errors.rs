@@ -20,4 +20,7 @@ pub enum GraphQLServerError {
 
     #[error("Internal API error: {0}")]
     InternalError(&'static str),
+
+    #[error("Data pruned by the node: {0}")]
+    Pruned(String),
 }
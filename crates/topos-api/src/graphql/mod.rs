@@ -3,4 +3,5 @@ pub mod checkpoint;
 pub mod errors;
 pub mod filter;
 pub mod query;
+pub mod status;
 pub mod subnet;
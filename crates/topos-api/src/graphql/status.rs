@@ -0,0 +1,65 @@
+use async_graphql::{Enum, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use super::{errors::GraphQLServerError, subnet::SubnetId};
+
+/// State of the synchronization of the node with its peers
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Enum, PartialEq, Eq)]
+pub enum SyncState {
+    /// Catching up with the certificates delivered by the peers
+    Syncing,
+    /// Every certificate known to be delivered by a peer is delivered locally
+    Synced,
+    /// Behind the peers without making any progress
+    Stalled,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct SubnetSyncStatus {
+    pub subnet_id: SubnetId,
+    /// Position of the local source head, unset if nothing is delivered yet
+    pub local_position: Option<u64>,
+    /// Highest position known to be delivered by a peer
+    pub best_known_position: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct SyncStatus {
+    pub state: SyncState,
+    pub subnets: Vec<SubnetSyncStatus>,
+}
+
+#[cfg(feature = "grpc")]
+impl TryFrom<crate::grpc::tce::v1::SyncStatus> for SyncStatus {
+    type Error = GraphQLServerError;
+
+    fn try_from(value: crate::grpc::tce::v1::SyncStatus) -> Result<Self, Self::Error> {
+        use crate::grpc::tce::v1::SyncState as GrpcSyncState;
+
+        let state = match value.state() {
+            GrpcSyncState::Synced => SyncState::Synced,
+            GrpcSyncState::Stalled => SyncState::Stalled,
+            GrpcSyncState::Syncing | GrpcSyncState::Unspecified => SyncState::Syncing,
+        };
+
+        let subnets = value
+            .subnets
+            .into_iter()
+            .map(|status| {
+                let subnet_id: topos_uci::SubnetId = status
+                    .subnet_id
+                    .ok_or(GraphQLServerError::ParseSubnetId)?
+                    .try_into()
+                    .map_err(|_| GraphQLServerError::ParseSubnetId)?;
+
+                Ok(SubnetSyncStatus {
+                    subnet_id: (&subnet_id).into(),
+                    local_position: status.local_position,
+                    best_known_position: status.best_known_position,
+                })
+            })
+            .collect::<Result<_, GraphQLServerError>>()?;
+
+        Ok(Self { state, subnets })
+    }
+}
@@ -1,7 +1,7 @@
 use crate::grpc::tce::v1::{
-    watch_certificates_request::{Command, OpenStream},
+    watch_certificates_request::{Acknowledge, Command, OpenStream},
     watch_certificates_response::{CertificatePushed, Event, StreamOpened},
-    WatchCertificatesRequest, WatchCertificatesResponse,
+    SubmitCertificateResult, WatchCertificatesRequest, WatchCertificatesResponse,
 };
 
 macro_rules! impl_command_conversion {
@@ -31,6 +31,14 @@ macro_rules! impl_event_conversion {
 }
 
 impl_command_conversion!(OpenStream);
+impl_command_conversion!(Acknowledge);
 
 impl_event_conversion!(StreamOpened);
 impl_event_conversion!(CertificatePushed);
+
+impl SubmitCertificateResult {
+    /// Whether the certificate has been refused by the node, submitting it again won't help
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Self::InvalidId | Self::SourceSubnetNotAllowed)
+    }
+}
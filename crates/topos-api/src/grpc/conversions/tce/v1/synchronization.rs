@@ -2,6 +2,7 @@ use prost::{bytes::Bytes, Message};
 
 use crate::grpc::tce::v1::{
     CheckpointRequest, CheckpointResponse, FetchCertificatesRequest, FetchCertificatesResponse,
+    SnapshotChunkRequest, SnapshotChunkResponse, SnapshotRequest, SnapshotResponse,
 };
 
 use crate::grpc::ConversionError;
@@ -37,12 +38,20 @@ impl_to_vec_conversion!(
     CheckpointRequest,
     CheckpointResponse,
     FetchCertificatesRequest,
-    FetchCertificatesResponse
+    FetchCertificatesResponse,
+    SnapshotRequest,
+    SnapshotResponse,
+    SnapshotChunkRequest,
+    SnapshotChunkResponse
 );
 
 impl_from_vec_conversion!(
     CheckpointResponse,
     CheckpointRequest,
     FetchCertificatesRequest,
-    FetchCertificatesResponse
+    FetchCertificatesResponse,
+    SnapshotRequest,
+    SnapshotResponse,
+    SnapshotChunkRequest,
+    SnapshotChunkResponse
 );
@@ -33,6 +33,32 @@ impl TryFrom<proto_v1::Certificate> for topos_uci::Certificate {
                 .receipts_root_hash
                 .try_into()
                 .map_err(|_| Error::InvalidReceiptsRootHash)?,
+            // Certificates of subnets without cross-subnet messages may leave it empty
+            messages_root: if certificate.messages_root.is_empty() {
+                Default::default()
+            } else {
+                certificate.messages_root.try_into().map_err(|_| {
+                    Error::UCI(topos_uci::Error::ValidationError(
+                        "invalid messages root".to_string(),
+                    ))
+                })?
+            },
+            block_range: certificate
+                .block_range
+                .map(|range| {
+                    if range.start > range.end {
+                        return Err(Error::UCI(topos_uci::Error::ValidationError(format!(
+                            "invalid block range {}..={}",
+                            range.start, range.end
+                        ))));
+                    }
+
+                    Ok(topos_uci::BlockRange {
+                        start: range.start,
+                        end: range.end,
+                    })
+                })
+                .transpose()?,
             target_subnets: certificate
                 .target_subnets
                 .into_iter()
@@ -63,6 +89,11 @@ impl From<topos_uci::Certificate> for proto_v1::Certificate {
             state_root: certificate.state_root.to_vec(),
             tx_root_hash: certificate.tx_root_hash.to_vec(),
             receipts_root_hash: certificate.receipts_root_hash.to_vec(),
+            messages_root: certificate.messages_root.to_vec(),
+            block_range: certificate.block_range.map(|range| proto_v1::BlockRange {
+                start: range.start,
+                end: range.end,
+            }),
             verifier: certificate.verifier,
             target_subnets: certificate
                 .target_subnets
@@ -127,6 +158,8 @@ fn test_proto_uci_certificate_conversion_id_random_0x() {
                 240, 230, 103, 81, 227, 99, 241, 130, 157, 188,
             ],
         }),
+        messages_root: Vec::new(),
+        block_range: None,
     };
     if let Err(e) = topos_uci::Certificate::try_from(valid_cert) {
         panic!("Unable to perform certificate conversion: {e}");
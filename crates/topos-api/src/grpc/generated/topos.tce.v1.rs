@@ -7,6 +7,10 @@ pub struct CheckpointRequest {
     pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
     #[prost(message, repeated, tag = "2")]
     pub checkpoint: ::prost::alloc::vec::Vec<ProofOfDelivery>,
+    /// Maximum number of proofs of delivery returned for each subnet, the default of the
+    /// peer applies if unset
+    #[prost(uint64, tag = "3")]
+    pub limit: u64,
 }
 #[derive(serde::Deserialize, serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -67,6 +71,59 @@ pub struct SignedReady {
     #[prost(string, tag = "2")]
     pub signature: ::prost::alloc::string::String,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnapshotRequest {
+    /// Provide a request_id to track response
+    #[prost(message, optional, tag = "1")]
+    pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnapshotResponse {
+    /// If the response is directly linked to a request this ID allow one to track it
+    #[prost(message, optional, tag = "1")]
+    pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
+    /// Proofs of delivery of the head of every source stream of the snapshot
+    #[prost(message, repeated, tag = "2")]
+    pub heads: ::prost::alloc::vec::Vec<ProofOfDelivery>,
+    /// Set when the first available position of one of the source streams is above 0, the
+    /// pruned beginning of the stream can't be served
+    #[prost(bool, tag = "3")]
+    pub pruned: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnapshotChunkRequest {
+    /// Provide a request_id to track response
+    #[prost(message, optional, tag = "1")]
+    pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
+    #[prost(message, optional, tag = "2")]
+    pub source_subnet_id: ::core::option::Option<super::super::shared::v1::SubnetId>,
+    /// Position of the first certificate of the chunk in the source stream
+    #[prost(uint64, tag = "3")]
+    pub from_position: u64,
+    /// Maximum number of certificates of the chunk
+    #[prost(uint64, tag = "4")]
+    pub limit: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnapshotChunkResponse {
+    /// If the response is directly linked to a request this ID allow one to track it
+    #[prost(message, optional, tag = "1")]
+    pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
+    #[prost(message, repeated, tag = "2")]
+    pub certificates: ::prost::alloc::vec::Vec<CertificateDelivered>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CertificateDelivered {
+    #[prost(message, optional, tag = "1")]
+    pub certificate: ::core::option::Option<super::super::uci::v1::Certificate>,
+    #[prost(message, optional, tag = "2")]
+    pub proof_of_delivery: ::core::option::Option<ProofOfDelivery>,
+}
 /// Generated client implementations.
 pub mod synchronizer_service_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -212,6 +269,50 @@ pub mod synchronizer_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn fetch_snapshot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SnapshotRequest>,
+        ) -> std::result::Result<tonic::Response<super::SnapshotResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.SynchronizerService/fetch_snapshot",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.SynchronizerService", "fetch_snapshot"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn fetch_snapshot_chunk(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SnapshotChunkRequest>,
+        ) -> std::result::Result<tonic::Response<super::SnapshotChunkResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.SynchronizerService/fetch_snapshot_chunk",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.SynchronizerService", "fetch_snapshot_chunk"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -235,6 +336,14 @@ pub mod synchronizer_service_server {
             tonic::Response<super::FetchCertificatesResponse>,
             tonic::Status,
         >;
+        async fn fetch_snapshot(
+            &self,
+            request: tonic::Request<super::SnapshotRequest>,
+        ) -> std::result::Result<tonic::Response<super::SnapshotResponse>, tonic::Status>;
+        async fn fetch_snapshot_chunk(
+            &self,
+            request: tonic::Request<super::SnapshotChunkRequest>,
+        ) -> std::result::Result<tonic::Response<super::SnapshotChunkResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct SynchronizerServiceServer<T: SynchronizerService> {
@@ -415,6 +524,98 @@ pub mod synchronizer_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/topos.tce.v1.SynchronizerService/fetch_snapshot" => {
+                    #[allow(non_camel_case_types)]
+                    struct fetch_snapshotSvc<T: SynchronizerService>(pub Arc<T>);
+                    impl<
+                        T: SynchronizerService,
+                    > tonic::server::UnaryService<super::SnapshotRequest>
+                    for fetch_snapshotSvc<T> {
+                        type Response = super::SnapshotResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SnapshotRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SynchronizerService>::fetch_snapshot(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = fetch_snapshotSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.SynchronizerService/fetch_snapshot_chunk" => {
+                    #[allow(non_camel_case_types)]
+                    struct fetch_snapshot_chunkSvc<T: SynchronizerService>(pub Arc<T>);
+                    impl<
+                        T: SynchronizerService,
+                    > tonic::server::UnaryService<super::SnapshotChunkRequest>
+                    for fetch_snapshot_chunkSvc<T> {
+                        type Response = super::SnapshotChunkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SnapshotChunkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SynchronizerService>::fetch_snapshot_chunk(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = fetch_snapshot_chunkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -465,7 +666,18 @@ pub struct SubmitCertificateRequest {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SubmitCertificateResponse {}
+pub struct SubmitCertificateResponse {
+    #[prost(enumeration = "SubmitCertificateResult", tag = "1")]
+    pub result: i32,
+    /// Id of the certificate in the pending pool, if accepted
+    #[prost(uint64, optional, tag = "2")]
+    pub pending_id: ::core::option::Option<u64>,
+    /// Certificate to be delivered first, if placed in the precedence pool
+    #[prost(message, optional, tag = "3")]
+    pub prev_certificate_id: ::core::option::Option<
+        super::super::super::shared::v1::CertificateId,
+    >,
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetSourceHeadRequest {
@@ -515,7 +727,7 @@ pub struct WatchCertificatesRequest {
     #[prost(message, optional, tag = "1")]
     pub request_id: ::core::option::Option<super::super::shared::v1::Uuid>,
     /// Define which command needs to be performed
-    #[prost(oneof = "watch_certificates_request::Command", tags = "2")]
+    #[prost(oneof = "watch_certificates_request::Command", tags = "2, 3")]
     pub command: ::core::option::Option<watch_certificates_request::Command>,
 }
 /// Nested message and enum types in `WatchCertificatesRequest`.
@@ -532,6 +744,25 @@ pub mod watch_certificates_request {
         pub source_checkpoint: ::core::option::Option<
             super::super::super::shared::v1::checkpoints::SourceCheckpoint,
         >,
+        /// Only push the certificates coming from these source subnets, every source if empty
+        #[prost(message, repeated, tag = "3")]
+        pub source_subnet_ids: ::prost::alloc::vec::Vec<
+            super::super::super::shared::v1::SubnetId,
+        >,
+        /// Push the certificates without their proof and signature
+        #[prost(bool, tag = "4")]
+        pub headers_only: bool,
+        /// Number of certificates that can be pushed before waiting for an acknowledgement,
+        /// no flow control is applied if not set
+        #[prost(uint32, optional, tag = "5")]
+        pub window_size: ::core::option::Option<u32>,
+    }
+    /// Sent to grant the TCE the right to push more certificates on the stream
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Acknowledge {
+        #[prost(uint32, tag = "1")]
+        pub credits: u32,
     }
     /// Define which command needs to be performed
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -539,6 +770,8 @@ pub mod watch_certificates_request {
     pub enum Command {
         #[prost(message, tag = "2")]
         OpenStream(OpenStream),
+        #[prost(message, tag = "3")]
+        Acknowledge(Acknowledge),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -584,6 +817,157 @@ pub mod watch_certificates_response {
         CertificatePushed(CertificatePushed),
     }
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCertificateStatusRequest {
+    #[prost(message, optional, tag = "1")]
+    pub certificate_id: ::core::option::Option<super::super::shared::v1::CertificateId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCertificateStatusResponse {
+    #[prost(message, optional, tag = "1")]
+    pub status: ::core::option::Option<CertificateStatus>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitForDeliveryRequest {
+    #[prost(message, optional, tag = "1")]
+    pub certificate_id: ::core::option::Option<super::super::shared::v1::CertificateId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitForDeliveryResponse {
+    #[prost(message, optional, tag = "1")]
+    pub status: ::core::option::Option<CertificateStatus>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CertificateStatus {
+    #[prost(oneof = "certificate_status::Status", tags = "1, 2, 3, 4, 5, 6")]
+    pub status: ::core::option::Option<certificate_status::Status>,
+}
+/// Nested message and enum types in `CertificateStatus`.
+pub mod certificate_status {
+    /// The certificate is not known by the node
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Unknown {}
+    /// The certificate waits for the delivery of its previous certificate
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct PrecedencePool {
+        #[prost(message, optional, tag = "1")]
+        pub prev_certificate_id: ::core::option::Option<
+            super::super::super::shared::v1::CertificateId,
+        >,
+    }
+    /// The certificate waits in the pending pool for its broadcast to start
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Pending {
+        #[prost(uint64, tag = "1")]
+        pub pending_id: u64,
+    }
+    /// The certificate is being broadcast
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Broadcasting {
+        #[prost(uint64, tag = "1")]
+        pub echo_count: u64,
+        #[prost(uint64, tag = "2")]
+        pub ready_count: u64,
+        #[prost(uint64, tag = "3")]
+        pub echo_threshold: u64,
+        #[prost(uint64, tag = "4")]
+        pub ready_threshold: u64,
+        #[prost(uint64, tag = "5")]
+        pub delivery_threshold: u64,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Delivered {
+        /// Position of the certificate in its source stream
+        #[prost(uint64, tag = "1")]
+        pub position: u64,
+        #[prost(message, optional, tag = "2")]
+        pub proof_of_delivery: ::core::option::Option<super::ProofOfDelivery>,
+    }
+    /// The broadcast of the certificate failed, it can be submitted again
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Failed {}
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Status {
+        #[prost(message, tag = "1")]
+        Unknown(Unknown),
+        #[prost(message, tag = "2")]
+        PrecedencePool(PrecedencePool),
+        #[prost(message, tag = "3")]
+        Pending(Pending),
+        #[prost(message, tag = "4")]
+        Broadcasting(Broadcasting),
+        #[prost(message, tag = "5")]
+        Delivered(Delivered),
+        #[prost(message, tag = "6")]
+        Failed(Failed),
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SubmitCertificateResult {
+    Unspecified = 0,
+    /// The certificate is in the pending pool, waiting to be broadcast
+    Accepted = 1,
+    /// The certificate waits for the delivery of its previous certificate
+    PrecedencePool = 2,
+    AlreadyDelivered = 3,
+    /// The certificate is already in the pending or precedence pool
+    Duplicate = 4,
+    /// The certificate id doesn't match its content
+    InvalidId = 5,
+    /// The node doesn't accept certificates from this source subnet
+    SourceSubnetNotAllowed = 6,
+}
+impl SubmitCertificateResult {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SubmitCertificateResult::Unspecified => "SUBMIT_CERTIFICATE_RESULT_UNSPECIFIED",
+            SubmitCertificateResult::Accepted => "SUBMIT_CERTIFICATE_RESULT_ACCEPTED",
+            SubmitCertificateResult::PrecedencePool => {
+                "SUBMIT_CERTIFICATE_RESULT_PRECEDENCE_POOL"
+            }
+            SubmitCertificateResult::AlreadyDelivered => {
+                "SUBMIT_CERTIFICATE_RESULT_ALREADY_DELIVERED"
+            }
+            SubmitCertificateResult::Duplicate => "SUBMIT_CERTIFICATE_RESULT_DUPLICATE",
+            SubmitCertificateResult::InvalidId => "SUBMIT_CERTIFICATE_RESULT_INVALID_ID",
+            SubmitCertificateResult::SourceSubnetNotAllowed => {
+                "SUBMIT_CERTIFICATE_RESULT_SOURCE_SUBNET_NOT_ALLOWED"
+            }
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SUBMIT_CERTIFICATE_RESULT_UNSPECIFIED" => Some(Self::Unspecified),
+            "SUBMIT_CERTIFICATE_RESULT_ACCEPTED" => Some(Self::Accepted),
+            "SUBMIT_CERTIFICATE_RESULT_PRECEDENCE_POOL" => Some(Self::PrecedencePool),
+            "SUBMIT_CERTIFICATE_RESULT_ALREADY_DELIVERED" => Some(Self::AlreadyDelivered),
+            "SUBMIT_CERTIFICATE_RESULT_DUPLICATE" => Some(Self::Duplicate),
+            "SUBMIT_CERTIFICATE_RESULT_INVALID_ID" => Some(Self::InvalidId),
+            "SUBMIT_CERTIFICATE_RESULT_SOURCE_SUBNET_NOT_ALLOWED" => {
+                Some(Self::SourceSubnetNotAllowed)
+            }
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod api_service_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -782,6 +1166,58 @@ pub mod api_service_client {
                 .insert(GrpcMethod::new("topos.tce.v1.APIService", "WatchCertificates"));
             self.inner.streaming(req, path, codec).await
         }
+        /// Report where a certificate stands, from its submission to its delivery
+        pub async fn get_certificate_status(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetCertificateStatusRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetCertificateStatusResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.APIService/GetCertificateStatus",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.APIService", "GetCertificateStatus"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Stream the status of a certificate every time it changes, until it is delivered
+        /// or its broadcast failed
+        /// Fails with NOT_FOUND if the certificate stays unknown to the node for a few seconds,
+        /// and with DEADLINE_EXCEEDED if it isn't delivered after a few minutes
+        pub async fn wait_for_delivery(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WaitForDeliveryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::WaitForDeliveryResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.APIService/WaitForDelivery",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.APIService", "WaitForDelivery"));
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -834,6 +1270,25 @@ pub mod api_service_server {
             tonic::Response<Self::WatchCertificatesStream>,
             tonic::Status,
         >;
+        /// Report where a certificate stands, from its submission to its delivery
+        async fn get_certificate_status(
+            &self,
+            request: tonic::Request<super::GetCertificateStatusRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetCertificateStatusResponse>, tonic::Status>;
+        /// Server streaming response type for the WaitForDelivery method.
+        type WaitForDeliveryStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::WaitForDeliveryResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Stream the status of a certificate every time it changes, until it is delivered
+        /// or its broadcast failed
+        /// Fails with NOT_FOUND if the certificate stays unknown to the node for a few seconds,
+        /// and with DEADLINE_EXCEEDED if it isn't delivered after a few minutes
+        async fn wait_for_delivery(
+            &self,
+            request: tonic::Request<super::WaitForDeliveryRequest>,
+        ) -> std::result::Result<tonic::Response<Self::WaitForDeliveryStream>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct ApiServiceServer<T: ApiService> {
@@ -1108,8 +1563,101 @@ pub mod api_service_server {
                     };
                     Box::pin(fut)
                 }
-                _ => {
-                    Box::pin(async move {
+                "/topos.tce.v1.APIService/GetCertificateStatus" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetCertificateStatusSvc<T: ApiService>(pub Arc<T>);
+                    impl<
+                        T: ApiService,
+                    > tonic::server::UnaryService<super::GetCertificateStatusRequest>
+                    for GetCertificateStatusSvc<T> {
+                        type Response = super::GetCertificateStatusResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCertificateStatusRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ApiService>::get_certificate_status(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetCertificateStatusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.APIService/WaitForDelivery" => {
+                    #[allow(non_camel_case_types)]
+                    struct WaitForDeliverySvc<T: ApiService>(pub Arc<T>);
+                    impl<
+                        T: ApiService,
+                    > tonic::server::ServerStreamingService<super::WaitForDeliveryRequest>
+                    for WaitForDeliverySvc<T> {
+                        type Response = super::WaitForDeliveryResponse;
+                        type ResponseStream = T::WaitForDeliveryStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WaitForDeliveryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ApiService>::wait_for_delivery(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WaitForDeliverySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
                         Ok(
                             http::Response::builder()
                                 .status(200)
@@ -1157,6 +1705,122 @@ pub struct StatusRequest {}
 pub struct StatusResponse {
     #[prost(bool, tag = "1")]
     pub has_active_sample: bool,
+    /// Synchronization of the node with its peers, unset until the first synchronization
+    #[prost(message, optional, tag = "2")]
+    pub sync_status: ::core::option::Option<SyncStatus>,
+    #[prost(enumeration = "NodeRole", tag = "3")]
+    pub role: i32,
+    /// Address of the signing key of the node
+    #[prost(string, tag = "4")]
+    pub validator_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub peer_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "6")]
+    pub connected_peers: u64,
+    #[prost(uint64, tag = "7")]
+    pub epoch: u64,
+    /// Estimated number of certificates in the storage, from the sizes of the tables
+    #[prost(uint64, tag = "8")]
+    pub delivered_certificates: u64,
+    #[prost(uint64, tag = "9")]
+    pub pending_certificates: u64,
+    #[prost(uint64, tag = "10")]
+    pub precedence_pool_certificates: u64,
+    /// Number of certificates being broadcast by the double echo
+    #[prost(uint64, tag = "11")]
+    pub active_broadcast_tasks: u64,
+    #[prost(string, tag = "12")]
+    pub version: ::prost::alloc::string::String,
+}
+/// Synchronization of the delivered certificates of the node with its peers
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncStatus {
+    #[prost(enumeration = "SyncState", tag = "1")]
+    pub state: i32,
+    #[prost(message, repeated, tag = "2")]
+    pub subnets: ::prost::alloc::vec::Vec<SubnetSyncStatus>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubnetSyncStatus {
+    #[prost(message, optional, tag = "1")]
+    pub subnet_id: ::core::option::Option<super::super::shared::v1::SubnetId>,
+    /// Position of the local source head, unset if nothing is delivered yet
+    #[prost(uint64, optional, tag = "2")]
+    pub local_position: ::core::option::Option<u64>,
+    /// Highest position known to be delivered by a peer
+    #[prost(uint64, optional, tag = "3")]
+    pub best_known_position: ::core::option::Option<u64>,
+    /// Number of certificates known to be delivered by a peer but not locally
+    #[prost(uint64, tag = "4")]
+    pub missing_certificates: u64,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum NodeRole {
+    Unspecified = 0,
+    /// Member of the validator set, taking part in the broadcast
+    Validator = 1,
+    /// Following the broadcast without being part of the validator set
+    FullNode = 2,
+}
+impl NodeRole {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            NodeRole::Unspecified => "NODE_ROLE_UNSPECIFIED",
+            NodeRole::Validator => "NODE_ROLE_VALIDATOR",
+            NodeRole::FullNode => "NODE_ROLE_FULL_NODE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NODE_ROLE_UNSPECIFIED" => Some(Self::Unspecified),
+            "NODE_ROLE_VALIDATOR" => Some(Self::Validator),
+            "NODE_ROLE_FULL_NODE" => Some(Self::FullNode),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SyncState {
+    Unspecified = 0,
+    /// The node is catching up with the certificates delivered by its peers
+    Syncing = 1,
+    /// The node holds every certificate known to be delivered by its peers
+    Synced = 2,
+    /// The node is behind its peers and does not make progress
+    Stalled = 3,
+}
+impl SyncState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SyncState::Unspecified => "SYNC_STATE_UNSPECIFIED",
+            SyncState::Syncing => "SYNC_STATE_SYNCING",
+            SyncState::Synced => "SYNC_STATE_SYNCED",
+            SyncState::Stalled => "SYNC_STATE_STALLED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SYNC_STATE_UNSPECIFIED" => Some(Self::Unspecified),
+            "SYNC_STATE_SYNCING" => Some(Self::Syncing),
+            "SYNC_STATE_SYNCED" => Some(Self::Synced),
+            "SYNC_STATE_STALLED" => Some(Self::Stalled),
+            _ => None,
+        }
+    }
 }
 /// Generated client implementations.
 pub mod console_service_client {
@@ -1502,3 +2166,916 @@ pub struct Batch {
     #[prost(bytes = "vec", repeated, tag = "1")]
     pub messages: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPendingCertificatesRequest {
+    /// Only list the certificates of this subnet if set
+    #[prost(message, optional, tag = "1")]
+    pub subnet_id: ::core::option::Option<super::super::shared::v1::SubnetId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPendingCertificatesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub certificates: ::prost::alloc::vec::Vec<PendingCertificate>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PendingCertificate {
+    #[prost(uint64, tag = "1")]
+    pub pending_id: u64,
+    #[prost(message, optional, tag = "2")]
+    pub certificate: ::core::option::Option<super::super::uci::v1::Certificate>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvictPendingCertificateRequest {
+    #[prost(message, optional, tag = "1")]
+    pub certificate_id: ::core::option::Option<super::super::shared::v1::CertificateId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvictPendingCertificateResponse {
+    /// Identifier of the certificate in the pending pool before its eviction
+    #[prost(uint64, tag = "1")]
+    pub pending_id: u64,
+    /// Certificates of the precedence pool evicted because they depend on the evicted certificate
+    #[prost(message, repeated, tag = "2")]
+    pub evicted_dependents: ::prost::alloc::vec::Vec<super::super::shared::v1::CertificateId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResyncFromPeerRequest {
+    #[prost(string, tag = "1")]
+    pub peer_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResyncFromPeerResponse {
+    /// Number of certificates delivered by the synchronization
+    #[prost(uint64, tag = "1")]
+    pub synchronized_certificates: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBroadcastStateRequest {
+    #[prost(message, optional, tag = "1")]
+    pub certificate_id: ::core::option::Option<super::super::shared::v1::CertificateId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBroadcastStateResponse {
+    #[prost(message, optional, tag = "1")]
+    pub state: ::core::option::Option<BroadcastState>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BroadcastState {
+    #[prost(message, optional, tag = "1")]
+    pub certificate_id: ::core::option::Option<super::super::shared::v1::CertificateId>,
+    #[prost(enumeration = "BroadcastStatus", tag = "2")]
+    pub status: i32,
+    /// The broadcast is waiting for the delivery of the previous certificate
+    #[prost(bool, tag = "3")]
+    pub awaiting_previous_certificate: bool,
+    #[prost(uint64, tag = "4")]
+    pub network_size: u64,
+    #[prost(uint64, tag = "5")]
+    pub echo_threshold: u64,
+    #[prost(uint64, tag = "6")]
+    pub ready_threshold: u64,
+    #[prost(uint64, tag = "7")]
+    pub delivery_threshold: u64,
+    /// Validators which did not send an echo yet
+    #[prost(message, repeated, tag = "8")]
+    pub missing_echoes: ::prost::alloc::vec::Vec<super::super::shared::v1::ValidatorId>,
+    /// Validators which did not send a ready yet
+    #[prost(message, repeated, tag = "9")]
+    pub missing_readies: ::prost::alloc::vec::Vec<super::super::shared::v1::ValidatorId>,
+    /// Position of the certificate in its source stream, once known
+    #[prost(uint64, optional, tag = "10")]
+    pub position: ::core::option::Option<u64>,
+    /// Time elapsed since the start of the broadcast, in milliseconds
+    #[prost(uint64, tag = "11")]
+    pub elapsed_ms: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetLogFilterRequest {
+    /// Filter directives, using the syntax of `RUST_LOG`
+    #[prost(string, tag = "1")]
+    pub filter: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetLogFilterResponse {
+    #[prost(string, tag = "1")]
+    pub previous_filter: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompactStorageRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompactStorageResponse {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BackupRequest {
+    /// Path of the backup on the node filesystem, it must not exist yet
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BackupResponse {
+    /// Number of certificates delivered in the backup
+    #[prost(uint64, tag = "1")]
+    pub delivered_certificates: u64,
+    /// Source head of every subnet known in the backup
+    #[prost(message, repeated, tag = "2")]
+    pub source_heads: ::prost::alloc::vec::Vec<
+        super::super::shared::v1::positions::SourceStreamPosition,
+    >,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum BroadcastStatus {
+    Unspecified = 0,
+    Pending = 1,
+    EchoSent = 2,
+    ReadySent = 3,
+    /// Delivered once enough readies were received, after sending its own ready
+    DeliveredWithReadySent = 4,
+    Delivered = 5,
+    /// The broadcast stopped without delivering the certificate
+    Failed = 6,
+}
+impl BroadcastStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            BroadcastStatus::Unspecified => "BROADCAST_STATUS_UNSPECIFIED",
+            BroadcastStatus::Pending => "BROADCAST_STATUS_PENDING",
+            BroadcastStatus::EchoSent => "BROADCAST_STATUS_ECHO_SENT",
+            BroadcastStatus::ReadySent => "BROADCAST_STATUS_READY_SENT",
+            BroadcastStatus::DeliveredWithReadySent => {
+                "BROADCAST_STATUS_DELIVERED_WITH_READY_SENT"
+            }
+            BroadcastStatus::Delivered => "BROADCAST_STATUS_DELIVERED",
+            BroadcastStatus::Failed => "BROADCAST_STATUS_FAILED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BROADCAST_STATUS_UNSPECIFIED" => Some(Self::Unspecified),
+            "BROADCAST_STATUS_PENDING" => Some(Self::Pending),
+            "BROADCAST_STATUS_ECHO_SENT" => Some(Self::EchoSent),
+            "BROADCAST_STATUS_READY_SENT" => Some(Self::ReadySent),
+            "BROADCAST_STATUS_DELIVERED_WITH_READY_SENT" => {
+                Some(Self::DeliveredWithReadySent)
+            }
+            "BROADCAST_STATUS_DELIVERED" => Some(Self::Delivered),
+            "BROADCAST_STATUS_FAILED" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod admin_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// Operational control of a running node, only served when an admin token is configured
+    /// and expecting it as a bearer token in the `authorization` metadata
+    #[derive(Debug, Clone)]
+    pub struct AdminServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl AdminServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> AdminServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> AdminServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            AdminServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// List the certificates of the pending pool
+        pub async fn list_pending_certificates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListPendingCertificatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListPendingCertificatesResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.AdminService/ListPendingCertificates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.AdminService", "ListPendingCertificates"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Remove a certificate from the pending pool
+        pub async fn evict_pending_certificate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::EvictPendingCertificateRequest>,
+        ) -> std::result::Result<tonic::Response<super::EvictPendingCertificateResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.AdminService/EvictPendingCertificate",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.AdminService", "EvictPendingCertificate"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Synchronize the delivered certificates with the checkpoints of the given peer
+        pub async fn resync_from_peer(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ResyncFromPeerRequest>,
+        ) -> std::result::Result<tonic::Response<super::ResyncFromPeerResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.AdminService/ResyncFromPeer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.AdminService", "ResyncFromPeer"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Dump the state of the broadcast of a certificate
+        pub async fn get_broadcast_state(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBroadcastStateRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetBroadcastStateResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.AdminService/GetBroadcastState",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.AdminService", "GetBroadcastState"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Replace the log filter of the node
+        pub async fn set_log_filter(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetLogFilterRequest>,
+        ) -> std::result::Result<tonic::Response<super::SetLogFilterResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.AdminService/SetLogFilter",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.AdminService", "SetLogFilter"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Trigger a compaction of the storage
+        pub async fn compact_storage(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CompactStorageRequest>,
+        ) -> std::result::Result<tonic::Response<super::CompactStorageResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.AdminService/CompactStorage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.AdminService", "CompactStorage"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Take a consistent backup of the storage of the running node
+        pub async fn backup(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BackupRequest>,
+        ) -> std::result::Result<tonic::Response<super::BackupResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topos.tce.v1.AdminService/Backup",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topos.tce.v1.AdminService", "Backup"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod admin_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with AdminServiceServer.
+    #[async_trait]
+    pub trait AdminService: Send + Sync + 'static {
+        /// List the certificates of the pending pool
+        async fn list_pending_certificates(
+            &self,
+            request: tonic::Request<super::ListPendingCertificatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListPendingCertificatesResponse>, tonic::Status>;
+        /// Remove a certificate from the pending pool
+        async fn evict_pending_certificate(
+            &self,
+            request: tonic::Request<super::EvictPendingCertificateRequest>,
+        ) -> std::result::Result<tonic::Response<super::EvictPendingCertificateResponse>, tonic::Status>;
+        /// Synchronize the delivered certificates with the checkpoints of the given peer
+        async fn resync_from_peer(
+            &self,
+            request: tonic::Request<super::ResyncFromPeerRequest>,
+        ) -> std::result::Result<tonic::Response<super::ResyncFromPeerResponse>, tonic::Status>;
+        /// Dump the state of the broadcast of a certificate
+        async fn get_broadcast_state(
+            &self,
+            request: tonic::Request<super::GetBroadcastStateRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetBroadcastStateResponse>, tonic::Status>;
+        /// Replace the log filter of the node
+        async fn set_log_filter(
+            &self,
+            request: tonic::Request<super::SetLogFilterRequest>,
+        ) -> std::result::Result<tonic::Response<super::SetLogFilterResponse>, tonic::Status>;
+        /// Trigger a compaction of the storage
+        async fn compact_storage(
+            &self,
+            request: tonic::Request<super::CompactStorageRequest>,
+        ) -> std::result::Result<tonic::Response<super::CompactStorageResponse>, tonic::Status>;
+        /// Take a consistent backup of the storage of the running node
+        async fn backup(
+            &self,
+            request: tonic::Request<super::BackupRequest>,
+        ) -> std::result::Result<tonic::Response<super::BackupResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct AdminServiceServer<T: AdminService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: AdminService> AdminServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for AdminServiceServer<T>
+    where
+        T: AdminService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/topos.tce.v1.AdminService/ListPendingCertificates" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPendingCertificatesSvc<T: AdminService>(pub Arc<T>);
+                    impl<
+                        T: AdminService,
+                    > tonic::server::UnaryService<super::ListPendingCertificatesRequest>
+                    for ListPendingCertificatesSvc<T> {
+                        type Response = super::ListPendingCertificatesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListPendingCertificatesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AdminService>::list_pending_certificates(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListPendingCertificatesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.AdminService/EvictPendingCertificate" => {
+                    #[allow(non_camel_case_types)]
+                    struct EvictPendingCertificateSvc<T: AdminService>(pub Arc<T>);
+                    impl<
+                        T: AdminService,
+                    > tonic::server::UnaryService<super::EvictPendingCertificateRequest>
+                    for EvictPendingCertificateSvc<T> {
+                        type Response = super::EvictPendingCertificateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::EvictPendingCertificateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AdminService>::evict_pending_certificate(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = EvictPendingCertificateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.AdminService/ResyncFromPeer" => {
+                    #[allow(non_camel_case_types)]
+                    struct ResyncFromPeerSvc<T: AdminService>(pub Arc<T>);
+                    impl<
+                        T: AdminService,
+                    > tonic::server::UnaryService<super::ResyncFromPeerRequest>
+                    for ResyncFromPeerSvc<T> {
+                        type Response = super::ResyncFromPeerResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ResyncFromPeerRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AdminService>::resync_from_peer(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ResyncFromPeerSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.AdminService/GetBroadcastState" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBroadcastStateSvc<T: AdminService>(pub Arc<T>);
+                    impl<
+                        T: AdminService,
+                    > tonic::server::UnaryService<super::GetBroadcastStateRequest>
+                    for GetBroadcastStateSvc<T> {
+                        type Response = super::GetBroadcastStateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBroadcastStateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AdminService>::get_broadcast_state(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBroadcastStateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.AdminService/SetLogFilter" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetLogFilterSvc<T: AdminService>(pub Arc<T>);
+                    impl<
+                        T: AdminService,
+                    > tonic::server::UnaryService<super::SetLogFilterRequest>
+                    for SetLogFilterSvc<T> {
+                        type Response = super::SetLogFilterResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetLogFilterRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AdminService>::set_log_filter(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetLogFilterSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.AdminService/CompactStorage" => {
+                    #[allow(non_camel_case_types)]
+                    struct CompactStorageSvc<T: AdminService>(pub Arc<T>);
+                    impl<
+                        T: AdminService,
+                    > tonic::server::UnaryService<super::CompactStorageRequest>
+                    for CompactStorageSvc<T> {
+                        type Response = super::CompactStorageResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CompactStorageRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AdminService>::compact_storage(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CompactStorageSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topos.tce.v1.AdminService/Backup" => {
+                    #[allow(non_camel_case_types)]
+                    struct BackupSvc<T: AdminService>(pub Arc<T>);
+                    impl<
+                        T: AdminService,
+                    > tonic::server::UnaryService<super::BackupRequest>
+                    for BackupSvc<T> {
+                        type Response = super::BackupResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BackupRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AdminService>::backup(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BackupSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: AdminService> Clone for AdminServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: AdminService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: AdminService> tonic::server::NamedService for AdminServiceServer<T> {
+        const NAME: &'static str = "topos.tce.v1.AdminService";
+    }
+}
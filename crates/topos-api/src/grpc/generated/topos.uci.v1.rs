@@ -23,6 +23,22 @@ pub struct Certificate {
     pub proof: ::core::option::Option<super::super::shared::v1::StarkProof>,
     #[prost(message, optional, tag = "10")]
     pub signature: ::core::option::Option<super::super::shared::v1::Frost>,
+    /// Root of the cross-subnet messages sent by the certified blocks
+    #[prost(bytes = "vec", tag = "11")]
+    pub messages_root: ::prost::alloc::vec::Vec<u8>,
+    /// Subnet blocks certified by the certificate
+    #[prost(message, optional, tag = "12")]
+    pub block_range: ::core::option::Option<BlockRange>,
+}
+/// Inclusive range of subnet blocks
+#[derive(Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockRange {
+    #[prost(uint64, tag = "1")]
+    pub start: u64,
+    #[prost(uint64, tag = "2")]
+    pub end: u64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
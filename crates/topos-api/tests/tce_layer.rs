@@ -16,9 +16,11 @@ use topos_api::grpc::tce::v1::api_service_server::{ApiService, ApiServiceServer}
 use topos_api::grpc::tce::v1::synchronizer_service_client::SynchronizerServiceClient;
 use topos_api::grpc::tce::v1::watch_certificates_request::{Command, OpenStream};
 use topos_api::grpc::tce::v1::{
-    GetLastPendingCertificatesRequest, GetLastPendingCertificatesResponse, GetSourceHeadRequest,
-    GetSourceHeadResponse, LastPendingCertificate, SubmitCertificateRequest,
-    SubmitCertificateResponse, WatchCertificatesRequest, WatchCertificatesResponse,
+    GetCertificateStatusRequest, GetCertificateStatusResponse, GetLastPendingCertificatesRequest,
+    GetLastPendingCertificatesResponse, GetSourceHeadRequest, GetSourceHeadResponse,
+    LastPendingCertificate, SubmitCertificateRequest, SubmitCertificateResponse,
+    SubmitCertificateResult, WaitForDeliveryRequest, WaitForDeliveryResponse,
+    WatchCertificatesRequest, WatchCertificatesResponse,
 };
 use topos_api::grpc::uci::v1::Certificate;
 use topos_api::grpc::{shared, GrpcClient};
@@ -36,11 +38,18 @@ async fn create_tce_layer() {
         type WatchCertificatesStream =
             Pin<Box<dyn Stream<Item = Result<WatchCertificatesResponse, Status>> + Send + 'static>>;
 
+        type WaitForDeliveryStream =
+            Pin<Box<dyn Stream<Item = Result<WaitForDeliveryResponse, Status>> + Send + 'static>>;
+
         async fn submit_certificate(
             &self,
             _request: Request<SubmitCertificateRequest>,
         ) -> Result<Response<SubmitCertificateResponse>, tonic::Status> {
-            Ok(Response::new(SubmitCertificateResponse {}))
+            Ok(Response::new(SubmitCertificateResponse {
+                result: SubmitCertificateResult::Accepted.into(),
+                pending_id: Some(0),
+                prev_certificate_id: None,
+            }))
         }
 
         async fn get_source_head(
@@ -131,6 +140,20 @@ async fn create_tce_layer() {
                 Box::pin(output) as Self::WatchCertificatesStream
             ))
         }
+
+        async fn get_certificate_status(
+            &self,
+            _request: Request<GetCertificateStatusRequest>,
+        ) -> Result<Response<GetCertificateStatusResponse>, Status> {
+            Err(Status::unimplemented("Not used by this test"))
+        }
+
+        async fn wait_for_delivery(
+            &self,
+            _request: Request<WaitForDeliveryRequest>,
+        ) -> Result<Response<Self::WaitForDeliveryStream>, Status> {
+            Err(Status::unimplemented("Not used by this test"))
+        }
     }
 
     let (tx, rx) = oneshot::channel();
@@ -173,7 +196,8 @@ async fn create_tce_layer() {
         .await
         .map(|r| r.into_inner())
         .unwrap();
-    assert_eq!(response, SubmitCertificateResponse {});
+    assert_eq!(response.result(), SubmitCertificateResult::Accepted);
+    assert_eq!(response.pending_id, Some(0));
 
     // Test get source head certificate
     let response = client
@@ -222,6 +246,7 @@ async fn create_tce_layer() {
             positions: Vec::new(),
         }),
         source_checkpoint: None,
+        ..Default::default()
     }));
     let request_id: shared::v1::Uuid = Uuid::new_v4().into();
     let first_request = WatchCertificatesRequest {
@@ -235,6 +260,7 @@ async fn create_tce_layer() {
             positions: Vec::new(),
         }),
         source_checkpoint: None,
+        ..Default::default()
     }
     .into();
     first_request_short.request_id = Some(request_id);
@@ -36,6 +36,8 @@ pub fn generate_test_certificate(
         generate_random_32b_array(),
         generate_random_32b_array(),
         generate_random_32b_array(),
+        Default::default(),
+        None,
         target_subnet_ids,
         0,
         STARK_BLOB.clone(),
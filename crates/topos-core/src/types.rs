@@ -6,7 +6,10 @@ use crate::errors::GrpcParsingError;
 use self::stream::CertificateSourceStreamPosition;
 use topos_api::grpc::{
     checkpoints::SourceStreamPosition,
-    tce::v1::{ProofOfDelivery as GrpcProofOfDelivery, SignedReady},
+    tce::v1::{
+        CertificateDelivered as GrpcCertificateDelivered, ProofOfDelivery as GrpcProofOfDelivery,
+        SignedReady,
+    },
 };
 
 pub mod stream;
@@ -49,6 +52,32 @@ pub struct ProofOfDelivery {
     pub threshold: u64,
 }
 
+impl TryFrom<GrpcCertificateDelivered> for CertificateDelivered {
+    type Error = GrpcParsingError;
+    fn try_from(value: GrpcCertificateDelivered) -> Result<Self, Self::Error> {
+        Ok(Self {
+            certificate: value
+                .certificate
+                .ok_or(GrpcParsingError::GrpcMalformedType("certificate"))?
+                .try_into()
+                .map_err(|_| GrpcParsingError::GrpcMalformedType("certificate"))?,
+            proof_of_delivery: value
+                .proof_of_delivery
+                .ok_or(GrpcParsingError::GrpcMalformedType("proof_of_delivery"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<CertificateDelivered> for GrpcCertificateDelivered {
+    fn from(value: CertificateDelivered) -> Self {
+        Self {
+            certificate: Some(value.certificate.into()),
+            proof_of_delivery: Some(value.proof_of_delivery.into()),
+        }
+    }
+}
+
 impl From<SourceStreamPosition> for CertificateSourceStreamPosition {
     fn from(value: SourceStreamPosition) -> Self {
         Self {
@@ -52,8 +52,17 @@ impl MessageSigner {
         payload: &[u8],
         public_key: Address,
     ) -> Result<(), SignatureError> {
-        let message: RecoveryMessage = payload.into();
-
-        signature.verify(message, public_key)
+        verify_signature(signature, payload, public_key)
     }
 }
+
+/// Verify that the payload has been signed by the owner of the given address
+pub fn verify_signature(
+    signature: Signature,
+    payload: &[u8],
+    public_key: Address,
+) -> Result<(), SignatureError> {
+    let message: RecoveryMessage = payload.into();
+
+    signature.verify(message, public_key)
+}
@@ -10,11 +10,13 @@ mod api;
 mod double_echo;
 mod p2p;
 mod storage;
+mod synchronizer;
 
 pub use api::*;
 pub use double_echo::*;
 pub use p2p::*;
 pub use storage::*;
+pub use synchronizer::*;
 
 lazy_static! {
     pub static ref TOPOS_METRIC_REGISTRY: Registry = Registry::new_custom(
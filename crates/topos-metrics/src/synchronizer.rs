@@ -0,0 +1,62 @@
+use prometheus::{
+    self, register_histogram_with_registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Histogram, IntCounter,
+    IntGauge, IntGaugeVec,
+};
+
+use lazy_static::lazy_static;
+
+use crate::TOPOS_METRIC_REGISTRY;
+
+lazy_static! {
+    pub static ref SYNCHRONIZER_CERTIFICATES_SYNCHRONIZED_TOTAL: IntCounter =
+        register_int_counter_with_registry!(
+            "synchronizer_certificates_synchronized_total",
+            "Number of certificates synchronized from peers.",
+            TOPOS_METRIC_REGISTRY
+        )
+        .unwrap();
+    pub static ref SYNCHRONIZER_MISSING_CERTIFICATES: IntGauge = register_int_gauge_with_registry!(
+        "synchronizer_missing_certificates",
+        "Number of certificates of the current checkpoint diff not synchronized yet.",
+        TOPOS_METRIC_REGISTRY
+    )
+    .unwrap();
+    pub static ref SYNCHRONIZER_FETCH_REQUEST_FAILED_TOTAL: IntCounter =
+        register_int_counter_with_registry!(
+            "synchronizer_fetch_request_failed_total",
+            "Number of failed certificate fetch requests.",
+            TOPOS_METRIC_REGISTRY
+        )
+        .unwrap();
+    pub static ref SYNCHRONIZER_FETCH_REQUEST_LATENCY: Histogram =
+        register_histogram_with_registry!(
+            "synchronizer_fetch_request_latency",
+            "Latency of the certificate fetch requests.",
+            vec![0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0],
+            TOPOS_METRIC_REGISTRY
+        )
+        .unwrap();
+    pub static ref SYNCHRONIZER_STATE: IntGauge = register_int_gauge_with_registry!(
+        "synchronizer_state",
+        "State of the synchronization: 0 syncing, 1 synced, 2 stalled.",
+        TOPOS_METRIC_REGISTRY
+    )
+    .unwrap();
+    pub static ref SYNCHRONIZER_LOCAL_POSITION: IntGaugeVec =
+        register_int_gauge_vec_with_registry!(
+            "synchronizer_local_position",
+            "Position of the local head of the source streams.",
+            &["subnet_id"],
+            TOPOS_METRIC_REGISTRY
+        )
+        .unwrap();
+    pub static ref SYNCHRONIZER_BEST_KNOWN_POSITION: IntGaugeVec =
+        register_int_gauge_vec_with_registry!(
+            "synchronizer_best_known_position",
+            "Highest position of the source streams known to be delivered by a peer.",
+            &["subnet_id"],
+            TOPOS_METRIC_REGISTRY
+        )
+        .unwrap();
+}
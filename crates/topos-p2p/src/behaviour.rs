@@ -1,16 +1,34 @@
 use self::{discovery::DiscoveryBehaviour, peer_info::PeerInfoBehaviour};
 use crate::event::ComposedEvent;
-use libp2p::swarm::NetworkBehaviour;
+use libp2p::{
+    connection_limits, memory_connection_limits,
+    swarm::{behaviour::toggle::Toggle, NetworkBehaviour},
+};
 
 pub(crate) mod discovery;
+pub(crate) mod gating;
 pub(crate) mod gossip;
 pub(crate) mod grpc;
 pub(crate) mod peer_info;
+pub(crate) mod peer_proof;
 pub(crate) mod topos;
 
 #[derive(NetworkBehaviour)]
 #[behaviour(to_swarm = "ComposedEvent")]
 pub(crate) struct Behaviour {
+    /// Deny connections exceeding the configured pending and established limits
+    pub(crate) limits: connection_limits::Behaviour,
+
+    /// Deny new connections when the process is under memory pressure
+    pub(crate) memory_limits: Toggle<memory_connection_limits::Behaviour>,
+
+    /// Restrict the connections to the allowlisted peers when the gating mode is enabled
+    pub(crate) gating: Toggle<gating::Behaviour>,
+
+    /// Challenges the peers waiting to be admitted by the gating and answers the
+    /// challenges of the remote peers with our own proof
+    pub(crate) peer_proof: peer_proof::Behaviour,
+
     /// Periodically pings and identifies the nodes we are connected to,
     /// and store information in a cache.
     pub(crate) peer_info: PeerInfoBehaviour,
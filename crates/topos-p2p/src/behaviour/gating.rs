@@ -0,0 +1,233 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use libp2p::{
+    core::Endpoint,
+    swarm::{
+        dummy, CloseConnection, ConnectionClosed, ConnectionDenied, ConnectionId, FromSwarm,
+        NetworkBehaviour, PollParameters, THandler, THandlerInEvent, THandlerOutEvent, ToSwarm,
+    },
+    Multiaddr, PeerId,
+};
+use rand::{thread_rng, RngCore};
+use tracing::{debug, info, warn};
+use void::Void;
+
+use crate::{
+    behaviour::peer_proof::CHALLENGE_SIZE,
+    config::{GatingConfig, PeerProofVerifier},
+};
+
+/// Delay given to a peer outside of the allowlist to present a valid proof
+const PROOF_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Gating behaviour restricting the connections of the node to an allowlist of peers
+///
+/// When a proof verifier is configured, peers outside of the allowlist are provisionally
+/// accepted and have [`PROOF_TIMEOUT`] to answer a random challenge with a valid proof,
+/// the connections of the peers failing to do so are closed.
+pub(crate) struct Behaviour {
+    /// Peers allowed to stay connected with the node
+    allowed_peers: HashSet<PeerId>,
+    /// Optional verifier of the proofs presented by peers outside of the allowlist
+    proof_verifier: Option<PeerProofVerifier>,
+    /// Peers connected without being allowed yet, waiting for their proof
+    provisional_peers: HashMap<PeerId, ProvisionalPeer>,
+    /// The list of pending events to send to the swarm
+    pending_events: VecDeque<ToSwarm<Void, THandlerInEvent<Self>>>,
+    tick: tokio::time::Interval,
+}
+
+impl Behaviour {
+    pub(crate) fn new(config: GatingConfig) -> Self {
+        Self {
+            allowed_peers: config.allowed_peers,
+            proof_verifier: config.proof_verifier,
+            provisional_peers: HashMap::new(),
+            pending_events: VecDeque::new(),
+            tick: tokio::time::interval(Duration::from_secs(1)),
+        }
+    }
+
+    pub(crate) fn is_allowed(&self, peer_id: &PeerId) -> bool {
+        self.allowed_peers.contains(peer_id)
+    }
+
+    pub(crate) fn allow_peer(&mut self, peer_id: PeerId) {
+        self.provisional_peers.remove(&peer_id);
+        self.allowed_peers.insert(peer_id);
+    }
+
+    /// Remove the peer from the allowlist and close every connection with it
+    pub(crate) fn disallow_peer(&mut self, peer_id: PeerId) {
+        self.provisional_peers.remove(&peer_id);
+        self.allowed_peers.remove(&peer_id);
+        self.pending_events.push_back(ToSwarm::CloseConnection {
+            peer_id,
+            connection: CloseConnection::All,
+        });
+    }
+
+    pub(crate) fn is_provisional(&self, peer_id: &PeerId) -> bool {
+        self.provisional_peers.contains_key(peer_id)
+    }
+
+    /// Challenge to be signed by a provisional peer, `None` if the peer isn't waiting for its proof
+    pub(crate) fn challenge(&self, peer_id: &PeerId) -> Option<Vec<u8>> {
+        self.provisional_peers
+            .get(peer_id)
+            .map(|provisional| provisional.challenge.to_vec())
+    }
+
+    /// Verify the proof presented by a peer against the challenge it was sent,
+    /// the peer is either added to the allowlist or disconnected
+    pub(crate) fn verify_proof(&mut self, peer_id: &PeerId, proof: &str) -> bool {
+        if self.is_allowed(peer_id) {
+            return true;
+        }
+
+        let verified = match (
+            self.proof_verifier.as_ref(),
+            self.provisional_peers.get(peer_id),
+        ) {
+            (Some(verifier), Some(provisional)) => {
+                verifier(peer_id, &provisional.challenge[..], proof)
+            }
+            _ => false,
+        };
+
+        if verified {
+            info!("Peer {peer_id} presented a valid proof, adding it to the allowlist");
+            self.allow_peer(*peer_id);
+        } else {
+            warn!("Peer {peer_id} presented an invalid proof, closing its connections");
+            self.disallow_peer(*peer_id);
+        }
+
+        verified
+    }
+
+    fn check_peer(&mut self, peer_id: PeerId) -> Result<THandler<Self>, ConnectionDenied> {
+        if self.is_allowed(&peer_id) {
+            return Ok(dummy::ConnectionHandler);
+        }
+
+        if self.proof_verifier.is_some() {
+            debug!("Provisionally accepting connection of {peer_id} waiting for its proof");
+            self.provisional_peers
+                .entry(peer_id)
+                .or_insert_with(ProvisionalPeer::new);
+
+            return Ok(dummy::ConnectionHandler);
+        }
+
+        Err(ConnectionDenied::new(NotAllowed { peer_id }))
+    }
+}
+
+/// Peer waiting to answer its challenge, a new challenge is drawn for every admission
+struct ProvisionalPeer {
+    since: Instant,
+    challenge: [u8; CHALLENGE_SIZE],
+}
+
+impl ProvisionalPeer {
+    fn new() -> Self {
+        let mut challenge = [0u8; CHALLENGE_SIZE];
+        thread_rng().fill_bytes(&mut challenge);
+
+        Self {
+            since: Instant::now(),
+            challenge,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NotAllowed {
+    peer_id: PeerId,
+}
+
+impl fmt::Display for NotAllowed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "peer {} is not in the allowlist", self.peer_id)
+    }
+}
+
+impl std::error::Error for NotAllowed {}
+
+impl NetworkBehaviour for Behaviour {
+    type ConnectionHandler = dummy::ConnectionHandler;
+
+    type ToSwarm = Void;
+
+    fn handle_established_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        peer: PeerId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        self.check_peer(peer)
+    }
+
+    fn handle_established_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        peer: PeerId,
+        _addr: &Multiaddr,
+        _role_override: Endpoint,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        self.check_peer(peer)
+    }
+
+    fn on_swarm_event(&mut self, event: FromSwarm<Self::ConnectionHandler>) {
+        if let FromSwarm::ConnectionClosed(ConnectionClosed {
+            peer_id,
+            remaining_established: 0,
+            ..
+        }) = event
+        {
+            self.provisional_peers.remove(&peer_id);
+        }
+    }
+
+    fn on_connection_handler_event(
+        &mut self,
+        _peer_id: PeerId,
+        _connection_id: ConnectionId,
+        event: THandlerOutEvent<Self>,
+    ) {
+        void::unreachable(event)
+    }
+
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+        _params: &mut impl PollParameters,
+    ) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+        if self.tick.poll_tick(cx).is_ready() {
+            let expired: Vec<PeerId> = self
+                .provisional_peers
+                .iter()
+                .filter(|(_, provisional)| provisional.since.elapsed() >= PROOF_TIMEOUT)
+                .map(|(peer_id, _)| *peer_id)
+                .collect();
+
+            for peer_id in expired {
+                warn!("Peer {peer_id} didn't present any proof in time, closing its connections");
+                self.disallow_peer(peer_id);
+            }
+        }
+
+        if let Some(event) = self.pending_events.pop_front() {
+            return Poll::Ready(event);
+        }
+
+        Poll::Pending
+    }
+}
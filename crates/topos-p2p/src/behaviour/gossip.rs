@@ -9,6 +9,7 @@ use libp2p::{
     gossipsub::{self, IdentTopic, Message, MessageAuthenticity, MessageId},
     identity::Keypair,
     swarm::{NetworkBehaviour, THandlerInEvent, ToSwarm},
+    PeerId,
 };
 use prost::Message as ProstMessage;
 use topos_api::grpc::tce::v1::Batch;
@@ -44,6 +45,12 @@ impl Behaviour {
         Ok(0)
     }
 
+    /// Explicit peers always receive the messages, regardless of the mesh, used by
+    /// sentries to relay gossip to the validators they are protecting
+    pub fn add_explicit_peer(&mut self, peer_id: &PeerId) {
+        self.gossipsub.add_explicit_peer(peer_id);
+    }
+
     pub fn subscribe(&mut self) -> Result<(), &'static str> {
         self.gossipsub
             .subscribe(&gossipsub::IdentTopic::new(TOPOS_GOSSIP))
@@ -68,6 +75,8 @@ impl Behaviour {
         let gossipsub = gossipsub::ConfigBuilder::default()
             .max_transmit_size(2 * 1024 * 1024)
             .validation_mode(gossipsub::ValidationMode::Strict)
+            // Messages are only forwarded once accepted by the runtime
+            .validate_messages()
             .build()
             .unwrap();
 
@@ -100,6 +109,22 @@ impl Behaviour {
             cache: HashSet::new(),
         }
     }
+
+    /// Report whether a received message is to be forwarded to the other peers or rejected
+    pub fn report_message_validation_result(
+        &mut self,
+        message_id: &MessageId,
+        propagation_source: &PeerId,
+        acceptance: gossipsub::MessageAcceptance,
+    ) {
+        if let Err(error) = self.gossipsub.report_message_validation_result(
+            message_id,
+            propagation_source,
+            acceptance,
+        ) {
+            debug!("Unable to report the validation of message {message_id}: {error:?}");
+        }
+    }
 }
 
 impl NetworkBehaviour for Behaviour {
@@ -242,6 +267,8 @@ impl NetworkBehaviour for Behaviour {
                             topic: TOPOS_GOSSIP,
                             message: data,
                             source,
+                            propagation_source,
+                            message_id,
                         },
                     )))
                 }
@@ -251,6 +278,8 @@ impl NetworkBehaviour for Behaviour {
                             topic: TOPOS_ECHO,
                             message: data,
                             source,
+                            propagation_source,
+                            message_id,
                         },
                     )))
                 }
@@ -260,6 +289,8 @@ impl NetworkBehaviour for Behaviour {
                             topic: TOPOS_READY,
                             message: data,
                             source,
+                            propagation_source,
+                            message_id,
                         },
                     )))
                 }
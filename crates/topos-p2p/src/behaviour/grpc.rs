@@ -22,7 +22,7 @@ use tokio::sync::{mpsc, oneshot};
 use tonic::transport::{server::Router, Channel};
 use tracing::{debug, info, warn};
 
-use crate::GrpcRouter;
+use crate::{config::LimitsConfig, GrpcRouter};
 
 use self::{
     connection::{
@@ -30,7 +30,7 @@ use self::{
     },
     error::OutboundError,
     handler::ProtocolRequest,
-    stream::GrpcStream,
+    stream::{GrpcStream, InboundStreamGuard},
 };
 pub(crate) use event::Event;
 
@@ -121,12 +121,22 @@ pub(crate) struct Behaviour {
     pending_negotiated_channels: FuturesUnordered<ChannelNegotiationFuture>,
     inbound_protocols: HashSet<String>,
     outbound_protocols: HashSet<String>,
+    /// Tracks the live inbound gRPC streams of each peer, every open stream holds a clone
+    inbound_streams: HashMap<PeerId, Arc<()>>,
+    /// The maximum number of live inbound gRPC streams for a single peer
+    max_inbound_streams_per_peer: usize,
+    /// Notifies the peers of the inbound gRPC streams being dropped
+    inbound_stream_closed_sender: mpsc::UnboundedSender<PeerId>,
+    inbound_stream_closed: mpsc::UnboundedReceiver<PeerId>,
+    /// The maximum number of outbound connections being opened at the same time
+    max_pending_outbound_connections: usize,
 }
 
 impl Behaviour {
     // TODO: Remove unused when gRPC behaviour is activated
     pub fn new(service: GrpcContext) -> Self {
         let (service, (inbound_protocols, outbound_protocols)) = service.into_parts();
+        let (inbound_stream_closed_sender, inbound_stream_closed) = mpsc::unbounded_channel();
 
         Self {
             service,
@@ -140,9 +150,34 @@ impl Behaviour {
             pending_outbound_connections: HashMap::new(),
             pending_events: VecDeque::new(),
             pending_negotiated_channels: FuturesUnordered::new(),
+            inbound_streams: HashMap::new(),
+            max_inbound_streams_per_peer: LimitsConfig::MAX_INBOUND_GRPC_STREAMS_PER_PEER,
+            inbound_stream_closed_sender,
+            inbound_stream_closed,
+            max_pending_outbound_connections: LimitsConfig::MAX_PENDING_OUTBOUND_GRPC_CONNECTIONS,
         }
     }
 
+    pub(crate) fn with_max_inbound_streams_per_peer(mut self, limit: usize) -> Self {
+        self.max_inbound_streams_per_peer = limit;
+
+        self
+    }
+
+    pub(crate) fn with_max_pending_outbound_connections(mut self, limit: usize) -> Self {
+        self.max_pending_outbound_connections = limit;
+
+        self
+    }
+
+    /// Number of live inbound gRPC streams opened by the given peer
+    pub(crate) fn inbound_streams_count(&self, peer_id: &PeerId) -> usize {
+        self.inbound_streams
+            .get(peer_id)
+            .map(|guard| Arc::strong_count(guard) - 1)
+            .unwrap_or(0)
+    }
+
     /// Adds a known address for a peer that can be used for
     /// dialing attempts by the `Swarm`
     ///
@@ -234,6 +269,18 @@ impl Behaviour {
 
     /// Try to open a connection with the given peer.
     fn open_connection(&mut self, peer_id: &PeerId, protocol: String) -> OutboundConnection {
+        if self.pending_outbound_connections.len() >= self.max_pending_outbound_connections {
+            warn!(
+                "Rejecting gRPC outbound connection to peer {peer_id}: the limit of {} pending \
+                 connections is reached",
+                self.max_pending_outbound_connections
+            );
+
+            return OutboundConnection::Rejected(OutboundError::TooManyPendingConnections(
+                self.max_pending_outbound_connections,
+            ));
+        }
+
         info!("Opening gRPC outbound connection to peer {peer_id}");
 
         let (notifier, receiver) = oneshot::channel();
@@ -490,8 +537,31 @@ impl NetworkBehaviour for Behaviour {
             }
             handler::event::Event::InboundNegotiatedStream { request_id, stream } => {
                 debug!("Inbound stream negotiated for request {request_id} with peer {peer_id}",);
+                if self.inbound_streams_count(&peer_id) >= self.max_inbound_streams_per_peer {
+                    warn!(
+                        "Rejecting inbound gRPC stream for request {request_id}: peer {peer_id} \
+                         reached the limit of {} concurrent streams",
+                        self.max_inbound_streams_per_peer
+                    );
+                    self.pending_events.push_back(ToSwarm::GenerateEvent(
+                        Event::InboundStreamRejected {
+                            peer_id,
+                            request_id,
+                        },
+                    ));
+
+                    return;
+                }
+
                 if let Some(sender) = &mut self.inbound_stream {
-                    _ = sender.send(Ok(GrpcStream::new(stream, peer_id, connection_id)));
+                    let guard = InboundStreamGuard::new(
+                        peer_id,
+                        self.inbound_streams.entry(peer_id).or_default().clone(),
+                        self.inbound_stream_closed_sender.clone(),
+                    );
+                    _ = sender.send(Ok(
+                        GrpcStream::new(stream, peer_id, connection_id).with_guard(guard)
+                    ));
                     self.pending_events.push_back(ToSwarm::GenerateEvent(
                         Event::InboundNegotiatedConnection {
                             request_id,
@@ -548,6 +618,13 @@ impl NetworkBehaviour for Behaviour {
             return Poll::Ready(ev);
         }
 
+        // Forgetting the peers without any live inbound stream left
+        while let Poll::Ready(Some(peer_id)) = self.inbound_stream_closed.poll_recv(cx) {
+            if self.inbound_streams_count(&peer_id) == 0 {
+                self.inbound_streams.remove(&peer_id);
+            }
+        }
+
         // When channel has been negotiated by the [`ConnectionHandler`] we need
         // to update the [`Connection`] with the channel.
         match self.pending_negotiated_channels.poll_next_unpin(cx) {
@@ -56,6 +56,8 @@ pub enum OutboundConnection {
         request_id: RequestId,
         receiver: oneshot::Receiver<Result<Channel, OutboundError>>,
     },
+    /// The connection can't be opened, the behaviour is at capacity
+    Rejected(OutboundError),
 }
 
 impl IntoFuture for OutboundConnection {
@@ -84,6 +86,7 @@ impl IntoFuture for OutboundConnection {
                         channel,
                     })
                 }
+                OutboundConnection::Rejected(error) => Err(error.into()),
             }
         }
         .boxed()
@@ -12,6 +12,8 @@ pub enum OutboundError {
     GrpcChannel(#[from] Arc<tonic::transport::Error>),
     #[error("Outbound connection timeout")]
     Timeout,
+    #[error("Too many pending outbound connections, the limit of {0} is reached")]
+    TooManyPendingConnections(usize),
 }
 
 #[derive(thiserror::Error, Debug)]
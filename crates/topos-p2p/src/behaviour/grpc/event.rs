@@ -22,6 +22,12 @@ pub enum Event {
         connection_id: ConnectionId,
     },
 
+    /// An inbound stream was dropped because the peer has too many streams opened
+    InboundStreamRejected {
+        peer_id: PeerId,
+        request_id: RequestId,
+    },
+
     OutboundNegotiatedConnection {
         peer_id: PeerId,
         request_id: RequestId,
@@ -23,6 +23,39 @@ pub(crate) struct GrpcStream {
     stream: libp2p::Stream,
     peer_id: PeerId,
     connection_id: libp2p::swarm::ConnectionId,
+    /// Keeps track of the stream being alive for the per peer inbound limits
+    guard: Option<InboundStreamGuard>,
+}
+
+/// Accounts an inbound stream in the per peer limits for as long as it is alive
+///
+/// The behaviour is notified once the stream is dropped to clean up the peer's entry.
+pub(crate) struct InboundStreamGuard {
+    peer_id: PeerId,
+    count: Option<Arc<()>>,
+    closed: mpsc::UnboundedSender<PeerId>,
+}
+
+impl InboundStreamGuard {
+    pub(crate) fn new(
+        peer_id: PeerId,
+        count: Arc<()>,
+        closed: mpsc::UnboundedSender<PeerId>,
+    ) -> Self {
+        Self {
+            peer_id,
+            count: Some(count),
+            closed,
+        }
+    }
+}
+
+impl Drop for InboundStreamGuard {
+    fn drop(&mut self) {
+        // Releasing the count before notifying, for the behaviour to see the stream as closed
+        drop(self.count.take());
+        _ = self.closed.send(self.peer_id);
+    }
 }
 
 /// Outbound GrpcStream initialization struct
@@ -77,9 +110,16 @@ impl GrpcStream {
             stream,
             peer_id,
             connection_id,
+            guard: None,
         }
     }
 
+    pub(crate) fn with_guard(mut self, guard: InboundStreamGuard) -> Self {
+        self.guard = Some(guard);
+
+        self
+    }
+
     /// Transform the GrpcStream into a [`tonic::transport::Channel`]
     pub async fn into_channel(self) -> Result<Channel, tonic::transport::Error> {
         let (sender, receiver) = mpsc::channel(1);
@@ -0,0 +1,86 @@
+use std::io;
+
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use libp2p::{
+    request_response::{self, Codec, ProtocolSupport},
+    StreamProtocol,
+};
+
+use crate::constants::PEER_PROOF_PROTOCOL;
+
+/// Size of the random challenge sent to the peers waiting to be admitted by the gating
+pub(crate) const CHALLENGE_SIZE: usize = 32;
+
+/// Upper bound on the size of a proof, protecting the node from oversized responses
+const MAX_PROOF_SIZE: u64 = 1024;
+
+/// Challenge-response protocol used by the gating to request a proof from the
+/// peers outside of the allowlist
+pub(crate) type Behaviour = request_response::Behaviour<PeerProofCodec>;
+
+pub(crate) fn new() -> Behaviour {
+    Behaviour::new(
+        [(
+            StreamProtocol::new(PEER_PROOF_PROTOCOL),
+            ProtocolSupport::Full,
+        )],
+        request_response::Config::default(),
+    )
+}
+
+/// Requests carry the challenge to sign, responses carry the proof
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PeerProofCodec;
+
+#[async_trait::async_trait]
+impl Codec for PeerProofCodec {
+    type Protocol = StreamProtocol;
+    type Request = Vec<u8>;
+    type Response = String;
+
+    async fn read_request<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<Vec<u8>>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut challenge = vec![0u8; CHALLENGE_SIZE];
+        io.read_exact(&mut challenge).await?;
+
+        Ok(challenge)
+    }
+
+    async fn read_response<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<String>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut proof = String::new();
+        io.take(MAX_PROOF_SIZE).read_to_string(&mut proof).await?;
+
+        Ok(proof)
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        challenge: Vec<u8>,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.write_all(&challenge).await?;
+        io.close().await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        proof: String,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.write_all(proof.as_bytes()).await?;
+        io.close().await
+    }
+}
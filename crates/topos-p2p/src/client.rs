@@ -46,6 +46,22 @@ impl NetworkClient {
         .await
     }
 
+    /// Add a peer to the allowlist, only available when the gating mode is enabled
+    pub async fn allow_peer(&self, peer_id: PeerId) -> Result<(), P2PError> {
+        let (sender, receiver) = oneshot::channel();
+        let command = Command::AllowPeer { peer_id, sender };
+
+        Self::send_command_with_receiver(&self.sender, command, receiver).await
+    }
+
+    /// Remove a peer from the allowlist, only available when the gating mode is enabled
+    pub async fn disallow_peer(&self, peer_id: PeerId) -> Result<(), P2PError> {
+        let (sender, receiver) = oneshot::channel();
+        let command = Command::DisallowPeer { peer_id, sender };
+
+        Self::send_command_with_receiver(&self.sender, command, receiver).await
+    }
+
     pub async fn disconnect(&self) -> Result<(), P2PError> {
         let (sender, receiver) = oneshot::channel();
         let command = Command::Disconnect { sender };
@@ -47,6 +47,18 @@ pub enum Command {
         response: oneshot::Sender<OutboundConnection>,
     },
 
+    /// Add a peer to the allowlist of the gating
+    AllowPeer {
+        peer_id: PeerId,
+        sender: oneshot::Sender<Result<(), P2PError>>,
+    },
+
+    /// Remove a peer from the allowlist of the gating, closing its connections
+    DisallowPeer {
+        peer_id: PeerId,
+        sender: oneshot::Sender<Result<(), P2PError>>,
+    },
+
     /// Ask for a random known peer
     RandomKnownPeer {
         sender: oneshot::Sender<Result<PeerId, P2PError>>,
@@ -63,6 +75,8 @@ impl Display for Command {
             Command::Gossip { .. } => write!(f, "GossipMessage"),
             Command::NewProxiedQuery { .. } => write!(f, "NewProxiedQuery"),
             Command::Discover { to, .. } => write!(f, "Discover(to: {to})"),
+            Command::AllowPeer { peer_id, .. } => write!(f, "AllowPeer({peer_id})"),
+            Command::DisallowPeer { peer_id, .. } => write!(f, "DisallowPeer({peer_id})"),
         }
     }
 }
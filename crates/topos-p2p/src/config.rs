@@ -1,12 +1,19 @@
-use std::{num::NonZeroUsize, time::Duration};
+use std::{collections::HashSet, num::NonZeroUsize, sync::Arc, time::Duration};
+
+use libp2p::{connection_limits::ConnectionLimits, PeerId};
 
 pub struct NetworkConfig {
     pub publish_retry: usize,
     pub minimum_cluster_size: usize,
     pub client_retry_ttl: u64,
     pub discovery: DiscoveryConfig,
+    pub limits: LimitsConfig,
+    /// Restrict the peers allowed to connect, the node is open to any peer if not set
+    pub gating: Option<GatingConfig>,
     pub yamux_max_buffer_size: usize,
     pub yamux_window_size: Option<u32>,
+    /// Dial and publish QUIC addresses first when the QUIC transport is enabled
+    pub prefer_quic: bool,
 }
 
 impl Default for NetworkConfig {
@@ -16,8 +23,11 @@ impl Default for NetworkConfig {
             minimum_cluster_size: Self::MINIMUM_CLUSTER_SIZE,
             client_retry_ttl: Self::CLIENT_RETRY_TTL,
             discovery: Default::default(),
-            yamux_max_buffer_size: usize::MAX,
+            limits: Default::default(),
+            gating: None,
+            yamux_max_buffer_size: Self::YAMUX_MAX_BUFFER_SIZE,
             yamux_window_size: None,
+            prefer_quic: true,
         }
     }
 }
@@ -26,6 +36,7 @@ impl NetworkConfig {
     pub const MINIMUM_CLUSTER_SIZE: usize = 5;
     pub const PUBLISH_RETRY: usize = 10;
     pub const CLIENT_RETRY_TTL: u64 = 200;
+    pub const YAMUX_MAX_BUFFER_SIZE: usize = 1024 * 1024 * 16;
 }
 
 pub struct DiscoveryConfig {
@@ -53,3 +64,122 @@ impl DiscoveryConfig {
         self
     }
 }
+
+/// Resource limits applied to the swarm, preventing a single peer or a burst of
+/// connections to exhaust the node.
+pub struct LimitsConfig {
+    pub max_pending_incoming: Option<u32>,
+    pub max_pending_outgoing: Option<u32>,
+    pub max_established_incoming: Option<u32>,
+    pub max_established_outgoing: Option<u32>,
+    pub max_established_per_peer: Option<u32>,
+    /// New connections are denied once the process memory goes above this
+    /// percentage (between 0 and 1) of the total system memory
+    pub max_memory_percentage: Option<f64>,
+    /// Maximum number of concurrent gRPC-over-p2p streams accepted from a single peer
+    pub max_inbound_grpc_streams_per_peer: usize,
+    /// Maximum number of concurrent requests served on a single gRPC-over-p2p stream
+    pub max_concurrent_grpc_requests_per_stream: usize,
+    /// Maximum number of gRPC-over-p2p connections being opened at the same time
+    pub max_pending_outbound_grpc_connections: usize,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_incoming: Some(Self::MAX_PENDING_INCOMING),
+            max_pending_outgoing: Some(Self::MAX_PENDING_OUTGOING),
+            max_established_incoming: Some(Self::MAX_ESTABLISHED_INCOMING),
+            max_established_outgoing: Some(Self::MAX_ESTABLISHED_OUTGOING),
+            max_established_per_peer: Some(Self::MAX_ESTABLISHED_PER_PEER),
+            max_memory_percentage: None,
+            max_inbound_grpc_streams_per_peer: Self::MAX_INBOUND_GRPC_STREAMS_PER_PEER,
+            max_concurrent_grpc_requests_per_stream: Self::MAX_CONCURRENT_GRPC_REQUESTS_PER_STREAM,
+            max_pending_outbound_grpc_connections: Self::MAX_PENDING_OUTBOUND_GRPC_CONNECTIONS,
+        }
+    }
+}
+
+impl LimitsConfig {
+    pub const MAX_PENDING_INCOMING: u32 = 128;
+    pub const MAX_PENDING_OUTGOING: u32 = 128;
+    pub const MAX_ESTABLISHED_INCOMING: u32 = 256;
+    pub const MAX_ESTABLISHED_OUTGOING: u32 = 256;
+    pub const MAX_ESTABLISHED_PER_PEER: u32 = 4;
+    pub const MAX_INBOUND_GRPC_STREAMS_PER_PEER: usize = 8;
+    pub const MAX_CONCURRENT_GRPC_REQUESTS_PER_STREAM: usize = 32;
+    pub const MAX_PENDING_OUTBOUND_GRPC_CONNECTIONS: usize = 64;
+
+    pub fn with_max_established_incoming(mut self, limit: Option<u32>) -> Self {
+        self.max_established_incoming = limit;
+
+        self
+    }
+
+    pub fn with_max_established_per_peer(mut self, limit: Option<u32>) -> Self {
+        self.max_established_per_peer = limit;
+
+        self
+    }
+
+    pub fn with_max_memory_percentage(mut self, percentage: Option<f64>) -> Self {
+        self.max_memory_percentage = percentage;
+
+        self
+    }
+
+    pub fn with_max_inbound_grpc_streams_per_peer(mut self, limit: usize) -> Self {
+        self.max_inbound_grpc_streams_per_peer = limit;
+
+        self
+    }
+
+    pub fn with_max_concurrent_grpc_requests_per_stream(mut self, limit: usize) -> Self {
+        self.max_concurrent_grpc_requests_per_stream = limit;
+
+        self
+    }
+
+    pub fn with_max_pending_outbound_grpc_connections(mut self, limit: usize) -> Self {
+        self.max_pending_outbound_grpc_connections = limit;
+
+        self
+    }
+
+    pub(crate) fn connection_limits(&self) -> ConnectionLimits {
+        ConnectionLimits::default()
+            .with_max_pending_incoming(self.max_pending_incoming)
+            .with_max_pending_outgoing(self.max_pending_outgoing)
+            .with_max_established_incoming(self.max_established_incoming)
+            .with_max_established_outgoing(self.max_established_outgoing)
+            .with_max_established_per_peer(self.max_established_per_peer)
+    }
+}
+
+/// Verifies the proof presented by a peer in response to the given challenge
+pub type PeerProofVerifier = Arc<dyn Fn(&PeerId, &[u8], &str) -> bool + Send + Sync>;
+
+/// Produces the proof of the local node for the challenge sent by a remote peer
+pub type PeerProofSigner = Arc<dyn Fn(&[u8]) -> Option<String> + Send + Sync>;
+
+/// Allowlist mode used by validators, only the allowed peers and the peers
+/// presenting a valid proof are able to stay connected with the node.
+#[derive(Default)]
+pub struct GatingConfig {
+    pub allowed_peers: HashSet<PeerId>,
+    pub proof_verifier: Option<PeerProofVerifier>,
+}
+
+impl GatingConfig {
+    pub fn with_allowed_peers(mut self, allowed_peers: HashSet<PeerId>) -> Self {
+        self.allowed_peers = allowed_peers;
+
+        self
+    }
+
+    pub fn with_proof_verifier(mut self, proof_verifier: PeerProofVerifier) -> Self {
+        self.proof_verifier = Some(proof_verifier);
+
+        self
+    }
+}
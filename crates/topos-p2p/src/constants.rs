@@ -29,6 +29,7 @@ lazy_static! {
 pub const DISCOVERY_PROTOCOL: &str = "/tce-disco/1";
 pub const PEER_INFO_PROTOCOL: &str = "/tce-peer-info/1";
 pub const GRPC_P2P_TOPOS_PROTOCOL: &str = "/topos-grpc-p2p/1.0";
+pub const PEER_PROOF_PROTOCOL: &str = "/topos-peer-proof/1";
 
 // FIXME: Considered as constant until customizable and exposed properly in the genesis file
 pub const TCE_BOOTNODE_PORT: u16 = 9090;
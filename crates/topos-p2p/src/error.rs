@@ -20,6 +20,8 @@ pub enum P2PError {
     DialError,
     #[error("Unable build a network: peer_key missing")]
     MissingPeerKey,
+    #[error("The gating mode is not enabled on this node")]
+    GatingDisabled,
 
     #[error(transparent)]
     CommandError(#[from] CommandExecutionError),
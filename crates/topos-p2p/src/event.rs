@@ -1,10 +1,14 @@
-use libp2p::{identify, kad::KademliaEvent, PeerId};
+use libp2p::{gossipsub::MessageId, identify, kad::KademliaEvent, request_response, PeerId};
 
 use crate::behaviour::grpc;
 
 #[derive(Debug)]
 pub struct GossipEvent {
     pub source: Option<PeerId>,
+    /// The peer that forwarded the message to us
+    pub propagation_source: PeerId,
+    /// Id of the message, used to report the result of its validation
+    pub message_id: MessageId,
     pub topic: &'static str,
     pub message: Vec<u8>,
 }
@@ -13,6 +17,7 @@ pub struct GossipEvent {
 pub enum ComposedEvent {
     Kademlia(Box<KademliaEvent>),
     PeerInfo(Box<identify::Event>),
+    PeerProof(Box<request_response::Event<Vec<u8>, String>>),
     Gossipsub(GossipEvent),
     Grpc(grpc::Event),
     Void,
@@ -35,6 +40,12 @@ impl From<identify::Event> for ComposedEvent {
     }
 }
 
+impl From<request_response::Event<Vec<u8>, String>> for ComposedEvent {
+    fn from(event: request_response::Event<Vec<u8>, String>) -> Self {
+        ComposedEvent::PeerProof(Box::new(event))
+    }
+}
+
 impl From<void::Void> for ComposedEvent {
     fn from(_: void::Void) -> Self {
         Self::Void
@@ -58,12 +58,23 @@ pub struct GrpcRouter {
 }
 
 impl GrpcRouter {
-    pub fn new(mut server: tonic::transport::Server) -> Self {
+    pub fn new(server: tonic::transport::Server) -> Self {
+        Self::new_with_limits(server, &config::LimitsConfig::default())
+    }
+
+    /// Create a router serving at most `max_concurrent_grpc_requests_per_stream`
+    /// requests at the same time on each gRPC-over-p2p stream
+    pub fn new_with_limits(
+        server: tonic::transport::Server,
+        limits: &config::LimitsConfig,
+    ) -> Self {
         let mut protocols = HashSet::new();
         protocols.insert(protocol_name!(InfoServiceServer::<GrpcP2pInfo>::NAME));
 
         Self {
-            server: server.add_optional_service::<InfoServiceServer<GrpcP2pInfo>>(None),
+            server: server
+                .concurrency_limit_per_connection(limits.max_concurrent_grpc_requests_per_stream)
+                .add_optional_service::<InfoServiceServer<GrpcP2pInfo>>(None),
             protocols,
         }
     }
@@ -1,7 +1,10 @@
 use super::{Behaviour, Event, NetworkClient, Runtime};
 use crate::{
-    behaviour::{discovery::DiscoveryBehaviour, gossip, grpc, peer_info::PeerInfoBehaviour},
-    config::{DiscoveryConfig, NetworkConfig},
+    behaviour::{
+        discovery::DiscoveryBehaviour, gating, gossip, grpc, peer_info::PeerInfoBehaviour,
+        peer_proof,
+    },
+    config::{DiscoveryConfig, GatingConfig, LimitsConfig, NetworkConfig, PeerProofSigner},
     constants::{
         self, COMMAND_STREAM_BUFFER_SIZE, DISCOVERY_PROTOCOL, EVENT_STREAM_BUFFER,
         PEER_INFO_PROTOCOL,
@@ -10,13 +13,14 @@ use crate::{
     utils::GrpcOverP2P,
     GrpcContext,
 };
-use futures::Stream;
+use futures::{future::Either, Stream};
 use libp2p::{
-    core::upgrade,
+    connection_limits,
+    core::{muxing::StreamMuxerBox, upgrade},
     dns::TokioDnsConfig,
     identity::Keypair,
     kad::store::MemoryStore,
-    noise,
+    memory_connection_limits, noise, quic,
     swarm::SwarmBuilder,
     tcp::{tokio::Transport, Config},
     Multiaddr, PeerId, Transport as TransportTrait,
@@ -41,8 +45,12 @@ pub struct NetworkBuilder<'a> {
     peer_key: Option<Keypair>,
     listen_addr: Option<Multiaddr>,
     exposed_addresses: Option<Multiaddr>,
+    quic_listen_addr: Option<Multiaddr>,
+    quic_exposed_addresses: Option<Multiaddr>,
     store: Option<MemoryStore>,
     known_peers: &'a [(PeerId, Multiaddr)],
+    explicit_peers: &'a [(PeerId, Multiaddr)],
+    peer_proof_signer: Option<PeerProofSigner>,
     local_port: Option<u8>,
     config: NetworkConfig,
     grpc_context: GrpcContext,
@@ -91,6 +99,32 @@ impl<'a> NetworkBuilder<'a> {
         self
     }
 
+    /// Enable the QUIC transport alongside TCP, listening on the given `/udp/../quic-v1` address
+    pub fn quic_listen_addr(mut self, addr: Multiaddr) -> Self {
+        self.quic_listen_addr = Some(addr);
+
+        self
+    }
+
+    pub fn quic_exposed_addresses(mut self, addr: Multiaddr) -> Self {
+        self.quic_exposed_addresses = Some(addr);
+
+        self
+    }
+
+    /// Prefer QUIC addresses over TCP ones when both are known for a peer
+    pub fn prefer_quic(mut self, prefer_quic: bool) -> Self {
+        self.config.prefer_quic = prefer_quic;
+
+        self
+    }
+
+    pub fn limits(mut self, limits: LimitsConfig) -> Self {
+        self.config.limits = limits;
+
+        self
+    }
+
     pub fn store(mut self, store: MemoryStore) -> Self {
         self.store = Some(store);
 
@@ -103,6 +137,28 @@ impl<'a> NetworkBuilder<'a> {
         self
     }
 
+    /// Peers always receiving our gossip and always allowed by the gating, used to
+    /// link a validator with its sentries
+    pub fn explicit_peers(mut self, explicit_peers: &'a [(PeerId, Multiaddr)]) -> Self {
+        self.explicit_peers = explicit_peers;
+
+        self
+    }
+
+    /// Enable the gating mode, restricting the peers allowed to connect
+    pub fn gating(mut self, gating: GatingConfig) -> Self {
+        self.config.gating = Some(gating);
+
+        self
+    }
+
+    /// Signer answering the challenges sent by the gated peers with the proof of the node
+    pub fn peer_proof_signer(mut self, signer: PeerProofSigner) -> Self {
+        self.peer_proof_signer = Some(signer);
+
+        self
+    }
+
     pub fn local_port(mut self, port: u8) -> Self {
         self.local_port = Some(port);
 
@@ -126,9 +182,30 @@ impl<'a> NetworkBuilder<'a> {
 
         let gossipsub = gossip::Behaviour::new(peer_key.clone()).await;
 
-        let grpc = grpc::Behaviour::new(self.grpc_context);
-
-        let behaviour = Behaviour {
+        let grpc = grpc::Behaviour::new(self.grpc_context)
+            .with_max_inbound_streams_per_peer(self.config.limits.max_inbound_grpc_streams_per_peer)
+            .with_max_pending_outbound_connections(
+                self.config.limits.max_pending_outbound_grpc_connections,
+            );
+
+        let gating = self.config.gating.take().map(|mut config| {
+            config
+                .allowed_peers
+                .extend(self.explicit_peers.iter().map(|(peer_id, _)| *peer_id));
+
+            gating::Behaviour::new(config)
+        });
+
+        let mut behaviour = Behaviour {
+            limits: connection_limits::Behaviour::new(self.config.limits.connection_limits()),
+            memory_limits: self
+                .config
+                .limits
+                .max_memory_percentage
+                .map(memory_connection_limits::Behaviour::with_max_percentage)
+                .into(),
+            gating: gating.into(),
+            peer_proof: peer_proof::new(),
             gossipsub,
             peer_info: PeerInfoBehaviour::new(PEER_INFO_PROTOCOL, &peer_key),
             discovery: DiscoveryBehaviour::create(
@@ -145,6 +222,11 @@ impl<'a> NetworkBuilder<'a> {
             grpc,
         };
 
+        for (peer_id, addr) in self.explicit_peers {
+            behaviour.discovery.inner.add_address(peer_id, addr.clone());
+            behaviour.gossipsub.add_explicit_peer(peer_id);
+        }
+
         let transport = {
             let dns_tcp =
                 TokioDnsConfig::system(Transport::new(Config::new().nodelay(true))).unwrap();
@@ -155,14 +237,32 @@ impl<'a> NetworkBuilder<'a> {
 
         let mut multiplex_config = libp2p::yamux::Config::default();
         multiplex_config.set_window_update_mode(libp2p::yamux::WindowUpdateMode::on_read());
-        multiplex_config.set_max_buffer_size(1024 * 1024 * 16);
+        multiplex_config.set_max_buffer_size(self.config.yamux_max_buffer_size);
+        if let Some(window_size) = self.config.yamux_window_size {
+            multiplex_config.set_receive_window_size(window_size);
+        }
 
-        let transport = transport
+        let tcp_transport = transport
             .upgrade(upgrade::Version::V1)
             .authenticate(noise::Config::new(&peer_key)?)
             .multiplex(multiplex_config)
-            .timeout(TWO_HOURS)
-            .boxed();
+            .timeout(TWO_HOURS);
+
+        // QUIC comes with its own encryption and stream multiplexing, it is only
+        // combined with the TCP stack when a QUIC listen address is provided.
+        let transport = if self.quic_listen_addr.is_some() {
+            let quic_transport = quic::tokio::Transport::new(quic::Config::new(&peer_key));
+
+            quic_transport
+                .or_transport(tcp_transport)
+                .map(|output, _| match output {
+                    Either::Left((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+                    Either::Right((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+                })
+                .boxed()
+        } else {
+            tcp_transport.boxed()
+        };
 
         let swarm = SwarmBuilder::with_tokio_executor(transport, behaviour, peer_id)
             .idle_connection_timeout(constants::IDLE_CONNECTION_TIMEOUT)
@@ -184,7 +284,9 @@ impl<'a> NetworkBuilder<'a> {
                 swarm,
                 config: self.config,
                 peer_set: self.known_peers.iter().map(|(p, _)| *p).collect(),
+                unproven_peers: HashMap::new(),
                 is_boot_node: self.known_peers.is_empty(),
+                peer_proof_signer: self.peer_proof_signer,
                 command_receiver,
                 event_sender,
                 local_peer_id: peer_id,
@@ -196,6 +298,8 @@ impl<'a> NetworkBuilder<'a> {
                     .exposed_addresses
                     .take()
                     .expect("P2P runtime expect a MultiAddr"),
+                quic_listening_on: self.quic_listen_addr.take(),
+                quic_addresses: self.quic_exposed_addresses.take(),
                 bootstrapped: false,
                 active_listeners: HashSet::new(),
                 pending_record_requests: HashMap::new(),
@@ -68,6 +68,36 @@ impl Runtime {
                 }
             }
 
+            Command::AllowPeer { peer_id, sender } => {
+                let result = match self.swarm.behaviour_mut().gating.as_mut() {
+                    Some(gating) => {
+                        gating.allow_peer(peer_id);
+                        self.promote_unproven_peer(&peer_id);
+                        Ok(())
+                    }
+                    None => Err(P2PError::GatingDisabled),
+                };
+
+                if sender.send(result).is_err() {
+                    warn!("Unable to notify AllowPeer response: initiator is dropped");
+                }
+            }
+
+            Command::DisallowPeer { peer_id, sender } => {
+                let result = match self.swarm.behaviour_mut().gating.as_mut() {
+                    Some(gating) => {
+                        gating.disallow_peer(peer_id);
+                        self.forget_peer(&peer_id);
+                        Ok(())
+                    }
+                    None => Err(P2PError::GatingDisabled),
+                };
+
+                if sender.send(result).is_err() {
+                    warn!("Unable to notify DisallowPeer response: initiator is dropped");
+                }
+            }
+
             Command::Disconnect { sender } if self.swarm.listeners().count() == 0 => {
                 if sender.send(Err(P2PError::AlreadyDisconnected)).is_err() {
                     warn!(
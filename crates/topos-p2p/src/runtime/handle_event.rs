@@ -1,15 +1,16 @@
 use libp2p::{
     multiaddr::Protocol,
-    swarm::{derive_prelude::Either, SwarmEvent},
+    swarm::{SwarmEvent, THandlerErr},
 };
 use tracing::{debug, error, info, warn};
 
-use crate::{event::ComposedEvent, Event, Runtime};
+use crate::{event::ComposedEvent, Behaviour, Event, Runtime};
 
 mod discovery;
 mod gossipsub;
 mod grpc;
 mod peer_info;
+mod peer_proof;
 
 #[async_trait::async_trait]
 pub(crate) trait EventHandler<T> {
@@ -31,6 +32,7 @@ impl EventHandler<ComposedEvent> for Runtime {
         match event {
             ComposedEvent::Kademlia(event) => self.handle(event).await,
             ComposedEvent::PeerInfo(event) => self.handle(event).await,
+            ComposedEvent::PeerProof(event) => self.handle(event).await,
             ComposedEvent::Gossipsub(event) => self.handle(event).await,
             ComposedEvent::Grpc(event) => self.handle(event).await,
             ComposedEvent::Void => (),
@@ -39,21 +41,8 @@ impl EventHandler<ComposedEvent> for Runtime {
 }
 
 #[async_trait::async_trait]
-impl
-    EventHandler<
-        SwarmEvent<
-            ComposedEvent,
-            Either<Either<Either<std::io::Error, std::io::Error>, void::Void>, void::Void>,
-        >,
-    > for Runtime
-{
-    async fn handle(
-        &mut self,
-        event: SwarmEvent<
-            ComposedEvent,
-            Either<Either<Either<std::io::Error, std::io::Error>, void::Void>, void::Void>,
-        >,
-    ) {
+impl EventHandler<SwarmEvent<ComposedEvent, THandlerErr<Behaviour>>> for Runtime {
+    async fn handle(&mut self, event: SwarmEvent<ComposedEvent, THandlerErr<Behaviour>>) {
         match event {
             SwarmEvent::NewListenAddr {
                 listener_id,
@@ -74,12 +63,26 @@ impl
             }
 
             SwarmEvent::ConnectionEstablished {
-                peer_id, endpoint, ..
+                peer_id,
+                endpoint,
+                num_established,
+                ..
             } => {
                 info!(
                     "Connection established with peer {peer_id} as {:?}",
                     endpoint.to_endpoint()
                 );
+
+                let behaviour = self.swarm.behaviour_mut();
+                if let Some(challenge) = behaviour
+                    .gating
+                    .as_ref()
+                    .filter(|_| num_established.get() == 1)
+                    .and_then(|gating| gating.challenge(&peer_id))
+                {
+                    debug!("Challenging provisional peer {peer_id} for its proof");
+                    behaviour.peer_proof.send_request(&peer_id, challenge);
+                }
             }
 
             incoming_connection_error @ SwarmEvent::IncomingConnectionError { .. } => {
@@ -105,8 +108,16 @@ impl
                 );
             }
 
-            SwarmEvent::ConnectionClosed { peer_id, cause, .. } => {
+            SwarmEvent::ConnectionClosed {
+                peer_id,
+                cause,
+                num_established,
+                ..
+            } => {
                 debug!("ConnectionClosed {peer_id} because of {cause:?}");
+                if num_established == 0 {
+                    self.unproven_peers.remove(&peer_id);
+                }
             }
 
             SwarmEvent::Dialing {
@@ -1,10 +1,7 @@
-use libp2p::{
-    kad::{GetRecordOk, KademliaEvent, QueryResult},
-    Multiaddr,
-};
+use libp2p::kad::{GetRecordOk, KademliaEvent, QueryResult};
 use tracing::{debug, error, warn};
 
-use crate::{error::CommandExecutionError, Runtime};
+use crate::{error::CommandExecutionError, runtime::decode_record_addresses, Runtime};
 
 use super::EventHandler;
 
@@ -57,25 +54,43 @@ impl EventHandler<Box<KademliaEvent>> for Runtime {
                 Ok(GetRecordOk::FoundRecord(result)) => {
                     debug!("GetRecordOk query: {id:?}, {result:?}");
                     if let Some(sender) = self.pending_record_requests.remove(&id) {
-                        if let Ok(addr) = Multiaddr::try_from(result.record.value.clone()) {
-                            if let Some(peer_id) = result.record.publisher {
-                                if !sender.is_closed() {
+                        let response = match (
+                            result.record.publisher,
+                            decode_record_addresses(&result.record.value),
+                        ) {
+                            (Some(peer_id), Some(addrs)) => {
+                                let addrs = self.dialable_addresses(addrs);
+                                for addr in &addrs {
                                     debug!("Adding {peer_id:?} address {addr:?} to DHT");
                                     self.swarm
                                         .behaviour_mut()
                                         .discovery
                                         .inner
                                         .add_address(&peer_id, addr.clone());
-
-                                    if sender.send(Ok(vec![addr.clone()])).is_err() {
-                                        // TODO: Hash the QueryId
-                                        warn!(
-                                            "Could not notify Record query ({id:?}) response \
-                                             because initiator is dropped"
-                                        );
-                                    }
                                 }
+
+                                Ok(addrs)
+                            }
+                            (None, _) => {
+                                warn!("Record of query {id:?} has no publisher");
+
+                                Err(CommandExecutionError::DHTGetRecordFailed)
+                            }
+                            (_, None) => {
+                                warn!(
+                                    "Unable to decode the addresses of the record of query {id:?}"
+                                );
+
+                                Err(CommandExecutionError::DHTGetRecordFailed)
                             }
+                        };
+
+                        if sender.send(response).is_err() {
+                            // TODO: Hash the QueryId
+                            warn!(
+                                "Could not notify Record query ({id:?}) response because \
+                                 initiator is dropped"
+                            );
                         }
                     }
                 }
@@ -6,6 +6,7 @@ use topos_metrics::{
 use tracing::{debug, error};
 
 use crate::{constants, event::GossipEvent, Event, Runtime, TOPOS_ECHO, TOPOS_GOSSIP, TOPOS_READY};
+use libp2p::gossipsub::MessageAcceptance;
 use prost::Message;
 use topos_api::grpc::tce::v1::Batch;
 
@@ -14,10 +15,44 @@ use super::EventHandler;
 #[async_trait::async_trait]
 impl EventHandler<GossipEvent> for Runtime {
     async fn handle(&mut self, event: GossipEvent) {
+        let allowed = self
+            .swarm
+            .behaviour()
+            .gating
+            .as_ref()
+            .map_or(true, |gating| gating.is_allowed(&event.propagation_source));
+
+        // Messages forwarded by provisional or unallowed peers are rejected before being
+        // relayed to the other peers
+        let acceptance = if allowed {
+            MessageAcceptance::Accept
+        } else {
+            debug!(
+                "Rejecting message on topic {:?} forwarded by unallowed peer {}",
+                event.topic, event.propagation_source
+            );
+
+            MessageAcceptance::Reject
+        };
+
+        self.swarm
+            .behaviour_mut()
+            .gossipsub
+            .report_message_validation_result(
+                &event.message_id,
+                &event.propagation_source,
+                acceptance,
+            );
+
+        if !allowed {
+            return;
+        }
+
         if let GossipEvent {
             source: Some(source),
             message,
             topic,
+            ..
         } = event
         {
             if self.event_sender.capacity() < *constants::CAPACITY_EVENT_STREAM_BUFFER {
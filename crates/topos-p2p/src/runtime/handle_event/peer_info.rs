@@ -1,5 +1,5 @@
 use libp2p::identify::{Event as IdentifyEvent, Info as IdentifyInfo};
-use tracing::info;
+use tracing::debug;
 
 use crate::{constants::PEER_INFO_PROTOCOL, Runtime};
 
@@ -17,22 +17,24 @@ impl EventHandler<Box<IdentifyEvent>> for Runtime {
                 ..
             } = info;
 
-            if !self.peer_set.contains(&peer_id)
-                && protocol_version.as_bytes() == PEER_INFO_PROTOCOL.as_bytes()
-            {
-                self.peer_set.insert(peer_id);
-                for addr in listen_addrs {
-                    info!(
-                        "Adding self-reported address {} from {} to Kademlia DHT.",
-                        addr, peer_id
-                    );
-                    self.swarm
-                        .behaviour_mut()
-                        .discovery
-                        .inner
-                        .add_address(&peer_id, addr);
+            if protocol_version.as_bytes() != PEER_INFO_PROTOCOL.as_bytes() {
+                return;
+            }
+
+            if let Some(gating) = self.swarm.behaviour().gating.as_ref() {
+                if gating.is_provisional(&peer_id) {
+                    debug!("Keeping provisional peer {peer_id} out of the peer set until proven");
+                    self.unproven_peers.insert(peer_id, listen_addrs);
+
+                    return;
+                }
+
+                if !gating.is_allowed(&peer_id) {
+                    return;
                 }
             }
+
+            self.add_known_peer(peer_id, listen_addrs);
         }
     }
 }
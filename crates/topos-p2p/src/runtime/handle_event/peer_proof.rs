@@ -0,0 +1,66 @@
+use libp2p::request_response::{Event, Message};
+use tracing::{debug, warn};
+
+use crate::Runtime;
+
+use super::EventHandler;
+
+#[async_trait::async_trait]
+impl EventHandler<Box<Event<Vec<u8>, String>>> for Runtime {
+    async fn handle(&mut self, event: Box<Event<Vec<u8>, String>>) {
+        match *event {
+            Event::Message {
+                peer,
+                message:
+                    Message::Request {
+                        request, channel, ..
+                    },
+            } => {
+                let Some(proof) = self
+                    .peer_proof_signer
+                    .as_ref()
+                    .and_then(|signer| signer(&request))
+                else {
+                    debug!("No proof to answer the challenge of {peer}");
+
+                    return;
+                };
+
+                if self
+                    .swarm
+                    .behaviour_mut()
+                    .peer_proof
+                    .send_response(channel, proof)
+                    .is_err()
+                {
+                    warn!("Unable to answer the challenge of {peer}, the channel is closed");
+                }
+            }
+            Event::Message {
+                peer,
+                message: Message::Response { response, .. },
+            } => {
+                if let Some(gating) = self.swarm.behaviour_mut().gating.as_mut() {
+                    if gating.verify_proof(&peer, &response) {
+                        self.promote_unproven_peer(&peer);
+                    } else {
+                        self.forget_peer(&peer);
+                    }
+                }
+            }
+            Event::OutboundFailure { peer, error, .. } => {
+                if let Some(gating) = self.swarm.behaviour_mut().gating.as_mut() {
+                    if gating.is_provisional(&peer) {
+                        warn!("Peer {peer} failed to answer its challenge: {error}");
+                        gating.disallow_peer(peer);
+                        self.forget_peer(&peer);
+                    }
+                }
+            }
+            Event::InboundFailure { peer, error, .. } => {
+                debug!("Inbound challenge from {peer} failed: {error}");
+            }
+            Event::ResponseSent { .. } => {}
+        }
+    }
+}
@@ -1,8 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    behaviour::discovery::PendingRecordRequest, config::NetworkConfig, error::P2PError,
-    event::ComposedEvent, runtime::handle_event::EventHandler, Behaviour, Command, Event,
+    behaviour::discovery::PendingRecordRequest,
+    config::{NetworkConfig, PeerProofSigner},
+    error::P2PError,
+    event::ComposedEvent,
+    runtime::handle_event::EventHandler,
+    Behaviour, Command, Event,
 };
 use libp2p::{
     core::transport::ListenerId,
@@ -10,6 +14,7 @@ use libp2p::{
         record::Key, BootstrapOk, KademliaEvent, PutRecordError, QueryId, QueryResult, Quorum,
         Record,
     },
+    multiaddr::Protocol,
     swarm::SwarmEvent,
     Multiaddr, PeerId, Swarm,
 };
@@ -21,6 +26,9 @@ pub struct Runtime {
     pub(crate) config: NetworkConfig,
     // TODO: check if needed
     pub(crate) peer_set: HashSet<PeerId>,
+    /// Self-reported addresses of the provisional peers, kept out of the peer set and
+    /// of the DHT until they present a valid proof
+    pub(crate) unproven_peers: HashMap<PeerId, Vec<Multiaddr>>,
     pub(crate) swarm: Swarm<Behaviour>,
     pub(crate) command_receiver: mpsc::Receiver<Command>,
     pub(crate) event_sender: mpsc::Sender<Event>,
@@ -28,8 +36,14 @@ pub struct Runtime {
     pub(crate) listening_on: Multiaddr,
     #[allow(unused)]
     pub(crate) addresses: Multiaddr,
+    /// QUIC listen address, the QUIC transport is disabled if not set
+    pub(crate) quic_listening_on: Option<Multiaddr>,
+    /// QUIC address exposed to the other peers
+    pub(crate) quic_addresses: Option<Multiaddr>,
     pub(crate) bootstrapped: bool,
     pub(crate) is_boot_node: bool,
+    /// Answers the challenges of the gated peers, challenges are left unanswered if not set
+    pub(crate) peer_proof_signer: Option<PeerProofSigner>,
 
     /// Contains current listenerId of the swarm
     pub active_listeners: HashSet<ListenerId>,
@@ -42,9 +56,76 @@ pub struct Runtime {
 }
 
 mod handle_command;
-mod handle_event;
+pub(crate) mod handle_event;
 
 impl Runtime {
+    /// Addresses published in the DHT for other peers to dial us, both the TCP and
+    /// the QUIC addresses are published and the dialer picks the ones it supports.
+    pub(crate) fn published_addresses(&self) -> Vec<Multiaddr> {
+        let mut addresses = vec![self.addresses.clone()];
+        addresses.extend(self.quic_addresses.clone());
+
+        self.dialable_addresses(addresses)
+    }
+
+    /// Filter out the QUIC addresses if the QUIC transport is disabled, otherwise
+    /// order them first when QUIC is preferred.
+    pub(crate) fn dialable_addresses(&self, mut addresses: Vec<Multiaddr>) -> Vec<Multiaddr> {
+        if self.quic_listening_on.is_none() {
+            addresses.retain(|addr| !is_quic(addr));
+        } else if self.config.prefer_quic {
+            // Stable sort, QUIC addresses end up first in the order they were reported
+            addresses.sort_by_key(|addr| !is_quic(addr));
+        }
+
+        addresses
+    }
+
+    /// Add the peer to the peer set and its self-reported addresses to the DHT
+    pub(crate) fn add_known_peer(&mut self, peer_id: PeerId, listen_addrs: Vec<Multiaddr>) {
+        if !self.peer_set.insert(peer_id) {
+            return;
+        }
+
+        for addr in self.dialable_addresses(listen_addrs) {
+            info!(
+                "Adding self-reported address {} from {} to Kademlia DHT.",
+                addr, peer_id
+            );
+            self.swarm
+                .behaviour_mut()
+                .discovery
+                .inner
+                .add_address(&peer_id, addr);
+        }
+    }
+
+    /// Add a provisional peer that proved itself to the peer set and the DHT
+    pub(crate) fn promote_unproven_peer(&mut self, peer_id: &PeerId) {
+        if let Some(listen_addrs) = self.unproven_peers.remove(peer_id) {
+            self.add_known_peer(*peer_id, listen_addrs);
+        }
+    }
+
+    /// Remove the peer from the peer set and the DHT
+    pub(crate) fn forget_peer(&mut self, peer_id: &PeerId) {
+        self.unproven_peers.remove(peer_id);
+        self.peer_set.remove(peer_id);
+        self.swarm
+            .behaviour_mut()
+            .discovery
+            .inner
+            .remove_peer(peer_id);
+    }
+
+    pub(crate) fn published_record(&self) -> Result<Record, P2PError> {
+        let key = Key::new(&self.local_peer_id.to_string());
+        let value = bincode::serialize(&self.published_addresses())
+            .map_err(|_| P2PError::BootstrapError("Unable to encode the addr Record"))?;
+
+        Ok(Record::new(key, value))
+    }
+
     fn start_listening(&mut self, peer_addr: Multiaddr) -> Result<(), P2PError> {
         self.swarm
             .listen_on(peer_addr)
@@ -73,6 +154,21 @@ impl Runtime {
             return Err(Box::new(error));
         }
 
+        if let Some(quic_addr) = self.quic_listening_on.clone() {
+            if let Err(error) = self.swarm.listen_on(quic_addr) {
+                error!(
+                    "Couldn't start listening on {:?} because of {error:?}",
+                    self.quic_listening_on
+                );
+
+                return Err(Box::new(error));
+            }
+
+            if let Some(quic_addresses) = self.quic_addresses.clone() {
+                self.swarm.add_external_address(quic_addresses);
+            }
+        }
+
         debug!("Starting a boot node ? {:?}", self.is_boot_node);
         if !self.is_boot_node {
             // First we need to be known and known some peers before publishing our addresses to
@@ -110,12 +206,14 @@ impl Runtime {
                                 "Publishing our addresses to the network ! We have {} peers",
                                 self.peer_set.len()
                             );
-                            let key = Key::new(&self.local_peer_id.to_string());
-                            addr_query_id = if let Ok(query_id_record) =
-                                self.swarm.behaviour_mut().discovery.inner.put_record(
-                                    Record::new(key, self.addresses.to_vec()),
-                                    Quorum::Majority,
-                                ) {
+                            let record = self.published_record()?;
+                            addr_query_id = if let Ok(query_id_record) = self
+                                .swarm
+                                .behaviour_mut()
+                                .discovery
+                                .inner
+                                .put_record(record, Quorum::Majority)
+                            {
                                 Some(query_id_record)
                             } else {
                                 return Err(Box::new(P2PError::BootstrapError(
@@ -166,12 +264,13 @@ impl Runtime {
                                     "QuorumFailure on DHT addr publication: key: {key:?}, \
                                      success: {success:?}, quorum: {quorum:?}, stats: {stats:?}"
                                 );
-                                let key = Key::new(&self.local_peer_id.to_string());
-                                if let Ok(query_id_record) =
-                                    self.swarm.behaviour_mut().discovery.inner.put_record(
-                                        Record::new(key, self.addresses.to_vec()),
-                                        Quorum::Majority,
-                                    )
+                                let record = self.published_record()?;
+                                if let Ok(query_id_record) = self
+                                    .swarm
+                                    .behaviour_mut()
+                                    .discovery
+                                    .inner
+                                    .put_record(record, Quorum::Majority)
                                 {
                                     addr_query_id = Some(query_id_record);
                                 } else {
@@ -217,7 +316,12 @@ impl Runtime {
                             event => warn!("Unhandle Kademlia event during Bootstrap: {event:?}"),
                         }
                     }
-                    SwarmEvent::ConnectionEstablished { .. } => {}
+                    // Provisional peers are challenged and challenges answered during the bootstrap
+                    // as well, gated peers would otherwise drop our connections
+                    event @ SwarmEvent::ConnectionEstablished { .. } => self.handle(event).await,
+                    SwarmEvent::Behaviour(ComposedEvent::PeerProof(event)) => {
+                        self.handle(event).await
+                    }
                     SwarmEvent::Dialing { .. } => {}
                     SwarmEvent::IncomingConnection { .. } => {}
                     SwarmEvent::NewListenAddr { .. } => {}
@@ -268,3 +372,20 @@ impl Runtime {
         Ok(())
     }
 }
+
+/// Decode the addresses held by a DHT record, the records published by older nodes
+/// holding a single address in its binary form
+pub(crate) fn decode_record_addresses(value: &[u8]) -> Option<Vec<Multiaddr>> {
+    bincode::deserialize::<Vec<Multiaddr>>(value)
+        .ok()
+        .or_else(|| {
+            Multiaddr::try_from(value.to_vec())
+                .ok()
+                .map(|addr| vec![addr])
+        })
+}
+
+fn is_quic(addr: &Multiaddr) -> bool {
+    addr.iter()
+        .any(|protocol| matches!(protocol, Protocol::QuicV1))
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashSet, future::IntoFuture, time::Duration};
 
-use libp2p::Swarm;
+use libp2p::{swarm::SwarmEvent, Swarm};
 use libp2p_swarm_test::SwarmExt;
 use rstest::rstest;
 use test_log::test;
@@ -306,3 +306,78 @@ async fn incompatible_protocol() {
         ))
     ));
 }
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn inbound_stream_limit_reached() {
+    let dummy = DummyServer {};
+
+    let router = GrpcContext::default()
+        .with_router(GrpcRouter::new(Server::builder()).add_service(GreeterServer::new(dummy)));
+
+    let mut client_swarm = Swarm::new_ephemeral(|_| grpc::Behaviour::new(GrpcContext::default()));
+    let mut server_swarm =
+        Swarm::new_ephemeral(|_| grpc::Behaviour::new(router).with_max_inbound_streams_per_peer(0));
+
+    let server_peer_id = *server_swarm.local_peer_id();
+    let client_peer_id = *client_swarm.local_peer_id();
+
+    server_swarm.listen().await;
+    client_swarm.connect(&mut server_swarm).await;
+
+    let _outbound_connection = client_swarm.behaviour_mut().open_outbound_connection(
+        &server_peer_id,
+        protocol_name!(GreeterServer::<DummyServer>::NAME),
+    );
+
+    spawn(async move {
+        loop {
+            client_swarm.next_swarm_event().await;
+        }
+    });
+
+    loop {
+        if let SwarmEvent::Behaviour(grpc::Event::InboundStreamRejected { peer_id, .. }) =
+            server_swarm.next_swarm_event().await
+        {
+            assert_eq!(peer_id, client_peer_id);
+            break;
+        }
+    }
+
+    assert_eq!(
+        server_swarm
+            .behaviour()
+            .inbound_streams_count(&client_peer_id),
+        0
+    );
+}
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn pending_outbound_connections_limit_reached() {
+    let mut client_swarm = Swarm::new_ephemeral(|_| {
+        grpc::Behaviour::new(GrpcContext::default()).with_max_pending_outbound_connections(1)
+    });
+
+    let protocol = protocol_name!(GreeterServer::<DummyServer>::NAME);
+
+    let _opening = client_swarm
+        .behaviour_mut()
+        .open_outbound_connection(&libp2p::PeerId::random(), protocol.clone());
+
+    let result = client_swarm
+        .behaviour_mut()
+        .open_outbound_connection(&libp2p::PeerId::random(), protocol)
+        .into_future()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(OutboundConnectionError::Outbound(
+            OutboundError::TooManyPendingConnections(1)
+        ))
+    ));
+}
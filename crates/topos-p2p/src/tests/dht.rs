@@ -2,14 +2,22 @@ use std::{num::NonZeroUsize, time::Duration};
 
 use futures::StreamExt;
 use libp2p::{
-    kad::{record::Key, KademliaEvent, PutRecordOk, QueryResult, Record},
+    kad::{
+        record::{Key, Record},
+        GetRecordOk, KademliaEvent, PeerRecord, ProgressStep, PutRecordOk, QueryResult, QueryStats,
+    },
     swarm::SwarmEvent,
+    Multiaddr,
 };
 use rstest::rstest;
 use test_log::test;
+use tokio::sync::oneshot;
 use topos_test_sdk::tce::NodeConfig;
 
-use crate::{config::DiscoveryConfig, event::ComposedEvent, wait_for_event};
+use crate::{
+    config::DiscoveryConfig, error::CommandExecutionError, event::ComposedEvent,
+    runtime::handle_event::EventHandler, wait_for_event,
+};
 
 #[rstest]
 #[test(tokio::test)]
@@ -34,15 +42,13 @@ async fn put_value_in_dht() {
         .expect("Unable to create p2p network");
 
     let mut runtime = runtime.bootstrap().await.unwrap();
-    let kad = &mut runtime.swarm.behaviour_mut().discovery;
 
     let input_key = Key::new(&runtime.local_peer_id.to_string());
+    let record = runtime.published_record().unwrap();
+    let kad = &mut runtime.swarm.behaviour_mut().discovery;
     _ = kad
         .inner
-        .put_record(
-            Record::new(input_key.clone(), runtime.addresses.to_vec()),
-            libp2p::kad::Quorum::One,
-        )
+        .put_record(record, libp2p::kad::Quorum::One)
         .unwrap();
 
     let mut swarm = runtime.swarm;
@@ -54,3 +60,80 @@ async fn put_value_in_dht() {
 
     join.abort();
 }
+
+#[rstest]
+#[case::addresses(bincode::serialize(&vec![record_addr()]).unwrap(), true)]
+#[case::legacy_single_address(record_addr().to_vec(), true)]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn get_record_decodes_the_addresses(#[case] value: Vec<u8>, #[case] with_publisher: bool) {
+    let result = get_record(value, with_publisher).await;
+
+    assert_eq!(result.unwrap(), vec![record_addr()]);
+}
+
+#[rstest]
+#[case::undecodable(vec![0xff; 4], true)]
+#[case::no_publisher(bincode::serialize(&vec![record_addr()]).unwrap(), false)]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn get_record_answers_failures(#[case] value: Vec<u8>, #[case] with_publisher: bool) {
+    let result = get_record(value, with_publisher).await;
+
+    assert!(matches!(
+        result,
+        Err(CommandExecutionError::DHTGetRecordFailed)
+    ));
+}
+
+fn record_addr() -> Multiaddr {
+    "/ip4/127.0.0.1/tcp/9090".parse().unwrap()
+}
+
+/// Feed the runtime with a record found for a pending discovery and return its answer
+async fn get_record(
+    value: Vec<u8>,
+    with_publisher: bool,
+) -> Result<Vec<Multiaddr>, CommandExecutionError> {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_2 = NodeConfig::from_seed(2);
+
+    let (_, _, mut runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let key = Key::new(&peer_2.peer_id().to_string());
+    let id = runtime
+        .swarm
+        .behaviour_mut()
+        .discovery
+        .inner
+        .get_record(key.clone());
+
+    let (sender, receiver) = oneshot::channel();
+    runtime.pending_record_requests.insert(id, sender);
+
+    let mut record = Record::new(key, value);
+    record.publisher = with_publisher.then(|| peer_2.peer_id());
+
+    runtime
+        .handle(Box::new(KademliaEvent::OutboundQueryProgressed {
+            id,
+            result: QueryResult::GetRecord(Ok(GetRecordOk::FoundRecord(PeerRecord {
+                peer: None,
+                record,
+            }))),
+            stats: QueryStats::empty(),
+            step: ProgressStep {
+                count: NonZeroUsize::new(1).unwrap(),
+                last: true,
+            },
+        }))
+        .await;
+
+    receiver.await.unwrap()
+}
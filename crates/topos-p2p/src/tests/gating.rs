@@ -0,0 +1,255 @@
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use libp2p::{identify, request_response, swarm::SwarmEvent};
+use rstest::rstest;
+use test_log::test;
+use tokio::spawn;
+use topos_test_sdk::tce::NodeConfig;
+
+use crate::{
+    config::{DiscoveryConfig, GatingConfig},
+    event::ComposedEvent,
+};
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn reject_peer_outside_of_allowlist() {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_2 = NodeConfig::from_seed(2);
+
+    let (client, _, runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .gating(GatingConfig::default())
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+    let join = spawn(runtime.run());
+
+    let (_, _, runtime) = crate::network::builder()
+        .peer_key(peer_2.keypair.clone())
+        .exposed_addresses(peer_2.addr.clone())
+        .listen_addr(peer_2.addr.clone())
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let mut swarm = runtime.swarm;
+    swarm.dial(peer_1.addr.clone()).unwrap();
+
+    while let Some(event) = swarm.next().await {
+        match event {
+            SwarmEvent::ConnectionClosed { peer_id, .. } if peer_id == peer_1.peer_id() => break,
+            SwarmEvent::OutgoingConnectionError { .. } => break,
+            _ => {}
+        }
+    }
+
+    assert!(!swarm.is_connected(&peer_1.peer_id()));
+    assert!(!client
+        .connected_peers()
+        .await
+        .unwrap()
+        .contains(&peer_2.peer_id()));
+
+    join.abort();
+}
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn accept_peer_with_valid_proof() {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_2 = NodeConfig::from_seed(2);
+
+    let (client, _, runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .gating(GatingConfig::default().with_proof_verifier(Arc::new(
+            |_peer_id, challenge, proof| proof == hex::encode(challenge),
+        )))
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+    let join = spawn(runtime.run());
+
+    let (_, _, runtime) = crate::network::builder()
+        .peer_key(peer_2.keypair.clone())
+        .known_peers(&[(peer_1.peer_id(), peer_1.addr.clone())])
+        .exposed_addresses(peer_2.addr.clone())
+        .listen_addr(peer_2.addr.clone())
+        .peer_proof_signer(Arc::new(|challenge| Some(hex::encode(challenge))))
+        .minimum_cluster_size(1)
+        .discovery_config(
+            DiscoveryConfig::default().with_replication_factor(NonZeroUsize::new(1).unwrap()),
+        )
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+
+    assert!(runtime.swarm.is_connected(&peer_1.peer_id()));
+    assert!(client
+        .connected_peers()
+        .await
+        .unwrap()
+        .contains(&peer_2.peer_id()));
+
+    join.abort();
+}
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn reject_peer_replaying_a_proof() {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_2 = NodeConfig::from_seed(2);
+
+    let (client, _, runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .gating(GatingConfig::default().with_proof_verifier(Arc::new(
+            |_peer_id, challenge, proof| proof == hex::encode(challenge),
+        )))
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+    let join = spawn(runtime.run());
+
+    // Proof signed for a previous challenge, answered manually below
+    let replayed_proof = hex::encode([1u8; 32]);
+    let (_, _, runtime) = crate::network::builder()
+        .peer_key(peer_2.keypair.clone())
+        .exposed_addresses(peer_2.addr.clone())
+        .listen_addr(peer_2.addr.clone())
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let mut swarm = runtime.swarm;
+    swarm.dial(peer_1.addr.clone()).unwrap();
+
+    while let Some(event) = swarm.next().await {
+        match event {
+            SwarmEvent::Behaviour(ComposedEvent::PeerProof(event)) => {
+                if let request_response::Event::Message {
+                    message:
+                        request_response::Message::Request {
+                            request, channel, ..
+                        },
+                    ..
+                } = *event
+                {
+                    assert_ne!(hex::encode(request), replayed_proof);
+                    _ = swarm
+                        .behaviour_mut()
+                        .peer_proof
+                        .send_response(channel, replayed_proof.clone());
+                }
+            }
+            SwarmEvent::ConnectionClosed { peer_id, .. } if peer_id == peer_1.peer_id() => break,
+            _ => {}
+        }
+    }
+
+    assert!(!swarm.is_connected(&peer_1.peer_id()));
+    assert!(!client
+        .connected_peers()
+        .await
+        .unwrap()
+        .contains(&peer_2.peer_id()));
+
+    join.abort();
+}
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn allowlist_update_requires_gating() {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_2 = NodeConfig::from_seed(2);
+
+    let (client, _, runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+    let join = spawn(runtime.run());
+
+    assert!(matches!(
+        client.allow_peer(peer_2.peer_id()).await,
+        Err(crate::error::P2PError::GatingDisabled)
+    ));
+
+    join.abort();
+}
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn provisional_peer_kept_out_of_peer_set() {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_2 = NodeConfig::from_seed(2);
+
+    let (client, _, runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .gating(GatingConfig::default().with_proof_verifier(Arc::new(|_, _, _| false)))
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+    let join = spawn(runtime.run());
+
+    // Never answering its challenge, the peer stays provisional
+    let (_, _, runtime) = crate::network::builder()
+        .peer_key(peer_2.keypair.clone())
+        .exposed_addresses(peer_2.addr.clone())
+        .listen_addr(peer_2.addr.clone())
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let mut swarm = runtime.swarm;
+    swarm.dial(peer_1.addr.clone()).unwrap();
+
+    while let Some(event) = swarm.next().await {
+        if let SwarmEvent::Behaviour(ComposedEvent::PeerInfo(event)) = event {
+            if let identify::Event::Received { peer_id, .. } = *event {
+                if peer_id == peer_1.peer_id() {
+                    break;
+                }
+            }
+        }
+    }
+
+    let peer_2_swarm = spawn(async move { while swarm.next().await.is_some() {} });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert!(client.random_known_peer().await.is_err());
+
+    client.allow_peer(peer_2.peer_id()).await.unwrap();
+
+    assert_eq!(client.random_known_peer().await.unwrap(), peer_2.peer_id());
+
+    peer_2_swarm.abort();
+    join.abort();
+}
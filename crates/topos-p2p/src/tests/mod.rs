@@ -1,4 +1,6 @@
 mod behaviour;
 mod command;
 mod dht;
+mod gating;
+mod quic;
 mod support;
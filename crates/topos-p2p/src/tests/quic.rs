@@ -0,0 +1,112 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use libp2p::Multiaddr;
+use rstest::rstest;
+use test_log::test;
+use tokio::spawn;
+use topos_test_sdk::{networking::get_available_port, tce::NodeConfig};
+
+use crate::config::DiscoveryConfig;
+
+fn local_quic_addr() -> Multiaddr {
+    format!("/ip4/127.0.0.1/udp/{}/quic-v1", get_available_port())
+        .parse()
+        .unwrap()
+}
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn bootstrap_over_quic() {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_2 = NodeConfig::from_seed(2);
+    let peer_1_quic_addr = local_quic_addr();
+    let peer_2_quic_addr = local_quic_addr();
+
+    let (_, _, runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .quic_exposed_addresses(peer_1_quic_addr.clone())
+        .quic_listen_addr(peer_1_quic_addr.clone())
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+    let join = spawn(runtime.run());
+
+    // Peer 2 only knows the QUIC address of peer 1
+    let (_, _, runtime) = crate::network::builder()
+        .peer_key(peer_2.keypair.clone())
+        .known_peers(&[(peer_1.peer_id(), peer_1_quic_addr)])
+        .exposed_addresses(peer_2.addr.clone())
+        .listen_addr(peer_2.addr.clone())
+        .quic_exposed_addresses(peer_2_quic_addr.clone())
+        .quic_listen_addr(peer_2_quic_addr.clone())
+        .minimum_cluster_size(1)
+        .discovery_config(
+            DiscoveryConfig::default().with_replication_factor(NonZeroUsize::new(1).unwrap()),
+        )
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+
+    assert!(runtime.swarm.is_connected(&peer_1.peer_id()));
+    assert_eq!(
+        runtime.published_addresses(),
+        vec![peer_2_quic_addr, peer_2.addr.clone()]
+    );
+
+    join.abort();
+}
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn publish_tcp_address_first_without_quic_preference() {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_1_quic_addr = local_quic_addr();
+
+    let (_, _, runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .quic_exposed_addresses(peer_1_quic_addr.clone())
+        .quic_listen_addr(peer_1_quic_addr.clone())
+        .prefer_quic(false)
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    let runtime = runtime.bootstrap().await.unwrap();
+
+    assert_eq!(
+        runtime.published_addresses(),
+        vec![peer_1.addr.clone(), peer_1_quic_addr]
+    );
+}
+
+#[rstest]
+#[test(tokio::test)]
+#[timeout(Duration::from_secs(5))]
+async fn ignore_quic_addresses_without_quic_transport() {
+    let peer_1 = NodeConfig::from_seed(1);
+    let peer_2 = NodeConfig::from_seed(2);
+
+    let (_, _, runtime) = crate::network::builder()
+        .peer_key(peer_1.keypair.clone())
+        .exposed_addresses(peer_1.addr.clone())
+        .listen_addr(peer_1.addr.clone())
+        .build()
+        .await
+        .expect("Unable to create p2p network");
+
+    assert_eq!(runtime.published_addresses(), vec![peer_1.addr.clone()]);
+    assert_eq!(
+        runtime.dialable_addresses(vec![local_quic_addr(), peer_2.addr.clone()]),
+        vec![peer_2.addr.clone()]
+    );
+}
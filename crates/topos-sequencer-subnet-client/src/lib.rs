@@ -1,3 +1,5 @@
+pub mod push_pipeline;
+pub mod subnet;
 pub mod subnet_contract;
 
 use crate::subnet_contract::{create_topos_core_contract_from_json, get_block_events};
@@ -13,9 +15,12 @@ use ethers::{
     signers::{LocalWallet, Signer, WalletError},
 };
 use ethers_providers::{Middleware, SubscriptionStream};
+pub use push_pipeline::{PushPipeline, PushPipelineConfig, PushResult};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
+pub use subnet::{BlockStream, EthersSubnet, Subnet};
+use tokio::sync::{mpsc, oneshot};
 use topos_core::api::grpc::checkpoints::TargetStreamPosition;
 pub use topos_core::uci::{
     Address, Certificate, CertificateId, ReceiptsRootHash, StateRoot, SubnetId, TxRootHash,
@@ -25,14 +30,32 @@ use tracing::{error, info};
 
 const PUSH_CERTIFICATE_GAS_LIMIT: u64 = 1000000;
 
+/// Number of new blocks buffered by a block stream
+const BLOCK_STREAM_CAPACITY: usize = 64;
+
 pub type BlockData = Vec<u8>;
 pub type BlockNumber = u64;
 pub type Hash = String;
 
 /// Event collected from the sending subnet
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SubnetEvent {
-    CrossSubnetMessageSent { target_subnet_id: SubnetId },
+    /// Target subnet of a cross-subnet message emitted without its sender and payload,
+    /// the message can't be proven to the target subnet
+    CrossSubnetTargetAdded { target_subnet_id: SubnetId },
+    CrossSubnetMessageSent {
+        target_subnet_id: SubnetId,
+        /// Account which sent the message, as reported by the event
+        sender: Address,
+        /// Keccak256 hash of the payload of the message
+        payload_hash: [u8; 32],
+        /// Hash of the transaction emitting the message
+        transaction_hash: [u8; 32],
+        /// Index of the transaction emitting the message in its block
+        transaction_index: u64,
+        /// Index of the log of the message in its block
+        log_index: u64,
+    },
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -139,10 +162,7 @@ impl SubnetClientListener {
     }
 
     /// Subscribe and listen to runtime finalized blocks
-    pub async fn get_finalized_block(
-        &mut self,
-        next_block_number: u64,
-    ) -> Result<BlockInfo, Error> {
+    pub async fn get_finalized_block(&self, next_block_number: u64) -> Result<BlockInfo, Error> {
         let latest_subnet_block_number = self
             .provider
             .get_block_number()
@@ -164,36 +184,11 @@ impl SubnetClientListener {
             .await
             .map_err(Error::EthersProviderError)?
             .ok_or(Error::InvalidBlockNumber(next_block_number))?;
-        let block_number = block
-            .number
-            .ok_or(Error::InvalidBlockNumber(next_block_number))?;
-        let events = match get_block_events(&self.contract, block_number).await {
-            Ok(events) => events,
-            Err(Error::EventDecodingError(e)) => {
-                // FIXME: Happens in block before subnet contract is deployed, seems like bug in ethers
-                error!(
-                    "Error decoding events from block {}: {e} \nTopos smart contracts may not be \
-                     deployed?",
-                    block_number
-                );
-                Vec::new()
-            }
-            Err(e) => {
-                error!("Unable to parse events from block {}: {e}", block_number);
-                return Err(e);
-            }
-        };
+        if block.number.is_none() {
+            return Err(Error::InvalidBlockNumber(next_block_number));
+        }
 
-        // Make block info result from all collected info
-        let block_info = BlockInfo {
-            hash: block.hash.unwrap_or_default().to_string(),
-            parent_hash: block.parent_hash.to_string(),
-            number: block_number.to_owned().as_u64(),
-            state_root: block.state_root.0,
-            tx_root_hash: block.transactions_root.0,
-            receipts_root_hash: block.receipts_root.0,
-            events,
-        };
+        let block_info = block_info(&self.contract, block).await?;
         info!(
             "Fetched new finalized block from subnet: {:?}",
             block_info.number
@@ -202,7 +197,7 @@ impl SubnetClientListener {
     }
 
     /// Subscribe and listen to runtime finalized blocks
-    pub async fn get_subnet_block_number(&mut self) -> Result<u64, Error> {
+    pub async fn get_subnet_block_number(&self) -> Result<u64, Error> {
         self.provider
             .get_block_number()
             .await
@@ -215,41 +210,86 @@ impl SubnetClientListener {
         stream: &mut SubscriptionStream<'_, Ws, ethers::types::Block<ethers::types::H256>>,
     ) -> Result<BlockInfo, Error> {
         if let Some(block) = stream.next().await {
-            let block_number = block.number.ok_or(Error::BlockNumberNotAvailable)?;
-            let events = match get_block_events(&self.contract, block_number).await {
-                Ok(events) => events,
-                Err(Error::EventDecodingError(e)) => {
-                    // FIXME: Happens in block before subnet contract is deployed, seems like bug in ethers
-                    error!(
-                        "Error decoding events from block {}: {e} \nTopos smart contracts may not \
-                         be deployed?",
-                        block_number
-                    );
-                    Vec::new()
+            block_info(&self.contract, block).await
+        } else {
+            Err(Error::StreamBlockNotAvailable)
+        }
+    }
+
+    /// Open a stream of the new blocks of the subnet, detached from the listener
+    pub async fn subscribe_blocks(&self) -> Result<BlockStream, Error> {
+        let provider = self.provider.clone();
+        let contract = self.contract.clone();
+        let (opened_sender, opened) = oneshot::channel();
+        let (sender, receiver) = mpsc::channel(BLOCK_STREAM_CAPACITY);
+
+        tokio::spawn(async move {
+            let mut stream = match provider.subscribe_blocks().await {
+                Ok(stream) => {
+                    _ = opened_sender.send(Ok(()));
+                    stream
                 }
                 Err(e) => {
-                    error!("Unable to parse events from block {}: {e}", block_number);
-                    return Err(e);
+                    _ = opened_sender.send(Err(Error::EthersProviderError(e)));
+                    return;
                 }
             };
 
-            // Make block info result from all collected info
-            let block_info = BlockInfo {
-                hash: block.hash.unwrap_or_default().to_string(),
-                parent_hash: block.parent_hash.to_string(),
-                number: block_number.to_owned().as_u64(),
-                state_root: block.state_root.0,
-                tx_root_hash: block.transactions_root.0,
-                receipts_root_hash: block.receipts_root.0,
-                events,
-            };
-            Ok(block_info)
-        } else {
-            Err(Error::StreamBlockNotAvailable)
-        }
+            while let Some(block) = stream.next().await {
+                if sender
+                    .send(block_info(&contract, block).await)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        opened.await.map_err(|_| Error::StreamBlockNotAvailable)??;
+
+        Ok(Box::pin(futures::stream::unfold(
+            receiver,
+            |mut receiver| async move { receiver.recv().await.map(|block| (block, receiver)) },
+        )))
     }
 }
 
+/// Collect the info and the subnet events of a block
+async fn block_info(
+    contract: &subnet_contract::IToposCore<Provider<Ws>>,
+    block: ethers::types::Block<ethers::types::H256>,
+) -> Result<BlockInfo, Error> {
+    let block_number = block.number.ok_or(Error::BlockNumberNotAvailable)?;
+    let events = match get_block_events(contract, block_number).await {
+        Ok(events) => events,
+        Err(Error::EventDecodingError(e)) => {
+            // FIXME: Happens in block before subnet contract is deployed, seems like bug in ethers
+            error!(
+                "Error decoding events from block {}: {e} \nTopos smart contracts may not be \
+                 deployed?",
+                block_number
+            );
+            Vec::new()
+        }
+        Err(e) => {
+            error!("Unable to parse events from block {}: {e}", block_number);
+            return Err(e);
+        }
+    };
+
+    // Make block info result from all collected info
+    Ok(BlockInfo {
+        hash: block.hash.unwrap_or_default().to_string(),
+        parent_hash: block.parent_hash.to_string(),
+        number: block_number.as_u64(),
+        state_root: block.state_root.0,
+        tx_root_hash: block.transactions_root.0,
+        receipts_root_hash: block.receipts_root.0,
+        events,
+    })
+}
+
 /// Create subnet client listener and open connection to the subnet
 /// Retry until connection is valid
 pub async fn connect_to_subnet_listener_with_retry(
@@ -346,34 +386,8 @@ impl SubnetClient {
         cert: &Certificate,
         cert_position: u64,
     ) -> Result<Option<TransactionReceipt>, Error> {
-        let prev_cert_id: Token = Token::FixedBytes(cert.prev_id.as_array().to_vec());
-        let source_subnet_id: Token = Token::FixedBytes(cert.source_subnet_id.into());
-        let state_root: Token = Token::FixedBytes(cert.state_root.to_vec());
-        let tx_root: Token = Token::FixedBytes(cert.tx_root_hash.to_vec());
-        let receipt_root: Token = Token::FixedBytes(cert.receipts_root_hash.to_vec());
-        let target_subnets: Token = Token::Array(
-            cert.target_subnets
-                .iter()
-                .map(|target_subnet| Token::FixedBytes((*target_subnet).into()))
-                .collect::<Vec<Token>>(),
-        );
-        let verifier = Token::Uint(U256::from(cert.verifier));
-        let cert_id: Token = Token::FixedBytes(cert.id.as_array().to_vec());
-        let stark_proof: Token = Token::Bytes(cert.proof.clone());
-        let signature: Token = Token::Bytes(cert.signature.clone());
+        let encoded_cert_bytes = encode_certificate(cert);
         let cert_position = U256::from(cert_position);
-        let encoded_cert_bytes = ethers::abi::encode(&[
-            prev_cert_id,
-            source_subnet_id,
-            state_root,
-            tx_root,
-            receipt_root,
-            target_subnets,
-            verifier,
-            cert_id,
-            stark_proof,
-            signature,
-        ]);
 
         let tx = self
             .contract
@@ -394,6 +408,18 @@ impl SubnetClient {
         Ok(receipt)
     }
 
+    /// Check whether the certificate was already pushed to the Topos Core contract
+    pub async fn certificate_exists(&self, cert_id: &CertificateId) -> Result<bool, Error> {
+        self.contract
+            .certificate_exists(*cert_id.as_array())
+            .call()
+            .await
+            .map_err(|e| {
+                error!("Unable to check certificate {cert_id} existence: {e}");
+                Error::ContractError(e.to_string())
+            })
+    }
+
     /// Ask subnet for latest pushed certificates, for every source subnet
     /// Returns list of latest stream positions for every source subnet
     pub async fn get_checkpoints(
@@ -449,6 +475,42 @@ impl SubnetClient {
     }
 }
 
+/// ABI encode the certificate as expected by the Topos Core contract
+///
+/// The messages root is appended after the fields decoded by the Topos Core contract, which
+/// ignores it, so that the contracts verifying cross-subnet messages can decode it.
+pub(crate) fn encode_certificate(cert: &Certificate) -> Vec<u8> {
+    let prev_cert_id: Token = Token::FixedBytes(cert.prev_id.as_array().to_vec());
+    let source_subnet_id: Token = Token::FixedBytes(cert.source_subnet_id.into());
+    let state_root: Token = Token::FixedBytes(cert.state_root.to_vec());
+    let tx_root: Token = Token::FixedBytes(cert.tx_root_hash.to_vec());
+    let receipt_root: Token = Token::FixedBytes(cert.receipts_root_hash.to_vec());
+    let target_subnets: Token = Token::Array(
+        cert.target_subnets
+            .iter()
+            .map(|target_subnet| Token::FixedBytes((*target_subnet).into()))
+            .collect::<Vec<Token>>(),
+    );
+    let verifier = Token::Uint(U256::from(cert.verifier));
+    let cert_id: Token = Token::FixedBytes(cert.id.as_array().to_vec());
+    let stark_proof: Token = Token::Bytes(cert.proof.clone());
+    let signature: Token = Token::Bytes(cert.signature.clone());
+    let messages_root: Token = Token::FixedBytes(cert.messages_root.to_vec());
+    ethers::abi::encode(&[
+        prev_cert_id,
+        source_subnet_id,
+        state_root,
+        tx_root,
+        receipt_root,
+        target_subnets,
+        verifier,
+        cert_id,
+        stark_proof,
+        signature,
+        messages_root,
+    ])
+}
+
 /// Create new backoff library error based on error that happened
 pub(crate) fn new_subnet_client_proxy_backoff_err<E: std::fmt::Display>(
     err: E,
@@ -487,3 +549,49 @@ pub async fn connect_to_subnet_with_retry(
 
     backoff::future::retry(backoff::ExponentialBackoff::default(), op).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::ParamType;
+
+    fn certificate_params() -> Vec<ParamType> {
+        vec![
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::Array(Box::new(ParamType::FixedBytes(32))),
+            ParamType::Uint(32),
+            ParamType::FixedBytes(32),
+            ParamType::Bytes,
+            ParamType::Bytes,
+        ]
+    }
+
+    #[test]
+    fn encode_messages_root_after_the_certificate() {
+        let cert = Certificate {
+            target_subnets: vec![[2u8; 32].into()],
+            messages_root: [3u8; 32],
+            proof: vec![4u8; 3],
+            signature: vec![5u8; 65],
+            ..Default::default()
+        };
+        let encoded = encode_certificate(&cert);
+
+        // The fields decoded by the Topos Core contract are left untouched
+        let tokens = ethers::abi::decode(&certificate_params(), &encoded).unwrap();
+        assert_eq!(
+            tokens[5],
+            Token::Array(vec![Token::FixedBytes(vec![2u8; 32])])
+        );
+        assert_eq!(tokens[9], Token::Bytes(vec![5u8; 65]));
+
+        let mut params = certificate_params();
+        params.push(ParamType::FixedBytes(32));
+        let tokens = ethers::abi::decode(&params, &encoded).unwrap();
+        assert_eq!(tokens[10], Token::FixedBytes(vec![3u8; 32]));
+    }
+}
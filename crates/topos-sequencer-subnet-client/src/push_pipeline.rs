@@ -0,0 +1,330 @@
+//! Pipelined submission of certificates to the Topos Core contract
+//!
+use crate::{encode_certificate, Error, SubnetClient};
+use ethers::abi::ethabi::ethereum_types::U256;
+use ethers::providers::{Middleware, PendingTransaction};
+use ethers::types::{
+    transaction::eip2718::TypedTransaction, BlockNumber, Eip1559TransactionRequest,
+    TransactionReceipt,
+};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use topos_core::uci::{Certificate, CertificateId};
+use tracing::{debug, error, info, warn};
+
+/// Outcome of the push of a certificate, once its transaction is mined
+pub type PushResult = (CertificateId, Result<TransactionReceipt, Error>);
+
+/// Upper bound of the delay between two attempts to push a certificate
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushPipelineConfig {
+    /// Maximum number of push transactions waiting for their receipt
+    pub max_in_flight: usize,
+    /// Send EIP-1559 transactions instead of legacy ones
+    pub eip1559: bool,
+    /// Margin, in percent, added on top of the estimated gas
+    pub gas_margin_percent: u64,
+    /// Number of attempts to push a certificate before reporting its failure
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on each attempt
+    pub retry_base_delay: Duration,
+    /// Increase, in percent, of the gas price on each retry
+    pub gas_bump_percent: u64,
+}
+
+impl Default for PushPipelineConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: 8,
+            // Polygon Edge only supports legacy transactions
+            eip1559: false,
+            gas_margin_percent: 20,
+            max_attempts: 5,
+            retry_base_delay: Duration::from_secs(2),
+            gas_bump_percent: 10,
+        }
+    }
+}
+
+/// Push of a certificate, along with its number of failed attempts
+#[derive(Debug, Clone)]
+struct Push {
+    cert: Certificate,
+    position: u64,
+    attempt: u32,
+}
+
+enum InFlight {
+    /// The push transaction is mined, or was dropped
+    Receipt(Push, Result<TransactionReceipt, Error>),
+    /// The delay before the next attempt of the push is elapsed
+    RetryDue(Push),
+}
+
+/// Pipeline pushing certificates to the Topos Core contract without waiting
+/// for the receipt of the previous push
+///
+/// The nonce of the admin account is managed locally so that several push
+/// transactions can be in flight at the same time. It is fetched again from
+/// the subnet node whenever a transaction fails to be sent or to be mined,
+/// the failed push being retried with a higher gas price after a delay.
+pub struct PushPipeline {
+    subnet_client: Arc<SubnetClient>,
+    config: PushPipelineConfig,
+    next_nonce: Option<U256>,
+    in_flight: JoinSet<InFlight>,
+    retries: VecDeque<Push>,
+    completed: VecDeque<PushResult>,
+}
+
+impl PushPipeline {
+    pub fn new(subnet_client: Arc<SubnetClient>, config: PushPipelineConfig) -> Self {
+        Self {
+            subnet_client,
+            config,
+            next_nonce: None,
+            in_flight: JoinSet::new(),
+            retries: VecDeque::new(),
+            completed: VecDeque::new(),
+        }
+    }
+
+    pub fn subnet_client(&self) -> &SubnetClient {
+        &self.subnet_client
+    }
+
+    /// Number of pushes waiting for their receipt or for their next attempt
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len() + self.retries.len()
+    }
+
+    /// Send the push transaction of the certificate
+    ///
+    /// Returns the hash of the transaction, or `None` if no transaction was sent
+    /// because the certificate is already held by the contract or because the push
+    /// is retried later. The receipt, or the failure of the last attempt, is reported
+    /// by [`PushPipeline::next_result`]. Waits for some in-flight transaction to be
+    /// mined if the pipeline is full.
+    pub async fn submit(
+        &mut self,
+        cert: &Certificate,
+        cert_position: u64,
+    ) -> Result<Option<ethers::types::TxHash>, Error> {
+        if self.subnet_client.certificate_exists(&cert.id).await? {
+            info!(
+                "Certificate {} is already held by the target subnet, skipping it",
+                cert.id
+            );
+            return Ok(None);
+        }
+
+        while self.in_flight.len() >= self.config.max_in_flight.max(1) {
+            match self.in_flight.join_next().await {
+                Some(Ok(in_flight)) => self.on_in_flight(in_flight),
+                Some(Err(e)) => error!("Failure while waiting for push transaction receipt: {e}"),
+                None => break,
+            }
+        }
+
+        Ok(self
+            .send(Push {
+                cert: cert.clone(),
+                position: cert_position,
+                attempt: 0,
+            })
+            .await)
+    }
+
+    /// Wait for the next push transaction to be mined, or to fail for good
+    ///
+    /// Returns `None` if there is no push in flight.
+    pub async fn next_result(&mut self) -> Option<PushResult> {
+        loop {
+            if let Some(result) = self.completed.pop_front() {
+                return Some(result);
+            }
+
+            // The push is dequeued once sent so that it isn't lost if this future is dropped
+            if let Some(push) = self.retries.front().cloned() {
+                self.retry(push).await;
+                self.retries.pop_front();
+
+                continue;
+            }
+
+            match self.in_flight.join_next().await? {
+                Ok(in_flight) => self.on_in_flight(in_flight),
+                Err(e) => warn!("Failure while waiting for push transaction receipt: {e}"),
+            }
+        }
+    }
+
+    fn on_in_flight(&mut self, in_flight: InFlight) {
+        match in_flight {
+            InFlight::Receipt(push, Ok(receipt)) => {
+                self.completed.push_back((push.cert.id, Ok(receipt)))
+            }
+            InFlight::Receipt(push, Err(error)) => {
+                // The nonces following the one of a dropped transaction can't be mined
+                self.next_nonce = None;
+                self.retry_later(push, error);
+            }
+            InFlight::RetryDue(push) => self.retries.push_back(push),
+        }
+    }
+
+    async fn retry(&mut self, push: Push) {
+        match self.subnet_client.certificate_exists(&push.cert.id).await {
+            Ok(true) => info!(
+                "Certificate {} is already held by the target subnet, no need to retry",
+                push.cert.id
+            ),
+            Ok(false) => {
+                self.send(push).await;
+            }
+            Err(error) => self.retry_later(push, error),
+        }
+    }
+
+    /// Schedule the next attempt of the push, or report its failure if it was the last one
+    fn retry_later(&mut self, push: Push, error: Error) {
+        let attempt = push.attempt + 1;
+        if attempt >= self.config.max_attempts {
+            error!(
+                "Giving up pushing certificate {} after {attempt} attempts: {error}",
+                push.cert.id
+            );
+            self.completed.push_back((push.cert.id, Err(error)));
+
+            return;
+        }
+
+        let delay = self
+            .config
+            .retry_base_delay
+            .saturating_mul(2u32.saturating_pow(push.attempt))
+            .min(MAX_RETRY_DELAY);
+        warn!(
+            "Push of certificate {} failed, retrying in {delay:?}: {error}",
+            push.cert.id
+        );
+
+        self.in_flight.spawn(async move {
+            tokio::time::sleep(delay).await;
+
+            InFlight::RetryDue(Push { attempt, ..push })
+        });
+    }
+
+    /// Send the push transaction, scheduling a retry if it can't be sent
+    async fn send(&mut self, push: Push) -> Option<ethers::types::TxHash> {
+        let (tx_hash, nonce) = match self.send_transaction(&push).await {
+            Ok(sent) => sent,
+            Err(error) => {
+                // The nonce may be out of sync with the subnet node, fetch it again on next push
+                self.next_nonce = None;
+                error!("Unable to push certificate {}: {error}", push.cert.id);
+                self.retry_later(push, error);
+
+                return None;
+            }
+        };
+        self.next_nonce = Some(nonce + 1);
+        debug!(
+            "Push transaction {tx_hash:?} of certificate {} sent with nonce {nonce} (attempt {})",
+            push.cert.id,
+            push.attempt + 1
+        );
+
+        let provider = self.subnet_client.contract.client().inner().clone();
+        self.in_flight.spawn(async move {
+            let receipt = PendingTransaction::new(tx_hash, &provider)
+                .interval(SubnetClient::NODE_POLLING_INTERVAL)
+                .await
+                .map_err(Error::EthersProviderError)
+                .and_then(|receipt| {
+                    receipt.ok_or_else(|| {
+                        Error::ContractError(format!("push transaction {tx_hash:?} was dropped"))
+                    })
+                })
+                .and_then(|receipt| {
+                    if receipt.status == Some(0u64.into()) {
+                        Err(Error::ContractError(format!(
+                            "push transaction {tx_hash:?} reverted"
+                        )))
+                    } else {
+                        Ok(receipt)
+                    }
+                });
+
+            InFlight::Receipt(push, receipt)
+        });
+
+        Some(tx_hash)
+    }
+
+    async fn send_transaction(&self, push: &Push) -> Result<(ethers::types::TxHash, U256), Error> {
+        let client = self.subnet_client.contract.client();
+        let call = self.subnet_client.contract.push_certificate(
+            encode_certificate(&push.cert).into(),
+            U256::from(push.position),
+        );
+
+        let mut tx = call.tx;
+        if self.config.eip1559 {
+            tx = TypedTransaction::Eip1559(Eip1559TransactionRequest {
+                from: tx.from().copied(),
+                to: tx.to().cloned(),
+                value: tx.value().copied(),
+                data: tx.data().cloned(),
+                ..Default::default()
+            });
+        }
+
+        let nonce = match self.next_nonce {
+            Some(nonce) => nonce,
+            None => client
+                .get_transaction_count(client.address(), Some(BlockNumber::Pending.into()))
+                .await
+                .map_err(|e| Error::ContractError(e.to_string()))?,
+        };
+        tx.set_nonce(nonce);
+
+        let estimated_gas = client
+            .estimate_gas(&tx, None)
+            .await
+            .map_err(|e| Error::ContractError(format!("unable to estimate gas: {e}")))?;
+        tx.set_gas(estimated_gas * (100 + self.config.gas_margin_percent) / 100);
+
+        // Outbid the transaction of the previous attempt, in case it is still in the mempool
+        if push.attempt > 0 {
+            let bump =
+                |fee: U256| fee * (100 + self.config.gas_bump_percent * push.attempt as u64) / 100;
+            if let TypedTransaction::Eip1559(request) = &mut tx {
+                let (max_fee, max_priority_fee) = client
+                    .estimate_eip1559_fees(None)
+                    .await
+                    .map_err(|e| Error::ContractError(e.to_string()))?;
+                request.max_fee_per_gas = Some(bump(max_fee));
+                request.max_priority_fee_per_gas = Some(bump(max_priority_fee));
+            } else {
+                let gas_price = client
+                    .get_gas_price()
+                    .await
+                    .map_err(|e| Error::ContractError(e.to_string()))?;
+                tx.set_gas_price(bump(gas_price));
+            }
+        }
+
+        let pending = client
+            .send_transaction(tx, None)
+            .await
+            .map_err(|e| Error::ContractError(e.to_string()))?;
+
+        Ok((pending.tx_hash(), nonce))
+    }
+}
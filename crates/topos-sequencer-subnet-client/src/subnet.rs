@@ -0,0 +1,119 @@
+//! Abstraction of the subnet certified by the sequencer
+//!
+use crate::{
+    connect_to_subnet_listener_with_retry, connect_to_subnet_with_retry, BlockInfo, Error,
+    PushPipeline, PushPipelineConfig, PushResult, SubnetClient, SubnetClientListener,
+};
+use async_trait::async_trait;
+use ethers::types::TxHash;
+use futures::stream::BoxStream;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use topos_core::api::grpc::checkpoints::TargetStreamPosition;
+use topos_core::uci::{Certificate, SubnetId};
+
+/// Stream of the new blocks produced by the subnet
+pub type BlockStream = BoxStream<'static, Result<BlockInfo, Error>>;
+
+/// Subnet from which the blocks are certified and to which the delivered
+/// certificates are pushed
+#[async_trait]
+pub trait Subnet: Send + Sync {
+    /// Subscribe to the new blocks produced by the subnet
+    async fn subscribe_blocks(&self) -> Result<BlockStream, Error>;
+
+    /// Number of the latest block of the subnet
+    async fn get_subnet_block_number(&self) -> Result<u64, Error>;
+
+    /// Retrieve a block of the subnet, failing with [`Error::BlockNotAvailable`]
+    /// if it isn't produced yet
+    async fn get_finalized_block(&self, block_number: u64) -> Result<BlockInfo, Error>;
+
+    /// Latest certificates pushed to the subnet, for every source subnet
+    async fn get_checkpoints(
+        &self,
+        target_subnet_id: &SubnetId,
+    ) -> Result<Vec<TargetStreamPosition>, Error>;
+
+    /// Push a certificate delivered by the TCE to the subnet
+    ///
+    /// Returns `None` if the subnet already holds the certificate, otherwise the
+    /// hash of the push transaction whose outcome is reported by
+    /// [`Subnet::next_push_result`].
+    async fn push_certificate(
+        &self,
+        cert: &Certificate,
+        cert_position: u64,
+    ) -> Result<Option<TxHash>, Error>;
+
+    /// Wait for the outcome of the next pending push, `None` if no push is pending
+    async fn next_push_result(&self) -> Option<PushResult>;
+}
+
+/// Subnet reached through the JSON-RPC API of an Ethereum compatible node
+pub struct EthersSubnet {
+    listener: SubnetClientListener,
+    client: Arc<SubnetClient>,
+    push_pipeline: Mutex<PushPipeline>,
+}
+
+impl EthersSubnet {
+    /// Connect to the subnet node, retrying until the connection is valid
+    pub async fn connect(
+        http_endpoint: &str,
+        ws_endpoint: &str,
+        signing_key: Vec<u8>,
+        contract_address: &str,
+        push_pipeline_config: PushPipelineConfig,
+    ) -> Result<Self, Error> {
+        let listener = connect_to_subnet_listener_with_retry(ws_endpoint, contract_address).await?;
+        let client = Arc::new(
+            connect_to_subnet_with_retry(http_endpoint, Some(signing_key), contract_address)
+                .await?,
+        );
+
+        Ok(Self {
+            listener,
+            push_pipeline: Mutex::new(PushPipeline::new(client.clone(), push_pipeline_config)),
+            client,
+        })
+    }
+}
+
+#[async_trait]
+impl Subnet for EthersSubnet {
+    async fn subscribe_blocks(&self) -> Result<BlockStream, Error> {
+        self.listener.subscribe_blocks().await
+    }
+
+    async fn get_subnet_block_number(&self) -> Result<u64, Error> {
+        self.listener.get_subnet_block_number().await
+    }
+
+    async fn get_finalized_block(&self, block_number: u64) -> Result<BlockInfo, Error> {
+        self.listener.get_finalized_block(block_number).await
+    }
+
+    async fn get_checkpoints(
+        &self,
+        target_subnet_id: &SubnetId,
+    ) -> Result<Vec<TargetStreamPosition>, Error> {
+        self.client.get_checkpoints(target_subnet_id).await
+    }
+
+    async fn push_certificate(
+        &self,
+        cert: &Certificate,
+        cert_position: u64,
+    ) -> Result<Option<TxHash>, Error> {
+        self.push_pipeline
+            .lock()
+            .await
+            .submit(cert, cert_position)
+            .await
+    }
+
+    async fn next_push_result(&self) -> Option<PushResult> {
+        self.push_pipeline.lock().await.next_result().await
+    }
+}
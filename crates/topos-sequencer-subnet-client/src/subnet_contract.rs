@@ -2,6 +2,7 @@ use crate::{Error, SubnetEvent};
 use ethers::abi::ethabi::ethereum_types::{H160, U64};
 use ethers::contract::ContractError;
 use ethers::signers::LocalWallet;
+use ethers::utils::keccak256;
 use ethers::{
     prelude::abigen,
     providers::{Middleware, Provider, Ws},
@@ -16,6 +17,14 @@ abigen!(
      sol/IToposCore.json"
 );
 
+// Cross-subnet message emitted by the Topos Core contract with its sender and payload
+abigen!(
+    CrossSubnetMessaging,
+    r#"[
+        event CrossSubnetMessageSent(bytes32 indexed targetSubnetId, address sender, bytes payload)
+    ]"#
+);
+
 pub(crate) fn create_topos_core_contract_from_json<T: Middleware>(
     contract_address: &str,
     client: Arc<T>,
@@ -58,14 +67,37 @@ pub(crate) async fn get_block_events(
                 "Received CrossSubnetMessageSentFilter event: {f:?}, meta {:?}",
                 meta
             );
-            result.push(SubnetEvent::CrossSubnetMessageSent {
+            result.push(SubnetEvent::CrossSubnetTargetAdded {
                 target_subnet_id: f.target_subnet_id.into(),
-            })
+            });
         } else {
             // Ignored for now other events Upgraded, CertStored
         }
     }
 
+    // The messages are emitted along with their sender and payload, so that they can be
+    // proven to the target subnet independently of the transaction emitting them
+    let messaging = CrossSubnetMessaging::new(contract.address(), contract.client());
+    let messages = messaging
+        .events()
+        .from_block(block_number)
+        .to_block(block_number)
+        .query_with_meta()
+        .await
+        .map_err(|e| Error::ContractError(e.to_string()))?;
+
+    for (f, meta) in messages {
+        info!("Received cross-subnet message: {f:?}, meta {:?}", meta);
+        result.push(SubnetEvent::CrossSubnetMessageSent {
+            target_subnet_id: f.target_subnet_id.into(),
+            sender: f.sender.0,
+            payload_hash: keccak256(&f.payload),
+            transaction_hash: meta.transaction_hash.0,
+            transaction_index: meta.transaction_index.as_u64(),
+            log_index: meta.log_index.as_u64(),
+        });
+    }
+
     Ok(result)
 }
 
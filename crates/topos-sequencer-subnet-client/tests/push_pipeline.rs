@@ -0,0 +1,474 @@
+use ethers::types::{transaction::eip2718::TypedTransaction, Bytes, H256, U256};
+use ethers::utils::{keccak256, rlp::Rlp};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use topos_sequencer_subnet_client::{
+    Certificate, CertificateId, PushPipeline, PushPipelineConfig, SubnetClient, SubnetId,
+};
+
+const SOURCE_SUBNET_ID: SubnetId = SubnetId::from_array([1u8; 32]);
+const ADMIN_PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const CONTRACT_ADDRESS: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+const INITIAL_NONCE: u64 = 5;
+const ESTIMATED_GAS: u64 = 100_000;
+const GAS_PRICE: u64 = 1_000_000_000;
+
+/// Transactions received by the JSON-RPC stand-in
+#[derive(Default)]
+struct ChainState {
+    /// Certificates already held by the Topos Core contract
+    held_certificates: HashSet<CertificateId>,
+    /// Mined transactions by hash
+    transactions: HashMap<H256, TypedTransaction>,
+    /// Nonces of the received transactions, by order of reception
+    nonces: Vec<U256>,
+    /// Gas prices of the received transactions, by order of reception
+    gas_prices: Vec<U256>,
+    /// Number of upcoming raw transactions to reject
+    rejected_sends: usize,
+    /// Number of upcoming raw transactions accepted but never mined
+    dropped_sends: usize,
+    /// Number of upcoming raw transactions mined but reverted
+    reverted_sends: usize,
+    /// Reverted transactions by hash
+    reverted: HashSet<H256>,
+}
+
+/// Minimal EVM JSON-RPC node answering the requests of the push pipeline
+async fn spawn_json_rpc_stand_in(state: Arc<Mutex<ChainState>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(serve_connection(stream, state.clone()));
+        }
+    });
+
+    endpoint
+}
+
+async fn serve_connection(stream: TcpStream, state: Arc<Mutex<ChainState>>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    loop {
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                return;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).await.unwrap();
+        let request: Value = serde_json::from_slice(&body).unwrap();
+
+        let response = match handle_request(&request, &state) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+            Err(message) => json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32000, "message": message }
+            }),
+        }
+        .to_string();
+
+        writer
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: \
+                     {}\r\n\r\n{response}",
+                    response.len()
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+    }
+}
+
+fn handle_request(request: &Value, state: &Mutex<ChainState>) -> Result<Value, String> {
+    let mut state = state.lock().unwrap();
+    let params = &request["params"];
+
+    Ok(match request["method"].as_str().unwrap() {
+        "eth_chainId" => json!("0x7a69"),
+        "eth_gasPrice" => json!(format!("{GAS_PRICE:#x}")),
+        "eth_blockNumber" => json!("0x1"),
+        "eth_estimateGas" => json!(format!("{ESTIMATED_GAS:#x}")),
+        "eth_getTransactionCount" => {
+            json!(format!(
+                "{:#x}",
+                INITIAL_NONCE + state.transactions.len() as u64
+            ))
+        }
+        "eth_call" => {
+            // Only `certificateExists(bytes32)` is called by the pipeline
+            let data: Bytes = serde_json::from_value(params[0]["data"].clone())
+                .or_else(|_| serde_json::from_value(params[0]["input"].clone()))
+                .unwrap();
+            let cert_id: [u8; 32] = data[4..36].try_into().unwrap();
+            let exists = state.held_certificates.contains(&cert_id.into());
+            json!(format!("0x{:064x}", exists as u8))
+        }
+        "eth_sendRawTransaction" => {
+            if state.rejected_sends > 0 {
+                state.rejected_sends -= 1;
+                return Err("nonce too low".to_string());
+            }
+
+            let raw: Bytes = serde_json::from_value(params[0].clone()).unwrap();
+            let (tx, _) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+            let hash = H256::from(keccak256(&raw));
+            state.nonces.push(*tx.nonce().unwrap());
+            state.gas_prices.push(tx.gas_price().unwrap());
+            if state.dropped_sends > 0 {
+                state.dropped_sends -= 1;
+                return Ok(json!(hash));
+            }
+            if state.reverted_sends > 0 {
+                state.reverted_sends -= 1;
+                state.reverted.insert(hash);
+            }
+            state.transactions.insert(hash, tx);
+            json!(hash)
+        }
+        "eth_getTransactionByHash" => {
+            let hash: H256 = serde_json::from_value(params[0].clone()).unwrap();
+            match state.transactions.get(&hash) {
+                Some(tx) => json!({
+                    "hash": hash,
+                    "nonce": tx.nonce(),
+                    "blockHash": H256::repeat_byte(1),
+                    "blockNumber": "0x1",
+                    "transactionIndex": "0x0",
+                    "from": tx.from(),
+                    "to": tx.to().and_then(|to| to.as_address()),
+                    "value": "0x0",
+                    "gasPrice": "0x3b9aca00",
+                    "gas": tx.gas(),
+                    "input": tx.data(),
+                    "v": "0x0",
+                    "r": "0x0",
+                    "s": "0x0",
+                }),
+                None => Value::Null,
+            }
+        }
+        "eth_getTransactionReceipt" => {
+            let hash: H256 = serde_json::from_value(params[0].clone()).unwrap();
+            let status = if state.reverted.contains(&hash) {
+                "0x0"
+            } else {
+                "0x1"
+            };
+            match state.transactions.get(&hash) {
+                Some(tx) => json!({
+                    "transactionHash": hash,
+                    "transactionIndex": "0x0",
+                    "blockHash": H256::repeat_byte(1),
+                    "blockNumber": "0x1",
+                    "from": tx.from(),
+                    "to": tx.to().and_then(|to| to.as_address()),
+                    "cumulativeGasUsed": tx.gas(),
+                    "gasUsed": tx.gas(),
+                    "contractAddress": null,
+                    "logs": [],
+                    "status": status,
+                    "logsBloom": format!("0x{}", "0".repeat(512)),
+                    "type": "0x0",
+                    "effectiveGasPrice": "0x3b9aca00",
+                }),
+                None => Value::Null,
+            }
+        }
+        method => return Err(format!("unsupported method {method}")),
+    })
+}
+
+fn certificate_chain(length: usize) -> Vec<Certificate> {
+    let mut prev_id = CertificateId::default();
+    (0..length)
+        .map(|_| {
+            let certificate =
+                Certificate::new_with_default_fields(prev_id, SOURCE_SUBNET_ID, &[]).unwrap();
+            prev_id = certificate.id;
+            certificate
+        })
+        .collect()
+}
+
+async fn push_pipeline(endpoint: &str, config: PushPipelineConfig) -> PushPipeline {
+    let subnet_client = SubnetClient::new(
+        endpoint,
+        Some(hex::decode(ADMIN_PRIVATE_KEY).unwrap()),
+        CONTRACT_ADDRESS,
+    )
+    .await
+    .unwrap();
+
+    PushPipeline::new(Arc::new(subnet_client), config)
+}
+
+#[tokio::test]
+async fn pipeline_pushes_and_skips_held_certificates() {
+    let certificates = certificate_chain(4);
+    let state = Arc::new(Mutex::new(ChainState {
+        held_certificates: HashSet::from([certificates[1].id]),
+        ..Default::default()
+    }));
+    let endpoint = spawn_json_rpc_stand_in(state.clone()).await;
+
+    let mut pipeline = push_pipeline(
+        &endpoint,
+        PushPipelineConfig {
+            max_in_flight: 2,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let mut tx_hashes = Vec::new();
+    for (position, certificate) in certificates.iter().enumerate() {
+        tx_hashes.push(pipeline.submit(certificate, position as u64).await.unwrap());
+        assert!(pipeline.in_flight() <= 2);
+    }
+    assert!(tx_hashes[1].is_none());
+
+    let mut delivered = Vec::new();
+    while let Some((cert_id, receipt)) =
+        tokio::time::timeout(Duration::from_secs(30), pipeline.next_result())
+            .await
+            .unwrap()
+    {
+        let receipt = receipt.unwrap();
+        assert_eq!(
+            tx_hashes
+                .iter()
+                .flatten()
+                .find(|hash| **hash == receipt.transaction_hash),
+            Some(&receipt.transaction_hash)
+        );
+        delivered.push(cert_id);
+    }
+    delivered.sort();
+
+    let mut expected = vec![certificates[0].id, certificates[2].id, certificates[3].id];
+    expected.sort();
+    assert_eq!(delivered, expected);
+
+    let state = state.lock().unwrap();
+    assert_eq!(
+        state.nonces,
+        vec![
+            INITIAL_NONCE.into(),
+            (INITIAL_NONCE + 1).into(),
+            (INITIAL_NONCE + 2).into()
+        ]
+    );
+    assert!(state
+        .transactions
+        .values()
+        .all(|tx| tx.gas() == Some(&U256::from(ESTIMATED_GAS * 120 / 100))));
+}
+
+#[tokio::test]
+async fn pipeline_fetches_nonce_again_after_failed_send() {
+    let certificates = certificate_chain(3);
+    let state = Arc::new(Mutex::new(ChainState::default()));
+    let endpoint = spawn_json_rpc_stand_in(state.clone()).await;
+
+    let mut pipeline = push_pipeline(
+        &endpoint,
+        PushPipelineConfig {
+            retry_base_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert!(pipeline
+        .submit(&certificates[0], 0)
+        .await
+        .unwrap()
+        .is_some());
+    // The rejected push is retried later instead of failing the submission
+    state.lock().unwrap().rejected_sends = 1;
+    assert!(pipeline
+        .submit(&certificates[1], 1)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(pipeline
+        .submit(&certificates[2], 2)
+        .await
+        .unwrap()
+        .is_some());
+
+    let delivered = wait_for_results(&mut pipeline).await;
+    assert_eq!(delivered.len(), 3);
+    assert!(delivered.iter().all(|(_, pushed)| *pushed));
+
+    let state = state.lock().unwrap();
+    assert_eq!(
+        state.nonces,
+        vec![
+            INITIAL_NONCE.into(),
+            (INITIAL_NONCE + 1).into(),
+            (INITIAL_NONCE + 2).into()
+        ]
+    );
+    // Each certificate is pushed once, the retry outbidding the failed attempt
+    assert_eq!(
+        state.gas_prices,
+        vec![
+            GAS_PRICE.into(),
+            GAS_PRICE.into(),
+            (GAS_PRICE * 110 / 100).into()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn pipeline_retries_dropped_push_with_a_gas_bump() {
+    let certificates = certificate_chain(1);
+    let state = Arc::new(Mutex::new(ChainState {
+        dropped_sends: 1,
+        ..Default::default()
+    }));
+    let endpoint = spawn_json_rpc_stand_in(state.clone()).await;
+
+    let mut pipeline = push_pipeline(
+        &endpoint,
+        PushPipelineConfig {
+            retry_base_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let dropped = pipeline.submit(&certificates[0], 0).await.unwrap().unwrap();
+
+    let (cert_id, receipt) = tokio::time::timeout(Duration::from_secs(30), pipeline.next_result())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(cert_id, certificates[0].id);
+    assert_ne!(receipt.unwrap().transaction_hash, dropped);
+
+    let state = state.lock().unwrap();
+    // The dropped transaction never consumed its nonce
+    assert_eq!(
+        state.nonces,
+        vec![INITIAL_NONCE.into(), INITIAL_NONCE.into()]
+    );
+    assert_eq!(
+        state.gas_prices,
+        vec![GAS_PRICE.into(), (GAS_PRICE * 110 / 100).into()]
+    );
+}
+
+#[tokio::test]
+async fn pipeline_retries_reverted_push_until_the_last_attempt() {
+    let certificates = certificate_chain(1);
+    let state = Arc::new(Mutex::new(ChainState {
+        reverted_sends: 2,
+        ..Default::default()
+    }));
+    let endpoint = spawn_json_rpc_stand_in(state.clone()).await;
+
+    let mut pipeline = push_pipeline(
+        &endpoint,
+        PushPipelineConfig {
+            retry_base_delay: Duration::from_millis(10),
+            max_attempts: 2,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    pipeline.submit(&certificates[0], 0).await.unwrap();
+
+    let (cert_id, receipt) = tokio::time::timeout(Duration::from_secs(30), pipeline.next_result())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(cert_id, certificates[0].id);
+    assert!(receipt.is_err());
+    assert!(pipeline.next_result().await.is_none());
+
+    let state = state.lock().unwrap();
+    assert_eq!(
+        state.nonces,
+        vec![INITIAL_NONCE.into(), (INITIAL_NONCE + 1).into()]
+    );
+    assert_eq!(
+        state.gas_prices,
+        vec![GAS_PRICE.into(), (GAS_PRICE * 110 / 100).into()]
+    );
+}
+
+#[tokio::test]
+async fn pipeline_skips_retry_of_certificate_held_in_the_meantime() {
+    let certificates = certificate_chain(1);
+    let state = Arc::new(Mutex::new(ChainState {
+        dropped_sends: 1,
+        ..Default::default()
+    }));
+    let endpoint = spawn_json_rpc_stand_in(state.clone()).await;
+
+    let mut pipeline = push_pipeline(
+        &endpoint,
+        PushPipelineConfig {
+            retry_base_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    pipeline.submit(&certificates[0], 0).await.unwrap();
+    // Pushed by another sequencer before the retry
+    state
+        .lock()
+        .unwrap()
+        .held_certificates
+        .insert(certificates[0].id);
+
+    assert!(
+        tokio::time::timeout(Duration::from_secs(30), pipeline.next_result())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(state.lock().unwrap().nonces.len(), 1);
+}
+
+/// Wait for every push in flight, returns whether each certificate was pushed
+async fn wait_for_results(pipeline: &mut PushPipeline) -> Vec<(CertificateId, bool)> {
+    let mut results = Vec::new();
+    while let Some((cert_id, receipt)) =
+        tokio::time::timeout(Duration::from_secs(30), pipeline.next_result())
+            .await
+            .unwrap()
+    {
+        results.push((cert_id, receipt.is_ok()));
+    }
+
+    results
+}
@@ -1,16 +1,218 @@
 use crate::Error;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashSet, LinkedList};
 use std::fmt::{Debug, Formatter};
+use std::ops::RangeInclusive;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
-use topos_core::uci::{Certificate, CertificateId, SubnetId};
-use topos_sequencer_subnet_client::{BlockInfo, SubnetEvent};
+use topos_core::uci::{
+    Address, BlockRange, Certificate, CertificateId, MessagesRoot, ReceiptsRootHash, SubnetId,
+    TxRootHash,
+};
+use topos_crypto::hash::calculate_hash;
+use topos_sequencer_subnet_client::{BlockInfo, Hash, SubnetEvent};
+use tracing::warn;
+
+/// Policy deciding which finalized blocks close a batch and get certified
+///
+/// A block emitting cross-subnet events always closes the batch, so that the
+/// messages of a certificate are all sent by the last block it covers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BatchingPolicy {
+    /// One certificate per finalized block
+    #[default]
+    EveryBlock,
+    /// One certificate every `n` finalized blocks
+    Blocks(u64),
+    /// One certificate for the blocks finalized within the time window
+    TimeWindow(Duration),
+    /// Only certify blocks emitting cross-subnet events
+    CrossSubnetEventsOnly,
+}
+
+/// Cross-subnet message sent by a certified block
+///
+/// The messages of a certificate are committed by its `messages_root`, the
+/// inclusion of one of them is proven with a [`MessageInclusionProof`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossSubnetMessage {
+    pub target_subnet_id: SubnetId,
+    /// Account which sent the message, as reported by the event
+    pub sender: Address,
+    /// Keccak256 hash of the payload of the message
+    pub payload_hash: [u8; 32],
+    /// Block emitting the message, the last one covered by the certificate
+    pub block_number: u64,
+    pub transaction_hash: [u8; 32],
+    pub transaction_index: u64,
+    /// Index of the log of the message in its block
+    pub log_index: u64,
+}
+
+impl CrossSubnetMessage {
+    /// Leaf of the message in the messages root of its certificate
+    pub fn hash(&self) -> [u8; 32] {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(self.target_subnet_id.as_array());
+        buffer.extend_from_slice(&self.sender);
+        buffer.extend_from_slice(&self.payload_hash);
+        buffer.extend_from_slice(&self.block_number.to_be_bytes());
+        buffer.extend_from_slice(&self.transaction_hash);
+        buffer.extend_from_slice(&self.transaction_index.to_be_bytes());
+        buffer.extend_from_slice(&self.log_index.to_be_bytes());
+
+        calculate_hash(&buffer)
+    }
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut buffer = [0u8; 64];
+    buffer[..32].copy_from_slice(left);
+    buffer[32..].copy_from_slice(right);
+
+    calculate_hash(&buffer)
+}
+
+/// Root of the Merkle tree of the messages, all zeros if there is none
+///
+/// The last node of a level without a sibling is promoted to the next level as is.
+pub fn messages_root(messages: &[CrossSubnetMessage]) -> MessagesRoot {
+    let mut level: Vec<[u8; 32]> = messages.iter().map(CrossSubnetMessage::hash).collect();
+    if level.is_empty() {
+        return MessagesRoot::default();
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_pair(left, right),
+                [node] => *node,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    level[0]
+}
+
+/// Proof of the inclusion of a cross-subnet message in the messages root of a certificate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageInclusionProof {
+    pub message: CrossSubnetMessage,
+    /// Index of the message among the messages of the certificate
+    pub index: u64,
+    /// Number of messages committed by the certificate
+    pub messages_count: u64,
+    /// Siblings of the nodes on the path from the message to the root
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl MessageInclusionProof {
+    /// Build the proof of inclusion of the message at `index`
+    pub fn new(messages: &[CrossSubnetMessage], index: usize) -> Option<Self> {
+        let message = messages.get(index)?.clone();
+        let mut level: Vec<[u8; 32]> = messages.iter().map(CrossSubnetMessage::hash).collect();
+        let mut position = index;
+        let mut siblings = Vec::new();
+
+        while level.len() > 1 {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+            position /= 2;
+        }
+
+        Some(Self {
+            message,
+            index: index as u64,
+            messages_count: messages.len() as u64,
+            siblings,
+        })
+    }
+
+    /// Check that the message is committed by the `messages_root` of a certificate
+    pub fn verify(&self, messages_root: &MessagesRoot) -> bool {
+        if self.index >= self.messages_count {
+            return false;
+        }
+
+        let mut node = self.message.hash();
+        let mut position = self.index;
+        let mut level_length = self.messages_count;
+        let mut siblings = self.siblings.iter();
+
+        while level_length > 1 {
+            // The last node of an odd level has no sibling
+            if position ^ 1 < level_length {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+
+                node = if position % 2 == 0 {
+                    hash_pair(&node, sibling)
+                } else {
+                    hash_pair(sibling, &node)
+                };
+            }
+
+            position /= 2;
+            level_length = (level_length + 1) / 2;
+        }
+
+        siblings.next().is_none() && node == *messages_root
+    }
+}
+
+/// Blocks of the certificate being batched
+///
+/// The transactions and receipts roots of the blocks are chained as they are appended,
+/// a batch of one block keeps the roots of this block as is.
+#[derive(Debug, Clone)]
+struct Batch {
+    start: u64,
+    started_at: Instant,
+    tx_root_hash: TxRootHash,
+    receipts_root_hash: ReceiptsRootHash,
+}
+
+impl Batch {
+    fn new(block: &BlockInfo) -> Self {
+        Self {
+            start: block.number,
+            started_at: Instant::now(),
+            tx_root_hash: block.tx_root_hash,
+            receipts_root_hash: block.receipts_root_hash,
+        }
+    }
+
+    fn append(&mut self, block: &BlockInfo) {
+        self.tx_root_hash = hash_pair(&self.tx_root_hash, &block.tx_root_hash);
+        self.receipts_root_hash = hash_pair(&self.receipts_root_hash, &block.receipts_root_hash);
+    }
+}
 
 pub struct Certification {
     /// Last known certificate id for subnet
     pub last_certificate_id: Option<CertificateId>,
     /// Latest BLOCK_HISTORY_LENGTH blocks kept in memory
     pub finalized_blocks: LinkedList<BlockInfo>,
+    /// Number of blocks to wait on top of a block before certifying it
+    pub finality_depth: u64,
+    /// Blocks received but not yet deep enough in the chain to be certified
+    unconfirmed_blocks: LinkedList<BlockInfo>,
+    /// Number and hash of the latest blocks handed over for certification
+    confirmed_blocks: LinkedList<(u64, Hash)>,
     /// Subnet id for which certificates are generated
     pub subnet_id: SubnetId,
     /// Type of verifier used
@@ -19,6 +221,10 @@ pub struct Certification {
     signing_key: Vec<u8>,
     /// Optional synchronization from particular block number
     pub start_block: Option<u64>,
+    /// Policy used to batch blocks into certificates
+    pub batching_policy: BatchingPolicy,
+    /// Blocks handed over for certification but not certified yet
+    batch: Option<Batch>,
 }
 
 impl Debug for Certification {
@@ -36,39 +242,111 @@ impl Certification {
         verifier: u32,
         signing_key: Vec<u8>,
         start_block: Option<u64>,
+        batching_policy: BatchingPolicy,
+        finality_depth: u64,
     ) -> Result<Arc<Mutex<Certification>>, crate::Error> {
         Ok(Arc::new(Mutex::from(Self {
             last_certificate_id: source_head_certificate_id,
             finalized_blocks: LinkedList::<BlockInfo>::new(),
+            finality_depth,
+            unconfirmed_blocks: LinkedList::<BlockInfo>::new(),
+            confirmed_blocks: LinkedList::new(),
             subnet_id: *subnet_id,
             verifier,
             signing_key,
             start_block,
+            batching_policy,
+            batch: None,
         })))
     }
 
     /// Generation of Certificates
-    pub(crate) async fn generate_certificates(&mut self) -> Result<Vec<Certificate>, Error> {
+    ///
+    /// Returns the generated certificates along with the range of blocks each one covers
+    /// and the cross-subnet messages it commits. The blocks are only consumed once every
+    /// certificate is built, they are kept for the next attempt on error.
+    pub(crate) async fn generate_certificates(
+        &mut self,
+    ) -> Result<Vec<(Certificate, RangeInclusive<u64>, Vec<CrossSubnetMessage>)>, Error> {
         let subnet_id = self.subnet_id;
         let mut generated_certificates = Vec::new();
 
-        // For every block, create one certificate
-        // This will change after MVP
+        // Check for inconsistencies
+        let first_block = self
+            .batch
+            .as_ref()
+            .map(|batch| batch.start)
+            .or_else(|| self.finalized_blocks.front().map(|b| b.number));
+        let is_genesis_certificate: bool = first_block
+            .map(|number| number == 0 || self.start_block.is_some())
+            .unwrap_or(false);
+        let last_known_certificate_id = if is_genesis_certificate {
+            // We are creating genesis certificate, there were no previous certificates
+            // In case where start block is present, we also consider start block as genesis certificate,
+            // so it has no history (prev cert id all 0)
+            CertificateId::default()
+        } else {
+            self.last_certificate_id
+                .ok_or(Error::InvalidPreviousCertificateId)?
+        };
+
+        let mut batch = self.batch.clone();
+        let mut last_certificate_id = self.last_certificate_id;
+
         for block_info in &self.finalized_blocks {
-            // Parse target subnets from events
+            let current = match batch.take() {
+                Some(mut batch) => {
+                    batch.append(block_info);
+                    batch
+                }
+                None => Batch::new(block_info),
+            };
+
+            // Parse target subnets and messages from events
             let mut target_subnets: HashSet<SubnetId> = HashSet::new();
+            let mut messages = Vec::new();
             for event in &block_info.events {
                 match event {
+                    SubnetEvent::CrossSubnetTargetAdded { target_subnet_id } => {
+                        target_subnets.insert(*target_subnet_id);
+                    }
                     SubnetEvent::CrossSubnetMessageSent {
-                        target_subnet_id, ..
+                        target_subnet_id,
+                        sender,
+                        payload_hash,
+                        transaction_hash,
+                        transaction_index,
+                        log_index,
                     } => {
                         target_subnets.insert(*target_subnet_id);
+                        messages.push(CrossSubnetMessage {
+                            target_subnet_id: *target_subnet_id,
+                            sender: *sender,
+                            payload_hash: *payload_hash,
+                            block_number: block_info.number,
+                            transaction_hash: *transaction_hash,
+                            transaction_index: *transaction_index,
+                            log_index: *log_index,
+                        });
                     }
                 }
             }
 
+            let closes_batch = !target_subnets.is_empty()
+                || match self.batching_policy {
+                    BatchingPolicy::EveryBlock => true,
+                    BatchingPolicy::Blocks(n) => block_info.number + 1 - current.start >= n,
+                    BatchingPolicy::TimeWindow(window) => current.started_at.elapsed() >= window,
+                    BatchingPolicy::CrossSubnetEventsOnly => false,
+                };
+
+            if !closes_batch {
+                batch = Some(current);
+                continue;
+            }
+
             // Get the id of the previous Certificate from local history
-            let previous_cert_id: CertificateId = match self.last_certificate_id {
+            let previous_cert_id: CertificateId = match last_certificate_id {
                 Some(cert_id) => cert_id,
                 None => {
                     // FIXME: This is genesis certificate we are generating because we are unable
@@ -80,12 +358,19 @@ impl Certification {
             // TODO: acquire proof
             let proof = Vec::new();
 
+            // The certificate commits to the state of the last block of the batch and
+            // to the transactions and receipts of all of them
             let mut certificate = Certificate::new(
                 previous_cert_id,
                 subnet_id,
                 block_info.state_root,
-                block_info.tx_root_hash,
-                block_info.receipts_root_hash,
+                current.tx_root_hash,
+                current.receipts_root_hash,
+                messages_root(&messages),
+                Some(BlockRange {
+                    start: current.start,
+                    end: block_info.number,
+                }),
                 &target_subnets.into_iter().collect::<Vec<_>>(),
                 self.verifier,
                 proof,
@@ -94,38 +379,21 @@ impl Certification {
             certificate
                 .update_signature(self.get_signing_key())
                 .map_err(Error::CertificateSigningError)?;
-            generated_certificates.push(certificate);
-        }
-
-        // Check for inconsistencies
-        let is_genesis_certificate: bool = self
-            .finalized_blocks
-            .front()
-            .map(|b| b.number == 0 || self.start_block.is_some())
-            .unwrap_or(false);
-        let last_known_certificate_id = if is_genesis_certificate {
-            // We are creating genesis certificate, there were no previous certificates
-            // In case where start block is present, we also consider start block as genesis certificate,
-            // so it has no history (prev cert id all 0)
-            CertificateId::default()
-        } else {
-            self.last_certificate_id
-                .ok_or(Error::InvalidPreviousCertificateId)?
-        };
 
-        for new_cert in &generated_certificates {
-            if last_known_certificate_id == new_cert.id {
+            if last_known_certificate_id == certificate.id {
                 // This should not happen
-                panic!("Same certificate generated multiple times: {new_cert:?}");
+                panic!("Same certificate generated multiple times: {certificate:?}");
             }
-        }
 
-        // Set info about latest known certificate for subnet
-        if let Some(generated_certificate) = generated_certificates.iter().last() {
-            self.last_certificate_id = Some(generated_certificate.id);
+            last_certificate_id = Some(certificate.id);
+
+            generated_certificates.push((certificate, current.start..=block_info.number, messages));
         }
 
-        // Remove processed blocks
+        // Set info about latest known certificate for subnet and remove processed blocks,
+        // the ones not certified yet are part of the current batch
+        self.last_certificate_id = last_certificate_id;
+        self.batch = batch;
         self.finalized_blocks.clear();
 
         Ok(generated_certificates)
@@ -135,11 +403,441 @@ impl Certification {
         self.signing_key.as_slice()
     }
 
+    /// Check whether the parent of the block is known, either as an unconfirmed
+    /// block or as a confirmed one
+    pub fn knows_parent(&self, block: &BlockInfo) -> bool {
+        (self.unconfirmed_blocks.is_empty() && self.confirmed_blocks.is_empty())
+            || self.knows_block(&block.parent_hash)
+    }
+
+    /// Check whether a block at this height was already handed over for certification
+    pub fn is_confirmed(&self, block_number: u64) -> bool {
+        self.confirmed_blocks
+            .back()
+            .map(|(number, _)| block_number <= *number)
+            .unwrap_or(false)
+    }
+
+    fn knows_block(&self, hash: &Hash) -> bool {
+        self.unconfirmed_blocks.iter().any(|b| b.hash == *hash)
+            || self.confirmed_blocks.iter().any(|(_, h)| h == hash)
+    }
+
     /// Expand short block history. Remove older blocks
-    pub fn append_blocks(&mut self, blocks: Vec<BlockInfo>) {
-        self.finalized_blocks.extend(blocks);
+    ///
+    /// Blocks are checked for chain continuity and kept aside until `finality_depth`
+    /// blocks are built on top of them. Unconfirmed blocks replaced by a reorg are
+    /// dropped, while a reorg of a confirmed block is reported as an error.
+    pub fn append_blocks(&mut self, blocks: Vec<BlockInfo>) -> Result<(), Error> {
+        for block in blocks {
+            if self.knows_block(&block.hash) {
+                continue;
+            }
+
+            if self.is_confirmed(block.number) {
+                return Err(Error::CertifiedBlockReorged {
+                    block_number: block.number,
+                });
+            }
+
+            if !self.knows_parent(&block) {
+                return Err(Error::UnknownParentBlock {
+                    block_number: block.number,
+                    parent_hash: block.parent_hash,
+                });
+            }
+
+            // Drop the unconfirmed blocks of the reorged branch, if any
+            let kept = self
+                .unconfirmed_blocks
+                .iter()
+                .position(|b| b.hash == block.parent_hash)
+                .map(|position| position + 1)
+                .unwrap_or(0);
+            let reorged = self.unconfirmed_blocks.split_off(kept);
+            if !reorged.is_empty() {
+                warn!(
+                    "Reorg detected at block {}, dropping {} unconfirmed blocks",
+                    block.number,
+                    reorged.len()
+                );
+            }
+
+            self.unconfirmed_blocks.push_back(block);
+
+            while self.unconfirmed_blocks.len() as u64 > self.finality_depth {
+                if let Some(confirmed) = self.unconfirmed_blocks.pop_front() {
+                    self.confirmed_blocks
+                        .push_back((confirmed.number, confirmed.hash.clone()));
+                    self.finalized_blocks.push_back(confirmed);
+                }
+            }
+        }
+
         while self.finalized_blocks.len() > Self::BLOCK_HISTORY_LENGTH {
             self.finalized_blocks.pop_front();
         }
+        while self.confirmed_blocks.len() > Self::BLOCK_HISTORY_LENGTH {
+            self.confirmed_blocks.pop_front();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE_SUBNET_ID: SubnetId = SubnetId::from_array([1u8; 32]);
+    const TARGET_SUBNET_ID: SubnetId = SubnetId::from_array([2u8; 32]);
+
+    fn block(number: u64, with_event: bool) -> BlockInfo {
+        BlockInfo {
+            hash: format!("0x{number}"),
+            parent_hash: number
+                .checked_sub(1)
+                .map(|parent| format!("0x{parent}"))
+                .unwrap_or_default(),
+            number,
+            events: if with_event {
+                vec![SubnetEvent::CrossSubnetMessageSent {
+                    target_subnet_id: TARGET_SUBNET_ID,
+                    sender: [3u8; 20],
+                    payload_hash: [4u8; 32],
+                    transaction_hash: [5u8; 32],
+                    transaction_index: 0,
+                    log_index: 1,
+                }]
+            } else {
+                Vec::new()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn forked_block(number: u64, parent_hash: &str) -> BlockInfo {
+        BlockInfo {
+            hash: format!("0x{number}-fork"),
+            parent_hash: parent_hash.to_string(),
+            number,
+            ..Default::default()
+        }
+    }
+
+    async fn certification(batching_policy: BatchingPolicy, finality_depth: u64) -> Certification {
+        let certification = Certification::new(
+            &SOURCE_SUBNET_ID,
+            None,
+            0,
+            vec![1u8; 32],
+            None,
+            batching_policy,
+            finality_depth,
+        )
+        .unwrap();
+
+        Arc::try_unwrap(certification).unwrap().into_inner()
+    }
+
+    #[tokio::test]
+    async fn batch_every_n_blocks() {
+        let mut certification = certification(BatchingPolicy::Blocks(3), 0).await;
+
+        certification
+            .append_blocks((0..5).map(|number| block(number, false)).collect())
+            .unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].1, 0..=2);
+        assert_eq!(certificates[0].0.prev_id, CertificateId::default());
+
+        certification.append_blocks(vec![block(5, false)]).unwrap();
+        let next_certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(next_certificates.len(), 1);
+        assert_eq!(next_certificates[0].1, 3..=5);
+        assert_eq!(next_certificates[0].0.prev_id, certificates[0].0.id);
+    }
+
+    #[tokio::test]
+    async fn batch_commits_the_roots_of_all_its_blocks() {
+        let mut certification = certification(BatchingPolicy::Blocks(2), 0).await;
+        let blocks: Vec<BlockInfo> = (0..3)
+            .map(|number| BlockInfo {
+                tx_root_hash: [number as u8 + 1; 32],
+                receipts_root_hash: [number as u8 + 10; 32],
+                ..block(number, false)
+            })
+            .collect();
+
+        certification.append_blocks(blocks.clone()).unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(
+            certificates[0].0.block_range,
+            Some(BlockRange { start: 0, end: 1 })
+        );
+        assert_eq!(
+            certificates[0].0.tx_root_hash,
+            hash_pair(&blocks[0].tx_root_hash, &blocks[1].tx_root_hash)
+        );
+        assert_eq!(
+            certificates[0].0.receipts_root_hash,
+            hash_pair(&blocks[0].receipts_root_hash, &blocks[1].receipts_root_hash)
+        );
+
+        // A batch of a single block commits to its roots as is
+        let mut every_block = certification(BatchingPolicy::EveryBlock, 0).await;
+        every_block.append_blocks(blocks.clone()).unwrap();
+        let certificates = every_block.generate_certificates().await.unwrap();
+
+        assert_eq!(
+            certificates[2].0.block_range,
+            Some(BlockRange { start: 2, end: 2 })
+        );
+        assert_eq!(certificates[2].0.tx_root_hash, blocks[2].tx_root_hash);
+        assert_eq!(
+            certificates[2].0.receipts_root_hash,
+            blocks[2].receipts_root_hash
+        );
+    }
+
+    #[tokio::test]
+    async fn keep_the_blocks_of_a_failed_certificate() {
+        let mut certification = certification(BatchingPolicy::Blocks(2), 0).await;
+        certification.signing_key = vec![0u8; 32];
+
+        certification
+            .append_blocks((0..2).map(|number| block(number, false)).collect())
+            .unwrap();
+
+        assert!(matches!(
+            certification.generate_certificates().await,
+            Err(Error::CertificateSigningError(_))
+        ));
+        assert_eq!(certification.last_certificate_id, None);
+
+        certification.signing_key = vec![1u8; 32];
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].1, 0..=1);
+    }
+
+    #[tokio::test]
+    async fn block_with_events_closes_the_batch() {
+        let mut certification = certification(BatchingPolicy::Blocks(10), 0).await;
+
+        certification
+            .append_blocks(vec![block(0, false), block(1, true), block(2, false)])
+            .unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].1, 0..=1);
+        assert_eq!(certificates[0].0.target_subnets, vec![TARGET_SUBNET_ID]);
+    }
+
+    #[tokio::test]
+    async fn certificate_carries_messages_of_its_blocks() {
+        let mut certification = certification(BatchingPolicy::EveryBlock, 0).await;
+
+        certification
+            .append_blocks(vec![block(0, false), block(1, true)])
+            .unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert!(certificates[0].2.is_empty());
+        assert_eq!(
+            certificates[1].2,
+            vec![CrossSubnetMessage {
+                target_subnet_id: TARGET_SUBNET_ID,
+                sender: [3u8; 20],
+                payload_hash: [4u8; 32],
+                block_number: 1,
+                transaction_hash: [5u8; 32],
+                transaction_index: 0,
+                log_index: 1,
+            }]
+        );
+        assert_eq!(certificates[0].0.messages_root, MessagesRoot::default());
+        assert_eq!(
+            certificates[1].0.messages_root,
+            messages_root(&certificates[1].2)
+        );
+    }
+
+    #[tokio::test]
+    async fn target_without_message_is_not_committed() {
+        let mut certification = certification(BatchingPolicy::EveryBlock, 0).await;
+
+        let mut target_only = block(0, false);
+        target_only.events = vec![SubnetEvent::CrossSubnetTargetAdded {
+            target_subnet_id: TARGET_SUBNET_ID,
+        }];
+        certification.append_blocks(vec![target_only]).unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(certificates[0].0.target_subnets, vec![TARGET_SUBNET_ID]);
+        assert!(certificates[0].2.is_empty());
+        assert_eq!(certificates[0].0.messages_root, MessagesRoot::default());
+    }
+
+    fn message(log_index: u64) -> CrossSubnetMessage {
+        CrossSubnetMessage {
+            target_subnet_id: TARGET_SUBNET_ID,
+            sender: [3u8; 20],
+            payload_hash: [4u8; 32],
+            block_number: 1,
+            transaction_hash: [5u8; 32],
+            transaction_index: 0,
+            log_index,
+        }
+    }
+
+    #[test]
+    fn messages_of_a_transaction_have_distinct_leaves() {
+        assert_ne!(message(0).hash(), message(1).hash());
+        assert_eq!(messages_root(&[]), MessagesRoot::default());
+        assert_eq!(messages_root(&[message(0)]), message(0).hash());
+    }
+
+    #[test]
+    fn prove_inclusion_of_messages() {
+        for count in 1..=6 {
+            let messages = (0..count).map(message).collect::<Vec<_>>();
+            let root = messages_root(&messages);
+
+            for index in 0..messages.len() {
+                let proof = MessageInclusionProof::new(&messages, index).unwrap();
+                assert!(proof.verify(&root), "{index} of {count} messages");
+
+                let mut forged = proof.clone();
+                forged.message.target_subnet_id = SOURCE_SUBNET_ID;
+                assert!(!forged.verify(&root));
+
+                let mut misplaced = proof.clone();
+                misplaced.index = (proof.index + 1) % proof.messages_count;
+                if count > 1 {
+                    assert!(!misplaced.verify(&root));
+                }
+            }
+
+            assert!(MessageInclusionProof::new(&messages, messages.len()).is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn only_certify_blocks_with_events() {
+        let mut certification = certification(BatchingPolicy::CrossSubnetEventsOnly, 0).await;
+
+        certification
+            .append_blocks((0..4).map(|number| block(number, false)).collect())
+            .unwrap();
+        assert!(certification
+            .generate_certificates()
+            .await
+            .unwrap()
+            .is_empty());
+
+        certification.append_blocks(vec![block(4, true)]).unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].1, 0..=4);
+    }
+
+    #[tokio::test]
+    async fn chain_certificates_of_every_block() {
+        let mut certification = certification(BatchingPolicy::EveryBlock, 0).await;
+
+        certification
+            .append_blocks((0..3).map(|number| block(number, false)).collect())
+            .unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(certificates.len(), 3);
+        assert_eq!(certificates[1].0.prev_id, certificates[0].0.id);
+        assert_eq!(certificates[2].0.prev_id, certificates[1].0.id);
+        assert_eq!(
+            certification.last_certificate_id,
+            Some(certificates[2].0.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn wait_for_finality_depth() {
+        let mut certification = certification(BatchingPolicy::EveryBlock, 2).await;
+
+        certification
+            .append_blocks((0..3).map(|number| block(number, false)).collect())
+            .unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].1, 0..=0);
+    }
+
+    #[tokio::test]
+    async fn drop_reorged_unconfirmed_blocks() {
+        let mut certification = certification(BatchingPolicy::EveryBlock, 2).await;
+
+        certification
+            .append_blocks((0..3).map(|number| block(number, false)).collect())
+            .unwrap();
+        certification.generate_certificates().await.unwrap();
+
+        // Block 1 and 2 are replaced by a fork of block 1
+        certification
+            .append_blocks(vec![forked_block(1, "0x0")])
+            .unwrap();
+        certification
+            .append_blocks(vec![
+                forked_block(2, "0x1-fork"),
+                forked_block(3, "0x2-fork"),
+            ])
+            .unwrap();
+        let certificates = certification.generate_certificates().await.unwrap();
+
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].1, 1..=1);
+        assert_eq!(
+            certificates[0].0.id,
+            certification.last_certificate_id.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn detect_unknown_parent() {
+        let mut certification = certification(BatchingPolicy::EveryBlock, 0).await;
+
+        certification
+            .append_blocks((0..2).map(|number| block(number, false)).collect())
+            .unwrap();
+
+        assert!(matches!(
+            certification.append_blocks(vec![forked_block(3, "0x2-fork")]),
+            Err(Error::UnknownParentBlock {
+                block_number: 3,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn detect_reorg_of_certified_block() {
+        let mut certification = certification(BatchingPolicy::EveryBlock, 1).await;
+
+        certification
+            .append_blocks((0..3).map(|number| block(number, false)).collect())
+            .unwrap();
+        certification.generate_certificates().await.unwrap();
+
+        assert!(matches!(
+            certification.append_blocks(vec![forked_block(1, "0x0")]),
+            Err(Error::CertifiedBlockReorged { block_number: 1 })
+        ));
     }
 }
@@ -3,19 +3,28 @@
 //! Abstracted from actual transport implementation.
 //! Abstracted from actual storage implementation.
 //!
+use messages::MessageStore;
 use proxy::SubnetRuntimeProxy;
+use std::path::PathBuf;
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::Mutex;
 use tokio::sync::{mpsc, oneshot};
 use topos_core::api::grpc::checkpoints::TargetStreamPosition;
-use topos_core::uci::{CertificateId, SubnetId};
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
+use topos_sequencer_subnet_client::EthersSubnet;
+use tracing::info;
 
 pub type Peer = String;
 
 pub mod certification;
+pub mod messages;
+pub mod outbox;
 pub mod proxy;
 
+pub use certification::{messages_root, BatchingPolicy, CrossSubnetMessage, MessageInclusionProof};
+pub use topos_sequencer_subnet_client::{PushPipelineConfig, Subnet};
+
 use crate::proxy::{SubnetRuntimeProxyCommand, SubnetRuntimeProxyEvent};
 
 #[derive(Debug, Error)]
@@ -73,6 +82,21 @@ pub enum Error {
 
     #[error("Invalid endpoint: {0}")]
     InvalidEndpoint(String),
+
+    #[error("Parent {parent_hash} of block {block_number} is unknown")]
+    UnknownParentBlock {
+        block_number: u64,
+        parent_hash: String,
+    },
+
+    #[error("Block {block_number} was reorged after being certified")]
+    CertifiedBlockReorged { block_number: u64 },
+
+    #[error("Outbox error: {0}")]
+    OutboxError(String),
+
+    #[error("Message store error: {0}")]
+    MessageStoreError(String),
 }
 
 #[derive(Debug, Clone)]
@@ -84,6 +108,14 @@ pub struct SubnetRuntimeProxyConfig {
     pub source_head_certificate_id: Option<CertificateId>,
     pub verifier: u32,
     pub start_block: Option<u64>,
+    pub batching_policy: BatchingPolicy,
+    pub finality_depth: u64,
+    pub outbox_path: Option<PathBuf>,
+    /// Path of the file retaining the cross-subnet messages of the certificates
+    pub messages_path: Option<PathBuf>,
+    /// Number of certificates whose cross-subnet messages are retained
+    pub messages_retention: usize,
+    pub push_pipeline: PushPipelineConfig,
 }
 
 /// Thread safe client to the protocol aggregate
@@ -102,7 +134,27 @@ impl SubnetRuntimeProxyWorker {
         config: SubnetRuntimeProxyConfig,
         signing_key: Vec<u8>,
     ) -> Result<Self, Error> {
-        let runtime_proxy = SubnetRuntimeProxy::spawn_new(config, signing_key)?;
+        let subnet = EthersSubnet::connect(
+            &config.http_endpoint,
+            &config.ws_endpoint,
+            signing_key.clone(),
+            &config.subnet_contract_address,
+            config.push_pipeline.clone(),
+        )
+        .await
+        .map_err(|source| Error::SubnetError { source })?;
+        info!("Connected to subnet node {}", &config.http_endpoint);
+
+        Self::with_subnet(config, signing_key, Arc::new(subnet)).await
+    }
+
+    /// Creates new instance of the aggregate on top of the given subnet backend
+    pub async fn with_subnet(
+        config: SubnetRuntimeProxyConfig,
+        signing_key: Vec<u8>,
+        subnet: Arc<dyn Subnet>,
+    ) -> Result<Self, Error> {
+        let runtime_proxy = SubnetRuntimeProxy::spawn_new(config, signing_key, subnet)?;
         let (events_sender, events_rcv) = mpsc::channel::<SubnetRuntimeProxyEvent>(256);
         let commands;
         {
@@ -150,6 +202,52 @@ impl SubnetRuntimeProxyWorker {
         SubnetRuntimeProxy::get_subnet_id(http_endpoint, contract_address).await
     }
 
+    /// Store retaining the cross-subnet messages of the certificates
+    pub async fn message_store(&self) -> Arc<Mutex<MessageStore>> {
+        self.runtime_proxy.lock().await.messages.clone()
+    }
+
+    /// Cross-subnet messages committed by a retained certificate
+    pub async fn cross_subnet_messages(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Option<Vec<CrossSubnetMessage>> {
+        let messages = self.message_store().await;
+        let messages = messages.lock().await;
+        messages.get(certificate_id).map(<[_]>::to_vec)
+    }
+
+    /// Proof of inclusion of the message emitted at `log_index` in the messages root of a
+    /// retained certificate
+    pub async fn cross_subnet_message_proof(
+        &self,
+        certificate_id: &CertificateId,
+        log_index: u64,
+    ) -> Option<MessageInclusionProof> {
+        let messages = self.message_store().await;
+        let messages = messages.lock().await;
+        messages.proof(certificate_id, log_index)
+    }
+
+    /// Reconcile the outbox with the latest delivered and pending certificates known by the TCE
+    ///
+    /// Returns the head from which the certification resumes, along with the number of the
+    /// last block it certifies.
+    pub async fn reconcile_outbox(
+        &self,
+        tce_delivered: Option<&Certificate>,
+        tce_pending: Option<&Certificate>,
+    ) -> Result<Option<(CertificateId, u64)>, Error> {
+        let mut runtime_proxy = self.runtime_proxy.lock().await;
+        runtime_proxy
+            .reconcile_outbox(tce_delivered, tce_pending)
+            .await
+    }
+
+    /// Set the head of the source stream, along with the number of the last block it certifies
+    ///
+    /// The certification resumes from the block following it, or from the genesis block
+    /// (or `start_block`) if there is no head.
     pub async fn set_source_head_certificate_id(
         &self,
         source_head_certificate_id: Option<(CertificateId, u64)>,
@@ -0,0 +1,164 @@
+//! Persistent store of the cross-subnet messages committed by the certificates of the sequencer
+//!
+use crate::outbox::write_atomically;
+use crate::{CrossSubnetMessage, Error, MessageInclusionProof};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use topos_core::uci::CertificateId;
+
+/// Default number of certificates whose messages are retained
+pub const DEFAULT_MESSAGES_RETENTION: usize = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MessagesEntry {
+    certificate_id: CertificateId,
+    messages: Vec<CrossSubnetMessage>,
+}
+
+/// Messages of the latest certificates, kept after their delivery so that the
+/// receiving subnets can retrieve them along with their inclusion proofs
+///
+/// Only the certificates committing messages are retained, the oldest ones are
+/// dropped beyond `retention`. The entries are persisted on every update if a
+/// path is provided.
+#[derive(Debug)]
+pub struct MessageStore {
+    path: Option<PathBuf>,
+    retention: usize,
+    entries: VecDeque<MessagesEntry>,
+}
+
+impl MessageStore {
+    /// Open the store, loading the entries persisted at `path` if any
+    pub fn open(path: Option<PathBuf>, retention: usize) -> Result<Self, Error> {
+        let entries = match &path {
+            Some(path) if path.exists() => {
+                let content = std::fs::read(path).map_err(|e| {
+                    Error::MessageStoreError(format!("unable to read messages: {e}"))
+                })?;
+                serde_json::from_slice(&content).map_err(|e| {
+                    Error::MessageStoreError(format!("unable to parse messages: {e}"))
+                })?
+            }
+            _ => VecDeque::new(),
+        };
+
+        Ok(Self {
+            path,
+            retention,
+            entries,
+        })
+    }
+
+    /// Retain the messages of a newly generated certificate
+    pub fn insert(
+        &mut self,
+        certificate_id: CertificateId,
+        messages: Vec<CrossSubnetMessage>,
+    ) -> Result<(), Error> {
+        if messages.is_empty() || self.get(&certificate_id).is_some() {
+            return Ok(());
+        }
+
+        self.entries.push_back(MessagesEntry {
+            certificate_id,
+            messages,
+        });
+        while self.entries.len() > self.retention {
+            self.entries.pop_front();
+        }
+
+        self.persist()
+    }
+
+    /// Messages committed by a certificate, `None` if it doesn't commit any or isn't retained
+    pub fn get(&self, certificate_id: &CertificateId) -> Option<&[CrossSubnetMessage]> {
+        self.entries
+            .iter()
+            .find(|entry| entry.certificate_id == *certificate_id)
+            .map(|entry| entry.messages.as_slice())
+    }
+
+    /// Proof of inclusion of the message emitted at `log_index` in the messages root of a
+    /// certificate
+    pub fn proof(
+        &self,
+        certificate_id: &CertificateId,
+        log_index: u64,
+    ) -> Option<MessageInclusionProof> {
+        let messages = self.get(certificate_id)?;
+        let index = messages
+            .iter()
+            .position(|message| message.log_index == log_index)?;
+
+        MessageInclusionProof::new(messages, index)
+    }
+
+    fn persist(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let content = serde_json::to_vec(&self.entries)
+            .map_err(|e| Error::MessageStoreError(format!("unable to serialize messages: {e}")))?;
+
+        write_atomically(path, &content)
+            .map_err(|e| Error::MessageStoreError(format!("unable to write messages: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use topos_core::uci::SubnetId;
+
+    fn message(log_index: u64) -> CrossSubnetMessage {
+        CrossSubnetMessage {
+            target_subnet_id: SubnetId::from_array([2u8; 32]),
+            sender: [3u8; 20],
+            payload_hash: [4u8; 32],
+            block_number: 1,
+            transaction_hash: [5u8; 32],
+            transaction_index: 0,
+            log_index,
+        }
+    }
+
+    #[test]
+    fn drop_the_oldest_certificates_beyond_retention() {
+        let mut store = MessageStore::open(None, 2).unwrap();
+        for id in 1..=3u8 {
+            store.insert([id; 32].into(), vec![message(0)]).unwrap();
+        }
+        store.insert([4u8; 32].into(), Vec::new()).unwrap();
+
+        assert!(store.get(&[1u8; 32].into()).is_none());
+        assert!(store.get(&[2u8; 32].into()).is_some());
+        assert!(store.get(&[3u8; 32].into()).is_some());
+        assert!(store.get(&[4u8; 32].into()).is_none());
+    }
+
+    #[test]
+    fn persist_messages() {
+        let path = std::env::temp_dir().join(format!("messages-{}.json", rand::random::<u64>()));
+        let certificate_id: CertificateId = [1u8; 32].into();
+
+        {
+            let mut store = MessageStore::open(Some(path.clone()), 2).unwrap();
+            store
+                .insert(certificate_id, vec![message(0), message(1)])
+                .unwrap();
+        }
+
+        let store = MessageStore::open(Some(path.clone()), 2).unwrap();
+        assert_eq!(
+            store.get(&certificate_id),
+            Some([message(0), message(1)].as_slice())
+        );
+        assert_eq!(store.proof(&certificate_id, 1).unwrap().index, 1);
+        assert!(store.proof(&certificate_id, 2).is_none());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
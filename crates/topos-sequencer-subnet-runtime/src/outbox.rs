@@ -0,0 +1,416 @@
+//! Persistent outbox of the certificates produced by the sequencer
+//!
+use crate::{CrossSubnetMessage, Error};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::Write;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use topos_core::uci::{Certificate, CertificateId};
+use tracing::warn;
+
+/// Submission state of a certificate produced by the sequencer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CertificateState {
+    /// Generated but not yet accepted by the TCE
+    Generated,
+    /// Accepted by the TCE, waiting to be delivered
+    Submitted,
+    /// Delivered by the TCE network
+    Delivered,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub certificate: Certificate,
+    /// Range of blocks covered by the certificate
+    pub block_range: RangeInclusive<u64>,
+    /// Cross-subnet messages committed by the certificate
+    #[serde(default)]
+    pub messages: Vec<CrossSubnetMessage>,
+    pub state: CertificateState,
+    /// Number of failed submissions since the start of the sequencer
+    #[serde(skip)]
+    pub failed_attempts: u32,
+}
+
+/// Outbox keeping track of the certificates until their delivery
+///
+/// The entries are persisted on every update if a path is provided. Entries
+/// preceding the latest delivered certificate are dropped.
+#[derive(Debug, Default)]
+pub struct Outbox {
+    path: Option<PathBuf>,
+    entries: VecDeque<OutboxEntry>,
+}
+
+impl Outbox {
+    /// Open the outbox, loading the entries persisted at `path` if any
+    pub fn open(path: Option<PathBuf>) -> Result<Self, Error> {
+        let entries = match &path {
+            Some(path) if path.exists() => {
+                let content = std::fs::read(path)
+                    .map_err(|e| Error::OutboxError(format!("unable to read outbox: {e}")))?;
+                serde_json::from_slice(&content)
+                    .map_err(|e| Error::OutboxError(format!("unable to parse outbox: {e}")))?
+            }
+            _ => VecDeque::new(),
+        };
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &OutboxEntry> {
+        self.entries.iter()
+    }
+
+    pub fn get(&self, certificate_id: &CertificateId) -> Option<&OutboxEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.certificate.id == *certificate_id)
+    }
+
+    /// Number of the last block covered by a certificate of the outbox
+    pub fn certified_block(&self, certificate_id: &CertificateId) -> Option<u64> {
+        self.get(certificate_id)
+            .map(|entry| *entry.block_range.end())
+    }
+
+    pub fn last_entry(&self) -> Option<&OutboxEntry> {
+        self.entries.back()
+    }
+
+    /// Add a newly generated certificate to the outbox
+    pub fn push(
+        &mut self,
+        certificate: Certificate,
+        block_range: RangeInclusive<u64>,
+        messages: Vec<CrossSubnetMessage>,
+    ) -> Result<(), Error> {
+        self.entries.push_back(OutboxEntry {
+            certificate,
+            block_range,
+            messages,
+            state: CertificateState::Generated,
+            failed_attempts: 0,
+        });
+
+        self.persist()
+    }
+
+    /// Mark the certificate as accepted by the TCE
+    pub fn mark_submitted(&mut self, certificate_id: &CertificateId) -> Result<(), Error> {
+        if let Some(entry) = self.get_mut(certificate_id) {
+            if entry.state == CertificateState::Generated {
+                entry.state = CertificateState::Submitted;
+                return self.persist();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record a failed submission, returning the entry to submit again with
+    /// its number of failed attempts
+    pub fn record_failed_attempt(&mut self, certificate_id: &CertificateId) -> Option<OutboxEntry> {
+        let entry = self.get_mut(certificate_id)?;
+        if entry.state != CertificateState::Generated {
+            return None;
+        }
+        entry.failed_attempts += 1;
+
+        Some(entry.clone())
+    }
+
+    /// Mark the certificate as delivered, dropping the entries preceding it
+    pub fn mark_delivered(&mut self, certificate_id: &CertificateId) -> Result<(), Error> {
+        let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| entry.certificate.id == *certificate_id)
+        else {
+            return Ok(());
+        };
+
+        self.entries.drain(..index);
+        if let Some(entry) = self.entries.front_mut() {
+            entry.state = CertificateState::Delivered;
+        }
+
+        self.persist()
+    }
+
+    /// Reconcile the outbox with the latest certificates known by the TCE for the subnet
+    ///
+    /// The entries preceding the latest delivered certificate are dropped, and the ones up to
+    /// the latest certificate of the TCE pending pool are considered submitted. Returns the
+    /// entries the TCE doesn't know about and which need to be submitted again. If the outbox
+    /// doesn't chain with the latest certificate known by the TCE, it is considered stale and
+    /// cleared.
+    pub fn reconcile(
+        &mut self,
+        tce_delivered: Option<CertificateId>,
+        tce_pending: Option<CertificateId>,
+    ) -> Result<Vec<OutboxEntry>, Error> {
+        if let Some(delivered) = tce_delivered {
+            self.mark_delivered(&delivered)?;
+        }
+
+        let head = tce_pending.or(tce_delivered).unwrap_or_default();
+        let first_unknown = self
+            .entries
+            .iter()
+            .position(|entry| entry.certificate.id == head)
+            .map(|index| index + 1)
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .position(|entry| entry.certificate.prev_id == head)
+            });
+
+        let Some(first_unknown) = first_unknown else {
+            if !self.entries.is_empty() {
+                warn!(
+                    "Outbox doesn't chain with the TCE head {head}, dropping {} entries",
+                    self.entries.len()
+                );
+                self.entries.clear();
+            }
+
+            return self.persist().map(|_| Vec::new());
+        };
+
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            if index < first_unknown {
+                if entry.state == CertificateState::Generated {
+                    entry.state = CertificateState::Submitted;
+                }
+            } else {
+                entry.state = CertificateState::Generated;
+            }
+        }
+
+        self.persist()?;
+
+        Ok(self.entries.iter().skip(first_unknown).cloned().collect())
+    }
+
+    fn get_mut(&mut self, certificate_id: &CertificateId) -> Option<&mut OutboxEntry> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.certificate.id == *certificate_id)
+    }
+
+    fn persist(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let content = serde_json::to_vec(&self.entries)
+            .map_err(|e| Error::OutboxError(format!("unable to serialize outbox: {e}")))?;
+
+        write_atomically(path, &content)
+            .map_err(|e| Error::OutboxError(format!("unable to write outbox: {e}")))
+    }
+}
+
+/// Write the file through a temporary one so that a crash can't corrupt it, both the file and
+/// the rename are synced so that the content survives a power loss
+pub(crate) fn write_atomically(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+
+    std::fs::rename(&tmp_path, path)?;
+
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    File::open(parent)?.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use topos_core::uci::SubnetId;
+
+    const SOURCE_SUBNET_ID: SubnetId = SubnetId::from_array([1u8; 32]);
+
+    fn certificate_chain(length: usize) -> Vec<Certificate> {
+        let mut prev_id = CertificateId::default();
+        (0..length)
+            .map(|_| {
+                let certificate =
+                    Certificate::new_with_default_fields(prev_id, SOURCE_SUBNET_ID, &[]).unwrap();
+                prev_id = certificate.id;
+                certificate
+            })
+            .collect()
+    }
+
+    fn outbox(certificates: &[Certificate]) -> Outbox {
+        let mut outbox = Outbox::open(None).unwrap();
+        for (number, certificate) in certificates.iter().enumerate() {
+            outbox
+                .push(
+                    certificate.clone(),
+                    number as u64..=number as u64,
+                    Vec::new(),
+                )
+                .unwrap();
+        }
+
+        outbox
+    }
+
+    #[test]
+    fn resubmit_certificates_unknown_by_the_tce() {
+        let certificates = certificate_chain(4);
+        let mut outbox = outbox(&certificates);
+
+        let to_resubmit = outbox.reconcile(None, Some(certificates[1].id)).unwrap();
+
+        assert_eq!(
+            to_resubmit
+                .iter()
+                .map(|entry| entry.certificate.id)
+                .collect::<Vec<_>>(),
+            vec![certificates[2].id, certificates[3].id]
+        );
+        assert_eq!(
+            outbox.get(&certificates[0].id).unwrap().state,
+            CertificateState::Submitted
+        );
+    }
+
+    #[test]
+    fn keep_certificates_of_the_tce_pending_pool() {
+        let certificates = certificate_chain(4);
+        let mut outbox = outbox(&certificates);
+
+        let to_resubmit = outbox
+            .reconcile(Some(certificates[0].id), Some(certificates[2].id))
+            .unwrap();
+
+        assert_eq!(
+            to_resubmit
+                .iter()
+                .map(|entry| entry.certificate.id)
+                .collect::<Vec<_>>(),
+            vec![certificates[3].id]
+        );
+        assert_eq!(
+            outbox
+                .entries()
+                .map(|entry| (entry.certificate.id, entry.state))
+                .collect::<Vec<_>>(),
+            vec![
+                (certificates[0].id, CertificateState::Delivered),
+                (certificates[1].id, CertificateState::Submitted),
+                (certificates[2].id, CertificateState::Submitted),
+                (certificates[3].id, CertificateState::Generated)
+            ]
+        );
+    }
+
+    #[test]
+    fn resubmit_everything_without_tce_head() {
+        let certificates = certificate_chain(2);
+        let mut outbox = outbox(&certificates);
+
+        assert_eq!(outbox.reconcile(None, None).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn clear_stale_outbox() {
+        let certificates = certificate_chain(3);
+        let mut outbox = outbox(&certificates[1..]);
+
+        let unknown_head =
+            Certificate::new_with_default_fields(certificates[2].id, SOURCE_SUBNET_ID, &[])
+                .unwrap();
+
+        assert!(outbox
+            .reconcile(Some(unknown_head.id), None)
+            .unwrap()
+            .is_empty());
+        assert!(outbox.last_entry().is_none());
+    }
+
+    #[test]
+    fn drop_entries_preceding_delivered_certificate() {
+        let certificates = certificate_chain(3);
+        let mut outbox = outbox(&certificates);
+
+        outbox.mark_submitted(&certificates[0].id).unwrap();
+        outbox.mark_submitted(&certificates[1].id).unwrap();
+        outbox.mark_delivered(&certificates[1].id).unwrap();
+
+        assert_eq!(
+            outbox
+                .entries()
+                .map(|entry| (entry.certificate.id, entry.state))
+                .collect::<Vec<_>>(),
+            vec![
+                (certificates[1].id, CertificateState::Delivered),
+                (certificates[2].id, CertificateState::Generated)
+            ]
+        );
+        assert!(outbox.record_failed_attempt(&certificates[1].id).is_none());
+        assert_eq!(
+            outbox
+                .record_failed_attempt(&certificates[2].id)
+                .unwrap()
+                .failed_attempts,
+            1
+        );
+    }
+
+    #[test]
+    fn keep_the_last_block_certified_by_the_delivered_certificate() {
+        let certificates = certificate_chain(3);
+        let mut outbox = Outbox::open(None).unwrap();
+        outbox
+            .push(certificates[0].clone(), 0..=4, Vec::new())
+            .unwrap();
+        outbox
+            .push(certificates[1].clone(), 5..=9, Vec::new())
+            .unwrap();
+
+        outbox.mark_delivered(&certificates[1].id).unwrap();
+
+        assert_eq!(outbox.certified_block(&certificates[0].id), None);
+        assert_eq!(outbox.certified_block(&certificates[1].id), Some(9));
+        assert_eq!(outbox.certified_block(&certificates[2].id), None);
+    }
+
+    #[test]
+    fn persist_outbox() {
+        let path = std::env::temp_dir().join(format!("outbox-{}.json", rand::random::<u64>()));
+        let certificates = certificate_chain(2);
+
+        {
+            let mut outbox = Outbox::open(Some(path.clone())).unwrap();
+            outbox
+                .push(certificates[0].clone(), 0..=3, Vec::new())
+                .unwrap();
+            outbox
+                .push(certificates[1].clone(), 4..=4, Vec::new())
+                .unwrap();
+            outbox.mark_submitted(&certificates[0].id).unwrap();
+        }
+
+        let outbox = Outbox::open(Some(path.clone())).unwrap();
+        let entries = outbox.entries().cloned().collect::<Vec<_>>();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].state, CertificateState::Submitted);
+        assert_eq!(entries[0].block_range, 0..=3);
+        assert_eq!(entries[1].certificate, certificates[1]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
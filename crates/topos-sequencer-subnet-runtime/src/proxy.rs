@@ -1,20 +1,31 @@
 //! Protocol implementation guts.
 //!
-use crate::{certification::Certification, Error, SubnetRuntimeProxyConfig};
+use crate::{
+    certification::Certification, messages::MessageStore, outbox::Outbox, CrossSubnetMessage,
+    Error, SubnetRuntimeProxyConfig,
+};
+use futures::StreamExt;
 use opentelemetry::trace::FutureExt;
 use opentelemetry::Context;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::{mpsc, oneshot};
 use tokio::time::Duration;
 use topos_core::api::grpc::checkpoints::TargetStreamPosition;
 use topos_core::uci::{Certificate, CertificateId, SubnetId};
-use topos_sequencer_subnet_client::{self, BlockInfo, SubnetClient, SubnetClientListener};
+use topos_sequencer_subnet_client::{self, BlockInfo, PushResult, Subnet};
 use tracing::{debug, error, field, info, info_span, instrument, warn, Instrument, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
+/// Delay before the first resubmission of a certificate rejected by the TCE
+const RESUBMISSION_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Maximum delay between two submissions of the same certificate
+const RESUBMISSION_MAX_DELAY: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Authorities {
     // TODO: proper dependencies to block type etc
@@ -25,7 +36,12 @@ pub enum SubnetRuntimeProxyEvent {
     /// New certificate is generated
     NewCertificate {
         cert: Box<Certificate>,
+        /// Last block covered by the certificate
         block_number: u64,
+        /// Range of blocks covered by the certificate
+        block_range: RangeInclusive<u64>,
+        /// Cross-subnet messages committed by the certificate
+        messages: Vec<CrossSubnetMessage>,
         ctx: Context,
     },
     /// New set of authorities in charge of the threshold signature
@@ -40,6 +56,12 @@ pub enum SubnetRuntimeProxyCommand {
         position: u64,
         ctx: Context,
     },
+    /// Upon successful submission of a Certificate of the subnet to the TCE
+    OnCertificateSubmitted { certificate_id: CertificateId },
+    /// Upon failed submission of a Certificate of the subnet to the TCE
+    OnCertificateSubmissionFailed { certificate_id: CertificateId },
+    /// Upon delivery of a Certificate of the subnet by the TCE network
+    OnCertificateDelivered { certificate_id: CertificateId },
 }
 
 pub struct SubnetRuntimeProxy {
@@ -47,6 +69,9 @@ pub struct SubnetRuntimeProxy {
     pub events_subscribers: Vec<mpsc::Sender<SubnetRuntimeProxyEvent>>,
     pub config: SubnetRuntimeProxyConfig,
     pub certification: Arc<Mutex<Certification>>,
+    pub outbox: Arc<Mutex<Outbox>>,
+    pub messages: Arc<Mutex<MessageStore>>,
+    subnet: Arc<dyn Subnet>,
     command_task_shutdown: mpsc::Sender<oneshot::Sender<()>>,
     block_task_shutdown: mpsc::Sender<oneshot::Sender<()>>,
     source_head_certificate_id_sender: Option<oneshot::Sender<Option<(CertificateId, u64)>>>,
@@ -62,6 +87,7 @@ impl SubnetRuntimeProxy {
     pub fn spawn_new(
         config: SubnetRuntimeProxyConfig,
         signing_key: Vec<u8>,
+        subnet: Arc<dyn Subnet>,
     ) -> Result<Arc<Mutex<SubnetRuntimeProxy>>, crate::Error> {
         info!(
             "Spawning new runtime proxy, http endpoint: {}, ws endpoint {} ethereum contract \
@@ -69,9 +95,6 @@ impl SubnetRuntimeProxy {
             &config.http_endpoint, &config.ws_endpoint, &config.subnet_contract_address
         );
         let (command_sender, mut command_rcv) = mpsc::channel::<SubnetRuntimeProxyCommand>(256);
-        let ws_runtime_endpoint = config.ws_endpoint.clone();
-        let http_runtime_endpoint = config.http_endpoint.clone();
-        let subnet_contract_address = Arc::new(config.subnet_contract_address.clone());
         let (command_task_shutdown_channel, mut command_task_shutdown) =
             mpsc::channel::<oneshot::Sender<()>>(1);
         let (block_task_shutdown_channel, mut block_task_shutdown) =
@@ -83,9 +106,16 @@ impl SubnetRuntimeProxy {
             &config.subnet_id,
             None,
             config.verifier,
-            signing_key.clone(),
+            signing_key,
             config.start_block,
+            config.batching_policy,
+            config.finality_depth,
         )?;
+        let outbox = Arc::new(Mutex::new(Outbox::open(config.outbox_path.clone())?));
+        let messages = Arc::new(Mutex::new(MessageStore::open(
+            config.messages_path.clone(),
+            config.messages_retention,
+        )?));
 
         let runtime_proxy = Arc::new(Mutex::from(Self {
             commands_channel: command_sender,
@@ -94,13 +124,16 @@ impl SubnetRuntimeProxy {
             command_task_shutdown: command_task_shutdown_channel,
             block_task_shutdown: block_task_shutdown_channel,
             certification: certification.clone(),
+            outbox,
+            messages,
+            subnet: subnet.clone(),
             source_head_certificate_id_sender: Some(source_head_certificate_id_sender),
         }));
 
         // Runtime block task
         {
             let runtime_proxy = runtime_proxy.clone();
-            let subnet_contract_address = subnet_contract_address.clone();
+            let subnet = subnet.clone();
             tokio::spawn(async move {
                 // If the `start_block` sequencer parameter is provided, first block retrieved from blockchain (for genesis certificate)
                 // will be `start_block`. `default_block_sync_start` is hence `start_block`-1
@@ -122,23 +155,23 @@ impl SubnetRuntimeProxy {
                         );
                         // Wait for last_certificate_id retrieved on TCE component setup
                         match source_head_certificate_id_received.await {
-                            Ok(certificate_and_position) => {
+                            Ok(certificate_and_block) => {
                                 info!(
                                     "Source head certificate id received {:?}",
-                                    certificate_and_position
+                                    certificate_and_block
                                 );
-                                // If tce source head position is provided, continue synchronizing from it
+                                // If tce source head is provided, continue synchronizing after the last block it certifies
                                 // If the `start_block` sequencer parameter is provided and tce source head is missing,
                                 // we should start synchronizing from that block instead of genesis
-                                // If neither tce source head position nor start_block parameters are provided,
+                                // If neither tce source head nor start_block parameters are provided,
                                 // sync should start form -1, so that first fetched is subnet genesis block
-                                let cert_id = certificate_and_position.map(|(id, _position)| id);
-                                let position: i128 = certificate_and_position
-                                    .map(|(_id, position)| position as i128)
+                                let cert_id = certificate_and_block.map(|(id, _block_number)| id);
+                                let last_certified_block: i128 = certificate_and_block
+                                    .map(|(_id, block_number)| block_number as i128)
                                     .unwrap_or(default_block_sync_start);
                                 // Certificate generation is now ready to run
                                 certification.last_certificate_id = cert_id;
-                                latest_acquired_subnet_block_number = position;
+                                latest_acquired_subnet_block_number = last_certified_block;
                             }
                             Err(e) => {
                                 panic!(
@@ -150,25 +183,10 @@ impl SubnetRuntimeProxy {
                     }
                 }
 
-                // Establish the connection with the Subnet
-                let subnet_listener: Option<SubnetClientListener> = tokio::select! {
-                        // Create subnet client
-                        Ok(client) = topos_sequencer_subnet_client::connect_to_subnet_listener_with_retry(
-                            ws_runtime_endpoint.as_str(),
-                            subnet_contract_address.as_str(),
-                        ) => {
-                            Some(client)
-                        }
-                        _ = block_task_shutdown.recv() => {
-                            None
-                    }
-                };
-                let mut subnet_listener = subnet_listener.expect("subnet listener");
-
                 // Sync missing blocks
                 loop {
                     let current_subnet_block_number: Option<i128> = tokio::select! {
-                            block_number = subnet_listener.get_subnet_block_number() => {
+                            block_number = subnet.get_subnet_block_number() => {
                                 match block_number {
                                     Ok(block_number) => {
                                         Some(block_number as i128)
@@ -204,7 +222,7 @@ impl SubnetRuntimeProxy {
                         info!("Retrieving historical block {}", next_block_number);
                         if let Err(e) = SubnetRuntimeProxy::retrieve_and_process_block(
                             runtime_proxy.clone(),
-                            &mut subnet_listener,
+                            subnet.as_ref(),
                             certification.clone(),
                             next_block_number as u64,
                         )
@@ -221,41 +239,52 @@ impl SubnetRuntimeProxy {
                 }
 
                 // Create a new subscription stream to listen for new blocks from subnet node
-                let mut subscription_stream =
-                    match subnet_listener.new_block_subscription_stream().await {
-                        Ok(stream) => stream,
-                        Err(e) => {
-                            panic!(
-                                "Failed to open subnet node block subscription stream, unable to \
-                                 proceed with certificate generation: {e}"
-                            )
-                        }
-                    };
+                let mut subscription_stream = match subnet.subscribe_blocks().await {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        panic!(
+                            "Failed to open subnet node block subscription stream, unable to \
+                             proceed with certificate generation: {e}"
+                        )
+                    }
+                };
 
                 info!("Block subscription stream opened, listening for new blocks...");
 
                 // Go to standard mode of listening for new blocks
                 let shutdowned: Option<oneshot::Sender<()>> = loop {
                     tokio::select! {
-                        result = subnet_listener.wait_for_new_block(&mut subscription_stream) => {
+                        result = subscription_stream.next() => {
                             match result {
-                                Ok(block) => {
+                                Some(Ok(block)) => {
                                     let new_block_number = block.number as i128;
                                     info!("Successfully received new block {} from the subnet subscription", new_block_number);
                                     if let Err(e) = SubnetRuntimeProxy::process_block(
                                         runtime_proxy.clone(),
+                                        subnet.as_ref(),
                                         certification.clone(),
                                         block
                                     ).await {
-                                        error!("Failed to process next block: {}", e);
+                                        if let Error::CertifiedBlockReorged { block_number } = e {
+                                            error!(
+                                                "Block {block_number} was certified but is no longer part of the \
+                                                 subnet chain, stopping certificate generation"
+                                            );
+                                        } else {
+                                            error!("Failed to process next block: {}", e);
+                                        }
                                         break None;
                                     }
                                 }
-                                Err(e) => {
+                                Some(Err(e)) => {
                                     error!("Failed to retrieve next block: {}, trying again soon", e);
                                     tokio::time::sleep(Duration::from_millis(1000)).await;
                                     continue;
                                 }
+                                None => {
+                                    error!("Subnet block subscription stream closed");
+                                    break None;
+                                }
                             }
                         }
                         shutdown = block_task_shutdown.recv() => {
@@ -274,28 +303,23 @@ impl SubnetRuntimeProxy {
         };
 
         // Runtime command task
+        let command_runtime_proxy = runtime_proxy.clone();
         tokio::spawn(async move {
-            // Establish the connection with the Subnet
-            let mut subnet_client: Option<SubnetClient> = tokio::select! {
-                    // Create subnet client
-                    Ok(client) = topos_sequencer_subnet_client::connect_to_subnet_with_retry(
-                        http_runtime_endpoint.as_ref(),
-                        Some(signing_key.clone()),
-                        subnet_contract_address.as_str(),
-                    ) => {
-                        info!("Connected to subnet node {}", &http_runtime_endpoint);
-                        Some(client)
-                    }
-                    _ = command_task_shutdown.recv() => {
-                        None
-                    }
-            };
-
             let shutdowned: Option<oneshot::Sender<()>> = loop {
                 tokio::select! {
                     // Poll runtime proxy commands channel
                     cmd = command_rcv.recv() => {
-                        Self::on_command(&config, subnet_client.as_mut().unwrap(), cmd).await;
+                        Self::on_command(
+                            &config,
+                            subnet.as_ref(),
+                            command_runtime_proxy.clone(),
+                            cmd,
+                        )
+                        .await;
+                    },
+                    // Poll receipts of the push transactions
+                    Some(push_result) = subnet.next_push_result() => {
+                        Self::on_push_result(push_result);
                     },
                     shutdown = command_task_shutdown.recv() => {
                         break shutdown;
@@ -316,11 +340,11 @@ impl SubnetRuntimeProxy {
 
     async fn retrieve_and_process_block(
         subnet_runtime_proxy: Arc<Mutex<SubnetRuntimeProxy>>,
-        subnet_listener: &mut SubnetClientListener,
+        subnet: &dyn Subnet,
         certification: Arc<Mutex<Certification>>,
         next_block: u64,
     ) -> Result<(), Error> {
-        match subnet_listener.get_finalized_block(next_block).await {
+        match subnet.get_finalized_block(next_block).await {
             Ok(block_info) => {
                 let block_number = block_info.number;
                 info!(
@@ -331,7 +355,7 @@ impl SubnetRuntimeProxy {
                 let mut certification = certification.lock().await;
 
                 // Update certificate block history
-                certification.append_blocks(vec![block_info]);
+                Self::append_block(subnet, &mut certification, block_info).await?;
 
                 let new_certificates = match certification.generate_certificates().await {
                     Ok(certificates) => certificates,
@@ -343,8 +367,14 @@ impl SubnetRuntimeProxy {
 
                 debug!("Generated new certificates {new_certificates:?}");
 
-                for cert in new_certificates {
-                    Self::send_new_certificate(subnet_runtime_proxy.clone(), cert, next_block).await
+                for (cert, block_range, messages) in new_certificates {
+                    Self::dispatch_new_certificate(
+                        subnet_runtime_proxy.clone(),
+                        cert,
+                        block_range,
+                        messages,
+                    )
+                    .await?
                 }
                 info!("Block {} processed", next_block);
                 Ok(())
@@ -365,6 +395,7 @@ impl SubnetRuntimeProxy {
 
     async fn process_block(
         subnet_runtime_proxy: Arc<Mutex<SubnetRuntimeProxy>>,
+        subnet: &dyn Subnet,
         certification: Arc<Mutex<Certification>>,
         block_info: BlockInfo,
     ) -> Result<(), Error> {
@@ -372,25 +403,93 @@ impl SubnetRuntimeProxy {
         let block_number = block_info.number;
 
         // Update certificate block history
-        certification.append_blocks(vec![block_info]);
+        Self::append_block(subnet, &mut certification, block_info).await?;
 
         let new_certificates = certification.generate_certificates().await?;
 
         debug!("Generated new certificates {new_certificates:?}");
 
-        for cert in new_certificates {
-            Self::send_new_certificate(subnet_runtime_proxy.clone(), cert, block_number).await
+        for (cert, block_range, messages) in new_certificates {
+            Self::dispatch_new_certificate(
+                subnet_runtime_proxy.clone(),
+                cert,
+                block_range,
+                messages,
+            )
+            .await?
         }
         info!("Block {} processed", block_number);
         Ok(())
     }
 
+    /// Append the block to the certification, retrieving first its missing ancestors
+    /// to follow the chain of the subnet in case of reorg
+    async fn append_block(
+        subnet: &dyn Subnet,
+        certification: &mut Certification,
+        block_info: BlockInfo,
+    ) -> Result<(), Error> {
+        let mut branch = vec![block_info];
+
+        while let Some(oldest) = branch.last() {
+            if oldest.number == 0 || certification.knows_parent(oldest) {
+                break;
+            }
+
+            let parent_number = oldest.number - 1;
+            if certification.is_confirmed(parent_number) {
+                return Err(Error::CertifiedBlockReorged {
+                    block_number: parent_number,
+                });
+            }
+
+            warn!(
+                "Parent of block {} is unknown, retrieving block {parent_number}",
+                oldest.number
+            );
+            let parent = subnet
+                .get_finalized_block(parent_number)
+                .await
+                .map_err(|source| Error::SubnetError { source })?;
+            branch.push(parent);
+        }
+
+        certification.append_blocks(branch.into_iter().rev().collect())
+    }
+
+    /// Store newly generated certificate in the outbox, and its messages in the message
+    /// store, before dispatching it
+    async fn dispatch_new_certificate(
+        subnet_runtime_proxy: Arc<Mutex<SubnetRuntimeProxy>>,
+        cert: Certificate,
+        block_range: RangeInclusive<u64>,
+        messages: Vec<CrossSubnetMessage>,
+    ) -> Result<(), Error> {
+        let (outbox, message_store) = {
+            let runtime_proxy = subnet_runtime_proxy.lock().await;
+            (runtime_proxy.outbox.clone(), runtime_proxy.messages.clone())
+        };
+        message_store
+            .lock()
+            .await
+            .insert(cert.id, messages.clone())?;
+        outbox
+            .lock()
+            .await
+            .push(cert.clone(), block_range.clone(), messages.clone())?;
+
+        Self::send_new_certificate(subnet_runtime_proxy, cert, block_range, messages).await;
+
+        Ok(())
+    }
+
     /// Dispatch newly generated certificate to TCE client
     #[instrument(name = "NewCertificate", fields(certification = field::Empty, source_subnet_id = field::Empty, certificate_id = field::Empty))]
     async fn send_new_certificate(
         subnet_runtime_proxy: Arc<Mutex<SubnetRuntimeProxy>>,
         cert: Certificate,
-        block_number: u64,
+        block_range: RangeInclusive<u64>,
+        messages: Vec<CrossSubnetMessage>,
     ) {
         let mut runtime_proxy = subnet_runtime_proxy.lock().await;
         Span::current().record("certificate_id", cert.id.to_string());
@@ -399,7 +498,9 @@ impl SubnetRuntimeProxy {
         runtime_proxy
             .send_out_event(SubnetRuntimeProxyEvent::NewCertificate {
                 cert: Box::new(cert),
-                block_number,
+                block_number: *block_range.end(),
+                block_range,
+                messages,
                 ctx: Span::current().context(),
             })
             .with_current_context()
@@ -410,7 +511,7 @@ impl SubnetRuntimeProxy {
     /// Send certificate to target subnet Topos Core contract for verification
     async fn push_certificate(
         runtime_proxy_config: &SubnetRuntimeProxyConfig,
-        subnet_client: &SubnetClient,
+        subnet: &dyn Subnet,
         cert: &Certificate,
         position: u64,
     ) -> Result<Option<String>, Error> {
@@ -418,16 +519,29 @@ impl SubnetRuntimeProxy {
             "Pushing certificate with id {} to target subnet {}, tcc {}",
             cert.id, runtime_proxy_config.subnet_id, runtime_proxy_config.subnet_contract_address,
         );
-        let receipt = subnet_client.push_certificate(cert, position).await?;
-        debug!("Push certificate transaction receipt: {:?}", &receipt);
-        let tx_hash =
-            receipt.map(|tx_receipt| "0x".to_string() + &hex::encode(tx_receipt.transaction_hash));
-        Ok(tx_hash)
+        let tx_hash = subnet.push_certificate(cert, position).await?;
+        Ok(tx_hash.map(|tx_hash| "0x".to_string() + &hex::encode(tx_hash)))
+    }
+
+    fn on_push_result((certificate_id, receipt): PushResult) {
+        match receipt {
+            Ok(receipt) => {
+                debug!("Push certificate transaction receipt: {:?}", &receipt);
+                info!(
+                    "Certificate {certificate_id} pushed to target subnet in tx 0x{}",
+                    hex::encode(receipt.transaction_hash)
+                );
+            }
+            Err(e) => {
+                error!("Failed to push the Certificate {certificate_id} to target subnet: {e}");
+            }
+        }
     }
 
     async fn on_command(
         runtime_proxy_config: &SubnetRuntimeProxyConfig,
-        subnet_client: &SubnetClient,
+        subnet: &dyn Subnet,
+        subnet_runtime_proxy: Arc<Mutex<SubnetRuntimeProxy>>,
         mb_cmd: Option<SubnetRuntimeProxyCommand>,
     ) {
         match mb_cmd {
@@ -473,7 +587,7 @@ impl SubnetRuntimeProxy {
                         // Push the Certificate to the ToposCore contract on the target subnet
                         match SubnetRuntimeProxy::push_certificate(
                             runtime_proxy_config,
-                            subnet_client,
+                            subnet,
                             &certificate,
                             position,
                         )
@@ -483,8 +597,7 @@ impl SubnetRuntimeProxy {
                         {
                             Ok(tx_hash) => {
                                 debug!(
-                                    "Successfully pushed the Certificate {} to target subnet with \
-                                     tx hash {:?}",
+                                    "Sent the Certificate {} to target subnet with tx hash {:?}",
                                     &certificate.id, &tx_hash
                                 );
                             }
@@ -500,6 +613,41 @@ impl SubnetRuntimeProxy {
                     .instrument(span_subnet_runtime_proxy)
                     .await
                 }
+                SubnetRuntimeProxyCommand::OnCertificateSubmitted { certificate_id } => {
+                    let outbox = subnet_runtime_proxy.lock().await.outbox.clone();
+                    if let Err(e) = outbox.lock().await.mark_submitted(&certificate_id) {
+                        error!("Unable to mark certificate {certificate_id} as submitted: {e}");
+                    }
+                }
+                SubnetRuntimeProxyCommand::OnCertificateSubmissionFailed { certificate_id } => {
+                    let outbox = subnet_runtime_proxy.lock().await.outbox.clone();
+                    let entry = outbox.lock().await.record_failed_attempt(&certificate_id);
+                    if let Some(entry) = entry {
+                        let delay = RESUBMISSION_BASE_DELAY
+                            .saturating_mul(2u32.saturating_pow(entry.failed_attempts))
+                            .min(RESUBMISSION_MAX_DELAY);
+                        warn!(
+                            "Submission of the certificate {certificate_id} failed, submitting it \
+                             again in {delay:?}"
+                        );
+                        tokio::spawn(async move {
+                            tokio::time::sleep(delay).await;
+                            Self::send_new_certificate(
+                                subnet_runtime_proxy,
+                                entry.certificate,
+                                entry.block_range,
+                                entry.messages,
+                            )
+                            .await;
+                        });
+                    }
+                }
+                SubnetRuntimeProxyCommand::OnCertificateDelivered { certificate_id } => {
+                    let outbox = subnet_runtime_proxy.lock().await.outbox.clone();
+                    if let Err(e) = outbox.lock().await.mark_delivered(&certificate_id) {
+                        error!("Unable to mark certificate {certificate_id} as delivered: {e}");
+                    }
+                }
             },
             _ => {
                 warn!("Empty command was passed");
@@ -537,46 +685,103 @@ impl SubnetRuntimeProxy {
         Ok(())
     }
 
+    /// Reconcile the outbox with the latest delivered and pending certificates known by the TCE
+    ///
+    /// Certificates of the outbox unknown by the TCE are submitted again. Returns the head from
+    /// which the certification resumes, the latest certificate known by the TCE or submitted
+    /// again, along with the number of the last block it certifies.
+    pub async fn reconcile_outbox(
+        &mut self,
+        tce_delivered: Option<&Certificate>,
+        tce_pending: Option<&Certificate>,
+    ) -> Result<Option<(CertificateId, u64)>, Error> {
+        let mut outbox = self.outbox.lock().await;
+        let to_resubmit = outbox.reconcile(
+            tce_delivered.map(|certificate| certificate.id),
+            tce_pending.map(|certificate| certificate.id),
+        )?;
+
+        let source_head = match to_resubmit.last() {
+            Some(entry) => {
+                info!(
+                    "Submitting again {} certificates of the outbox unknown by the TCE",
+                    to_resubmit.len()
+                );
+                Some((entry.certificate.id, *entry.block_range.end()))
+            }
+            None => match tce_pending.or(tce_delivered) {
+                Some(head) => Some((head.id, self.certified_block(&outbox, head).await?)),
+                None => None,
+            },
+        };
+
+        let events_subscribers = self.events_subscribers.clone();
+        tokio::spawn(async move {
+            for entry in to_resubmit {
+                let event = SubnetRuntimeProxyEvent::NewCertificate {
+                    cert: Box::new(entry.certificate),
+                    block_number: *entry.block_range.end(),
+                    block_range: entry.block_range,
+                    messages: entry.messages,
+                    ctx: Context::current(),
+                };
+                for tx in &events_subscribers {
+                    if let Err(e) = tx.send(event.clone()).await {
+                        error!("Unable to send subnet runtime proxy event: {e}");
+                    }
+                }
+            }
+        });
+
+        Ok(source_head)
+    }
+
+    /// Resolve the last block certified by the TCE head
+    ///
+    /// The outbox may not know the head (e.g. lost outbox or new host), in which case the block
+    /// is taken from the block range of the certificate. Certificates without block range fall
+    /// back to the latest block of the subnet, blocks produced in between are not certified.
+    async fn certified_block(&self, outbox: &Outbox, head: &Certificate) -> Result<u64, Error> {
+        if let Some(block_number) = outbox
+            .certified_block(&head.id)
+            .or(head.block_range.map(|block_range| block_range.end))
+        {
+            return Ok(block_number);
+        }
+
+        let block_number = self.subnet.get_subnet_block_number().await?;
+        warn!(
+            "The last block certified by the TCE head {} is unknown, resuming the certification \
+             after the latest block of the subnet {block_number}",
+            head.id
+        );
+
+        Ok(block_number)
+    }
+
+    /// Set the head from which the certification starts, along with the number of the last
+    /// block it certifies
     pub async fn set_source_head_certificate_id(
         &mut self,
         source_head_certificate_id: Option<(CertificateId, u64)>,
     ) -> Result<(), Error> {
-        self.source_head_certificate_id_sender
+        let sender = self
+            .source_head_certificate_id_sender
             .take()
             .ok_or_else(|| {
                 Error::SourceHeadCertChannelError(
                     "source head certificate id was previously set".to_string(),
                 )
-            })?
+            })?;
+
+        sender
             .send(source_head_certificate_id)
             .map_err(|_| Error::SourceHeadCertChannelError("channel error".to_string()))
     }
 
     pub async fn get_checkpoints(&self) -> Result<Vec<TargetStreamPosition>, Error> {
-        info!("Connecting to subnet to query for checkpoints...");
-        let http_runtime_endpoint = self.config.http_endpoint.as_ref();
-        // Create subnet client
-        let subnet_client = match topos_sequencer_subnet_client::connect_to_subnet_with_retry(
-            http_runtime_endpoint,
-            None, // We do not need actual key here as we are just reading state
-            self.config.subnet_contract_address.as_str(),
-        )
-        .await
-        {
-            Ok(subnet_client) => {
-                info!(
-                    "Connected to subnet node to acquire checkpoints {}",
-                    http_runtime_endpoint
-                );
-                subnet_client
-            }
-            Err(e) => {
-                error!("Unable to connect to the subnet node to get checkpoints: {e}");
-                return Err(Error::SubnetError { source: e });
-            }
-        };
-
-        match subnet_client.get_checkpoints(&self.config.subnet_id).await {
+        info!("Querying the subnet for checkpoints...");
+        match self.subnet.get_checkpoints(&self.config.subnet_id).await {
             Ok(checkpoints) => {
                 info!("Successfully retrieved the Checkpoints");
                 Ok(checkpoints)
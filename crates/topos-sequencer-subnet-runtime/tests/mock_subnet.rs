@@ -0,0 +1,248 @@
+use rstest::*;
+use std::sync::Arc;
+use std::time::Duration;
+use test_log::test;
+use topos_core::uci::{BlockRange, Certificate, CertificateId, SubnetId, SUBNET_ID_LENGTH};
+use topos_sequencer_subnet_client::SubnetEvent;
+use topos_sequencer_subnet_runtime::messages::DEFAULT_MESSAGES_RETENTION;
+use topos_sequencer_subnet_runtime::proxy::{SubnetRuntimeProxyCommand, SubnetRuntimeProxyEvent};
+use topos_sequencer_subnet_runtime::{
+    BatchingPolicy, CrossSubnetMessage, PushPipelineConfig, Subnet, SubnetRuntimeProxyConfig,
+    SubnetRuntimeProxyWorker,
+};
+use topos_test_sdk::constants::TARGET_SUBNET_ID_1;
+use topos_test_sdk::sequencer::subnet::MockSubnet;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+mod common;
+use crate::common::subnet_test_data::generate_test_private_key;
+
+async fn new_worker(subnet: Arc<MockSubnet>) -> (SubnetRuntimeProxyWorker, SubnetId) {
+    let signing_key = generate_test_private_key();
+    let public_key = topos_crypto::keys::derive_public_key(signing_key.as_slice()).unwrap();
+    let subnet_id = SubnetId::from_array(
+        TryInto::<[u8; SUBNET_ID_LENGTH]>::try_into(&public_key[1..33]).unwrap(),
+    );
+
+    let worker = SubnetRuntimeProxyWorker::with_subnet(
+        SubnetRuntimeProxyConfig {
+            subnet_id,
+            http_endpoint: String::new(),
+            ws_endpoint: String::new(),
+            subnet_contract_address: String::new(),
+            verifier: 0,
+            source_head_certificate_id: None,
+            start_block: None,
+            batching_policy: BatchingPolicy::EveryBlock,
+            finality_depth: 0,
+            outbox_path: None,
+            messages_path: None,
+            messages_retention: DEFAULT_MESSAGES_RETENTION,
+            push_pipeline: PushPipelineConfig::default(),
+        },
+        signing_key,
+        subnet,
+    )
+    .await
+    .unwrap();
+
+    (worker, subnet_id)
+}
+
+async fn spawn_worker(subnet: Arc<MockSubnet>) -> (SubnetRuntimeProxyWorker, SubnetId) {
+    let (worker, subnet_id) = new_worker(subnet).await;
+    worker.set_source_head_certificate_id(None).await.unwrap();
+
+    (worker, subnet_id)
+}
+
+async fn next_certificate(worker: &mut SubnetRuntimeProxyWorker) -> (Certificate, u64) {
+    loop {
+        let event = tokio::time::timeout(Duration::from_secs(5), worker.next_event())
+            .await
+            .expect("new certificate")
+            .unwrap();
+        if let SubnetRuntimeProxyEvent::NewCertificate {
+            cert, block_number, ..
+        } = event
+        {
+            return (*cert, block_number);
+        }
+    }
+}
+
+async fn wait_for_subscription(subnet: &MockSubnet) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while subnet.subscribers() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("block subscription");
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn certify_blocks_of_mock_subnet() {
+    let subnet = Arc::new(MockSubnet::new());
+    let (mut worker, subnet_id) = spawn_worker(subnet.clone()).await;
+
+    // Genesis block is retrieved while synchronizing
+    let (genesis_certificate, block_number) = next_certificate(&mut worker).await;
+    assert_eq!(block_number, 0);
+    assert_eq!(genesis_certificate.prev_id, CertificateId::default());
+    assert_eq!(genesis_certificate.source_subnet_id, subnet_id);
+
+    wait_for_subscription(&subnet).await;
+    subnet.produce_block(Vec::new());
+    subnet.produce_block(Vec::new());
+
+    let (first_certificate, block_number) = next_certificate(&mut worker).await;
+    assert_eq!(block_number, 1);
+    assert_eq!(first_certificate.prev_id, genesis_certificate.id);
+
+    let (second_certificate, block_number) = next_certificate(&mut worker).await;
+    assert_eq!(block_number, 2);
+    assert_eq!(second_certificate.prev_id, first_certificate.id);
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn expose_cross_subnet_messages_of_certificates() {
+    let subnet = Arc::new(MockSubnet::new());
+    let (mut worker, _) = spawn_worker(subnet.clone()).await;
+    next_certificate(&mut worker).await;
+
+    wait_for_subscription(&subnet).await;
+    subnet.produce_block(vec![SubnetEvent::CrossSubnetMessageSent {
+        target_subnet_id: TARGET_SUBNET_ID_1,
+        sender: [1u8; 20],
+        payload_hash: [2u8; 32],
+        transaction_hash: [3u8; 32],
+        transaction_index: 4,
+        log_index: 5,
+    }]);
+
+    let (certificate, block_number) = next_certificate(&mut worker).await;
+    assert_eq!(certificate.target_subnets, vec![TARGET_SUBNET_ID_1]);
+    assert_eq!(
+        worker.cross_subnet_messages(&certificate.id).await,
+        Some(vec![CrossSubnetMessage {
+            target_subnet_id: TARGET_SUBNET_ID_1,
+            sender: [1u8; 20],
+            payload_hash: [2u8; 32],
+            block_number,
+            transaction_hash: [3u8; 32],
+            transaction_index: 4,
+            log_index: 5,
+        }])
+    );
+
+    let proof = worker
+        .cross_subnet_message_proof(&certificate.id, 5)
+        .await
+        .unwrap();
+    assert!(proof.verify(&certificate.messages_root));
+    assert!(worker
+        .cross_subnet_message_proof(&certificate.id, 4)
+        .await
+        .is_none());
+
+    // The messages are retained once the certificate is dropped from the outbox
+    subnet.produce_block(Vec::new());
+    let (delivered, _) = next_certificate(&mut worker).await;
+    worker
+        .eval(SubnetRuntimeProxyCommand::OnCertificateDelivered {
+            certificate_id: delivered.id,
+        })
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let retained = worker
+        .cross_subnet_message_proof(&certificate.id, 5)
+        .await
+        .unwrap();
+    assert_eq!(retained, proof);
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn push_delivered_certificates_to_mock_subnet() {
+    let subnet = Arc::new(MockSubnet::new());
+    let (mut worker, subnet_id) = spawn_worker(subnet.clone()).await;
+
+    let (certificate, _) = next_certificate(&mut worker).await;
+
+    // Delivering twice the same certificate pushes it only once
+    for _ in 0..2 {
+        worker
+            .eval(SubnetRuntimeProxyCommand::OnNewDeliveredCertificate {
+                certificate: certificate.clone(),
+                position: 0,
+                ctx: Span::current().context(),
+            })
+            .await
+            .unwrap();
+    }
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while subnet.pushed_certificates().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("pushed certificate");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(subnet.pushed_certificates(), vec![(certificate.clone(), 0)]);
+
+    let checkpoints = worker.get_checkpoints().await.unwrap();
+    assert_eq!(checkpoints.len(), 1);
+    assert_eq!(checkpoints[0].source_subnet_id, subnet_id);
+    assert_eq!(checkpoints[0].certificate_id, Some(certificate.id));
+    assert_eq!(
+        subnet.get_checkpoints(&subnet_id).await.unwrap(),
+        checkpoints
+    );
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn resume_from_tce_head_unknown_to_the_outbox() {
+    let subnet = Arc::new(MockSubnet::new());
+    subnet.produce_block(Vec::new());
+    subnet.produce_block(Vec::new());
+    let (mut worker, subnet_id) = new_worker(subnet.clone()).await;
+
+    // The outbox is empty, the last block certified by the head comes from its block range
+    let mut head = Certificate::new_with_default_fields([1u8; 32], subnet_id, &[]).unwrap();
+    head.block_range = Some(BlockRange { start: 0, end: 1 });
+    let source_head = worker.reconcile_outbox(Some(&head), None).await.unwrap();
+    assert_eq!(source_head, Some((head.id, 1)));
+
+    worker
+        .set_source_head_certificate_id(source_head)
+        .await
+        .unwrap();
+
+    let (certificate, block_number) = next_certificate(&mut worker).await;
+    assert_eq!(block_number, 2);
+    assert_eq!(certificate.prev_id, head.id);
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn resume_from_latest_subnet_block_without_block_range() {
+    let subnet = Arc::new(MockSubnet::new());
+    subnet.produce_block(Vec::new());
+    subnet.produce_block(Vec::new());
+    let (worker, subnet_id) = new_worker(subnet.clone()).await;
+
+    let head = Certificate::new_with_default_fields([1u8; 32], subnet_id, &[]).unwrap();
+    assert_eq!(
+        worker.reconcile_outbox(None, Some(&head)).await.unwrap(),
+        Some((head.id, 2))
+    );
+}
@@ -21,6 +21,7 @@ use std::sync::Arc;
 use test_log::test;
 use tokio::sync::{oneshot, Mutex};
 use topos_core::uci::{Certificate, CertificateId, SubnetId, SUBNET_ID_LENGTH};
+use topos_sequencer_subnet_runtime::messages::DEFAULT_MESSAGES_RETENTION;
 use topos_sequencer_subnet_runtime::proxy::{SubnetRuntimeProxyCommand, SubnetRuntimeProxyEvent};
 use tracing::{error, info, warn, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
@@ -28,7 +29,9 @@ use tracing_opentelemetry::OpenTelemetrySpanExt;
 mod common;
 use crate::common::subnet_test_data::generate_test_private_key;
 use topos_core::api::grpc::checkpoints::TargetStreamPosition;
-use topos_sequencer_subnet_runtime::{SubnetRuntimeProxyConfig, SubnetRuntimeProxyWorker};
+use topos_sequencer_subnet_runtime::{
+    BatchingPolicy, PushPipelineConfig, SubnetRuntimeProxyConfig, SubnetRuntimeProxyWorker,
+};
 
 use topos_test_sdk::constants::*;
 
@@ -478,6 +481,7 @@ async fn check_received_certificate(
             cert,
             block_number,
             ctx: _,
+            ..
         } = event
         {
             info!(
@@ -607,7 +611,7 @@ async fn test_subnet_node_get_block_info(
     )
     .await
     {
-        Ok(mut subnet_client) => match subnet_client.get_finalized_block(6).await {
+        Ok(subnet_client) => match subnet_client.get_finalized_block(6).await {
             Ok(block_info) => {
                 info!(
                     "Block info successfully retrieved for block {}",
@@ -645,6 +649,12 @@ async fn test_create_runtime() -> Result<(), Box<dyn std::error::Error>> {
             verifier: 0,
             source_head_certificate_id: None,
             start_block: None,
+            batching_policy: BatchingPolicy::EveryBlock,
+            finality_depth: 0,
+            outbox_path: None,
+            messages_path: None,
+            messages_retention: DEFAULT_MESSAGES_RETENTION,
+            push_pipeline: PushPipelineConfig::default(),
         },
         test_private_key,
     )
@@ -678,6 +688,12 @@ async fn test_subnet_certificate_push_call(
             verifier: 0,
             source_head_certificate_id: None,
             start_block: None,
+            batching_policy: BatchingPolicy::EveryBlock,
+            finality_depth: 0,
+            outbox_path: None,
+            messages_path: None,
+            messages_retention: DEFAULT_MESSAGES_RETENTION,
+            push_pipeline: PushPipelineConfig::default(),
         },
         admin_key.clone(),
     )
@@ -967,6 +983,12 @@ async fn test_subnet_send_token_processing(
             verifier: 0,
             source_head_certificate_id: None,
             start_block: None,
+            batching_policy: BatchingPolicy::EveryBlock,
+            finality_depth: 0,
+            outbox_path: None,
+            messages_path: None,
+            messages_retention: DEFAULT_MESSAGES_RETENTION,
+            push_pipeline: PushPipelineConfig::default(),
         },
         test_private_key.clone(),
     )
@@ -1025,6 +1047,7 @@ async fn test_subnet_send_token_processing(
                 cert,
                 block_number: _,
                 ctx: _,
+                ..
             } = event
             {
                 info!(
@@ -1093,6 +1116,12 @@ async fn test_sync_from_genesis_and_particular_source_head(
             verifier: 0,
             source_head_certificate_id: None,
             start_block: None,
+            batching_policy: BatchingPolicy::EveryBlock,
+            finality_depth: 0,
+            outbox_path: None,
+            messages_path: None,
+            messages_retention: DEFAULT_MESSAGES_RETENTION,
+            push_pipeline: PushPipelineConfig::default(),
         },
         test_private_key.clone(),
     )
@@ -1162,6 +1191,12 @@ async fn test_sync_from_genesis_and_particular_source_head(
             verifier: 0,
             source_head_certificate_id: None,
             start_block: None,
+            batching_policy: BatchingPolicy::EveryBlock,
+            finality_depth: 0,
+            outbox_path: None,
+            messages_path: None,
+            messages_retention: DEFAULT_MESSAGES_RETENTION,
+            push_pipeline: PushPipelineConfig::default(),
         },
         test_private_key.clone(),
     )
@@ -1259,6 +1294,12 @@ async fn test_sync_from_start_block(
             verifier: 0,
             source_head_certificate_id: None,
             start_block: Some(start_block),
+            batching_policy: BatchingPolicy::EveryBlock,
+            finality_depth: 0,
+            outbox_path: None,
+            messages_path: None,
+            messages_retention: DEFAULT_MESSAGES_RETENTION,
+            push_pipeline: PushPipelineConfig::default(),
         },
         test_private_key.clone(),
     )
@@ -1348,6 +1389,12 @@ async fn test_subnet_multiple_send_token_in_a_block(
             verifier: 0,
             source_head_certificate_id: None,
             start_block: None,
+            batching_policy: BatchingPolicy::EveryBlock,
+            finality_depth: 0,
+            outbox_path: None,
+            messages_path: None,
+            messages_retention: DEFAULT_MESSAGES_RETENTION,
+            push_pipeline: PushPipelineConfig::default(),
         },
         test_private_key.clone(),
     )
@@ -1546,6 +1593,7 @@ async fn test_subnet_multiple_send_token_in_a_block(
                 cert,
                 block_number,
                 ctx: _,
+                ..
             } = event
             {
                 info!(
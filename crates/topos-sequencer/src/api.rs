@@ -0,0 +1,226 @@
+//!
+//! REST API serving the cross-subnet messages of the certificates and their inclusion proofs
+//!
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use topos_core::uci::CertificateId;
+use topos_sequencer_subnet_runtime::messages::MessageStore;
+use topos_sequencer_subnet_runtime::{CrossSubnetMessage, MessageInclusionProof};
+use tracing::{error, info};
+
+type AppState = State<Arc<Mutex<MessageStore>>>;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Message {
+    target_subnet_id: String,
+    sender: String,
+    payload_hash: String,
+    block_number: u64,
+    transaction_hash: String,
+    transaction_index: u64,
+    log_index: u64,
+}
+
+impl From<&CrossSubnetMessage> for Message {
+    fn from(message: &CrossSubnetMessage) -> Self {
+        Self {
+            target_subnet_id: message.target_subnet_id.to_string(),
+            sender: format!("0x{}", hex::encode(message.sender)),
+            payload_hash: format!("0x{}", hex::encode(message.payload_hash)),
+            block_number: message.block_number,
+            transaction_hash: format!("0x{}", hex::encode(message.transaction_hash)),
+            transaction_index: message.transaction_index,
+            log_index: message.log_index,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InclusionProof {
+    message: Message,
+    index: u64,
+    messages_count: u64,
+    siblings: Vec<String>,
+}
+
+impl From<MessageInclusionProof> for InclusionProof {
+    fn from(proof: MessageInclusionProof) -> Self {
+        Self {
+            message: (&proof.message).into(),
+            index: proof.index,
+            messages_count: proof.messages_count,
+            siblings: proof
+                .siblings
+                .iter()
+                .map(|sibling| format!("0x{}", hex::encode(sibling)))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+enum ApiError {
+    BadRequest(String),
+    NotFound(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            Self::BadRequest(error) => (StatusCode::BAD_REQUEST, error),
+            Self::NotFound(error) => (StatusCode::NOT_FOUND, error),
+        };
+
+        (status, Json(ErrorResponse { error })).into_response()
+    }
+}
+
+fn parse_certificate_id(certificate_id: &str) -> Result<CertificateId, ApiError> {
+    let bytes = hex::decode(certificate_id.trim_start_matches("0x"))
+        .map_err(|e| ApiError::BadRequest(format!("Invalid certificate id: {e}")))?;
+
+    CertificateId::try_from(bytes.as_slice())
+        .map_err(|e| ApiError::BadRequest(format!("Invalid certificate id: {e}")))
+}
+
+async fn get_messages(
+    State(store): AppState,
+    Path(certificate_id): Path<String>,
+) -> Result<Json<Vec<Message>>, ApiError> {
+    let certificate_id = parse_certificate_id(&certificate_id)?;
+    let store = store.lock().await;
+    let messages = store
+        .get(&certificate_id)
+        .ok_or_else(|| ApiError::NotFound(format!("No message retained for {certificate_id}")))?;
+
+    Ok(Json(messages.iter().map(Message::from).collect()))
+}
+
+async fn get_message_proof(
+    State(store): AppState,
+    Path((certificate_id, log_index)): Path<(String, u64)>,
+) -> Result<Json<InclusionProof>, ApiError> {
+    let certificate_id = parse_certificate_id(&certificate_id)?;
+    let proof = store
+        .lock()
+        .await
+        .proof(&certificate_id, log_index)
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No message retained for {certificate_id} at log index {log_index}"
+            ))
+        })?;
+
+    Ok(Json(proof.into()))
+}
+
+fn router(store: Arc<Mutex<MessageStore>>) -> Router {
+    Router::new()
+        .route(
+            "/v1/certificates/:certificate_id/messages",
+            get(get_messages),
+        )
+        .route(
+            "/v1/certificates/:certificate_id/messages/:log_index/proof",
+            get(get_message_proof),
+        )
+        .with_state(store)
+}
+
+/// Serve the API on `addr` until the sequencer stops
+pub(crate) fn spawn(addr: SocketAddr, store: Arc<Mutex<MessageStore>>) {
+    info!("Serving the cross-subnet messages on {addr}");
+    tokio::spawn(async move {
+        if let Err(e) = axum::Server::bind(&addr)
+            .serve(router(store).into_make_service())
+            .await
+        {
+            error!("Cross-subnet messages API failure: {e}");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use topos_core::uci::SubnetId;
+    use tower::ServiceExt;
+
+    fn message(log_index: u64) -> CrossSubnetMessage {
+        CrossSubnetMessage {
+            target_subnet_id: SubnetId::from_array([2u8; 32]),
+            sender: [3u8; 20],
+            payload_hash: [4u8; 32],
+            block_number: 1,
+            transaction_hash: [5u8; 32],
+            transaction_index: 0,
+            log_index,
+        }
+    }
+
+    async fn get(router: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = router
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn serve_retained_messages_and_proofs() {
+        let certificate_id: CertificateId = [1u8; 32].into();
+        let mut store = MessageStore::open(None, 16).unwrap();
+        store
+            .insert(certificate_id, vec![message(0), message(1)])
+            .unwrap();
+        let router = router(Arc::new(Mutex::new(store)));
+
+        let (status, messages) = get(
+            router.clone(),
+            &format!("/v1/certificates/{certificate_id}/messages"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(messages.as_array().unwrap().len(), 2);
+        assert_eq!(messages[1]["logIndex"], 1);
+
+        let (status, proof) = get(
+            router.clone(),
+            &format!("/v1/certificates/{certificate_id}/messages/1/proof"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(proof["index"], 1);
+        assert_eq!(proof["messagesCount"], 2);
+
+        let (status, _) = get(
+            router.clone(),
+            &format!("/v1/certificates/{certificate_id}/messages/2/proof"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = get(router, "/v1/certificates/0x12/messages").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -3,14 +3,28 @@
 //!
 use crate::SequencerConfiguration;
 use opentelemetry::trace::FutureExt;
+use std::collections::HashMap;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
+use topos_core::uci::SubnetId;
 use topos_sequencer_subnet_runtime::proxy::{SubnetRuntimeProxyCommand, SubnetRuntimeProxyEvent};
 use topos_sequencer_subnet_runtime::SubnetRuntimeProxyWorker;
-use topos_tce_proxy::{worker::TceProxyWorker, TceProxyCommand, TceProxyEvent};
+use topos_tce_proxy::{
+    is_tce_healthy, worker::TceProxyWorker, TceProxyCommand, TceProxyConfig, TceProxyEvent,
+};
 use tracing::{debug, error, info, info_span, warn, Instrument, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
+/// Delay between two attempts to restart the TCE proxy worker
+const TCE_PROXY_RESTART_DELAY: Duration = Duration::from_secs(5);
+
+/// Interval between two health checks of the TCE the proxy worker is connected to
+const TCE_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Number of consecutive failed health checks before failing over to another TCE
+const TCE_HEALTH_CHECK_MAX_FAILURES: usize = 3;
+
 /// Top-level transducer sequencer app context & driver (alike)
 ///
 /// Implements <...Host> traits for network and Api, listens for protocol events in events
@@ -23,6 +37,8 @@ pub struct AppContext {
     pub config: SequencerConfiguration,
     pub subnet_runtime_proxy_worker: SubnetRuntimeProxyWorker,
     pub tce_proxy_worker: TceProxyWorker,
+    /// Position of the latest delivered certificate forwarded to the subnet, per source subnet
+    delivered_positions: HashMap<SubnetId, u64>,
 }
 
 impl AppContext {
@@ -36,11 +52,15 @@ impl AppContext {
             config,
             subnet_runtime_proxy_worker: runtime_proxy_worker,
             tce_proxy_worker,
+            delivered_positions: HashMap::new(),
         }
     }
 
     /// Main processing loop
     pub(crate) async fn run(&mut self, shutdown: (CancellationToken, mpsc::Sender<()>)) {
+        let mut health_check = tokio::time::interval(TCE_HEALTH_CHECK_INTERVAL);
+        let mut failed_health_checks = 0;
+
         loop {
             tokio::select! {
 
@@ -54,9 +74,29 @@ impl AppContext {
                 // TCE event handling
                 Ok(tce_evt) = self.tce_proxy_worker.next_event() => {
                     debug!("tce_proxy_worker.next_event(): {:?}", &tce_evt);
-                    self.on_tce_proxy_event(tce_evt).await;
+                    self.on_tce_proxy_event(tce_evt, &shutdown.0).await;
                 },
 
+                // Periodic health check of the TCE, failing over after repeated failures
+                _ = health_check.tick() => {
+                    let tce_endpoint = &self.tce_proxy_worker.tce_endpoint;
+                    if is_tce_healthy(tce_endpoint, &self.tce_proxy_worker.config.tls).await {
+                        failed_health_checks = 0;
+                        continue;
+                    }
+
+                    failed_health_checks += 1;
+                    warn!(
+                        "Health check of the TCE at {tce_endpoint} failed \
+                         ({failed_health_checks}/{TCE_HEALTH_CHECK_MAX_FAILURES})"
+                    );
+                    if failed_health_checks >= TCE_HEALTH_CHECK_MAX_FAILURES {
+                        warn!("TCE at {tce_endpoint} is unhealthy, restarting tce proxy worker...");
+                        failed_health_checks = 0;
+                        self.fail_over_tce_proxy_worker(&shutdown.0).await;
+                    }
+                }
+
                 // Shutdown signal
                 _ = shutdown.0.cancelled() => {
                     info!("Shutting down Sequencer app context...");
@@ -76,9 +116,17 @@ impl AppContext {
         match evt {
             SubnetRuntimeProxyEvent::NewCertificate {
                 cert,
-                block_number: _,
+                block_range,
+                messages,
                 ctx,
+                ..
             } => {
+                debug!(
+                    "New certificate {} covering blocks {:?} with {} cross-subnet messages",
+                    cert.id,
+                    block_range,
+                    messages.len()
+                );
                 let span = info_span!("Sequencer app context");
                 span.set_parent(ctx);
                 if let Err(e) = self
@@ -100,7 +148,7 @@ impl AppContext {
         }
     }
 
-    async fn on_tce_proxy_event(&mut self, evt: TceProxyEvent) {
+    async fn on_tce_proxy_event(&mut self, evt: TceProxyEvent, shutdown: &CancellationToken) {
         match evt {
             TceProxyEvent::NewDeliveredCerts { certificates, ctx } => {
                 let span = info_span!("Sequencer app context");
@@ -109,6 +157,21 @@ impl AppContext {
                 async {
                     // New certificates acquired from TCE
                     for (cert, cert_position) in certificates {
+                        // Certificates already forwarded can be streamed again after a restart of the TCE proxy
+                        if let Some(position) = self.delivered_positions.get(&cert.source_subnet_id)
+                        {
+                            if cert_position <= *position {
+                                debug!(
+                                    "Skipping certificate {} at position {cert_position}, already \
+                                     forwarded",
+                                    cert.id
+                                );
+                                continue;
+                            }
+                        }
+                        self.delivered_positions
+                            .insert(cert.source_subnet_id, cert_position);
+
                         self.subnet_runtime_proxy_worker
                             .eval(SubnetRuntimeProxyCommand::OnNewDeliveredCertificate {
                                 certificate: cert,
@@ -124,36 +187,122 @@ impl AppContext {
                 .await
             }
             TceProxyEvent::WatchCertificatesChannelFailed => {
-                warn!("Restarting tce proxy worker...");
-                let config = &self.tce_proxy_worker.config;
-                // Here try to restart tce proxy
-                _ = self.tce_proxy_worker.shutdown().await;
-
-                // TODO: Retrieve subnet checkpoint from where to start receiving certificates, again
-                let (tce_proxy_worker, _source_head_certificate_id) =
-                    match TceProxyWorker::new(topos_tce_proxy::TceProxyConfig {
-                        subnet_id: config.subnet_id,
-                        tce_endpoint: config.tce_endpoint.clone(),
-                        positions: Vec::new(), // TODO: acquire from subnet
-                    })
-                    .await
-                    {
-                        Ok((tce_proxy_worker, source_head_certificate)) => {
-                            info!(
-                                "TCE proxy client is restarted for the source subnet {:?} from \
-                                 the head {:?}",
-                                config.subnet_id, source_head_certificate
-                            );
-                            let source_head_certificate_id =
-                                source_head_certificate.map(|cert| cert.0.id);
-                            (tce_proxy_worker, source_head_certificate_id)
-                        }
-                        Err(e) => {
-                            panic!("Unable to create TCE Proxy: {e}");
-                        }
-                    };
+                warn!(
+                    "Watch certificates channel with the TCE at {} failed, restarting tce proxy \
+                     worker...",
+                    self.tce_proxy_worker.tce_endpoint
+                );
+                self.fail_over_tce_proxy_worker(shutdown).await;
+            }
+            TceProxyEvent::CertificateSubmitted { certificate_id } => {
+                self.eval_runtime_command(SubnetRuntimeProxyCommand::OnCertificateSubmitted {
+                    certificate_id,
+                })
+                .await;
+            }
+            TceProxyEvent::CertificateSubmissionFailed { certificate_id } => {
+                self.eval_runtime_command(
+                    SubnetRuntimeProxyCommand::OnCertificateSubmissionFailed { certificate_id },
+                )
+                .await;
+            }
+            TceProxyEvent::SourceHeadUpdated {
+                certificate_id,
+                position: _,
+            } => {
+                self.eval_runtime_command(SubnetRuntimeProxyCommand::OnCertificateDelivered {
+                    certificate_id,
+                })
+                .await;
+            }
+        }
+    }
+
+    async fn eval_runtime_command(&self, cmd: SubnetRuntimeProxyCommand) {
+        if let Err(e) = self.subnet_runtime_proxy_worker.eval(cmd).await {
+            error!("Unable to send subnet runtime proxy command {e}");
+        }
+    }
+
+    /// Shut down the TCE proxy worker and restart it, on another TCE endpoint if any
+    ///
+    /// The current worker is kept if the sequencer is shut down before the restart succeeds.
+    async fn fail_over_tce_proxy_worker(&mut self, shutdown: &CancellationToken) {
+        _ = self.tce_proxy_worker.shutdown().await;
+
+        if let Some(tce_proxy_worker) = self.restart_tce_proxy_worker(shutdown).await {
+            self.tce_proxy_worker = tce_proxy_worker;
+        }
+    }
+
+    /// Restart the TCE proxy worker, failing over to the other TCE endpoints first
+    ///
+    /// The certificate stream is resumed from the target checkpoints of the subnet,
+    /// certificates already forwarded are skipped on reception.
+    /// Returns `None` if the sequencer is shut down in the meantime.
+    async fn restart_tce_proxy_worker(
+        &self,
+        shutdown: &CancellationToken,
+    ) -> Option<TceProxyWorker> {
+        let config = &self.tce_proxy_worker.config;
+        let failed_endpoint = &self.tce_proxy_worker.tce_endpoint;
+        let mut tce_endpoints: Vec<String> = config
+            .tce_endpoints
+            .iter()
+            .filter(|endpoint| *endpoint != failed_endpoint)
+            .cloned()
+            .collect();
+        tce_endpoints.push(failed_endpoint.clone());
+
+        loop {
+            let positions = match self.subnet_runtime_proxy_worker.get_checkpoints().await {
+                Ok(checkpoints) => checkpoints,
+                Err(e) => {
+                    error!("Unable to get checkpoints from the subnet: {e}");
+                    Self::wait_before_restart(shutdown).await?;
+                    continue;
+                }
+            };
+
+            let tce_proxy_worker = tokio::select! {
+                tce_proxy_worker = TceProxyWorker::new(TceProxyConfig {
+                    subnet_id: config.subnet_id,
+                    tce_endpoints: tce_endpoints.clone(),
+                    positions,
+                    tls: config.tls.clone(),
+                    api_key: config.api_key.clone(),
+                }) => tce_proxy_worker,
+                _ = shutdown.cancelled() => {
+                    info!("Shutdown requested, stopping the restart of the TCE proxy");
+                    return None;
+                }
+            };
+
+            match tce_proxy_worker {
+                Ok((tce_proxy_worker, source_head)) => {
+                    info!(
+                        "TCE proxy client is restarted with the TCE at {} for the source subnet \
+                         {:?} from the head {:?}",
+                        tce_proxy_worker.tce_endpoint, config.subnet_id, source_head
+                    );
+                    return Some(tce_proxy_worker);
+                }
+                Err(e) => {
+                    error!("Unable to restart the TCE proxy, trying again soon: {e}");
+                    Self::wait_before_restart(shutdown).await?;
+                }
+            }
+        }
+    }
 
-                self.tce_proxy_worker = tce_proxy_worker;
+    /// Wait before the next attempt to restart the TCE proxy worker,
+    /// returns `None` if the sequencer is shut down in the meantime
+    async fn wait_before_restart(shutdown: &CancellationToken) -> Option<()> {
+        tokio::select! {
+            _ = tokio::time::sleep(TCE_PROXY_RESTART_DELAY) => Some(()),
+            _ = shutdown.cancelled() => {
+                info!("Shutdown requested, stopping the restart of the TCE proxy");
+                None
             }
         }
     }
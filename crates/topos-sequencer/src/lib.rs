@@ -1,5 +1,7 @@
 use crate::app_context::AppContext;
 use std::io::ErrorKind::InvalidInput;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use tokio::{
     spawn,
     sync::{
@@ -10,10 +12,14 @@ use tokio::{
 use tokio_util::sync::CancellationToken;
 use topos_core::uci::{CertificateId, SubnetId};
 use topos_sequencer_subnet_runtime::{SubnetRuntimeProxyConfig, SubnetRuntimeProxyWorker};
-use topos_tce_proxy::{worker::TceProxyWorker, TceProxyConfig};
+
+pub use topos_sequencer_subnet_runtime::messages::DEFAULT_MESSAGES_RETENTION;
+pub use topos_sequencer_subnet_runtime::{BatchingPolicy, PushPipelineConfig};
+use topos_tce_proxy::{worker::TceProxyWorker, ApiKey, TceClientTls, TceProxyConfig};
 use topos_wallet::SecretKey;
 use tracing::{debug, info, warn};
 
+mod api;
 mod app_context;
 
 #[derive(Debug)]
@@ -23,10 +29,28 @@ pub struct SequencerConfiguration {
     pub subnet_jsonrpc_http: String,
     pub subnet_jsonrpc_ws: Option<String>,
     pub subnet_contract_address: String,
-    pub tce_grpc_endpoint: String,
+    pub tce_grpc_endpoints: Vec<String>,
+    /// CA verifying the `https` TCE endpoints, the native roots are used if not set
+    pub tce_ca_certificate: Option<PathBuf>,
+    /// Certificate presented to the TCE nodes which authenticate their clients
+    pub tce_client_certificate: Option<PathBuf>,
+    /// Private key of the certificate presented to the TCE nodes
+    pub tce_client_key: Option<PathBuf>,
+    /// API key presented to the TCE nodes restricting the access to their public APIs
+    pub tce_api_key: Option<String>,
     pub signing_key: SecretKey,
     pub verifier: u32,
     pub start_block: Option<u64>,
+    pub batching_policy: BatchingPolicy,
+    pub finality_depth: u64,
+    pub outbox_path: Option<PathBuf>,
+    /// File retaining the cross-subnet messages of the certificates
+    pub messages_path: Option<PathBuf>,
+    /// Number of certificates whose cross-subnet messages are retained
+    pub messages_retention: usize,
+    /// Address of the API serving the cross-subnet messages, not served if not set
+    pub api_addr: Option<SocketAddr>,
+    pub push_pipeline: PushPipelineConfig,
 }
 
 pub async fn launch(
@@ -93,6 +117,12 @@ pub async fn launch(
             source_head_certificate_id: None, // Must be acquired later after TCE proxy is connected
             verifier: config.verifier,
             start_block: config.start_block,
+            batching_policy: config.batching_policy,
+            finality_depth: config.finality_depth,
+            outbox_path: config.outbox_path.clone(),
+            messages_path: config.messages_path.clone(),
+            messages_retention: config.messages_retention,
+            push_pipeline: config.push_pipeline.clone(),
         },
         config.signing_key.clone(),
     )
@@ -104,6 +134,10 @@ pub async fn launch(
         }
     };
 
+    if let Some(api_addr) = config.api_addr {
+        api::spawn(api_addr, subnet_runtime_proxy_worker.message_store().await);
+    }
+
     // Get subnet checkpoints from subnet to pass them to the TCE node
     // It will retry using backoff algorithm, but if it fails (default max backoff elapsed time is 15 min) we can not proceed
     let target_subnet_stream_positions = match subnet_runtime_proxy_worker.get_checkpoints().await {
@@ -113,44 +147,79 @@ pub async fn launch(
         }
     };
 
+    let tce_client_identity = match (&config.tce_client_certificate, &config.tce_client_key) {
+        (Some(certificate), Some(key)) => Some((certificate.as_path(), key.as_path())),
+        (None, None) => None,
+        _ => {
+            return Err(Box::new(std::io::Error::new(
+                InvalidInput,
+                "Authenticating to the TCE requires both a client certificate and a key",
+            )));
+        }
+    };
+    let tce_tls =
+        TceClientTls::from_pem_files(config.tce_ca_certificate.as_deref(), tce_client_identity)?;
+    let tce_api_key = ApiKey::new(config.tce_api_key.as_deref())?;
+
     // Launch Tce proxy worker for handling interaction with TCE node
     // For initialization it will retry using backoff algorithm, but if it fails (default max backoff elapsed time is 15 min) we can not proceed
     // Once it is initialized, TCE proxy will try reconnecting in the loop (with backoff) if TCE becomes unavailable
     // TODO: Revise this approach?
-    let (tce_proxy_worker, source_head_certificate_id) = match TceProxyWorker::new(TceProxyConfig {
+    let (tce_proxy_worker, source_head) = match TceProxyWorker::new(TceProxyConfig {
         subnet_id,
-        tce_endpoint: config.tce_grpc_endpoint.clone(),
+        tce_endpoints: config.tce_grpc_endpoints.clone(),
         positions: target_subnet_stream_positions,
+        tls: tce_tls,
+        api_key: tce_api_key,
     })
     .await
     {
-        Ok((tce_proxy_worker, mut source_head_certificate)) => {
-            // FIXME: If TCE returns all zeros for the source head certificate, it means that it does not have
-            // any information about the subnet. Until registration of the subnets with the topos subnet is implemented,
-            // we get genesis block (and create genesis certificate) directly from the subnet block 0
-            if let Some((cert, _position)) = &mut source_head_certificate {
-                if cert.id == CertificateId::default() {
-                    warn!(
-                        "Tce has not provided source head certificate, starting from subnet \
-                         genesis block..."
-                    );
-                    source_head_certificate = None;
-                }
-            }
-
+        Ok((tce_proxy_worker, source_head)) => {
             info!(
                 "TCE proxy client is starting for the source subnet {:?} from the head {:?}",
-                subnet_id, source_head_certificate
+                subnet_id, source_head
             );
-            let source_head_certificate_id =
-                source_head_certificate.map(|(cert, position)| (cert.id, position));
-            (tce_proxy_worker, source_head_certificate_id)
+            (tce_proxy_worker, source_head)
         }
         Err(e) => {
             panic!("Unable to create TCE Proxy: {e}");
         }
     };
 
+    // FIXME: If TCE returns all zeros for the source head certificate, it means that it does not have
+    // any information about the subnet. Until registration of the subnets with the topos subnet is implemented,
+    // we get genesis block (and create genesis certificate) directly from the subnet block 0
+    let tce_delivered = source_head
+        .delivered
+        .filter(|(cert, _position)| cert.id != CertificateId::default());
+    let tce_pending = source_head.pending;
+    if tce_delivered.is_none() && tce_pending.is_none() {
+        warn!(
+            "Tce has not provided source head certificate, starting from subnet genesis block..."
+        );
+    }
+
+    // The certificates of the outbox unknown by the TCE (neither delivered nor pending) are
+    // submitted again, the certification resumes after the last block certified by the head
+    let source_head_certificate_id = match subnet_runtime_proxy_worker
+        .reconcile_outbox(
+            tce_delivered.as_ref().map(|(cert, _position)| cert),
+            tce_pending.as_ref(),
+        )
+        .await
+    {
+        Ok(source_head_certificate_id) => source_head_certificate_id,
+        Err(e) => {
+            // Without the outbox, the position of the delivered head in the source stream is
+            // the best estimate of the last block it certifies
+            warn!(
+                "Unable to reconcile the outbox with the TCE, resuming from the delivered head: \
+                 {e}"
+            );
+            tce_delivered.map(|(cert, position)| (cert.id, position))
+        }
+    };
+
     // Set source head certificate to know from where to
     // start producing certificates
     if let Err(e) = subnet_runtime_proxy_worker
@@ -0,0 +1,244 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+    net::SocketAddr,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::State,
+    http::{
+        header::{self, AUTHORIZATION},
+        HeaderMap, HeaderName, Request, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use tonic::{metadata::MetadataMap, Status};
+use tower_http::cors::AllowHeaders;
+
+/// Header carrying the API key of a client, `Authorization: Bearer <key>` is accepted as well
+pub(crate) const API_KEY_HEADER: &str = "x-api-key";
+
+/// Number of tracked keys above which a rate limiter drops its expired windows
+const RATE_LIMITER_CLEANUP_THRESHOLD: usize = 10_000;
+
+/// Access restrictions of the public APIs
+#[derive(Debug, Clone, Default)]
+pub struct ApiAccess {
+    /// Keys expected from the clients of the public APIs, every client is accepted if not set
+    pub api_keys: Option<ApiKeys>,
+    /// Submission rate limit of a client, identified by its API key, by its TLS certificate or
+    /// by its IP address
+    pub submission_rate_limit_per_client: Option<RateLimit>,
+    /// Submission rate limit of a source subnet, counted for each client so that a client can't
+    /// exhaust the limit of a subnet for the others
+    pub submission_rate_limit_per_subnet: Option<RateLimit>,
+    /// Maximum depth of a GraphQL query
+    pub graphql_max_depth: Option<usize>,
+    /// Maximum complexity of a GraphQL query
+    pub graphql_max_complexity: Option<usize>,
+}
+
+/// API keys accepted by the public APIs
+#[derive(Clone)]
+pub struct ApiKeys(Arc<HashSet<String>>);
+
+impl ApiKeys {
+    pub fn new(keys: HashSet<String>) -> Self {
+        Self(Arc::new(keys))
+    }
+
+    fn is_valid(&self, key: &str) -> bool {
+        self.0
+            .iter()
+            .any(|expected| constant_time_eq(key.as_bytes(), expected.as_bytes()))
+    }
+
+    /// Returns the identity of the client if it presented a valid key
+    fn authenticate(&self, api_key: Option<&str>, authorization: Option<&str>) -> Option<ClientId> {
+        api_key
+            .or_else(|| authorization.and_then(|value| value.strip_prefix("Bearer ")))
+            .filter(|key| self.is_valid(key))
+            .map(|key| ClientId(format!("key:{key}")))
+    }
+
+    pub(crate) fn authenticate_metadata(&self, metadata: &MetadataMap) -> Option<ClientId> {
+        self.authenticate(
+            metadata
+                .get(API_KEY_HEADER)
+                .and_then(|value| value.to_str().ok()),
+            metadata
+                .get("authorization")
+                .and_then(|value| value.to_str().ok()),
+        )
+    }
+
+    pub(crate) fn authenticate_headers(&self, headers: &HeaderMap) -> Option<ClientId> {
+        self.authenticate(
+            headers
+                .get(API_KEY_HEADER)
+                .and_then(|value| value.to_str().ok()),
+            headers
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok()),
+        )
+    }
+}
+
+impl Debug for ApiKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never log the keys themselves
+        write!(f, "ApiKeys({} keys)", self.0.len())
+    }
+}
+
+/// Compare two secrets in constant time to not leak them through timing
+pub(crate) fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len() && left.iter().zip(right).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Identity of a client of the public APIs, used to apply the per client rate limits
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ClientId(String);
+
+impl ClientId {
+    /// Identity of a client, by its API key if it was authenticated, by the certificate it
+    /// presented over mTLS, or by its IP address
+    pub(crate) fn resolve(
+        authenticated: Option<&ClientId>,
+        certificate: Option<&[u8]>,
+        remote_addr: Option<SocketAddr>,
+    ) -> Self {
+        if let Some(client) = authenticated {
+            return client.clone();
+        }
+
+        if let Some(certificate) = certificate {
+            return Self(format!(
+                "cert:{}",
+                hex::encode(tiny_keccak::keccak256(certificate))
+            ));
+        }
+
+        Self(remote_addr.map_or_else(|| "unknown".to_string(), |addr| format!("ip:{}", addr.ip())))
+    }
+}
+
+/// Interceptor of the public gRPC services rejecting the requests without a valid API key
+#[derive(Clone)]
+pub(crate) struct ApiKeyInterceptor {
+    pub(crate) api_keys: Option<ApiKeys>,
+}
+
+impl tonic::service::Interceptor for ApiKeyInterceptor {
+    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
+        let Some(api_keys) = &self.api_keys else {
+            return Ok(request);
+        };
+
+        let client = api_keys
+            .authenticate_metadata(request.metadata())
+            .ok_or_else(|| Status::unauthenticated("Invalid or missing API key"))?;
+        request.extensions_mut().insert(client);
+
+        Ok(request)
+    }
+}
+
+#[derive(Serialize)]
+struct UnauthorizedResponse {
+    error: &'static str,
+}
+
+/// Middleware of the HTTP servers rejecting the requests without a valid API key
+pub(crate) async fn require_api_key<B>(
+    State(api_keys): State<ApiKeys>,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    match api_keys.authenticate_headers(request.headers()) {
+        Some(client) => {
+            request.extensions_mut().insert(client);
+
+            next.run(request).await
+        }
+        None => (
+            StatusCode::UNAUTHORIZED,
+            Json(UnauthorizedResponse {
+                error: "Invalid or missing API key",
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Headers allowed by the CORS layers, the API key headers only when keys are expected
+pub(crate) fn cors_allowed_headers(api_keys: Option<&ApiKeys>) -> AllowHeaders {
+    let mut headers = vec![header::CONTENT_TYPE];
+    if api_keys.is_some() {
+        headers.extend([AUTHORIZATION, HeaderName::from_static(API_KEY_HEADER)]);
+    }
+
+    AllowHeaders::list(headers)
+}
+
+/// Maximum number of requests accepted over a period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl RateLimit {
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            requests,
+            period: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Fixed window rate limiter, counting the requests of each key independently
+pub(crate) struct RateLimiter<K> {
+    limit: RateLimit,
+    windows: Mutex<HashMap<K, (Instant, u32)>>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a request for the key, returns `false` if the key exceeded its limit
+    pub(crate) fn check(&self, key: K) -> bool {
+        let now = Instant::now();
+        let period = self.limit.period;
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if windows.len() >= RATE_LIMITER_CLEANUP_THRESHOLD {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < period);
+        }
+
+        let (start, count) = windows.entry(key).or_insert((now, 0));
+        if now.duration_since(*start) >= period {
+            *start = now;
+            *count = 0;
+        }
+
+        if *count >= self.limit.requests {
+            return false;
+        }
+
+        *count += 1;
+
+        true
+    }
+}
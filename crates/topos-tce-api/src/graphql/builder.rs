@@ -2,19 +2,22 @@ use std::{net::SocketAddr, sync::Arc};
 
 use async_graphql::{EmptyMutation, Schema};
 use async_graphql_axum::GraphQLSubscription;
-use axum::{extract::Extension, routing::get, Router, Server};
-use http::{header, Method};
-use hyper;
-use tokio::sync::mpsc;
+use axum::{extract::Extension, middleware, routing::get, Router, Server};
+use futures::{future::BoxFuture, FutureExt};
+use http::Method;
+use tokio::sync::{mpsc, RwLock};
 use tower_http::cors::{Any, CorsLayer};
 
 use crate::{
+    access::{cors_allowed_headers, require_api_key, ApiKeys},
     graphql::{
         query::{QueryRoot, ServiceSchema},
         routes::{graphql_playground, health},
     },
     runtime::InternalRuntimeCommand,
+    tls::{self, TlsConfig},
 };
+use topos_core::api::grpc::tce::v1::StatusResponse;
 use topos_tce_storage::fullnode::FullNodeStore;
 
 use super::query::SubscriptionRoot;
@@ -24,6 +27,11 @@ pub struct ServerBuilder {
     store: Option<Arc<FullNodeStore>>,
     serve_addr: Option<SocketAddr>,
     runtime: Option<mpsc::Sender<InternalRuntimeCommand>>,
+    status: Option<Arc<RwLock<StatusResponse>>>,
+    api_keys: Option<ApiKeys>,
+    max_depth: Option<usize>,
+    max_complexity: Option<usize>,
+    tls: Option<TlsConfig>,
 }
 
 impl ServerBuilder {
@@ -35,6 +43,41 @@ impl ServerBuilder {
 
         self
     }
+    /// Sets the status of the node, shared with the gRPC console service
+    pub(crate) fn status(mut self, status: Arc<RwLock<StatusResponse>>) -> Self {
+        self.status = Some(status);
+
+        self
+    }
+
+    /// Sets the API keys expected from the clients, every client is accepted if not set
+    ///
+    /// The health route stays public.
+    pub(crate) fn api_keys(mut self, api_keys: Option<ApiKeys>) -> Self {
+        self.api_keys = api_keys;
+
+        self
+    }
+
+    /// Sets the maximum depth and complexity of the queries
+    pub(crate) fn limits(
+        mut self,
+        max_depth: Option<usize>,
+        max_complexity: Option<usize>,
+    ) -> Self {
+        self.max_depth = max_depth;
+        self.max_complexity = max_complexity;
+
+        self
+    }
+
+    /// Serves over TLS if set
+    pub(crate) fn tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+
+        self
+    }
+
     pub(crate) fn store(mut self, store: Arc<FullNodeStore>) -> Self {
         self.store = Some(store);
 
@@ -47,14 +90,12 @@ impl ServerBuilder {
         self
     }
 
-    pub async fn build(
-        mut self,
-    ) -> Server<hyper::server::conn::AddrIncoming, axum::routing::IntoMakeService<Router>> {
+    pub async fn build(mut self) -> BoxFuture<'static, Result<(), hyper::Error>> {
         let cors = CorsLayer::new()
             // allow `GET` and `POST` when accessing the resource
             .allow_methods([Method::GET, Method::POST])
-            // allow 'application/json' requests
-            .allow_headers([header::CONTENT_TYPE])
+            // allow 'application/json' requests, authenticated if API keys are expected
+            .allow_headers(cors_allowed_headers(self.api_keys.as_ref()))
             // allow requests from any origin
             .allow_origin(Any);
 
@@ -68,23 +109,48 @@ impl ServerBuilder {
             .take()
             .expect("Cannot build GraphQL server without the internal runtime channel");
 
-        let schema: ServiceSchema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        let status = self
+            .status
+            .take()
+            .expect("Cannot build GraphQL server without the node status");
+
+        let mut schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
             .data(store)
             .data(runtime)
-            .finish();
+            .data(status);
+        if let Some(max_depth) = self.max_depth {
+            schema = schema.limit_depth(max_depth);
+        }
+        if let Some(max_complexity) = self.max_complexity {
+            schema = schema.limit_complexity(max_complexity);
+        }
+        let schema: ServiceSchema = schema.finish();
 
-        let app = Router::new()
+        let mut app = Router::new()
             .route(
                 "/",
                 get(graphql_playground)
                     .post_service(async_graphql_axum::GraphQL::new(schema.clone())),
             )
-            .route_service("/ws", GraphQLSubscription::new(schema.clone()))
+            .route_service("/ws", GraphQLSubscription::new(schema.clone()));
+
+        if let Some(api_keys) = self.api_keys.take() {
+            app = app.route_layer(middleware::from_fn_with_state(api_keys, require_api_key));
+        }
+
+        let app = app
             .route("/health", get(health))
             .layer(cors)
             .layer(Extension(schema));
 
         let serve_addr = self.serve_addr.take().expect("Server address is not set");
-        Server::bind(&serve_addr).serve(app.into_make_service())
+        match self.tls.take() {
+            Some(tls) => Server::builder(tls::incoming(serve_addr, tls.acceptor()))
+                .serve(app.into_make_service())
+                .boxed(),
+            None => Server::bind(&serve_addr)
+                .serve(app.into_make_service())
+                .boxed(),
+        }
     }
 }
@@ -4,15 +4,18 @@ use std::sync::Arc;
 use async_graphql::{Context, EmptyMutation, Object, Schema, Subscription};
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, RwLock};
 use topos_api::graphql::errors::GraphQLServerError;
 use topos_api::graphql::filter::SubnetFilter;
 use topos_api::graphql::{
     certificate::{Certificate, CertificateId},
     checkpoint::SourceCheckpoint,
     query::CertificateQuery,
+    status::SyncStatus,
 };
+use topos_core::api::grpc::tce::v1::StatusResponse;
 use topos_core::types::stream::CertificateSourceStreamPosition;
+use topos_tce_storage::errors::StorageError;
 use topos_tce_storage::fullnode::FullNodeStore;
 use topos_tce_storage::store::ReadStore;
 
@@ -24,6 +27,14 @@ use crate::stream::TransientStream;
 use super::filter::FilterIs;
 
 pub struct QueryRoot;
+
+/// Expose the pruning errors of the storage, the other ones being internal
+fn storage_error(error: StorageError) -> GraphQLServerError {
+    match error {
+        StorageError::Pruned(error) => GraphQLServerError::Pruned(error.to_string()),
+        _ => GraphQLServerError::StorageError,
+    }
+}
 pub(crate) type ServiceSchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
 
 #[async_trait]
@@ -55,7 +66,7 @@ impl CertificateQuery for QueryRoot {
                     },
                     first,
                 )
-                .map_err(|_| GraphQLServerError::StorageError)?;
+                .map_err(storage_error)?;
 
             debug!("Returned from storage: {certificates_with_position:?}");
             certificates.extend(
@@ -86,7 +97,7 @@ impl CertificateQuery for QueryRoot {
                     .try_into()
                     .map_err(|_| GraphQLServerError::ParseCertificateId)?,
             )
-            .map_err(|_| GraphQLServerError::StorageError)
+            .map_err(storage_error)
             .and_then(|c| {
                 c.map(|c| Certificate::from(&c.certificate))
                     .ok_or(GraphQLServerError::StorageError)
@@ -113,6 +124,22 @@ impl QueryRoot {
     ) -> Result<Certificate, GraphQLServerError> {
         Self::certificate_by_id(ctx, certificate_id).await
     }
+
+    /// Synchronization status of the node with its peers, unset until the first synchronization
+    async fn sync_status(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<SyncStatus>, GraphQLServerError> {
+        let status = ctx.data::<Arc<RwLock<StatusResponse>>>().map_err(|_| {
+            tracing::error!("Failed to get the node status from context");
+
+            GraphQLServerError::ParseDataConnector
+        })?;
+
+        let sync_status = status.read().await.sync_status.clone();
+
+        sync_status.map(TryInto::try_into).transpose()
+    }
 }
 
 pub struct SubscriptionRoot;
@@ -9,7 +9,8 @@ use async_graphql::{http, value, EmptyMutation, Schema};
 use futures::{SinkExt, StreamExt};
 use rstest::rstest;
 use test_log::test;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, RwLock};
+use topos_core::api::grpc::tce::v1::{StatusResponse, SubnetSyncStatus, SyncState, SyncStatus};
 use topos_core::uci::{Certificate, INITIAL_CERTIFICATE_ID};
 use topos_test_sdk::constants::{SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_3};
 use uuid::Uuid;
@@ -148,3 +149,47 @@ async fn open_watch_certificate_delivered() {
         }),
     );
 }
+
+#[rstest]
+#[test(tokio::test)]
+async fn query_sync_status() {
+    let status = Arc::new(RwLock::new(StatusResponse {
+        has_active_sample: true,
+        ..Default::default()
+    }));
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot {})
+        .data(status.clone())
+        .finish();
+    let query =
+        "{ syncStatus { state subnets { subnetId { value } localPosition bestKnownPosition } } }";
+
+    let response = schema.execute(query).await;
+    assert!(response.errors.is_empty());
+    assert_eq!(response.data, value!({ "syncStatus": null }));
+
+    status.write().await.sync_status = Some(SyncStatus {
+        state: SyncState::Syncing.into(),
+        subnets: vec![SubnetSyncStatus {
+            subnet_id: Some(SOURCE_SUBNET_ID_2.into()),
+            local_position: None,
+            best_known_position: Some(4),
+            missing_certificates: 5,
+        }],
+    });
+
+    let response = schema.execute(query).await;
+    assert!(response.errors.is_empty());
+    assert_eq!(
+        response.data,
+        value!({
+            "syncStatus": {
+                "state": "SYNCING",
+                "subnets": [{
+                    "subnetId": { "value": SOURCE_SUBNET_ID_2.to_string() },
+                    "localPosition": null,
+                    "bestKnownPosition": 4,
+                }],
+            }
+        })
+    );
+}
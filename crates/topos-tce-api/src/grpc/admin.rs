@@ -0,0 +1,312 @@
+use std::{fmt::Debug, path::PathBuf, str::FromStr, sync::Arc};
+
+use tokio::sync::{mpsc::Sender, oneshot};
+use tonic::{metadata::MetadataMap, Request, Response, Status};
+use topos_core::{
+    api::grpc::shared::v1::positions::SourceStreamPosition,
+    api::grpc::tce::v1::{
+        admin_service_server::AdminService, BackupRequest, BackupResponse, BroadcastState,
+        BroadcastStatus, CompactStorageRequest, CompactStorageResponse,
+        EvictPendingCertificateRequest, EvictPendingCertificateResponse, GetBroadcastStateRequest,
+        GetBroadcastStateResponse, ListPendingCertificatesRequest, ListPendingCertificatesResponse,
+        PendingCertificate, ResyncFromPeerRequest, ResyncFromPeerResponse, SetLogFilterRequest,
+        SetLogFilterResponse,
+    },
+    uci::SubnetId,
+};
+use topos_p2p::PeerId;
+use topos_tce_storage::{
+    errors::{BackupError, StorageError},
+    store::ReadStore,
+    validator::ValidatorStore,
+};
+use tracing::{error, info};
+
+use super::parse_certificate_id;
+use crate::{access::constant_time_eq, runtime::InternalRuntimeCommand};
+
+/// Handle used to replace the log filter of the running node
+///
+/// The closure receives the new filter directives and returns the previous ones.
+#[derive(Clone)]
+pub struct LogFilterHandle(Arc<dyn Fn(&str) -> Result<String, String> + Send + Sync>);
+
+impl LogFilterHandle {
+    pub fn new<F>(reload: F) -> Self
+    where
+        F: Fn(&str) -> Result<String, String> + Send + Sync + 'static,
+    {
+        Self(Arc::new(reload))
+    }
+
+    /// Replace the log filter, returning the previous one
+    pub fn reload(&self, filter: &str) -> Result<String, String> {
+        (self.0)(filter)
+    }
+}
+
+impl Debug for LogFilterHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("LogFilterHandle").finish()
+    }
+}
+
+/// Interceptor rejecting the requests which don't carry the admin token
+#[derive(Clone)]
+pub(crate) struct AdminTokenInterceptor {
+    pub(crate) token: Arc<String>,
+}
+
+impl AdminTokenInterceptor {
+    fn is_authorized(&self, metadata: &MetadataMap) -> bool {
+        let Some(token) = metadata
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+        else {
+            return false;
+        };
+
+        constant_time_eq(token.as_bytes(), self.token.as_bytes())
+    }
+}
+
+impl tonic::service::Interceptor for AdminTokenInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        if self.is_authorized(request.metadata()) {
+            Ok(request)
+        } else {
+            Err(Status::unauthenticated("Invalid or missing admin token"))
+        }
+    }
+}
+
+pub(crate) struct TceAdminService {
+    pub(crate) command_sender: Sender<InternalRuntimeCommand>,
+    pub(crate) store: Arc<ValidatorStore>,
+    pub(crate) log_filter: Option<LogFilterHandle>,
+}
+
+fn storage_error(error: StorageError) -> Status {
+    error!("Unable to access the storage: {error}");
+    Status::internal("Unable to access the storage")
+}
+
+#[tonic::async_trait]
+impl AdminService for TceAdminService {
+    async fn list_pending_certificates(
+        &self,
+        request: Request<ListPendingCertificatesRequest>,
+    ) -> Result<Response<ListPendingCertificatesResponse>, Status> {
+        let subnet_id = request
+            .into_inner()
+            .subnet_id
+            .map(SubnetId::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Invalid subnet id"))?;
+
+        let certificates = self
+            .store
+            .get_pending_certificates()
+            .map_err(storage_error)?
+            .into_iter()
+            .filter(|(_, certificate)| {
+                subnet_id.map_or(true, |subnet_id| certificate.source_subnet_id == subnet_id)
+            })
+            .map(|(pending_id, certificate)| PendingCertificate {
+                pending_id,
+                certificate: Some(certificate.into()),
+            })
+            .collect();
+
+        Ok(Response::new(ListPendingCertificatesResponse {
+            certificates,
+        }))
+    }
+
+    async fn evict_pending_certificate(
+        &self,
+        request: Request<EvictPendingCertificateRequest>,
+    ) -> Result<Response<EvictPendingCertificateResponse>, Status> {
+        let certificate_id = parse_certificate_id(request.into_inner().certificate_id)?;
+
+        match self
+            .store
+            .evict_pending_certificate(&certificate_id)
+            .map_err(storage_error)?
+        {
+            Some((pending_id, dependents)) => {
+                info!(
+                    "Certificate {certificate_id} evicted from the pending pool along with {} \
+                     dependent certificates",
+                    dependents.len()
+                );
+
+                let mut certificate_ids = dependents.clone();
+                certificate_ids.push(certificate_id);
+                if self
+                    .command_sender
+                    .send(InternalRuntimeCommand::EvictCertificates { certificate_ids })
+                    .await
+                    .is_err()
+                {
+                    error!("Unable to stop the broadcast of the evicted certificates");
+                }
+
+                Ok(Response::new(EvictPendingCertificateResponse {
+                    pending_id,
+                    evicted_dependents: dependents.into_iter().map(Into::into).collect(),
+                }))
+            }
+            None => Err(Status::not_found(format!(
+                "Certificate {certificate_id} is not in the pending pool"
+            ))),
+        }
+    }
+
+    async fn resync_from_peer(
+        &self,
+        request: Request<ResyncFromPeerRequest>,
+    ) -> Result<Response<ResyncFromPeerResponse>, Status> {
+        let peer_id = PeerId::from_str(&request.into_inner().peer_id)
+            .map_err(|_| Status::invalid_argument("Invalid peer id"))?;
+
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(InternalRuntimeCommand::ResyncFromPeer { peer_id, sender })
+            .await
+            .map_err(|_| Status::internal("Unable to reach the runtime"))?;
+
+        let synchronized = receiver
+            .await
+            .map_err(|_| Status::internal("Unable to receive the synchronization result"))?
+            .map_err(|error| Status::internal(error.to_string()))?;
+
+        Ok(Response::new(ResyncFromPeerResponse {
+            synchronized_certificates: synchronized as u64,
+        }))
+    }
+
+    async fn get_broadcast_state(
+        &self,
+        request: Request<GetBroadcastStateRequest>,
+    ) -> Result<Response<GetBroadcastStateResponse>, Status> {
+        let certificate_id = parse_certificate_id(request.into_inner().certificate_id)?;
+
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(InternalRuntimeCommand::GetBroadcastState {
+                certificate_id,
+                sender,
+            })
+            .await
+            .map_err(|_| Status::internal("Unable to reach the runtime"))?;
+
+        let state = receiver
+            .await
+            .map_err(|_| Status::internal("Unable to receive the broadcast state"))?
+            .map_err(|error| Status::internal(error.to_string()))?;
+
+        if let Some(state) = state {
+            return Ok(Response::new(GetBroadcastStateResponse {
+                state: Some(state),
+            }));
+        }
+
+        // The broadcast is over once the certificate is delivered
+        match self
+            .store
+            .get_certificate(&certificate_id)
+            .map_err(storage_error)?
+        {
+            Some(delivered) => Ok(Response::new(GetBroadcastStateResponse {
+                state: Some(BroadcastState {
+                    certificate_id: Some(certificate_id.into()),
+                    status: BroadcastStatus::Delivered.into(),
+                    position: Some(*delivered.proof_of_delivery.delivery_position.position),
+                    ..Default::default()
+                }),
+            })),
+            None => Err(Status::not_found(format!(
+                "Certificate {certificate_id} is neither being broadcast nor delivered"
+            ))),
+        }
+    }
+
+    async fn set_log_filter(
+        &self,
+        request: Request<SetLogFilterRequest>,
+    ) -> Result<Response<SetLogFilterResponse>, Status> {
+        let Some(log_filter) = &self.log_filter else {
+            return Err(Status::unimplemented(
+                "The log filter of this node can't be changed at runtime",
+            ));
+        };
+
+        let filter = request.into_inner().filter;
+        let previous_filter = log_filter
+            .reload(&filter)
+            .map_err(|error| Status::invalid_argument(format!("Invalid log filter: {error}")))?;
+        info!("Log filter changed from {previous_filter:?} to {filter:?}");
+
+        Ok(Response::new(SetLogFilterResponse { previous_filter }))
+    }
+
+    async fn compact_storage(
+        &self,
+        _request: Request<CompactStorageRequest>,
+    ) -> Result<Response<CompactStorageResponse>, Status> {
+        let store = self.store.clone();
+
+        tokio::task::spawn_blocking(move || store.compact())
+            .await
+            .map_err(|_| Status::internal("The storage compaction was interrupted"))?
+            .map_err(|error| {
+                error!("Unable to compact the storage: {error}");
+                Status::internal("Unable to compact the storage")
+            })?;
+
+        Ok(Response::new(CompactStorageResponse {}))
+    }
+
+    async fn backup(
+        &self,
+        request: Request<BackupRequest>,
+    ) -> Result<Response<BackupResponse>, Status> {
+        let path = request.into_inner().path;
+        if path.is_empty() {
+            return Err(Status::invalid_argument("Missing backup path"));
+        }
+
+        // Taking the checkpoints of the storage blocks on the filesystem
+        let store = self.store.clone();
+        let runtime = tokio::runtime::Handle::current();
+        let manifest = tokio::task::spawn_blocking(move || {
+            runtime.block_on(store.backup(&PathBuf::from(path)))
+        })
+        .await
+        .map_err(|_| Status::internal("The backup was interrupted"))?
+        .map_err(|error| match error {
+            StorageError::Backup(BackupError::BackupAlreadyExists(_)) => {
+                Status::already_exists(error.to_string())
+            }
+            error => {
+                error!("Unable to take the backup: {error}");
+                Status::internal(format!("Unable to take the backup: {error}"))
+            }
+        })?;
+
+        Ok(Response::new(BackupResponse {
+            delivered_certificates: manifest.delivered_certificates as u64,
+            source_heads: manifest
+                .source_heads
+                .into_iter()
+                .map(|head| SourceStreamPosition {
+                    source_subnet_id: Some(head.subnet_id.into()),
+                    position: *head.position,
+                    certificate_id: Some(head.certificate_id.into()),
+                })
+                .collect(),
+        }))
+    }
+}
@@ -1,24 +1,45 @@
 use std::{net::SocketAddr, sync::Arc};
 
-use futures::{future::BoxFuture, FutureExt};
-use tokio::sync::{mpsc::Sender, RwLock};
+use futures::{future::BoxFuture, FutureExt, TryFutureExt};
+use tokio::sync::{mpsc::Sender, RwLock, Semaphore};
 use tonic_health::server::HealthReporter;
 use topos_core::api::grpc::tce::v1::{
-    api_service_server::ApiServiceServer, console_service_server::ConsoleServiceServer,
-    StatusResponse,
+    admin_service_server::AdminServiceServer, api_service_server::ApiServiceServer,
+    console_service_server::ConsoleServiceServer, NodeRole, StatusResponse,
 };
+use topos_p2p::NetworkClient;
 use topos_tce_storage::validator::ValidatorStore;
 
-use crate::runtime::InternalRuntimeCommand;
+use crate::{
+    access::{ApiKeyInterceptor, ApiKeys},
+    constants::MAX_CONCURRENT_WAIT_FOR_DELIVERY,
+    runtime::InternalRuntimeCommand,
+    tls::TlsConfig,
+};
 
-use super::{console::TceConsoleService, TceGrpcService};
+use super::{
+    admin::{AdminTokenInterceptor, LogFilterHandle, TceAdminService},
+    console::TceConsoleService,
+    submission::SubmissionPolicy,
+    TceGrpcService,
+};
 
 #[derive(Default)]
 pub struct ServerBuilder {
     store: Option<Arc<ValidatorStore>>,
     local_peer_id: String,
+    validator_id: String,
+    role: NodeRole,
+    version: String,
+    network_client: Option<NetworkClient>,
     command_sender: Option<Sender<InternalRuntimeCommand>>,
     serve_addr: Option<SocketAddr>,
+    admin_serve_addr: Option<SocketAddr>,
+    admin_token: Option<String>,
+    log_filter: Option<LogFilterHandle>,
+    submission_policy: Option<Arc<SubmissionPolicy>>,
+    api_keys: Option<ApiKeys>,
+    tls: Option<TlsConfig>,
 }
 
 impl ServerBuilder {
@@ -34,6 +55,31 @@ impl ServerBuilder {
         self
     }
 
+    pub(crate) fn with_validator_id(mut self, validator_id: String) -> Self {
+        self.validator_id = validator_id;
+
+        self
+    }
+
+    pub(crate) fn with_role(mut self, role: NodeRole) -> Self {
+        self.role = role;
+
+        self
+    }
+
+    pub(crate) fn with_version(mut self, version: String) -> Self {
+        self.version = version;
+
+        self
+    }
+
+    /// Sets the network client used to report the connected peers
+    pub(crate) fn with_network_client(mut self, network_client: Option<NetworkClient>) -> Self {
+        self.network_client = network_client;
+
+        self
+    }
+
     pub(crate) fn command_sender(mut self, sender: Sender<InternalRuntimeCommand>) -> Self {
         self.command_sender = Some(sender);
 
@@ -46,6 +92,49 @@ impl ServerBuilder {
         self
     }
 
+    /// Serves the admin service on its own listener, so that it can stay unreachable from the
+    /// outside while the public services are exposed
+    pub(crate) fn admin_serve_addr(mut self, addr: Option<SocketAddr>) -> Self {
+        self.admin_serve_addr = addr;
+
+        self
+    }
+
+    /// Sets the token expected by the admin service, which is only served if a token is set
+    pub(crate) fn with_admin_token(mut self, admin_token: Option<String>) -> Self {
+        self.admin_token = admin_token;
+
+        self
+    }
+
+    /// Sets the handle used by the admin service to change the log filter
+    pub(crate) fn with_log_filter(mut self, log_filter: Option<LogFilterHandle>) -> Self {
+        self.log_filter = log_filter;
+
+        self
+    }
+
+    /// Sets the restrictions applied to the submitted certificates, shared with the REST gateway
+    pub(crate) fn with_submission_policy(mut self, policy: Arc<SubmissionPolicy>) -> Self {
+        self.submission_policy = Some(policy);
+
+        self
+    }
+
+    /// Sets the API keys expected by the public services, every client is accepted if not set
+    pub(crate) fn with_api_keys(mut self, api_keys: Option<ApiKeys>) -> Self {
+        self.api_keys = api_keys;
+
+        self
+    }
+
+    /// Serves over TLS if set
+    pub(crate) fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+
+        self
+    }
+
     pub async fn build(
         mut self,
     ) -> (
@@ -63,23 +152,57 @@ impl ServerBuilder {
         // So as soon as the node starts it is ready to send and receive ECHO messages.
         let status = Arc::new(RwLock::new(StatusResponse {
             has_active_sample: true,
+            role: self.role.into(),
+            validator_id: self.validator_id,
+            peer_id: self.local_peer_id,
+            version: self.version,
+            ..Default::default()
         }));
 
-        let console = ConsoleServiceServer::new(TceConsoleService {
-            command_sender: command_sender.clone(),
-            status: status.clone(),
-        });
-
         let store = self
             .store
             .take()
             .expect("Cannot build GraphQL server without a FullNode store");
 
-        let service = ApiServiceServer::new(TceGrpcService {
-            store,
-            command_sender,
+        let api_key_interceptor = ApiKeyInterceptor {
+            api_keys: self.api_keys.take(),
+        };
+
+        let console = ConsoleServiceServer::with_interceptor(
+            TceConsoleService {
+                command_sender: command_sender.clone(),
+                status: status.clone(),
+                store: store.clone(),
+                network_client: self.network_client.take(),
+            },
+            api_key_interceptor.clone(),
+        );
+
+        let admin = self.admin_token.take().map(|token| {
+            AdminServiceServer::with_interceptor(
+                TceAdminService {
+                    command_sender: command_sender.clone(),
+                    store: store.clone(),
+                    log_filter: self.log_filter.take(),
+                },
+                AdminTokenInterceptor {
+                    token: Arc::new(token),
+                },
+            )
         });
 
+        let service = ApiServiceServer::with_interceptor(
+            TceGrpcService {
+                store,
+                command_sender,
+                submission_policy: self.submission_policy.take().unwrap_or_default(),
+                wait_for_delivery_permits: Arc::new(Semaphore::new(
+                    MAX_CONCURRENT_WAIT_FOR_DELIVERY,
+                )),
+            },
+            api_key_interceptor,
+        );
+
         let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
 
         health_reporter
@@ -96,13 +219,39 @@ impl ServerBuilder {
             .take()
             .expect("Cannot build gRPC without a valid serve_addr");
 
-        let grpc = tonic::transport::Server::builder()
+        let mut server = tonic::transport::Server::builder();
+        if let Some(tls) = self.tls.take() {
+            server = server
+                .tls_config(tls.tonic_config())
+                .expect("Cannot build gRPC with an invalid TLS configuration");
+        }
+
+        let grpc = server
             .add_service(health_service)
             .add_service(service)
             .add_service(console)
             .add_service(reflexion)
-            .serve(serve_addr)
-            .boxed();
+            .serve(serve_addr);
+
+        let grpc = match (admin, self.admin_serve_addr.take()) {
+            (Some(admin), Some(admin_serve_addr)) => {
+                tracing::info!("Serving the admin gRPC service on {}", admin_serve_addr);
+
+                let admin = tonic::transport::Server::builder()
+                    .add_service(admin)
+                    .serve(admin_serve_addr);
+
+                futures::future::try_join(grpc, admin)
+                    .map_ok(|_| ())
+                    .boxed()
+            }
+            (Some(_), None) => {
+                tracing::warn!("Not serving the admin gRPC service, no address is configured");
+
+                grpc.boxed()
+            }
+            (None, _) => grpc.boxed(),
+        };
 
         (health_reporter, status, grpc)
     }
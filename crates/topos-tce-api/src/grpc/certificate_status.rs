@@ -0,0 +1,114 @@
+use tokio::sync::{mpsc::Sender, oneshot};
+use tonic::Status;
+use topos_core::{
+    api::grpc::tce::v1::{
+        certificate_status::{
+            Broadcasting, Delivered, Failed, Pending, PrecedencePool, Status as StatusKind, Unknown,
+        },
+        BroadcastStatus, CertificateStatus,
+    },
+    uci::CertificateId,
+};
+use topos_tce_storage::{errors::StorageError, store::ReadStore, validator::ValidatorStore};
+
+use crate::runtime::InternalRuntimeCommand;
+
+fn storage_error(error: StorageError) -> Status {
+    Status::internal(format!("Can't get the certificate status: {error}"))
+}
+
+/// Whether the status of a certificate won't change anymore
+pub(crate) fn is_final(status: &CertificateStatus) -> bool {
+    matches!(
+        status.status,
+        Some(StatusKind::Delivered(_)) | Some(StatusKind::Failed(_))
+    )
+}
+
+/// Resolve the status of a certificate, looking at the storage first and then at its broadcast
+pub(crate) async fn certificate_status(
+    store: &ValidatorStore,
+    command_sender: &Sender<InternalRuntimeCommand>,
+    certificate_id: CertificateId,
+) -> Result<CertificateStatus, Status> {
+    if let Some(delivered) = store
+        .get_certificate(&certificate_id)
+        .map_err(storage_error)?
+    {
+        let proof_of_delivery = delivered.proof_of_delivery;
+
+        return Ok(CertificateStatus {
+            status: Some(StatusKind::Delivered(Delivered {
+                position: *proof_of_delivery.delivery_position.position,
+                proof_of_delivery: Some(proof_of_delivery.into()),
+            })),
+        });
+    }
+
+    let (sender, receiver) = oneshot::channel();
+    command_sender
+        .send(InternalRuntimeCommand::GetBroadcastState {
+            certificate_id,
+            sender,
+        })
+        .await
+        .map_err(|_| Status::internal("Unable to reach the runtime"))?;
+
+    let broadcast_state = receiver
+        .await
+        .map_err(|_| Status::internal("Unable to receive the broadcast state"))?
+        .map_err(|error| Status::internal(error.to_string()))?;
+
+    let mut awaiting_previous_certificate = false;
+    if let Some(state) = broadcast_state {
+        if state.status() == BroadcastStatus::Failed {
+            return Ok(CertificateStatus {
+                status: Some(StatusKind::Failed(Failed {})),
+            });
+        }
+
+        if !state.awaiting_previous_certificate {
+            let network_size = state.network_size;
+
+            return Ok(CertificateStatus {
+                status: Some(StatusKind::Broadcasting(Broadcasting {
+                    echo_count: network_size.saturating_sub(state.missing_echoes.len() as u64),
+                    ready_count: network_size.saturating_sub(state.missing_readies.len() as u64),
+                    echo_threshold: state.echo_threshold,
+                    ready_threshold: state.ready_threshold,
+                    delivery_threshold: state.delivery_threshold,
+                })),
+            });
+        }
+
+        awaiting_previous_certificate = true;
+    }
+
+    if let Some(pending_id) = store
+        .get_pending_id(&certificate_id)
+        .map_err(storage_error)?
+    {
+        return Ok(CertificateStatus {
+            status: Some(StatusKind::Pending(Pending { pending_id })),
+        });
+    }
+
+    let status = match store
+        .get_precedence_pool_certificate(&certificate_id)
+        .map_err(storage_error)?
+    {
+        Some(certificate) => StatusKind::PrecedencePool(PrecedencePool {
+            prev_certificate_id: Some(certificate.prev_id.into()),
+        }),
+        // A certificate received from the network waits for its previous one without being
+        // stored in the precedence pool
+        None if awaiting_previous_certificate => StatusKind::PrecedencePool(PrecedencePool {
+            prev_certificate_id: None,
+        }),
+        None => StatusKind::Unknown(Unknown {}),
+    };
+
+    Ok(CertificateStatus {
+        status: Some(status),
+    })
+}
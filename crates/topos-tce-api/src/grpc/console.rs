@@ -7,6 +7,10 @@ use tonic::{Request, Response, Status};
 use topos_core::api::grpc::tce::v1::{
     console_service_server::ConsoleService, StatusRequest, StatusResponse,
 };
+use topos_metrics::DOUBLE_ECHO_ACTIVE_TASKS_COUNT;
+use topos_p2p::NetworkClient;
+use topos_tce_storage::{errors::StorageError, validator::ValidatorStore};
+use tracing::{error, warn};
 
 pub(crate) struct TceConsoleService {
     // We want to allow this unused command_sender, because we need it in the future again.
@@ -15,6 +19,8 @@ pub(crate) struct TceConsoleService {
     #[allow(dead_code)]
     pub(crate) command_sender: Sender<InternalRuntimeCommand>,
     pub(crate) status: Arc<RwLock<StatusResponse>>,
+    pub(crate) store: Arc<ValidatorStore>,
+    pub(crate) network_client: Option<NetworkClient>,
 }
 
 #[tonic::async_trait]
@@ -23,8 +29,38 @@ impl ConsoleService for TceConsoleService {
         &self,
         _request: Request<StatusRequest>,
     ) -> Result<Response<StatusResponse>, Status> {
-        let status = self.status.read().await;
+        let mut status = self.status.read().await.clone();
 
-        Ok(Response::new(status.clone()))
+        let storage_error = |error: StorageError| {
+            error!("Unable to read the node status from the storage: {error}");
+            Status::internal("Unable to read the node status from the storage")
+        };
+
+        status.epoch = self.store.get_fullnode_store().current_epoch();
+        // Counting the tables would iterate over them on every request, their sizes are
+        // estimated instead
+        status.delivered_certificates = self
+            .store
+            .get_fullnode_store()
+            .estimate_certificates_delivered()
+            .map_err(storage_error)?;
+        status.pending_certificates = self
+            .store
+            .estimate_pending_certificates()
+            .map_err(storage_error)?;
+        status.precedence_pool_certificates = self
+            .store
+            .estimate_precedence_pool_certificates()
+            .map_err(storage_error)?;
+        status.active_broadcast_tasks = DOUBLE_ECHO_ACTIVE_TASKS_COUNT.get().max(0) as u64;
+
+        if let Some(network_client) = &self.network_client {
+            match network_client.connected_peers().await {
+                Ok(peers) => status.connected_peers = peers.len() as u64,
+                Err(error) => warn!("Unable to get the connected peers: {error}"),
+            }
+        }
+
+        Ok(Response::new(status))
     }
 }
@@ -1,5 +1,8 @@
+use std::collections::HashSet;
+
 use tonic::Status;
 use topos_core::api::grpc::checkpoints::{TargetCheckpoint, TargetStreamPosition};
+use topos_core::api::grpc::tce::v1::watch_certificates_request::Acknowledge as GrpcAcknowledge;
 use topos_core::api::grpc::tce::v1::watch_certificates_request::Command;
 use topos_core::api::grpc::tce::v1::watch_certificates_request::OpenStream as GrpcOpenStream;
 use topos_core::api::grpc::tce::v1::watch_certificates_response::CertificatePushed as GrpcCertificatePushed;
@@ -9,10 +12,19 @@ use topos_core::uci::{Certificate, SubnetId};
 
 pub enum InboundMessage {
     OpenStream(OpenStream),
+    Acknowledge(Acknowledge),
 }
 
 pub struct OpenStream {
     pub(crate) target_checkpoint: TargetCheckpoint,
+    /// Source subnets to push the certificates of, every source if empty
+    pub(crate) source_subnet_ids: HashSet<SubnetId>,
+    pub(crate) headers_only: bool,
+    pub(crate) window_size: Option<u32>,
+}
+
+pub struct Acknowledge {
+    pub(crate) credits: u32,
 }
 
 #[derive(Debug)]
@@ -38,6 +50,9 @@ impl TryFrom<Command> for InboundMessage {
     fn try_from(command: Command) -> Result<Self, Self::Error> {
         match command {
             Command::OpenStream(value) => Ok(OpenStream::try_from(value)?.into()),
+            Command::Acknowledge(GrpcAcknowledge { credits }) => {
+                Ok(Self::Acknowledge(Acknowledge { credits }))
+            }
         }
     }
 }
@@ -51,6 +66,17 @@ impl TryFrom<GrpcOpenStream> for OpenStream {
                 Err(Status::invalid_argument("missing target_checkpoint")),
                 |value| value.map_err(|_| Status::invalid_argument("invalid checkpoint")),
             )?,
+            source_subnet_ids: value
+                .source_subnet_ids
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()
+                .map_err(|_| Status::invalid_argument("invalid source subnet id"))?,
+            headers_only: value.headers_only,
+            window_size: match value.window_size {
+                Some(0) => return Err(Status::invalid_argument("window_size can't be zero")),
+                window_size => window_size,
+            },
         })
     }
 }
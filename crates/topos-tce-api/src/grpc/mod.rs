@@ -2,30 +2,43 @@ use base64ct::{Base64, Encoding};
 use futures::{FutureExt, Stream as FutureStream, StreamExt};
 use std::pin::Pin;
 use std::sync::Arc;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, Semaphore};
+use tokio::time::{sleep, Instant};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status, Streaming};
 use topos_api::grpc::tce::v1::LastPendingCertificate;
 use topos_core::api::grpc::tce::v1::{
-    api_service_server::ApiService, GetLastPendingCertificatesRequest,
+    api_service_server::ApiService, certificate_status::Status as StatusKind,
+    GetCertificateStatusRequest, GetCertificateStatusResponse, GetLastPendingCertificatesRequest,
     GetLastPendingCertificatesResponse, GetSourceHeadRequest, GetSourceHeadResponse,
-    SubmitCertificateRequest, SubmitCertificateResponse, WatchCertificatesRequest,
-    WatchCertificatesResponse,
+    SubmitCertificateRequest, SubmitCertificateResponse, WaitForDeliveryRequest,
+    WaitForDeliveryResponse, WatchCertificatesRequest, WatchCertificatesResponse,
 };
-use topos_core::uci::SubnetId;
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
 use topos_metrics::API_GRPC_CERTIFICATE_RECEIVED_TOTAL;
 use topos_tce_storage::validator::ValidatorStore;
 use tracing::{error, info, Span};
 use uuid::Uuid;
 
 use crate::{
+    access::ClientId,
+    constants::{
+        WAIT_FOR_DELIVERY_POLLING_INTERVAL, WAIT_FOR_DELIVERY_TIMEOUT,
+        WAIT_FOR_DELIVERY_UNKNOWN_GRACE_PERIOD,
+    },
     runtime::InternalRuntimeCommand,
-    stream::{Stream, StreamError, StreamErrorKind},
+    stream::{Stream, StreamError, StreamErrorKind, TransientStream},
 };
 
-use self::messaging::{InboundMessage, OutboundMessage};
+use self::{
+    messaging::{InboundMessage, OutboundMessage},
+    submission::SubmissionPolicy,
+};
 
+pub(crate) mod admin;
+pub(crate) mod certificate_status;
 pub(crate) mod console;
+pub(crate) mod submission;
 #[cfg(test)]
 mod tests;
 
@@ -37,6 +50,18 @@ pub(crate) mod messaging;
 pub(crate) struct TceGrpcService {
     store: Arc<ValidatorStore>,
     command_sender: mpsc::Sender<InternalRuntimeCommand>,
+    submission_policy: Arc<SubmissionPolicy>,
+    /// Permits of the clients waiting for the delivery of a certificate
+    wait_for_delivery_permits: Arc<Semaphore>,
+}
+
+pub(crate) fn parse_certificate_id(
+    certificate_id: Option<topos_core::api::grpc::shared::v1::CertificateId>,
+) -> Result<CertificateId, Status> {
+    certificate_id
+        .ok_or_else(|| Status::invalid_argument("Missing certificate id"))?
+        .try_into()
+        .map_err(|_| Status::invalid_argument("Invalid certificate id"))
 }
 
 impl TceGrpcService {
@@ -83,15 +108,23 @@ impl ApiService for TceGrpcService {
         request: Request<SubmitCertificateRequest>,
     ) -> Result<Response<SubmitCertificateResponse>, Status> {
         async {
+            let client_certificate = request.peer_certs();
+            let client = ClientId::resolve(
+                request.extensions().get(),
+                client_certificate
+                    .as_ref()
+                    .and_then(|certificates| certificates.first())
+                    .map(|certificate| certificate.get_ref()),
+                request.remote_addr(),
+            );
             let data = request.into_inner();
             if let Some(certificate) = data.certificate {
                 if let Some(ref id) = certificate.id {
                     Span::current().record("certificate_id", id.to_string());
 
-                    let (sender, receiver) = oneshot::channel();
                     // FIXME: remove certificate cloning (may be a lot of data) when we
                     // resolve the issue with invalid certificate error
-                    let certificate = match certificate.clone().try_into() {
+                    let certificate: Certificate = match certificate.clone().try_into() {
                         Ok(c) => c,
                         Err(e) => {
                             error!(
@@ -103,27 +136,16 @@ impl ApiService for TceGrpcService {
                         }
                     };
 
-                    if self
-                        .command_sender
-                        .send(InternalRuntimeCommand::CertificateSubmitted {
-                            certificate: Box::new(certificate),
-                            sender,
-                        })
-                        .await
-                        .is_err()
-                    {
-                        return Err(Status::internal("Can't submit certificate: sender dropped"));
-                    } else {
-                        API_GRPC_CERTIFICATE_RECEIVED_TOTAL.inc();
-                    }
+                    API_GRPC_CERTIFICATE_RECEIVED_TOTAL.inc();
 
-                    receiver
-                        .map(|value| match value {
-                            Ok(Ok(_)) => Ok(Response::new(SubmitCertificateResponse {})),
-                            Ok(Err(_)) => Err(Status::internal("Can't submit certificate")),
-                            Err(_) => Err(Status::internal("Can't submit certificate")),
-                        })
-                        .await
+                    submission::submit_certificate(
+                        &self.command_sender,
+                        &self.submission_policy,
+                        client,
+                        certificate,
+                    )
+                    .await
+                    .map(Response::new)
                 } else {
                     error!("No certificate id provided");
                     Err(Status::invalid_argument("Certificate is malformed"))
@@ -250,6 +272,149 @@ impl ApiService for TceGrpcService {
         }))
     }
 
+    async fn get_certificate_status(
+        &self,
+        request: Request<GetCertificateStatusRequest>,
+    ) -> Result<Response<GetCertificateStatusResponse>, Status> {
+        let certificate_id = parse_certificate_id(request.into_inner().certificate_id)?;
+
+        let status = certificate_status::certificate_status(
+            &self.store,
+            &self.command_sender,
+            certificate_id,
+        )
+        .await?;
+
+        Ok(Response::new(GetCertificateStatusResponse {
+            status: Some(status),
+        }))
+    }
+
+    ///Server streaming response type for the WaitForDelivery method.
+    type WaitForDeliveryStream =
+        Pin<Box<dyn FutureStream<Item = Result<WaitForDeliveryResponse, Status>> + Send + 'static>>;
+
+    /// Push the status of a certificate every time it changes, until it is final
+    async fn wait_for_delivery(
+        &self,
+        request: Request<WaitForDeliveryRequest>,
+    ) -> Result<Response<Self::WaitForDeliveryStream>, Status> {
+        let certificate_id = parse_certificate_id(request.into_inner().certificate_id)?;
+
+        let permit = self
+            .wait_for_delivery_permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| {
+                Status::resource_exhausted("Too many clients waiting for a delivery, retry later")
+            })?;
+
+        // Listen for the deliveries before looking at the status to not miss the one we wait for
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(InternalRuntimeCommand::NewTransientStream { sender })
+            .await
+            .map_err(|_| Status::internal("Unable to reach the runtime"))?;
+
+        let mut deliveries: TransientStream = receiver
+            .await
+            .map_err(|_| Status::internal("Unable to listen for the deliveries"))?
+            .map_err(|error| Status::internal(error.to_string()))?;
+
+        let store = self.store.clone();
+        let command_sender = self.command_sender.clone();
+        let (sender, receiver) = mpsc::channel(DEFAULT_CHANNEL_STREAM_CAPACITY);
+
+        tokio::spawn(async move {
+            let _permit = permit;
+            let started = Instant::now();
+            let deadline = started + WAIT_FOR_DELIVERY_TIMEOUT;
+            let mut previous_status = None;
+            loop {
+                match certificate_status::certificate_status(
+                    &store,
+                    &command_sender,
+                    certificate_id,
+                )
+                .await
+                {
+                    Ok(status) => {
+                        let is_final = certificate_status::is_final(&status);
+                        if previous_status.as_ref() != Some(&status) {
+                            if sender
+                                .send(Ok(WaitForDeliveryResponse {
+                                    status: Some(status.clone()),
+                                }))
+                                .await
+                                .is_err()
+                            {
+                                break;
+                            }
+
+                            previous_status = Some(status);
+                        }
+
+                        if is_final {
+                            break;
+                        }
+
+                        if matches!(
+                            previous_status
+                                .as_ref()
+                                .and_then(|status| status.status.as_ref()),
+                            Some(StatusKind::Unknown(_))
+                        ) && started.elapsed() >= WAIT_FOR_DELIVERY_UNKNOWN_GRACE_PERIOD
+                        {
+                            _ = sender
+                                .send(Err(Status::not_found(format!(
+                                    "Certificate {certificate_id} is unknown"
+                                ))))
+                                .await;
+
+                            break;
+                        }
+                    }
+                    Err(error) => {
+                        _ = sender.send(Err(error)).await;
+
+                        break;
+                    }
+                }
+
+                // Look at the status again once the certificate is delivered, or periodically to
+                // follow the progress of its broadcast
+                let delivered = async {
+                    while let Some(certificate) = deliveries.next().await {
+                        if certificate.id == certificate_id {
+                            return;
+                        }
+                    }
+
+                    futures::future::pending::<()>().await
+                };
+
+                tokio::select! {
+                    _ = delivered => {}
+                    _ = sleep(WAIT_FOR_DELIVERY_POLLING_INTERVAL) => {}
+                    _ = tokio::time::sleep_until(deadline) => {
+                        _ = sender
+                            .send(Err(Status::deadline_exceeded(format!(
+                                "Certificate {certificate_id} not delivered in time"
+                            ))))
+                            .await;
+
+                        break;
+                    }
+                    _ = sender.closed() => break,
+                }
+            }
+        });
+
+        Ok(Response::new(
+            Box::pin(ReceiverStream::new(receiver)) as Self::WaitForDeliveryStream
+        ))
+    }
+
     ///Server streaming response type for the WatchCertificates method.
     type WatchCertificatesStream = Pin<
         Box<dyn FutureStream<Item = Result<WatchCertificatesResponse, Status>> + Send + 'static>,
@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+
+use tokio::sync::{mpsc::Sender, oneshot};
+use tonic::Status;
+use topos_core::{
+    api::grpc::tce::v1::{SubmitCertificateResponse, SubmitCertificateResult},
+    uci::{Certificate, CertificateId, SubnetId},
+};
+use topos_tce_storage::{
+    errors::{InternalStorageError, StorageError},
+    types::PendingResult,
+};
+use tracing::{error, warn};
+
+use crate::{
+    access::{ApiAccess, ClientId, RateLimiter},
+    runtime::{error::RuntimeError, InternalRuntimeCommand},
+};
+
+/// Restrictions applied to the certificates submitted through the public APIs
+#[derive(Default)]
+pub(crate) struct SubmissionPolicy {
+    /// Source subnets from which certificates are accepted, every subnet is accepted if not set
+    allowed_source_subnets: Option<HashSet<SubnetId>>,
+    per_client: Option<RateLimiter<ClientId>>,
+    /// Counted for each client, the source subnet of a certificate is only authenticated once
+    /// it is broadcast
+    per_source_subnet: Option<RateLimiter<(ClientId, SubnetId)>>,
+}
+
+impl SubmissionPolicy {
+    pub(crate) fn new(
+        allowed_source_subnets: Option<HashSet<SubnetId>>,
+        access: &ApiAccess,
+    ) -> Self {
+        Self {
+            allowed_source_subnets,
+            per_client: access
+                .submission_rate_limit_per_client
+                .map(RateLimiter::new),
+            per_source_subnet: access
+                .submission_rate_limit_per_subnet
+                .map(RateLimiter::new),
+        }
+    }
+}
+
+/// Checks done before accepting a submitted certificate
+fn check_submitted_certificate(
+    certificate: &Certificate,
+    allowed_source_subnets: Option<&HashSet<SubnetId>>,
+) -> Result<(), SubmitCertificateResult> {
+    if allowed_source_subnets
+        .is_some_and(|allowed| !allowed.contains(&certificate.source_subnet_id))
+    {
+        return Err(SubmitCertificateResult::SourceSubnetNotAllowed);
+    }
+
+    // Only the id can be checked, the node doesn't know the keys of the subnets to verify
+    // their signatures
+    if certificate.check_id().is_err() {
+        return Err(SubmitCertificateResult::InvalidId);
+    }
+
+    Ok(())
+}
+
+/// Map the outcome of the insertion of a submitted certificate to the response of the submission
+fn submission_response(
+    result: Result<PendingResult, RuntimeError>,
+    prev_id: CertificateId,
+) -> Result<SubmitCertificateResponse, Status> {
+    let result = match result {
+        Ok(PendingResult::InPending(pending_id)) => {
+            return Ok(SubmitCertificateResponse {
+                result: SubmitCertificateResult::Accepted.into(),
+                pending_id: Some(pending_id),
+                ..Default::default()
+            })
+        }
+        Ok(PendingResult::AwaitPrecedence) => {
+            return Ok(SubmitCertificateResponse {
+                result: SubmitCertificateResult::PrecedencePool.into(),
+                prev_certificate_id: Some(prev_id.into()),
+                ..Default::default()
+            })
+        }
+        Ok(PendingResult::AlreadyDelivered)
+        | Err(RuntimeError::Store(StorageError::InternalStorage(
+            InternalStorageError::CertificateAlreadyExists,
+        ))) => SubmitCertificateResult::AlreadyDelivered,
+        Err(RuntimeError::Store(StorageError::InternalStorage(
+            InternalStorageError::CertificateAlreadyPending,
+        ))) => SubmitCertificateResult::Duplicate,
+        Err(error @ RuntimeError::NotSynced(_)) => {
+            warn!("Unable to submit the certificate: {error}");
+
+            return Err(Status::unavailable(error.to_string()));
+        }
+        Err(error) => {
+            error!("Unable to submit the certificate: {error}");
+
+            return Err(Status::internal("Can't submit certificate"));
+        }
+    };
+
+    Ok(SubmitCertificateResponse {
+        result: result.into(),
+        ..Default::default()
+    })
+}
+
+/// Check a submitted certificate and hand it to the runtime, shared by the public APIs
+pub(crate) async fn submit_certificate(
+    command_sender: &Sender<InternalRuntimeCommand>,
+    policy: &SubmissionPolicy,
+    client: ClientId,
+    certificate: Certificate,
+) -> Result<SubmitCertificateResponse, Status> {
+    if policy
+        .per_client
+        .as_ref()
+        .is_some_and(|limiter| !limiter.check(client.clone()))
+    {
+        return Err(Status::resource_exhausted(
+            "Submission rate limit of the client exceeded",
+        ));
+    }
+
+    if let Err(result) =
+        check_submitted_certificate(&certificate, policy.allowed_source_subnets.as_ref())
+    {
+        warn!(
+            "Certificate {} rejected: {}",
+            certificate.id,
+            result.as_str_name()
+        );
+
+        return Ok(SubmitCertificateResponse {
+            result: result.into(),
+            ..Default::default()
+        });
+    }
+
+    if policy
+        .per_source_subnet
+        .as_ref()
+        .is_some_and(|limiter| !limiter.check((client, certificate.source_subnet_id)))
+    {
+        return Err(Status::resource_exhausted(format!(
+            "Submission rate limit of the subnet {} exceeded",
+            certificate.source_subnet_id
+        )));
+    }
+
+    let prev_id = certificate.prev_id;
+    let (sender, receiver) = oneshot::channel();
+    command_sender
+        .send(InternalRuntimeCommand::CertificateSubmitted {
+            certificate: Box::new(certificate),
+            sender,
+        })
+        .await
+        .map_err(|_| Status::internal("Can't submit certificate: sender dropped"))?;
+
+    let result = receiver
+        .await
+        .map_err(|_| Status::internal("Can't submit certificate"))?;
+
+    submission_response(result, prev_id)
+}
@@ -1,8 +1,11 @@
+mod access;
 mod graphql;
 mod grpc;
 mod metrics;
+mod rest;
 mod runtime;
 mod stream;
+mod tls;
 
 #[cfg(test)]
 mod tests;
@@ -13,7 +16,38 @@ pub(crate) mod constants {
 
     /// Constant size of every transient stream channel in the crate
     pub(crate) const TRANSIENT_STREAM_CHANNEL_SIZE: usize = 1024;
+
+    /// Interval between two lookups of the status of a certificate awaiting its delivery
+    pub(crate) const WAIT_FOR_DELIVERY_POLLING_INTERVAL: std::time::Duration =
+        std::time::Duration::from_millis(500);
+
+    /// Delay after which waiting for a certificate unknown to the node fails with `NOT_FOUND`
+    pub(crate) const WAIT_FOR_DELIVERY_UNKNOWN_GRACE_PERIOD: std::time::Duration =
+        std::time::Duration::from_secs(5);
+
+    /// Delay after which waiting for the delivery of a certificate fails with
+    /// `DEADLINE_EXCEEDED`
+    pub(crate) const WAIT_FOR_DELIVERY_TIMEOUT: std::time::Duration =
+        std::time::Duration::from_secs(600);
+
+    /// Number of clients waiting for the delivery of a certificate at the same time
+    pub(crate) const MAX_CONCURRENT_WAIT_FOR_DELIVERY: usize = 256;
+
+    /// Number of clients watching the delivered certificates over the REST gateway at the
+    /// same time
+    pub(crate) const MAX_CONCURRENT_WATCH_DELIVERED_CERTIFICATES: usize = 256;
+
+    /// Delay after which a client which didn't complete its TLS handshake is disconnected
+    pub(crate) const TLS_HANDSHAKE_TIMEOUT: std::time::Duration =
+        std::time::Duration::from_secs(10);
+
+    /// Number of TLS handshakes an HTTP server runs at the same time, new connections are
+    /// dropped above it
+    pub(crate) const MAX_CONCURRENT_TLS_HANDSHAKES: usize = 64;
 }
+pub use access::{ApiAccess, ApiKeys, RateLimit};
+pub use grpc::admin::LogFilterHandle;
 pub use runtime::{
     error::RuntimeError, Runtime, RuntimeClient, RuntimeCommand, RuntimeContext, RuntimeEvent,
 };
+pub use tls::{TlsConfig, TlsError};
@@ -2,15 +2,25 @@ use std::net::SocketAddr;
 
 use topos_metrics::gather_metrics;
 
-use axum::{routing::get, Router, Server};
+use axum::{middleware, routing::get, Router, Server};
 use tracing::info;
 
+use crate::access::{require_api_key, ApiKeys};
+
 #[derive(Default)]
 pub struct ServerBuilder {
     serve_addr: Option<SocketAddr>,
+    api_keys: Option<ApiKeys>,
 }
 
 impl ServerBuilder {
+    /// Sets the API keys expected from the scrapers, every scraper is accepted if not set
+    pub(crate) fn api_keys(mut self, api_keys: Option<ApiKeys>) -> Self {
+        self.api_keys = api_keys;
+
+        self
+    }
+
     pub fn serve_addr(mut self, addr: Option<SocketAddr>) -> Self {
         self.serve_addr = addr;
 
@@ -20,7 +30,7 @@ impl ServerBuilder {
     pub async fn build(
         mut self,
     ) -> Server<hyper::server::conn::AddrIncoming, axum::routing::IntoMakeService<Router>> {
-        let app = Router::new().route(
+        let mut app = Router::new().route(
             "/metrics",
             get(|| async {
                 let topos_metrics = gather_metrics();
@@ -32,6 +42,10 @@ impl ServerBuilder {
             }),
         );
 
+        if let Some(api_keys) = self.api_keys.take() {
+            app = app.route_layer(middleware::from_fn_with_state(api_keys, require_api_key));
+        }
+
         let serve_addr = self
             .serve_addr
             .take()
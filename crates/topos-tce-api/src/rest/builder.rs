@@ -0,0 +1,131 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router, Server,
+};
+use futures::{future::BoxFuture, FutureExt};
+use http::Method;
+use tokio::sync::{mpsc, Semaphore};
+use tower_http::cors::{Any, CorsLayer};
+
+use crate::{
+    access::{cors_allowed_headers, require_api_key, ApiKeys},
+    constants::MAX_CONCURRENT_WATCH_DELIVERED_CERTIFICATES,
+    grpc::submission::SubmissionPolicy,
+    rest::routes::{
+        get_certificate, get_last_pending_certificates, get_source_head, get_stream_positions,
+        submit_certificate, watch_delivered_certificates, RestState,
+    },
+    runtime::InternalRuntimeCommand,
+    tls::{self, ClientConnection, TlsConfig},
+};
+use topos_tce_storage::validator::ValidatorStore;
+
+#[derive(Default)]
+pub struct ServerBuilder {
+    store: Option<Arc<ValidatorStore>>,
+    serve_addr: Option<SocketAddr>,
+    runtime: Option<mpsc::Sender<InternalRuntimeCommand>>,
+    submission_policy: Option<Arc<SubmissionPolicy>>,
+    api_keys: Option<ApiKeys>,
+    tls: Option<TlsConfig>,
+}
+
+impl ServerBuilder {
+    /// Sets the runtime command channel
+    ///
+    /// Used to submit certificates and to open the transient streams of the SSE endpoint
+    pub(crate) fn runtime(mut self, runtime: mpsc::Sender<InternalRuntimeCommand>) -> Self {
+        self.runtime = Some(runtime);
+
+        self
+    }
+
+    pub(crate) fn store(mut self, store: Arc<ValidatorStore>) -> Self {
+        self.store = Some(store);
+
+        self
+    }
+
+    /// Sets the restrictions applied to the submitted certificates, shared with the gRPC API
+    pub(crate) fn submission_policy(mut self, policy: Arc<SubmissionPolicy>) -> Self {
+        self.submission_policy = Some(policy);
+
+        self
+    }
+
+    /// Sets the API keys expected from the clients, every client is accepted if not set
+    pub(crate) fn api_keys(mut self, api_keys: Option<ApiKeys>) -> Self {
+        self.api_keys = api_keys;
+
+        self
+    }
+
+    /// Serves over TLS if set
+    pub(crate) fn tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+
+        self
+    }
+
+    pub(crate) fn serve_addr(mut self, addr: Option<SocketAddr>) -> Self {
+        self.serve_addr = addr;
+
+        self
+    }
+
+    pub async fn build(mut self) -> BoxFuture<'static, Result<(), hyper::Error>> {
+        let cors = CorsLayer::new()
+            // allow `GET` and `POST` when accessing the resource
+            .allow_methods([Method::GET, Method::POST])
+            // allow 'application/json' requests, authenticated if API keys are expected
+            .allow_headers(cors_allowed_headers(self.api_keys.as_ref()))
+            // allow requests from any origin
+            .allow_origin(Any);
+
+        let state = Arc::new(RestState {
+            store: self
+                .store
+                .take()
+                .expect("Cannot build REST server without a Validator store"),
+            runtime: self
+                .runtime
+                .take()
+                .expect("Cannot build REST server without the internal runtime channel"),
+            submission_policy: self.submission_policy.take().unwrap_or_default(),
+            watch_permits: Arc::new(Semaphore::new(MAX_CONCURRENT_WATCH_DELIVERED_CERTIFICATES)),
+        });
+
+        let mut app = Router::new()
+            .route("/v1/certificates", post(submit_certificate))
+            .route("/v1/certificates/:certificate_id", get(get_certificate))
+            .route("/v1/subnets/:subnet_id/source-head", get(get_source_head))
+            .route(
+                "/v1/pending-certificates",
+                get(get_last_pending_certificates),
+            )
+            .route("/v1/positions", get(get_stream_positions))
+            .route(
+                "/v1/events/delivered-certificates",
+                get(watch_delivered_certificates),
+            );
+
+        if let Some(api_keys) = self.api_keys.take() {
+            app = app.route_layer(middleware::from_fn_with_state(api_keys, require_api_key));
+        }
+
+        let app = app.layer(cors).with_state(state);
+
+        let serve_addr = self.serve_addr.take().expect("Server address is not set");
+        // The address of the clients identifies them for the submission rate limit
+        let app = app.into_make_service_with_connect_info::<ClientConnection>();
+        match self.tls.take() {
+            Some(tls) => Server::builder(tls::incoming(serve_addr, tls.acceptor()))
+                .serve(app)
+                .boxed(),
+            None => Server::bind(&serve_addr).serve(app).boxed(),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use tonic::Code;
+use topos_tce_storage::errors::StorageError;
+use tracing::error;
+
+use super::types::ErrorResponse;
+
+#[derive(Debug)]
+pub(crate) enum RestError {
+    BadRequest(String),
+    NotFound(String),
+    TooManyRequests(String),
+    Unavailable(String),
+    Internal(String),
+}
+
+impl From<StorageError> for RestError {
+    fn from(error: StorageError) -> Self {
+        match error {
+            // Pruned data is expected on nodes running in pruned mode
+            StorageError::Pruned(error) => Self::NotFound(error.to_string()),
+            error => {
+                error!("Unable to access the storage: {error}");
+                Self::Internal("Unable to access the storage".to_string())
+            }
+        }
+    }
+}
+
+impl From<tonic::Status> for RestError {
+    fn from(status: tonic::Status) -> Self {
+        let message = status.message().to_string();
+        match status.code() {
+            Code::InvalidArgument => Self::BadRequest(message),
+            Code::NotFound => Self::NotFound(message),
+            Code::ResourceExhausted => Self::TooManyRequests(message),
+            Code::Unavailable => Self::Unavailable(message),
+            _ => Self::Internal(message),
+        }
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            Self::BadRequest(error) => (StatusCode::BAD_REQUEST, error),
+            Self::NotFound(error) => (StatusCode::NOT_FOUND, error),
+            Self::TooManyRequests(error) => (StatusCode::TOO_MANY_REQUESTS, error),
+            Self::Unavailable(error) => (StatusCode::SERVICE_UNAVAILABLE, error),
+            Self::Internal(error) => (StatusCode::INTERNAL_SERVER_ERROR, error),
+        };
+
+        (status, Json(ErrorResponse { error })).into_response()
+    }
+}
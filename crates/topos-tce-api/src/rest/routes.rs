@@ -0,0 +1,266 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{ConnectInfo, Path, Query, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    Extension, Json,
+};
+use futures::{Stream, StreamExt};
+use serde::Deserialize;
+use tokio::sync::{mpsc, oneshot, Semaphore};
+use topos_core::{
+    api::grpc::tce::v1::SubmitCertificateResult,
+    uci::{self, SubnetId},
+};
+use topos_tce_storage::{store::ReadStore, validator::ValidatorStore};
+
+use super::{
+    error::RestError,
+    types::{
+        parse_certificate_id, parse_subnet_id, Certificate, LastPendingCertificate,
+        PositionedCertificate, StreamPosition, SubmissionResponse,
+    },
+};
+use crate::{
+    access::ClientId,
+    grpc::submission::{self, SubmissionPolicy},
+    runtime::InternalRuntimeCommand,
+    stream::TransientStream,
+    tls::ClientConnection,
+    RuntimeError,
+};
+
+pub(crate) struct RestState {
+    pub(crate) store: Arc<ValidatorStore>,
+    pub(crate) runtime: mpsc::Sender<InternalRuntimeCommand>,
+    pub(crate) submission_policy: Arc<SubmissionPolicy>,
+    /// Bounds the number of transient streams opened to watch the delivered certificates
+    pub(crate) watch_permits: Arc<Semaphore>,
+}
+
+type AppState = State<Arc<RestState>>;
+
+#[derive(Deserialize)]
+pub(crate) struct SubnetsQuery {
+    /// Comma separated list of subnet ids
+    subnets: Option<String>,
+}
+
+impl SubnetsQuery {
+    fn subnet_ids(&self) -> Result<Option<Vec<SubnetId>>, RestError> {
+        self.subnets
+            .as_ref()
+            .map(|subnets| {
+                subnets
+                    .split(',')
+                    .filter(|subnet_id| !subnet_id.is_empty())
+                    .map(parse_subnet_id)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(RestError::BadRequest)
+            })
+            .transpose()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeliveredCertificatesQuery {
+    source_subnet_id: Option<String>,
+    target_subnet_id: Option<String>,
+}
+
+pub(crate) async fn submit_certificate(
+    State(state): AppState,
+    ConnectInfo(connection): ConnectInfo<ClientConnection>,
+    authenticated: Option<Extension<ClientId>>,
+    Json(certificate): Json<Certificate>,
+) -> Result<(StatusCode, Json<SubmissionResponse>), RestError> {
+    let certificate: uci::Certificate = certificate.try_into().map_err(RestError::BadRequest)?;
+    let client = ClientId::resolve(
+        authenticated.as_ref().map(|Extension(client)| client),
+        connection.certificate.as_deref().map(Vec::as_slice),
+        Some(connection.remote_addr),
+    );
+
+    let response = submission::submit_certificate(
+        &state.runtime,
+        &state.submission_policy,
+        client,
+        certificate,
+    )
+    .await?;
+
+    let status = match response.result() {
+        SubmitCertificateResult::Accepted | SubmitCertificateResult::PrecedencePool => {
+            StatusCode::ACCEPTED
+        }
+        result if result.is_rejection() => StatusCode::UNPROCESSABLE_ENTITY,
+        _ => StatusCode::OK,
+    };
+
+    Ok((status, Json(response.into())))
+}
+
+pub(crate) async fn get_certificate(
+    State(state): AppState,
+    Path(certificate_id): Path<String>,
+) -> Result<Json<PositionedCertificate>, RestError> {
+    let certificate_id = parse_certificate_id(&certificate_id).map_err(RestError::BadRequest)?;
+
+    let delivered = state
+        .store
+        .get_certificate(&certificate_id)?
+        .ok_or_else(|| {
+            RestError::NotFound(format!("Certificate {certificate_id} is not delivered"))
+        })?;
+
+    Ok(Json(PositionedCertificate {
+        certificate: (&delivered.certificate).into(),
+        position: *delivered.proof_of_delivery.delivery_position.position,
+    }))
+}
+
+pub(crate) async fn get_source_head(
+    State(state): AppState,
+    Path(subnet_id): Path<String>,
+) -> Result<Json<PositionedCertificate>, RestError> {
+    let subnet_id = parse_subnet_id(&subnet_id).map_err(RestError::BadRequest)?;
+
+    let (sender, receiver) = oneshot::channel();
+    state
+        .runtime
+        .send(InternalRuntimeCommand::GetSourceHead { subnet_id, sender })
+        .await
+        .map_err(|_| RestError::Internal("Unable to reach the runtime".to_string()))?;
+
+    match receiver
+        .await
+        .map_err(|_| RestError::Internal("Unable to receive the source head".to_string()))?
+    {
+        Ok(Some((position, certificate))) => Ok(Json(PositionedCertificate {
+            certificate: (&certificate).into(),
+            position,
+        })),
+        Ok(None) | Err(RuntimeError::UnknownSubnet(_)) => Err(RestError::NotFound(format!(
+            "No certificate delivered for subnet {subnet_id}"
+        ))),
+        Err(error) => Err(RestError::Internal(error.to_string())),
+    }
+}
+
+pub(crate) async fn get_last_pending_certificates(
+    State(state): AppState,
+    Query(query): Query<SubnetsQuery>,
+) -> Result<Json<HashMap<String, LastPendingCertificate>>, RestError> {
+    let subnet_ids = query.subnet_ids()?.unwrap_or_default();
+
+    let last_pending_certificates = state
+        .store
+        .get_pending_certificates_for_subnets(&subnet_ids)?
+        .into_iter()
+        .map(|(subnet_id, (index, certificate))| {
+            (
+                subnet_id.to_string(),
+                LastPendingCertificate {
+                    index,
+                    certificate: certificate.as_ref().map(Into::into),
+                },
+            )
+        })
+        .collect();
+
+    Ok(Json(last_pending_certificates))
+}
+
+/// Returns the head of the source stream of every subnet, or of the requested ones
+pub(crate) async fn get_stream_positions(
+    State(state): AppState,
+    Query(query): Query<SubnetsQuery>,
+) -> Result<Json<HashMap<String, StreamPosition>>, RestError> {
+    let subnet_ids = query.subnet_ids()?;
+
+    let positions = state
+        .store
+        .get_checkpoint()?
+        .into_iter()
+        .filter(|(subnet_id, _)| {
+            subnet_ids
+                .as_ref()
+                .map_or(true, |subnet_ids| subnet_ids.contains(subnet_id))
+        })
+        .map(|(subnet_id, head)| {
+            (
+                subnet_id.to_string(),
+                StreamPosition {
+                    certificate_id: head.certificate_id.to_string(),
+                    position: *head.position,
+                },
+            )
+        })
+        .collect();
+
+    Ok(Json(positions))
+}
+
+/// Server-Sent Events of the certificates delivered from now on, optionally filtered by subnet
+pub(crate) async fn watch_delivered_certificates(
+    State(state): AppState,
+    Query(query): Query<DeliveredCertificatesQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, RestError> {
+    let source_subnet_id = query
+        .source_subnet_id
+        .as_deref()
+        .map(parse_subnet_id)
+        .transpose()
+        .map_err(RestError::BadRequest)?;
+    let target_subnet_id = query
+        .target_subnet_id
+        .as_deref()
+        .map(parse_subnet_id)
+        .transpose()
+        .map_err(RestError::BadRequest)?;
+
+    let permit = state
+        .watch_permits
+        .clone()
+        .try_acquire_owned()
+        .map_err(|_| {
+            RestError::TooManyRequests(
+                "Too many clients watching the delivered certificates, retry later".to_string(),
+            )
+        })?;
+
+    let (sender, receiver) = oneshot::channel();
+    state
+        .runtime
+        .send(InternalRuntimeCommand::NewTransientStream { sender })
+        .await
+        .map_err(|_| RestError::Internal("Unable to reach the runtime".to_string()))?;
+
+    let stream: TransientStream = receiver
+        .await
+        .map_err(|_| RestError::Internal("Unable to create the stream".to_string()))?
+        .map_err(|error| RestError::Internal(error.to_string()))?;
+
+    let stream = stream
+        .filter(move |certificate| {
+            futures::future::ready(
+                source_subnet_id
+                    .map_or(true, |subnet_id| certificate.source_subnet_id == subnet_id)
+                    && target_subnet_id.map_or(true, |subnet_id| {
+                        certificate.target_subnets.contains(&subnet_id)
+                    }),
+            )
+        })
+        .map(move |certificate| {
+            // The permit is released along with the stream once the client disconnects
+            let _permit = &permit;
+
+            Event::default()
+                .event("certificate")
+                .json_data(Certificate::from(certificate.as_ref()))
+        });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
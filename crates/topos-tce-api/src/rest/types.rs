@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use topos_core::{
+    api::grpc::tce::v1::{SubmitCertificateResponse, SubmitCertificateResult},
+    uci::{self, CertificateId, SubnetId},
+};
+
+/// Certificate exchanged through the REST API, every byte field is hex encoded with a `0x` prefix
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Certificate {
+    pub id: String,
+    pub prev_id: String,
+    pub source_subnet_id: String,
+    pub state_root: String,
+    pub tx_root_hash: String,
+    pub receipts_root_hash: String,
+    /// Left empty by the subnets which don't commit their cross-subnet messages
+    #[serde(default)]
+    pub messages_root: String,
+    /// Subnet blocks certified, left unset by the subnets which don't record them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_range: Option<uci::BlockRange>,
+    #[serde(default)]
+    pub target_subnets: Vec<String>,
+    #[serde(default)]
+    pub verifier: u32,
+    #[serde(default)]
+    pub proof: String,
+    #[serde(default)]
+    pub signature: String,
+}
+
+fn encode_hex(value: impl AsRef<[u8]>) -> String {
+    format!("0x{}", hex::encode(value))
+}
+
+pub(crate) fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .map_err(|error| format!("Invalid {field}: {error}"))
+}
+
+fn decode_array<const N: usize>(field: &str, value: &str) -> Result<[u8; N], String> {
+    decode_hex(field, value)?
+        .try_into()
+        .map_err(|_| format!("Invalid {field}: expected {N} bytes"))
+}
+
+pub(crate) fn parse_subnet_id(value: &str) -> Result<SubnetId, String> {
+    decode_array("subnet id", value).map(SubnetId::from_array)
+}
+
+pub(crate) fn parse_certificate_id(value: &str) -> Result<CertificateId, String> {
+    decode_array("certificate id", value).map(CertificateId::from_array)
+}
+
+impl From<&uci::Certificate> for Certificate {
+    fn from(certificate: &uci::Certificate) -> Self {
+        Self {
+            id: certificate.id.to_string(),
+            prev_id: certificate.prev_id.to_string(),
+            source_subnet_id: certificate.source_subnet_id.to_string(),
+            state_root: encode_hex(certificate.state_root),
+            tx_root_hash: encode_hex(certificate.tx_root_hash),
+            receipts_root_hash: encode_hex(certificate.receipts_root_hash),
+            messages_root: encode_hex(certificate.messages_root),
+            block_range: certificate.block_range,
+            target_subnets: certificate
+                .target_subnets
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            verifier: certificate.verifier,
+            proof: encode_hex(&certificate.proof),
+            signature: encode_hex(&certificate.signature),
+        }
+    }
+}
+
+impl TryFrom<Certificate> for uci::Certificate {
+    type Error = String;
+
+    fn try_from(certificate: Certificate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: parse_certificate_id(&certificate.id)?,
+            prev_id: parse_certificate_id(&certificate.prev_id)?,
+            source_subnet_id: parse_subnet_id(&certificate.source_subnet_id)?,
+            state_root: decode_array("state root", &certificate.state_root)?,
+            tx_root_hash: decode_array("tx root hash", &certificate.tx_root_hash)?,
+            receipts_root_hash: decode_array(
+                "receipts root hash",
+                &certificate.receipts_root_hash,
+            )?,
+            messages_root: if certificate.messages_root.is_empty() {
+                Default::default()
+            } else {
+                decode_array("messages root", &certificate.messages_root)?
+            },
+            block_range: match certificate.block_range {
+                Some(range) if range.start > range.end => {
+                    return Err(format!(
+                        "Invalid block range: {} is after {}",
+                        range.start, range.end
+                    ))
+                }
+                block_range => block_range,
+            },
+            target_subnets: certificate
+                .target_subnets
+                .iter()
+                .map(|subnet_id| parse_subnet_id(subnet_id))
+                .collect::<Result<_, _>>()?,
+            verifier: certificate.verifier,
+            proof: decode_hex("proof", &certificate.proof)?,
+            signature: decode_hex("signature", &certificate.signature)?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SubmissionResult {
+    Unspecified,
+    Accepted,
+    PrecedencePool,
+    AlreadyDelivered,
+    Duplicate,
+    InvalidId,
+    SourceSubnetNotAllowed,
+}
+
+impl From<SubmitCertificateResult> for SubmissionResult {
+    fn from(result: SubmitCertificateResult) -> Self {
+        match result {
+            SubmitCertificateResult::Unspecified => Self::Unspecified,
+            SubmitCertificateResult::Accepted => Self::Accepted,
+            SubmitCertificateResult::PrecedencePool => Self::PrecedencePool,
+            SubmitCertificateResult::AlreadyDelivered => Self::AlreadyDelivered,
+            SubmitCertificateResult::Duplicate => Self::Duplicate,
+            SubmitCertificateResult::InvalidId => Self::InvalidId,
+            SubmitCertificateResult::SourceSubnetNotAllowed => Self::SourceSubnetNotAllowed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SubmissionResponse {
+    pub result: SubmissionResult,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_certificate_id: Option<String>,
+}
+
+impl From<SubmitCertificateResponse> for SubmissionResponse {
+    fn from(response: SubmitCertificateResponse) -> Self {
+        Self {
+            result: response.result().into(),
+            pending_id: response.pending_id,
+            prev_certificate_id: response
+                .prev_certificate_id
+                .and_then(|id| CertificateId::try_from(id).ok())
+                .map(|id| id.to_string()),
+        }
+    }
+}
+
+/// Certificate with its position in the source stream of its subnet
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PositionedCertificate {
+    pub certificate: Certificate,
+    pub position: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LastPendingCertificate {
+    pub index: u64,
+    pub certificate: Option<Certificate>,
+}
+
+/// Head of the source stream of a subnet
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StreamPosition {
+    pub certificate_id: String,
+    pub position: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ErrorResponse {
+    pub error: String,
+}
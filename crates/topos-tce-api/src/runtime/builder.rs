@@ -1,19 +1,28 @@
 use futures::Stream;
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    sync::Arc,
+};
 use tokio::{
     spawn,
     sync::{broadcast, mpsc, oneshot, RwLock},
 };
 use tokio_stream::wrappers::ReceiverStream;
-use topos_core::api::grpc::tce::v1::StatusResponse;
+use topos_core::{
+    api::grpc::tce::v1::{NodeRole, StatusResponse},
+    uci::SubnetId,
+};
+use topos_p2p::NetworkClient;
 use topos_tce_storage::{
     types::CertificateDeliveredWithPositions, validator::ValidatorStore, StorageClient,
 };
 
 use crate::{
     constants::CHANNEL_SIZE, graphql::builder::ServerBuilder as GraphQLBuilder,
-    grpc::builder::ServerBuilder, metrics::builder::ServerBuilder as MetricsBuilder, Runtime,
-    RuntimeClient, RuntimeEvent,
+    grpc::builder::ServerBuilder, grpc::submission::SubmissionPolicy,
+    metrics::builder::ServerBuilder as MetricsBuilder, rest::builder::ServerBuilder as RestBuilder,
+    ApiAccess, LogFilterHandle, Runtime, RuntimeClient, RuntimeEvent, TlsConfig,
 };
 
 #[derive(Default)]
@@ -22,10 +31,21 @@ pub struct RuntimeBuilder {
     store: Option<Arc<ValidatorStore>>,
     broadcast_stream: Option<broadcast::Receiver<CertificateDeliveredWithPositions>>,
     local_peer_id: String,
+    validator_id: String,
+    role: NodeRole,
+    version: String,
+    network_client: Option<NetworkClient>,
     grpc_socket_addr: Option<SocketAddr>,
+    admin_socket_addr: Option<SocketAddr>,
     graphql_socket_addr: Option<SocketAddr>,
+    rest_socket_addr: Option<SocketAddr>,
     metrics_socket_addr: Option<SocketAddr>,
     status: Option<RwLock<StatusResponse>>,
+    admin_token: Option<String>,
+    log_filter: Option<LogFilterHandle>,
+    allowed_source_subnets: Option<HashSet<SubnetId>>,
+    api_access: ApiAccess,
+    tls: Option<TlsConfig>,
 }
 
 impl RuntimeBuilder {
@@ -44,18 +64,95 @@ impl RuntimeBuilder {
         self
     }
 
+    pub fn with_validator_id(mut self, validator_id: String) -> Self {
+        self.validator_id = validator_id;
+
+        self
+    }
+
+    pub fn with_role(mut self, role: NodeRole) -> Self {
+        self.role = role;
+
+        self
+    }
+
+    pub fn with_version(mut self, version: String) -> Self {
+        self.version = version;
+
+        self
+    }
+
+    /// Sets the network client used to report the connected peers in the node status
+    pub fn with_network_client(mut self, network_client: NetworkClient) -> Self {
+        self.network_client = Some(network_client);
+
+        self
+    }
+
+    /// Serves the admin gRPC service, expecting the given token from its clients
+    pub fn with_admin_token(mut self, admin_token: Option<String>) -> Self {
+        self.admin_token = admin_token;
+
+        self
+    }
+
+    /// Sets the handle used by the admin gRPC service to change the log filter
+    pub fn with_log_filter(mut self, log_filter: Option<LogFilterHandle>) -> Self {
+        self.log_filter = log_filter;
+
+        self
+    }
+
+    /// Only accepts the submission of certificates from the given source subnets if set
+    pub fn with_allowed_source_subnets(
+        mut self,
+        allowed_source_subnets: Option<HashSet<SubnetId>>,
+    ) -> Self {
+        self.allowed_source_subnets = allowed_source_subnets;
+
+        self
+    }
+
+    /// Sets the authentication, rate limits and query limits of the public APIs
+    pub fn with_api_access(mut self, api_access: ApiAccess) -> Self {
+        self.api_access = api_access;
+
+        self
+    }
+
+    /// Serves the gRPC, GraphQL and REST APIs over TLS if set, the metrics stay in plaintext
+    pub fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+
+        self
+    }
+
     pub fn serve_grpc_addr(mut self, addr: SocketAddr) -> Self {
         self.grpc_socket_addr = Some(addr);
 
         self
     }
 
+    /// Serves the admin gRPC service on the given address, apart from the public APIs
+    pub fn serve_admin_addr(mut self, addr: SocketAddr) -> Self {
+        self.admin_socket_addr = Some(addr);
+
+        self
+    }
+
     pub fn serve_graphql_addr(mut self, addr: SocketAddr) -> Self {
         self.graphql_socket_addr = Some(addr);
 
         self
     }
 
+    /// Serves the JSON REST gateway of the public API on the given address
+    pub fn serve_rest_addr(mut self, addr: SocketAddr) -> Self {
+        self.rest_socket_addr = Some(addr);
+
+        self
+    }
+
     pub fn serve_metrics_addr(mut self, addr: SocketAddr) -> Self {
         self.metrics_socket_addr = Some(addr);
 
@@ -91,6 +188,12 @@ impl RuntimeBuilder {
             mpsc::channel(CHANNEL_SIZE);
         let (api_event_sender, api_event_receiver) = mpsc::channel(CHANNEL_SIZE);
 
+        // Shared by the gRPC API and the REST gateway so that the rate limits apply to both
+        let submission_policy = Arc::new(SubmissionPolicy::new(
+            self.allowed_source_subnets.take(),
+            &self.api_access,
+        ));
+
         let (health_reporter, tce_status, grpc) = ServerBuilder::default()
             .with_store(
                 self.store
@@ -99,8 +202,18 @@ impl RuntimeBuilder {
                     .expect("Unable to build gRPC Server, Store is missing"),
             )
             .with_peer_id(self.local_peer_id)
+            .with_validator_id(self.validator_id)
+            .with_role(self.role)
+            .with_version(self.version)
+            .with_network_client(self.network_client.take())
             .command_sender(internal_runtime_command_sender.clone())
             .serve_addr(self.grpc_socket_addr)
+            .with_admin_token(self.admin_token.take())
+            .admin_serve_addr(self.admin_socket_addr)
+            .with_log_filter(self.log_filter.take())
+            .with_submission_policy(submission_policy.clone())
+            .with_api_keys(self.api_access.api_keys.clone())
+            .with_tls(self.tls.clone())
             .build()
             .await;
 
@@ -109,6 +222,29 @@ impl RuntimeBuilder {
 
         let grpc_handler = spawn(grpc);
 
+        let rest_handler = if let Some(rest_addr) = self.rest_socket_addr {
+            tracing::info!("Serving REST on {}", rest_addr);
+
+            let rest = RestBuilder::default()
+                .store(
+                    self.store
+                        .clone()
+                        .expect("Unable to build REST Server, Store is missing"),
+                )
+                .runtime(internal_runtime_command_sender.clone())
+                .submission_policy(submission_policy)
+                .api_keys(self.api_access.api_keys.clone())
+                .tls(self.tls.clone())
+                .serve_addr(Some(rest_addr))
+                .build();
+            spawn(rest.await)
+        } else {
+            spawn(async move {
+                tracing::info!("Not serving REST");
+                Ok(())
+            })
+        };
+
         let graphql_handler = if let Some(graphql_addr) = self.graphql_socket_addr {
             tracing::info!("Serving GraphQL on {}", graphql_addr);
 
@@ -120,6 +256,13 @@ impl RuntimeBuilder {
                         .expect("Unable to build GraphQL Server, Store is missing"),
                 )
                 .runtime(internal_runtime_command_sender.clone())
+                .status(tce_status.clone())
+                .api_keys(self.api_access.api_keys.clone())
+                .limits(
+                    self.api_access.graphql_max_depth,
+                    self.api_access.graphql_max_complexity,
+                )
+                .tls(self.tls.clone())
                 .serve_addr(Some(graphql_addr))
                 .build();
             spawn(graphql.await)
@@ -134,6 +277,7 @@ impl RuntimeBuilder {
             tracing::info!("Serving metrics on {}", metrics_addr);
 
             let metrics_server = MetricsBuilder::default()
+                .api_keys(self.api_access.api_keys.clone())
                 .serve_addr(Some(metrics_addr))
                 .build();
             spawn(metrics_server.await)
@@ -156,6 +300,7 @@ impl RuntimeBuilder {
                 .expect("Unable to build Runtime, Storage is missing"),
             active_streams: HashMap::new(),
             pending_streams: HashMap::new(),
+            flow_controlled_streams: HashSet::new(),
             subnet_subscriptions: HashMap::new(),
             internal_runtime_command_receiver,
             runtime_command_receiver,
@@ -177,6 +322,7 @@ impl RuntimeBuilder {
             RuntimeContext {
                 grpc_handler,
                 graphql_handler,
+                rest_handler,
                 metrics_handler,
                 runtime_handler,
             },
@@ -194,6 +340,7 @@ impl RuntimeBuilder {
 pub struct RuntimeContext {
     grpc_handler: tokio::task::JoinHandle<Result<(), tonic::transport::Error>>,
     graphql_handler: tokio::task::JoinHandle<Result<(), hyper::Error>>,
+    rest_handler: tokio::task::JoinHandle<Result<(), hyper::Error>>,
     metrics_handler: tokio::task::JoinHandle<Result<(), hyper::Error>>,
     runtime_handler: tokio::task::JoinHandle<()>,
 }
@@ -203,6 +350,7 @@ impl Drop for RuntimeContext {
         tracing::warn!("Dropping RuntimeContext");
         self.grpc_handler.abort();
         self.graphql_handler.abort();
+        self.rest_handler.abort();
         self.metrics_handler.abort();
         self.runtime_handler.abort();
     }
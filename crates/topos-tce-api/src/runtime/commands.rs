@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use tokio::sync::{mpsc::Sender, oneshot};
 use topos_core::api::grpc::checkpoints::TargetStreamPosition;
-use topos_core::uci::{Certificate, SubnetId};
+use topos_core::api::grpc::tce::v1::BroadcastState;
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
+use topos_p2p::PeerId;
 use topos_tce_storage::types::PendingResult;
 use uuid::Uuid;
 
@@ -36,7 +38,11 @@ pub(crate) enum InternalRuntimeCommand {
     },
 
     /// Notify that a Stream has successfully handshake with the server
-    Handshaked { stream_id: Uuid },
+    /// `flow_controlled` is set when the stream opted into a flow control window
+    Handshaked {
+        stream_id: Uuid,
+        flow_controlled: bool,
+    },
 
     /// Dispatch when a certificate has been submitted to the TCE.
     /// This command will be used to trigger the DoubleEcho process.
@@ -55,4 +61,19 @@ pub(crate) enum InternalRuntimeCommand {
     NewTransientStream {
         sender: oneshot::Sender<Result<TransientStream, RuntimeError>>,
     },
+
+    /// Synchronize the delivered certificates with the checkpoints of the given peer
+    ResyncFromPeer {
+        peer_id: PeerId,
+        sender: oneshot::Sender<Result<usize, RuntimeError>>,
+    },
+
+    /// Get the state of the broadcast of a certificate, `None` if it isn't being broadcast
+    GetBroadcastState {
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Result<Option<BroadcastState>, RuntimeError>>,
+    },
+
+    /// Stop the broadcast of certificates evicted from the pending pool
+    EvictCertificates { certificate_ids: Vec<CertificateId> },
 }
@@ -1,5 +1,6 @@
 use thiserror::Error;
-use topos_core::uci::SubnetId;
+use topos_core::uci::{CertificateId, SubnetId};
+use topos_p2p::PeerId;
 use topos_tce_storage::errors::StorageError;
 use uuid::Uuid;
 
@@ -14,6 +15,15 @@ pub enum RuntimeError {
     #[error("Unknown subnet with subnet id {0}")]
     UnknownSubnet(SubnetId),
 
+    #[error("Unable to synchronize from peer {0}: {1}")]
+    UnableToResync(PeerId, String),
+
+    #[error("Unable to get the broadcast state of certificate {0}: {1}")]
+    UnableToGetBroadcastState(CertificateId, String),
+
+    #[error("The node is not synced with its peers ({0})")]
+    NotSynced(&'static str),
+
     #[error("Unexpected store error: {0}")]
     Store(#[from] StorageError),
 }
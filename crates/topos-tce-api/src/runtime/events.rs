@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use tokio::sync::oneshot;
-use topos_core::uci::{Certificate, SubnetId};
+use topos_core::api::grpc::tce::v1::BroadcastState;
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
+use topos_p2p::PeerId;
 use topos_tce_storage::types::PendingResult;
 
 use super::error::RuntimeError;
@@ -23,4 +25,18 @@ pub enum RuntimeEvent {
         sender:
             oneshot::Sender<Result<HashMap<SubnetId, Option<(Certificate, u64)>>, RuntimeError>>,
     },
+
+    ResyncFromPeer {
+        peer_id: PeerId,
+        sender: oneshot::Sender<Result<usize, RuntimeError>>,
+    },
+
+    GetBroadcastState {
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Result<Option<BroadcastState>, RuntimeError>>,
+    },
+
+    EvictCertificates {
+        certificate_ids: Vec<CertificateId>,
+    },
 }
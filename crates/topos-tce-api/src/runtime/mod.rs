@@ -7,7 +7,7 @@ use std::{
     time::Duration,
 };
 use tokio::{
-    sync::mpsc::{self, Receiver, Sender},
+    sync::mpsc::{self, error::TrySendError, Receiver, Sender},
     sync::{broadcast, oneshot},
 };
 use tokio_util::sync::CancellationToken;
@@ -17,7 +17,7 @@ use topos_core::api::grpc::tce::v1::api_service_server::ApiServiceServer;
 use topos_core::uci::{Certificate, SubnetId};
 use topos_tce_storage::{types::CertificateDeliveredWithPositions, StorageClient};
 
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::{
@@ -65,6 +65,8 @@ pub struct Runtime {
     pub(crate) active_streams: HashMap<Uuid, Sender<StreamCommand>>,
     /// Streams that are currently in negotiation
     pub(crate) pending_streams: HashMap<Uuid, Sender<StreamCommand>>,
+    /// Active streams that opted into a flow control window
+    pub(crate) flow_controlled_streams: HashSet<Uuid>,
     /// Mapping between a subnet_id and streams that are subscribed to it
     pub(crate) subnet_subscriptions: HashMap<SubnetId, HashSet<Uuid>>,
     /// Receiver for Internal API command
@@ -157,6 +159,7 @@ impl Runtime {
 
                 self.active_streams.remove(&stream_id);
                 self.pending_streams.remove(&stream_id);
+                self.flow_controlled_streams.remove(&stream_id);
             }
             Err(StreamError { stream_id, kind }) => match kind {
                 StreamErrorKind::HandshakeFailed(_)
@@ -170,6 +173,7 @@ impl Runtime {
 
                     self.active_streams.remove(&stream_id);
                     self.pending_streams.remove(&stream_id);
+                    self.flow_controlled_streams.remove(&stream_id);
                 }
             },
         }
@@ -204,34 +208,65 @@ impl Runtime {
                     });
                 }
 
+                // A stream subscribed to several targets of the certificate receives it once,
+                // along with its position on each of these targets
+                let mut stream_positions: HashMap<Uuid, Vec<TargetStreamPosition>> = HashMap::new();
                 for target_subnet_id in target_subnets {
                     let target_subnet_id = *target_subnet_id;
                     let target_position = positions.remove(&target_subnet_id);
                     if let Some(stream_list) = self.subnet_subscriptions.get(&target_subnet_id) {
-                        let uuids: Vec<&Uuid> = stream_list.iter().collect();
-                        for uuid in uuids {
-                            if let Some(sender) = self.active_streams.get(uuid) {
-                                let sender = sender.clone();
-                                let certificate = certificate.clone();
-                                info!("Sending certificate to {uuid}");
-                                if let Some(target_position) = target_position.clone() {
-                                    if let Err(error) = sender
-                                        .send(StreamCommand::PushCertificate {
-                                            certificate,
-                                            positions: vec![target_position],
-                                        })
-                                        .await
-                                    {
-                                        error!(%error, "Can't push certificate because the receiver is dropped");
-                                    }
-                                } else {
+                        let Some(target_position) = target_position else {
+                            error!(
+                                "Invalid target stream position for cert id {}, target subnet id \
+                                 {target_subnet_id}, dispatch failed",
+                                &certificate.id
+                            );
+
+                            continue;
+                        };
+
+                        for uuid in stream_list {
+                            if self.active_streams.contains_key(uuid) {
+                                stream_positions
+                                    .entry(*uuid)
+                                    .or_default()
+                                    .push(target_position.clone());
+                            }
+                        }
+                    }
+                }
+
+                // A stream waiting for acknowledgements stops receiving its commands, the
+                // dispatch doesn't wait for it to not hold back the other streams. A flow
+                // controlled stream lagging a full channel behind is released, its client
+                // resumes from its checkpoint once the stream is closed. Streams without a
+                // window keep being waited for, they have no way to resume otherwise.
+                for (uuid, positions) in stream_positions {
+                    if let Some(sender) = self.active_streams.get(&uuid) {
+                        info!("Sending certificate to {uuid}");
+                        match sender.try_send(StreamCommand::PushCertificate {
+                            certificate: certificate.clone(),
+                            positions,
+                        }) {
+                            Ok(()) => {}
+                            Err(TrySendError::Full(command))
+                                if !self.flow_controlled_streams.contains(&uuid) =>
+                            {
+                                if sender.send(command).await.is_err() {
                                     error!(
-                                        "Invalid target stream position for cert id {}, target \
-                                         subnet id {target_subnet_id}, dispatch failed",
-                                        &certificate.id
+                                        "Can't push certificate because the receiver is dropped"
                                     );
                                 }
                             }
+                            Err(TrySendError::Full(_)) => {
+                                warn!("Stream {uuid} is lagging behind, releasing it");
+
+                                self.active_streams.remove(&uuid);
+                                self.flow_controlled_streams.remove(&uuid);
+                            }
+                            Err(TrySendError::Closed(_)) => {
+                                error!("Can't push certificate because the receiver is dropped");
+                            }
                         }
                     }
                 }
@@ -283,9 +318,15 @@ impl Runtime {
                 self.streams.push(Box::pin(stream.run()));
             }
 
-            InternalRuntimeCommand::Handshaked { stream_id } => {
+            InternalRuntimeCommand::Handshaked {
+                stream_id,
+                flow_controlled,
+            } => {
                 if let Some(sender) = self.pending_streams.remove(&stream_id) {
                     self.active_streams.insert(stream_id, sender);
+                    if flow_controlled {
+                        self.flow_controlled_streams.insert(stream_id);
+                    }
                     info!("Stream {stream_id} has successfully handshake");
                 }
             }
@@ -383,6 +424,44 @@ impl Runtime {
                     );
                 }
             }
+
+            InternalRuntimeCommand::ResyncFromPeer { peer_id, sender } => {
+                info!("Synchronization from peer {peer_id} has been requested");
+
+                if let Err(error) = self
+                    .api_event_sender
+                    .send(RuntimeEvent::ResyncFromPeer { peer_id, sender })
+                    .await
+                {
+                    error!(%error, "Can't request synchronization, receiver is dropped");
+                }
+            }
+
+            InternalRuntimeCommand::GetBroadcastState {
+                certificate_id,
+                sender,
+            } => {
+                if let Err(error) = self
+                    .api_event_sender
+                    .send(RuntimeEvent::GetBroadcastState {
+                        certificate_id,
+                        sender,
+                    })
+                    .await
+                {
+                    error!(%error, "Can't request broadcast state, receiver is dropped");
+                }
+            }
+
+            InternalRuntimeCommand::EvictCertificates { certificate_ids } => {
+                if let Err(error) = self
+                    .api_event_sender
+                    .send(RuntimeEvent::EvictCertificates { certificate_ids })
+                    .await
+                {
+                    error!(%error, "Can't stop the broadcast of evicted certificates, receiver is dropped");
+                }
+            }
         }
     }
 }
@@ -11,7 +11,10 @@ use topos_api::grpc::checkpoints::TargetStreamPosition;
 use topos_core::types::stream::CertificateTargetStreamPosition;
 use topos_core::types::CertificateDelivered;
 use topos_core::uci::SubnetId;
-use topos_tce_storage::{FetchCertificatesFilter, FetchCertificatesPosition, StorageClient};
+use topos_tce_storage::{
+    errors::{PruningError, StorageError},
+    FetchCertificatesFilter, FetchCertificatesPosition, StorageClient,
+};
 use tracing::{debug, error, info};
 use uuid::Uuid;
 
@@ -41,6 +44,11 @@ pub(crate) enum SyncTaskError {
     },
     /// Invalid certificate position was being fetched
     InvalidCertificatePosition,
+    /// The certificates to push to the stream have been pruned from the storage
+    Pruned {
+        #[allow(dead_code)]
+        error: PruningError,
+    },
 }
 
 /// When registering a stream, a [`SyncTask`] is started to fetch certificates from the storage
@@ -134,7 +142,7 @@ impl IntoFuture for SyncTask {
                         self.status = SyncTaskStatus::Cancelled;
                         return (self.stream_id, self.status);
                     }
-                    if let Ok(certificates_with_positions) = self
+                    match self
                         .storage
                         .fetch_certificates(FetchCertificatesFilter::Target {
                             target_stream_position: CertificateTargetStreamPosition {
@@ -146,7 +154,16 @@ impl IntoFuture for SyncTask {
                         })
                         .await
                     {
-                        collector.extend(certificates_with_positions)
+                        Ok(certificates_with_positions) => {
+                            collector.extend(certificates_with_positions)
+                        }
+                        Err(StorageError::Pruned(error)) => {
+                            error!("Unable to sync stream {}: {}", self.stream_id, error);
+                            self.status =
+                                SyncTaskStatus::Error(Box::new(SyncTaskError::Pruned { error }));
+                            return (self.stream_id, self.status);
+                        }
+                        Err(_) => {}
                     }
                 }
             }
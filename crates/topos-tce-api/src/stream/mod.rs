@@ -1,6 +1,10 @@
 use futures::{stream::BoxStream, StreamExt, TryStreamExt};
 use std::sync::Arc;
-use std::{collections::HashMap, fmt::Debug, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    time::Duration,
+};
 use tokio::{
     sync::{
         mpsc::{self, Receiver, Sender},
@@ -22,7 +26,7 @@ mod tests;
 
 use crate::{
     grpc::messaging::{
-        CertificatePushed, InboundMessage, OpenStream, OutboundMessage, StreamOpened,
+        Acknowledge, CertificatePushed, InboundMessage, OpenStream, OutboundMessage, StreamOpened,
     },
     runtime::InternalRuntimeCommand,
     RuntimeError,
@@ -74,6 +78,17 @@ pub struct Stream {
     /// Mapping for each target subnet to the set of position per source subnet
     pub(crate) target_subnet_listeners: HashMap<SubnetId, HashMap<SubnetId, TargetStreamPosition>>,
 
+    /// Source subnets whose certificates are pushed, every source if empty
+    pub(crate) source_subnet_ids: HashSet<SubnetId>,
+    /// Strip the proof and the signature of the pushed certificates
+    pub(crate) headers_only: bool,
+    /// Maximum number of certificates pushed without being acknowledged,
+    /// the stream isn't flow controlled if `None`
+    pub(crate) window_size: Option<u32>,
+    /// Number of certificates that can be pushed before waiting for an acknowledgement,
+    /// no command is received while the stream runs out of credits
+    pub(crate) credits: u32,
+
     pub(crate) command_receiver: Receiver<StreamCommand>,
     pub(crate) internal_runtime_command_sender: Sender<InternalRuntimeCommand>,
 
@@ -89,6 +104,10 @@ impl Debug for Stream {
         f.debug_struct("Stream")
             .field("stream_id", &self.stream_id)
             .field("target_subnet_listeners", &self.target_subnet_listeners)
+            .field("source_subnet_ids", &self.source_subnet_ids)
+            .field("headers_only", &self.headers_only)
+            .field("window_size", &self.window_size)
+            .field("credits", &self.credits)
             .finish()
     }
 }
@@ -104,6 +123,10 @@ impl Stream {
         Self {
             stream_id,
             target_subnet_listeners: HashMap::new(),
+            source_subnet_ids: HashSet::new(),
+            headers_only: false,
+            window_size: None,
+            credits: 0,
             command_receiver,
             outbound_stream,
             inbound_stream,
@@ -114,12 +137,25 @@ impl Stream {
     pub async fn run(mut self) -> Result<Uuid, StreamError> {
         // Prestart is the phase that waits for a particular message to being able to process the
         // handshake. For now we do not have authentication nor authorization.
-        let (request_id, checkpoint) = self.pre_start().await?;
+        let (
+            request_id,
+            OpenStream {
+                target_checkpoint,
+                source_subnet_ids,
+                headers_only,
+                window_size,
+            },
+        ) = self.pre_start().await?;
+
+        self.source_subnet_ids = source_subnet_ids;
+        self.headers_only = headers_only;
+        self.window_size = window_size;
+        self.credits = window_size.unwrap_or_default();
 
         // The handshake is preparing the stream to broadcast certificates to the client.
         // Notifying the manager about the subscriptions and defining everything related to
         // the stream management.
-        self.handshake(checkpoint)
+        self.handshake(target_checkpoint)
             .await
             .map_err(|error| StreamError::new(self.stream_id, StreamErrorKind::from(error)))?;
 
@@ -143,14 +179,24 @@ impl Stream {
 
         loop {
             tokio::select! {
-                Some(command) = self.command_receiver.recv() => {
+                // Leaving the commands in the channel while the window is full applies the
+                // backpressure to the producers instead of buffering in the stream
+                command = self.command_receiver.recv(), if self.has_credits() => {
+                    let Some(command) = command else {
+                        info!("Stream {} is no longer fed by the runtime, closing it", self.stream_id);
+
+                        break
+                    };
+
                     if self.handle_command(command).await? {
                         break
                     }
                 }
 
-                Some(_stream_packet) = self.inbound_stream.next() => {
-
+                Some(stream_packet) = self.inbound_stream.next() => {
+                    if let Ok((_, InboundMessage::Acknowledge(Acknowledge { credits }))) = stream_packet {
+                        self.acknowledge(credits).await?;
+                    }
                 }
 
                 // For graceful shutdown in case streams are closed
@@ -165,62 +211,113 @@ impl Stream {
     async fn handle_command(&mut self, command: StreamCommand) -> Result<bool, StreamError> {
         match command {
             StreamCommand::PushCertificate {
-                certificate,
+                mut certificate,
                 positions,
             } => {
-                let certificate_id = certificate.id;
-                if let Err(error) = self
-                    .outbound_stream
-                    .send(Ok((
-                        None,
-                        OutboundMessage::CertificatePushed(Box::new(CertificatePushed {
-                            certificate,
-                            positions,
-                        })),
-                    )))
-                    .await
+                if !self.source_subnet_ids.is_empty()
+                    && !self
+                        .source_subnet_ids
+                        .contains(&certificate.source_subnet_id)
                 {
-                    error!(%error, "Can't forward WatchCertificatesResponse to stream, channel seems dropped certificate {certificate_id}");
-
-                    return Err(StreamError::new(
-                        self.stream_id,
-                        StreamErrorKind::StreamClosed,
-                    ));
-                } else {
-                    info!(
-                        "Certificate {} sent to gRPC stream {}",
-                        certificate_id, self.stream_id
+                    trace!(
+                        "Certificate {} from {} filtered out of the stream {}",
+                        certificate.id,
+                        certificate.source_subnet_id,
+                        self.stream_id
                     );
+
+                    return Ok(false);
+                }
+
+                if self.headers_only {
+                    certificate.proof = Default::default();
+                    certificate.signature = Default::default();
                 }
+
+                self.push_certificate(CertificatePushed {
+                    certificate,
+                    positions,
+                })
+                .await?;
             }
         }
 
         Ok(false)
     }
 
-    async fn pre_start(&mut self) -> Result<(Option<Uuid>, TargetCheckpoint), StreamError> {
+    async fn push_certificate(
+        &mut self,
+        certificate_pushed: CertificatePushed,
+    ) -> Result<(), StreamError> {
+        let certificate_id = certificate_pushed.certificate.id;
+        if let Err(error) = self
+            .outbound_stream
+            .send(Ok((
+                None,
+                OutboundMessage::CertificatePushed(Box::new(certificate_pushed)),
+            )))
+            .await
+        {
+            error!(%error, "Can't forward WatchCertificatesResponse to stream, channel seems dropped certificate {certificate_id}");
+
+            return Err(StreamError::new(
+                self.stream_id,
+                StreamErrorKind::StreamClosed,
+            ));
+        }
+
+        info!(
+            "Certificate {} sent to gRPC stream {}",
+            certificate_id, self.stream_id
+        );
+
+        if self.window_size.is_some() {
+            self.credits -= 1;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the stream can push a certificate without waiting for an acknowledgement
+    fn has_credits(&self) -> bool {
+        self.window_size.is_none() || self.credits > 0
+    }
+
+    /// Grant credits to the stream, resuming the reception of certificates
+    async fn acknowledge(&mut self, credits: u32) -> Result<(), StreamError> {
+        let Some(window_size) = self.window_size else {
+            debug!(
+                "Stream {} acknowledged certificates without a flow control window",
+                self.stream_id
+            );
+
+            return Ok(());
+        };
+
+        self.credits = self.credits.saturating_add(credits).min(window_size);
+
+        Ok(())
+    }
+
+    async fn pre_start(&mut self) -> Result<(Option<Uuid>, OpenStream), StreamError> {
         let waiting_for_open_stream = async {
-            if let Ok(Some((
-                request_id,
-                InboundMessage::OpenStream(OpenStream {
-                    target_checkpoint, ..
-                }),
-            ))) = self.inbound_stream.try_next().await
+            if let Ok(Some((request_id, InboundMessage::OpenStream(open_stream)))) =
+                self.inbound_stream.try_next().await
             {
-                Ok((request_id, target_checkpoint))
+                Ok((request_id, open_stream))
             } else {
                 Err(())
             }
         };
 
         match timeout(Duration::from_millis(100), waiting_for_open_stream).await {
-            Ok(Ok(checkpoint)) => {
+            Ok(Ok(open_stream)) => {
                 info!(
                     "Received an OpenStream command for the stream {}",
                     self.stream_id
                 );
 
-                Ok(checkpoint)
+                Ok(open_stream)
             }
             Ok(Err(_)) => {
                 if let Err(error) = self
@@ -262,6 +359,7 @@ impl Stream {
         self.internal_runtime_command_sender
             .send(InternalRuntimeCommand::Handshaked {
                 stream_id: self.stream_id,
+                flow_controlled: self.window_size.is_some(),
             })
             .await
             .map_err(Box::new)?;
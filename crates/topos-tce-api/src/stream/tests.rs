@@ -3,7 +3,9 @@ use std::time::Duration;
 use tokio::sync::{mpsc, oneshot};
 use tokio_stream::StreamExt;
 use topos_core::uci::{Certificate, SUBNET_ID_LENGTH};
-use topos_test_sdk::constants::{PREV_CERTIFICATE_ID, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1};
+use topos_test_sdk::constants::{
+    PREV_CERTIFICATE_ID, SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1,
+};
 use uuid::Uuid;
 
 use self::utils::StreamBuilder;
@@ -16,6 +18,7 @@ use test_log::test;
 use tokio::spawn;
 use topos_core::api::grpc::shared::v1::checkpoints::TargetCheckpoint;
 use topos_core::api::grpc::shared::v1::positions::TargetStreamPosition;
+use topos_core::api::grpc::tce::v1::watch_certificates_request::Acknowledge as GrpcAcknowledge;
 use topos_core::api::grpc::tce::v1::watch_certificates_request::OpenStream as GrpcOpenStream;
 use topos_core::api::grpc::tce::v1::WatchCertificatesRequest;
 
@@ -58,6 +61,7 @@ pub async fn sending_open_stream_message() -> Result<(), Box<dyn std::error::Err
             positions: Vec::new(),
         }),
         source_checkpoint: None,
+        ..Default::default()
     }
     .into();
 
@@ -93,6 +97,7 @@ async fn subscribing_to_one_target_with_position() -> Result<(), Box<dyn std::er
             }],
         }),
         source_checkpoint: None,
+        ..Default::default()
     }
     .into();
 
@@ -136,6 +141,7 @@ async fn receive_expected_certificate_from_zero() -> Result<(), Box<dyn std::err
             positions: vec![],
         }),
         source_checkpoint: None,
+        ..Default::default()
     }
     .into();
 
@@ -192,6 +198,263 @@ async fn receive_expected_certificate_from_zero() -> Result<(), Box<dyn std::err
     Ok(())
 }
 
+#[rstest]
+#[timeout(Duration::from_millis(100))]
+#[test(tokio::test)]
+async fn receive_headers_of_filtered_source_subnets() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let filtered_out = Certificate::new_with_default_fields(
+        PREV_CERTIFICATE_ID,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+    let mut expected_certificate = Certificate::new_with_default_fields(
+        PREV_CERTIFICATE_ID,
+        SOURCE_SUBNET_ID_2,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+    expected_certificate.proof = vec![1; 32];
+    expected_certificate.signature = vec![2; 32];
+
+    let join = spawn(stream.run());
+
+    let msg: WatchCertificatesRequest = GrpcOpenStream {
+        target_checkpoint: Some(TargetCheckpoint {
+            target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
+            positions: vec![],
+        }),
+        source_checkpoint: None,
+        source_subnet_ids: vec![SOURCE_SUBNET_ID_2.into()],
+        headers_only: true,
+        window_size: None,
+    }
+    .into();
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let expected_stream_id = context.stream_id;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Register { stream_id, sender, .. } if stream_id == expected_stream_id => {
+            sender.send(Ok(()))
+        }
+    );
+
+    wait_for_command!(
+        context.stream_receiver,
+        matches: Ok((_, OutboundMessage::StreamOpened(_)))
+    );
+
+    for certificate in [&filtered_out, &expected_certificate] {
+        context
+            .command_sender
+            .send(crate::stream::StreamCommand::PushCertificate {
+                certificate: certificate.clone(),
+                positions: vec![topos_core::api::grpc::checkpoints::TargetStreamPosition {
+                    position: 0,
+                    certificate_id: Some(certificate.id),
+                    target_subnet_id: TARGET_SUBNET_ID_1,
+                    source_subnet_id: certificate.source_subnet_id,
+                }],
+            })
+            .await
+            .expect("Unable to send certificate during test");
+    }
+
+    let msg = context.stream_receiver.recv().await;
+    assert!(
+        matches!(
+            msg,
+            Some(Ok((_, OutboundMessage::CertificatePushed(ref certificate_pushed))))
+                if certificate_pushed.certificate.id == expected_certificate.id
+                && certificate_pushed.certificate.proof.is_empty()
+                && certificate_pushed.certificate.signature.is_empty()
+        ),
+        "Expected CertificatePushed with the headers of {}, received: {:?}",
+        expected_certificate.id,
+        msg
+    );
+
+    join.abort();
+    Ok(())
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(200))]
+#[test(tokio::test)]
+async fn wait_for_acknowledgement_when_window_is_full() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let first = Certificate::new_with_default_fields(
+        PREV_CERTIFICATE_ID,
+        SOURCE_SUBNET_ID_2,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+    let second =
+        Certificate::new_with_default_fields(first.id, SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_1])
+            .unwrap();
+
+    let join = spawn(stream.run());
+
+    let msg: WatchCertificatesRequest = GrpcOpenStream {
+        target_checkpoint: Some(TargetCheckpoint {
+            target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
+            positions: vec![],
+        }),
+        source_checkpoint: None,
+        source_subnet_ids: vec![],
+        headers_only: false,
+        window_size: Some(1),
+    }
+    .into();
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let expected_stream_id = context.stream_id;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Register { stream_id, sender, .. } if stream_id == expected_stream_id => {
+            sender.send(Ok(()))
+        }
+    );
+
+    wait_for_command!(
+        context.stream_receiver,
+        matches: Ok((_, OutboundMessage::StreamOpened(_)))
+    );
+
+    for (index, certificate) in [&first, &second].into_iter().enumerate() {
+        context
+            .command_sender
+            .send(crate::stream::StreamCommand::PushCertificate {
+                certificate: certificate.clone(),
+                positions: vec![topos_core::api::grpc::checkpoints::TargetStreamPosition {
+                    position: index as u64,
+                    certificate_id: Some(certificate.id),
+                    target_subnet_id: TARGET_SUBNET_ID_1,
+                    source_subnet_id: certificate.source_subnet_id,
+                }],
+            })
+            .await
+            .expect("Unable to send certificate during test");
+    }
+
+    wait_for_command!(
+        context.stream_receiver,
+        matches: Ok((_, OutboundMessage::CertificatePushed(certificate_pushed))) if certificate_pushed.certificate.id == first.id
+    );
+
+    // The window is full, the second certificate waits for an acknowledgement
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(context.stream_receiver.try_recv().is_err());
+
+    let msg: WatchCertificatesRequest = GrpcAcknowledge { credits: 1 }.into();
+    _ = tx.send_data(encode(&msg)?).await;
+
+    wait_for_command!(
+        context.stream_receiver,
+        matches: Ok((_, OutboundMessage::CertificatePushed(certificate_pushed))) if certificate_pushed.certificate.id == second.id
+    );
+
+    join.abort();
+    Ok(())
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(300))]
+#[test(tokio::test)]
+async fn deliver_more_certificates_than_the_window_after_acknowledgements(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut tx, stream, mut context) = StreamBuilder::default().build();
+
+    let mut certificates = Vec::new();
+    let mut previous = PREV_CERTIFICATE_ID;
+    for _ in 0..5 {
+        let certificate = Certificate::new_with_default_fields(
+            previous,
+            SOURCE_SUBNET_ID_2,
+            &[TARGET_SUBNET_ID_1],
+        )
+        .unwrap();
+        previous = certificate.id;
+        certificates.push(certificate);
+    }
+
+    let join = spawn(stream.run());
+
+    let msg: WatchCertificatesRequest = GrpcOpenStream {
+        target_checkpoint: Some(TargetCheckpoint {
+            target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
+            positions: vec![],
+        }),
+        source_checkpoint: None,
+        source_subnet_ids: vec![],
+        headers_only: false,
+        window_size: Some(2),
+    }
+    .into();
+
+    _ = tx.send_data(encode(&msg)?).await;
+
+    let expected_stream_id = context.stream_id;
+
+    wait_for_command!(
+        context.runtime_receiver,
+        matches: InternalRuntimeCommand::Register { stream_id, sender, .. } if stream_id == expected_stream_id => {
+            sender.send(Ok(()))
+        }
+    );
+
+    wait_for_command!(
+        context.stream_receiver,
+        matches: Ok((_, OutboundMessage::StreamOpened(_)))
+    );
+
+    for (index, certificate) in certificates.iter().enumerate() {
+        context
+            .command_sender
+            .send(crate::stream::StreamCommand::PushCertificate {
+                certificate: certificate.clone(),
+                positions: vec![topos_core::api::grpc::checkpoints::TargetStreamPosition {
+                    position: index as u64,
+                    certificate_id: Some(certificate.id),
+                    target_subnet_id: TARGET_SUBNET_ID_1,
+                    source_subnet_id: certificate.source_subnet_id,
+                }],
+            })
+            .await
+            .expect("Unable to send certificate during test");
+    }
+
+    // The certificates stay in the command channel until they are acknowledged,
+    // the stream isn't closed however far behind its client is
+    for window in certificates.chunks(2) {
+        for certificate in window {
+            wait_for_command!(
+                context.stream_receiver,
+                matches: Ok((_, OutboundMessage::CertificatePushed(certificate_pushed))) if certificate_pushed.certificate.id == certificate.id
+            );
+        }
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(context.stream_receiver.try_recv().is_err());
+
+        let msg: WatchCertificatesRequest = GrpcAcknowledge { credits: 2 }.into();
+        _ = tx.send_data(encode(&msg)?).await;
+    }
+
+    assert!(!join.is_finished());
+
+    join.abort();
+    Ok(())
+}
+
 #[test(tokio::test)]
 #[ignore = "not yet implemented"]
 async fn pausing_all_subscription() {}
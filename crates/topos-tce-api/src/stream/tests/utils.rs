@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use futures::{stream::BoxStream, StreamExt};
 use hyper::body::Sender;
@@ -93,6 +93,10 @@ impl StreamBuilder {
         let testable_stream = Stream {
             stream_id,
             target_subnet_listeners: HashMap::new(),
+            source_subnet_ids: HashSet::new(),
+            headers_only: false,
+            window_size: None,
+            credits: 0,
             outbound_stream: sender,
             inbound_stream: stream,
             internal_runtime_command_sender,
@@ -0,0 +1,253 @@
+use std::{
+    fmt::Debug,
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+};
+
+use axum::extract::connect_info::Connected;
+use futures::future::poll_fn;
+use hyper::server::{
+    accept::{self, Accept},
+    conn::{AddrIncoming, AddrStream},
+};
+use tokio::sync::{mpsc, Semaphore};
+use tokio_rustls::{
+    rustls::{
+        server::AllowAnyAuthenticatedClient, Certificate, PrivateKey, RootCertStore, ServerConfig,
+    },
+    server::TlsStream,
+    TlsAcceptor,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::{Identity, ServerTlsConfig};
+use tracing::debug;
+
+use crate::constants::{MAX_CONCURRENT_TLS_HANDSHAKES, TLS_HANDSHAKE_TIMEOUT};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error("Unable to read {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("Invalid PEM file: {0}")]
+    InvalidPem(io::Error),
+    #[error("No certificate found in the PEM file")]
+    NoCertificate,
+    #[error("No private key found in the PEM file")]
+    NoPrivateKey,
+    #[error("Invalid TLS configuration: {0}")]
+    Rustls(#[from] tokio_rustls::rustls::Error),
+}
+
+/// TLS identity of the public API servers, and CA of their clients if they have to present a
+/// certificate
+#[derive(Clone)]
+pub struct TlsConfig {
+    certificate: Vec<u8>,
+    key: Vec<u8>,
+    client_ca: Option<Vec<u8>>,
+}
+
+impl TlsConfig {
+    /// Loads the PEM encoded certificate chain and private key of the servers, and the CA
+    /// authenticating the clients if set
+    pub fn from_pem_files(
+        certificate: &Path,
+        key: &Path,
+        client_ca: Option<&Path>,
+    ) -> Result<Self, TlsError> {
+        let read = |path: &Path| {
+            std::fs::read(path).map_err(|source| TlsError::Read {
+                path: path.to_path_buf(),
+                source,
+            })
+        };
+
+        let config = Self {
+            certificate: read(certificate)?,
+            key: read(key)?,
+            client_ca: client_ca.map(read).transpose()?,
+        };
+
+        // Reject an invalid configuration when the node starts rather than when serving
+        config.rustls_config()?;
+
+        Ok(config)
+    }
+
+    /// Configuration of the gRPC server
+    pub(crate) fn tonic_config(&self) -> ServerTlsConfig {
+        let config =
+            ServerTlsConfig::new().identity(Identity::from_pem(&self.certificate, &self.key));
+
+        match &self.client_ca {
+            Some(client_ca) => {
+                config.client_ca_root(tonic::transport::Certificate::from_pem(client_ca))
+            }
+            None => config,
+        }
+    }
+
+    /// Acceptor of the TLS connections of the HTTP servers
+    pub(crate) fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(Arc::new(
+            self.rustls_config()
+                .expect("TLS configuration is checked when loaded"),
+        ))
+    }
+
+    fn rustls_config(&self) -> Result<ServerConfig, TlsError> {
+        let certificates = parse_certificates(&self.certificate)?;
+        let key = rustls_pemfile::read_all(&mut self.key.as_slice())
+            .map_err(TlsError::InvalidPem)?
+            .into_iter()
+            .find_map(|item| match item {
+                rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+                _ => None,
+            })
+            .ok_or(TlsError::NoPrivateKey)?;
+
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let mut config = match &self.client_ca {
+            Some(client_ca) => {
+                let mut roots = RootCertStore::empty();
+                for certificate in parse_certificates(client_ca)? {
+                    roots.add(&certificate)?;
+                }
+
+                builder
+                    .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+                    .with_single_cert(certificates, key)?
+            }
+            None => builder
+                .with_no_client_auth()
+                .with_single_cert(certificates, key)?,
+        };
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        Ok(config)
+    }
+}
+
+impl Debug for TlsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never log the private key
+        f.debug_struct("TlsConfig")
+            .field("client_authentication", &self.client_ca.is_some())
+            .finish()
+    }
+}
+
+fn parse_certificates(pem: &[u8]) -> Result<Vec<Certificate>, TlsError> {
+    let certificates: Vec<Certificate> = rustls_pemfile::certs(&mut &pem[..])
+        .map_err(TlsError::InvalidPem)?
+        .into_iter()
+        .map(Certificate)
+        .collect();
+
+    if certificates.is_empty() {
+        return Err(TlsError::NoCertificate);
+    }
+
+    Ok(certificates)
+}
+
+/// Incoming TLS connections of an HTTP server
+///
+/// Connections keep being accepted while handshakes are running, each handshake being done in
+/// its own task so that a slow client can't hold the others back. New connections are dropped
+/// while too many handshakes are running, as are the connections failing their handshake,
+/// without stopping the server.
+pub(crate) fn incoming(
+    addr: SocketAddr,
+    acceptor: TlsAcceptor,
+) -> impl Accept<Conn = TlsStream<AddrStream>, Error = io::Error> {
+    let mut incoming = AddrIncoming::bind(&addr)
+        .unwrap_or_else(|error| panic!("error binding to {addr}: {error}"));
+
+    let (sender, receiver) =
+        mpsc::channel::<io::Result<TlsStream<AddrStream>>>(MAX_CONCURRENT_TLS_HANDSHAKES);
+    let handshakes = Arc::new(Semaphore::new(MAX_CONCURRENT_TLS_HANDSHAKES));
+
+    tokio::spawn(async move {
+        loop {
+            let connection = tokio::select! {
+                connection = poll_fn(|cx| Pin::new(&mut incoming).poll_accept(cx)) => connection,
+                // The server is shut down
+                _ = sender.closed() => break,
+            };
+
+            let connection = match connection {
+                Some(Ok(connection)) => connection,
+                Some(Err(error)) => {
+                    debug!("Unable to accept a connection: {error}");
+                    continue;
+                }
+                None => break,
+            };
+
+            let Ok(permit) = handshakes.clone().try_acquire_owned() else {
+                debug!(
+                    "Too many TLS handshakes running, dropping the connection of {}",
+                    connection.remote_addr()
+                );
+                continue;
+            };
+
+            let acceptor = acceptor.clone();
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                let remote_addr = connection.remote_addr();
+                match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(connection)).await
+                {
+                    Ok(Ok(connection)) => {
+                        _ = sender.send(Ok(connection)).await;
+                    }
+                    Ok(Err(error)) => {
+                        debug!("Unable to accept a TLS connection from {remote_addr}: {error}")
+                    }
+                    Err(_) => debug!("TLS handshake timeout of {remote_addr}"),
+                }
+
+                drop(permit);
+            });
+        }
+    });
+
+    accept::from_stream(ReceiverStream::new(receiver))
+}
+
+/// Connection of the client of an HTTP server, whether it is connected over TLS or not
+#[derive(Debug, Clone)]
+pub(crate) struct ClientConnection {
+    pub(crate) remote_addr: SocketAddr,
+    /// DER encoded certificate presented by the client over mTLS
+    pub(crate) certificate: Option<Arc<Vec<u8>>>,
+}
+
+impl Connected<&AddrStream> for ClientConnection {
+    fn connect_info(target: &AddrStream) -> Self {
+        Self {
+            remote_addr: target.remote_addr(),
+            certificate: None,
+        }
+    }
+}
+
+impl Connected<&TlsStream<AddrStream>> for ClientConnection {
+    fn connect_info(target: &TlsStream<AddrStream>) -> Self {
+        let (stream, session) = target.get_ref();
+
+        Self {
+            remote_addr: stream.remote_addr(),
+            certificate: session
+                .peer_certificates()
+                .and_then(|certificates| certificates.first())
+                .map(|certificate| Arc::new(certificate.0.clone())),
+        }
+    }
+}
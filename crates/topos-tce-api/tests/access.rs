@@ -0,0 +1,384 @@
+use futures::{Stream, StreamExt};
+use rstest::rstest;
+use serde_json::{json, Value};
+use std::{collections::HashSet, net::SocketAddr, str::FromStr, time::Duration};
+use test_log::test;
+use tokio::sync::broadcast;
+use tonic::{transport::channel, Code, Request};
+use topos_core::api::grpc::tce::v1::{
+    admin_service_client::AdminServiceClient, api_service_client::ApiServiceClient, BackupRequest,
+    GetLastPendingCertificatesRequest, SubmitCertificateRequest,
+};
+use topos_core::uci::Certificate;
+use topos_tce_api::{
+    ApiAccess, ApiKeys, RateLimit, Runtime, RuntimeClient, RuntimeContext, RuntimeEvent,
+};
+use topos_tce_storage::{types::PendingResult, StorageClient};
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1},
+    networking::get_available_addr,
+    storage::{create_folder, create_validator_store},
+};
+
+const API_KEY: &str = "secret-api-key";
+
+struct Api {
+    grpc_addr: SocketAddr,
+    graphql_addr: SocketAddr,
+    rest_addr: SocketAddr,
+    metrics_addr: SocketAddr,
+    _client: RuntimeClient,
+    _context: RuntimeContext,
+}
+
+async fn launch_api(api_access: ApiAccess) -> (Api, impl Stream<Item = RuntimeEvent>) {
+    let grpc_addr = get_available_addr();
+    let graphql_addr = get_available_addr();
+    let rest_addr = get_available_addr();
+    let metrics_addr = get_available_addr();
+
+    let store = create_validator_store::default().await;
+    let (_, broadcast_stream) = broadcast::channel(10);
+    let (client, events, context) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(StorageClient::new(store.clone()))
+        .store(store)
+        .serve_grpc_addr(grpc_addr)
+        .serve_graphql_addr(graphql_addr)
+        .serve_rest_addr(rest_addr)
+        .serve_metrics_addr(metrics_addr)
+        .with_api_access(api_access)
+        .build_and_launch()
+        .await;
+
+    // Wait for servers to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    (
+        Api {
+            grpc_addr,
+            graphql_addr,
+            rest_addr,
+            metrics_addr,
+            _client: client,
+            _context: context,
+        },
+        events,
+    )
+}
+
+/// Accept every submitted certificate in the pending pool
+fn accept_submissions(events: impl Stream<Item = RuntimeEvent> + Send + 'static) {
+    tokio::spawn(async move {
+        let mut events = Box::pin(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::CertificateSubmitted { sender, .. } = event {
+                _ = sender.send(Ok(PendingResult::InPending(0)));
+            }
+        }
+    });
+}
+
+fn grpc_client(addr: SocketAddr) -> ApiServiceClient<channel::Channel> {
+    ApiServiceClient::new(
+        channel::Endpoint::from_str(&format!("http://{addr}"))
+            .unwrap()
+            .connect_lazy(),
+    )
+}
+
+fn with_api_key<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("x-api-key", API_KEY.parse().unwrap());
+
+    request
+}
+
+fn certificate_body(certificate: &Certificate) -> Value {
+    json!({
+        "id": certificate.id.to_string(),
+        "prevId": certificate.prev_id.to_string(),
+        "sourceSubnetId": certificate.source_subnet_id.to_string(),
+        "stateRoot": format!("0x{}", hex::encode(certificate.state_root)),
+        "txRootHash": format!("0x{}", hex::encode(certificate.tx_root_hash)),
+        "receiptsRootHash": format!("0x{}", hex::encode(certificate.receipts_root_hash)),
+        "messagesRoot": format!("0x{}", hex::encode(certificate.messages_root)),
+        "targetSubnets": certificate
+            .target_subnets
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        "verifier": certificate.verifier,
+        "proof": format!("0x{}", hex::encode(&certificate.proof)),
+        "signature": format!("0x{}", hex::encode(&certificate.signature)),
+    })
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn public_apis_require_an_api_key() {
+    let (api, _events) = launch_api(ApiAccess {
+        api_keys: Some(ApiKeys::new(HashSet::from([API_KEY.to_string()]))),
+        ..Default::default()
+    })
+    .await;
+
+    let mut client = grpc_client(api.grpc_addr);
+    let status = client
+        .get_last_pending_certificates(GetLastPendingCertificatesRequest::default())
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    assert!(client
+        .get_last_pending_certificates(with_api_key(GetLastPendingCertificatesRequest::default()))
+        .await
+        .is_ok());
+
+    let http = reqwest::Client::new();
+    let query = json!({ "query": "{ __typename }" });
+
+    let response = http
+        .post(format!("http://{}", api.graphql_addr))
+        .json(&query)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let response = http
+        .post(format!("http://{}", api.graphql_addr))
+        .bearer_auth(API_KEY)
+        .json(&query)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    // Health checks stay public
+    let response = http
+        .get(format!("http://{}/health", api.graphql_addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let response = http
+        .get(format!("http://{}/v1/positions", api.rest_addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let response = http
+        .get(format!("http://{}/v1/positions", api.rest_addr))
+        .header("x-api-key", API_KEY)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let response = http
+        .get(format!("http://{}/metrics", api.metrics_addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn submission_rate_limits_are_shared_between_apis() {
+    let (api, events) = launch_api(ApiAccess {
+        submission_rate_limit_per_client: Some(RateLimit::per_minute(2)),
+        submission_rate_limit_per_subnet: Some(RateLimit::per_minute(1)),
+        ..Default::default()
+    })
+    .await;
+    accept_submissions(events);
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+    let other_certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_1], 1);
+    let http = reqwest::Client::new();
+    let mut client = grpc_client(api.grpc_addr);
+
+    let response = http
+        .post(format!("http://{}/v1/certificates", api.rest_addr))
+        .json(&certificate_body(&certificates[0].certificate))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::ACCEPTED);
+
+    // The subnet already submitted its certificate for this minute
+    let status = client
+        .submit_certificate(SubmitCertificateRequest {
+            certificate: Some(certificates[1].certificate.clone().into()),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+
+    // Both submissions count for the client, whichever API they went through
+    let response = http
+        .post(format!("http://{}/v1/certificates", api.rest_addr))
+        .json(&certificate_body(&other_certificates[0].certificate))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+
+    let body: Value = response.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("client"));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn subnet_rate_limit_is_counted_per_client() {
+    const OTHER_API_KEY: &str = "other-secret-api-key";
+
+    let (api, events) = launch_api(ApiAccess {
+        api_keys: Some(ApiKeys::new(HashSet::from([
+            API_KEY.to_string(),
+            OTHER_API_KEY.to_string(),
+        ]))),
+        submission_rate_limit_per_subnet: Some(RateLimit::per_minute(1)),
+        ..Default::default()
+    })
+    .await;
+    accept_submissions(events);
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+    let mut client = grpc_client(api.grpc_addr);
+
+    assert!(client
+        .submit_certificate(with_api_key(SubmitCertificateRequest {
+            certificate: Some(certificates[0].certificate.clone().into()),
+        }))
+        .await
+        .is_ok());
+
+    let status = client
+        .submit_certificate(with_api_key(SubmitCertificateRequest {
+            certificate: Some(certificates[1].certificate.clone().into()),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+
+    // Another client submitting for the same subnet isn't held back by the first one
+    let mut request = Request::new(SubmitCertificateRequest {
+        certificate: Some(certificates[1].certificate.clone().into()),
+    });
+    request
+        .metadata_mut()
+        .insert("x-api-key", OTHER_API_KEY.parse().unwrap());
+    assert!(client.submit_certificate(request).await.is_ok());
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn graphql_rejects_queries_over_the_limits() {
+    let (api, _events) = launch_api(ApiAccess {
+        graphql_max_depth: Some(2),
+        ..Default::default()
+    })
+    .await;
+
+    let query = format!(
+        r#"
+        query {{
+            certificates(
+                fromSourceCheckpoint: {{
+                    sourceSubnetIds: [{{ value: "{SOURCE_SUBNET_ID_1}" }}],
+                    positions: [{{ sourceSubnetId: {{ value: "{SOURCE_SUBNET_ID_1}" }}, position: 0 }}]
+                }},
+                first: 10
+            ) {{
+                id
+                sourceSubnetId {{ value }}
+            }}
+        }}
+        "#
+    );
+
+    let response: Value = reqwest::Client::new()
+        .post(format!("http://{}", api.graphql_addr))
+        .json(&json!({ "query": query }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    let errors = response["errors"].as_array().unwrap();
+    assert!(errors[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("nested too deep"));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(10))]
+#[test(tokio::test)]
+async fn backup_requires_the_admin_token() {
+    let admin_addr = get_available_addr();
+    let store = create_validator_store::default().await;
+    let (_, broadcast_stream) = broadcast::channel(10);
+    let (_client, _events, _context) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(StorageClient::new(store.clone()))
+        .store(store)
+        .serve_grpc_addr(get_available_addr())
+        .serve_graphql_addr(get_available_addr())
+        .serve_rest_addr(get_available_addr())
+        .serve_metrics_addr(get_available_addr())
+        .serve_admin_addr(admin_addr)
+        .with_admin_token(Some("admin-token".to_string()))
+        .build_and_launch()
+        .await;
+
+    // Wait for servers to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut client = AdminServiceClient::new(
+        channel::Endpoint::from_str(&format!("http://{admin_addr}"))
+            .unwrap()
+            .connect_lazy(),
+    );
+    let backup_request = |path: &str| {
+        let mut request = Request::new(BackupRequest {
+            path: path.to_string(),
+        });
+        request
+            .metadata_mut()
+            .insert("authorization", "Bearer admin-token".parse().unwrap());
+
+        request
+    };
+    let path = create_folder("admin_backup")
+        .join("backup")
+        .to_string_lossy()
+        .into_owned();
+
+    let status = client
+        .backup(BackupRequest { path: path.clone() })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let status = client.backup(backup_request("")).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    // The in-memory storage of the test has no checkpoint to back up
+    let status = client.backup(backup_request(&path)).await.unwrap_err();
+    assert_eq!(status.code(), Code::Internal);
+}
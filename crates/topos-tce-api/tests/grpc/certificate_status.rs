@@ -0,0 +1,196 @@
+use futures::StreamExt;
+use rstest::rstest;
+use std::{sync::Arc, time::Duration};
+use test_log::test;
+use topos_api::grpc::tce::v1::{
+    certificate_status::{Pending, PrecedencePool, Status},
+    BroadcastState, BroadcastStatus, CertificateStatus, GetCertificateStatusRequest,
+    WaitForDeliveryRequest,
+};
+use topos_core::uci::CertificateId;
+use topos_tce_api::RuntimeEvent;
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{CERTIFICATE_ID_9, SOURCE_SUBNET_ID_1, TARGET_SUBNET_ID_1},
+    storage::{create_fullnode_store, create_validator_store, storage_client},
+    tce::public_api::{broadcast_stream, create_public_api},
+};
+
+use topos_tce_storage::validator::ValidatorStore;
+
+#[rstest]
+#[test(tokio::test)]
+async fn get_status_of_pending_and_failed_certificates() {
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store: Arc<ValidatorStore> =
+        create_validator_store(vec![], futures::future::ready(fullnode_store.clone())).await;
+
+    let (api_context, events) = create_public_api(
+        storage_client(vec![]),
+        broadcast_stream(),
+        futures::future::ready(validator_store.clone()),
+    )
+    .await;
+    let mut client = api_context.api_client;
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+
+    assert!(validator_store
+        .insert_pending_certificate(&certificates[1].certificate)
+        .unwrap()
+        .is_none());
+
+    let pending_id = validator_store
+        .insert_pending_certificate(&certificates[0].certificate)
+        .unwrap()
+        .unwrap();
+
+    // Only the unknown certificate has a broadcast, which failed
+    let failed_certificate_id: CertificateId = CERTIFICATE_ID_9;
+    tokio::spawn(async move {
+        let mut events = Box::pin(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::GetBroadcastState {
+                certificate_id,
+                sender,
+            } = event
+            {
+                _ = sender.send(Ok((certificate_id == failed_certificate_id).then(|| {
+                    BroadcastState {
+                        certificate_id: Some(certificate_id.into()),
+                        status: BroadcastStatus::Failed.into(),
+                        ..Default::default()
+                    }
+                })));
+            }
+        }
+    });
+
+    let get_status = |certificate_id: CertificateId| {
+        let mut client = client.clone();
+        async move {
+            client
+                .get_certificate_status(GetCertificateStatusRequest {
+                    certificate_id: Some(certificate_id.into()),
+                })
+                .await
+                .unwrap()
+                .into_inner()
+                .status
+                .unwrap()
+                .status
+                .unwrap()
+        }
+    };
+
+    assert_eq!(
+        get_status(certificates[0].certificate.id).await,
+        Status::Pending(Pending { pending_id })
+    );
+    assert_eq!(
+        get_status(certificates[1].certificate.id).await,
+        Status::PrecedencePool(PrecedencePool {
+            prev_certificate_id: Some(certificates[0].certificate.id.into()),
+        })
+    );
+    assert!(matches!(
+        get_status(failed_certificate_id).await,
+        Status::Failed(_)
+    ));
+
+    let status = client
+        .get_certificate_status(GetCertificateStatusRequest {
+            certificate_id: None,
+        })
+        .await;
+    assert_eq!(status.unwrap_err().code(), tonic::Code::InvalidArgument);
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn wait_for_delivery_of_delivered_certificate() {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1);
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store: Arc<ValidatorStore> = create_validator_store(
+        certificates.clone(),
+        futures::future::ready(fullnode_store.clone()),
+    )
+    .await;
+
+    let (api_context, _events) = create_public_api(
+        storage_client(vec![]),
+        broadcast_stream(),
+        futures::future::ready(validator_store.clone()),
+    )
+    .await;
+    let mut client = api_context.api_client;
+
+    let statuses: Vec<CertificateStatus> = client
+        .wait_for_delivery(WaitForDeliveryRequest {
+            certificate_id: Some(certificates[0].certificate.id.into()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|response| response.unwrap().status.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(statuses.len(), 1);
+    assert!(matches!(
+        &statuses[0].status,
+        Some(Status::Delivered(delivered))
+            if delivered.position == 0 && delivered.proof_of_delivery.is_some()
+    ));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(10))]
+#[test(tokio::test)]
+async fn wait_for_delivery_of_unknown_certificate_fails() {
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store: Arc<ValidatorStore> =
+        create_validator_store(vec![], futures::future::ready(fullnode_store.clone())).await;
+
+    let (api_context, events) = create_public_api(
+        storage_client(vec![]),
+        broadcast_stream(),
+        futures::future::ready(validator_store.clone()),
+    )
+    .await;
+    let mut client = api_context.api_client;
+
+    tokio::spawn(async move {
+        let mut events = Box::pin(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::GetBroadcastState { sender, .. } = event {
+                _ = sender.send(Ok(None));
+            }
+        }
+    });
+
+    let responses: Vec<_> = client
+        .wait_for_delivery(WaitForDeliveryRequest {
+            certificate_id: Some(CERTIFICATE_ID_9.into()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .collect()
+        .await;
+
+    assert_eq!(responses.len(), 2);
+    assert!(matches!(
+        responses[0]
+            .as_ref()
+            .unwrap()
+            .status
+            .as_ref()
+            .unwrap()
+            .status,
+        Some(Status::Unknown(_))
+    ));
+    assert_eq!(
+        responses[1].as_ref().unwrap_err().code(),
+        tonic::Code::NotFound
+    );
+}
@@ -1 +1,3 @@
 mod certificate_precedence;
+mod certificate_status;
+mod submit_certificate;
@@ -0,0 +1,100 @@
+use futures::StreamExt;
+use rstest::rstest;
+use std::sync::Arc;
+use test_log::test;
+use topos_api::grpc::tce::v1::{SubmitCertificateRequest, SubmitCertificateResult};
+use topos_core::uci::Certificate;
+use topos_tce_api::RuntimeEvent;
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{CERTIFICATE_ID_9, SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1},
+    storage::{create_fullnode_store, create_validator_store, storage_client},
+    tce::public_api::{broadcast_stream, create_public_api},
+};
+
+use topos_tce_storage::{types::PendingResult, validator::ValidatorStore};
+
+#[rstest]
+#[test(tokio::test)]
+async fn submit_certificates_with_typed_results() {
+    let delivered = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_1], 1);
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store: Arc<ValidatorStore> = create_validator_store(
+        delivered.clone(),
+        futures::future::ready(fullnode_store.clone()),
+    )
+    .await;
+
+    let (api_context, events) = create_public_api(
+        storage_client(vec![]),
+        broadcast_stream(),
+        futures::future::ready(validator_store.clone()),
+    )
+    .await;
+    let client = api_context.api_client;
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+
+    let store = validator_store.clone();
+    tokio::spawn(async move {
+        let mut events = Box::pin(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::CertificateSubmitted {
+                certificate,
+                sender,
+            } = event
+            {
+                _ = sender.send(
+                    store
+                        .insert_pending_certificate(&certificate)
+                        .map(|pending_id| {
+                            pending_id.map_or(PendingResult::AwaitPrecedence, |pending_id| {
+                                PendingResult::InPending(pending_id)
+                            })
+                        })
+                        .map_err(Into::into),
+                );
+            }
+        }
+    });
+
+    let submit = |certificate: Certificate| {
+        let mut client = client.clone();
+        async move {
+            client
+                .submit_certificate(SubmitCertificateRequest {
+                    certificate: Some(certificate.into()),
+                })
+                .await
+                .unwrap()
+                .into_inner()
+        }
+    };
+
+    let response = submit(certificates[1].certificate.clone()).await;
+    assert_eq!(response.result(), SubmitCertificateResult::PrecedencePool);
+    assert_eq!(
+        response.prev_certificate_id,
+        Some(certificates[0].certificate.id.into())
+    );
+
+    let response = submit(certificates[1].certificate.clone()).await;
+    assert_eq!(response.result(), SubmitCertificateResult::Duplicate);
+
+    let response = submit(certificates[0].certificate.clone()).await;
+    assert_eq!(response.result(), SubmitCertificateResult::Accepted);
+    assert_eq!(
+        response.pending_id,
+        validator_store
+            .get_pending_id(&certificates[0].certificate.id)
+            .unwrap()
+    );
+
+    let response = submit(delivered[0].certificate.clone()).await;
+    assert_eq!(response.result(), SubmitCertificateResult::AlreadyDelivered);
+
+    let mut invalid_certificate = certificates[0].certificate.clone();
+    invalid_certificate.id = CERTIFICATE_ID_9;
+    let response = submit(invalid_certificate).await;
+    assert_eq!(response.result(), SubmitCertificateResult::InvalidId);
+    assert!(response.result().is_rejection());
+}
@@ -0,0 +1,227 @@
+use futures::StreamExt;
+use rstest::rstest;
+use serde_json::{json, Value};
+use std::{sync::Arc, time::Duration};
+use test_log::test;
+use topos_core::uci::Certificate;
+use topos_tce_api::{RuntimeError, RuntimeEvent};
+use topos_tce_storage::{types::PendingResult, validator::ValidatorStore};
+use topos_test_sdk::{
+    certificates::create_certificate_chain,
+    constants::{CERTIFICATE_ID_9, SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1},
+    storage::{create_fullnode_store, create_validator_store, storage_client},
+    tce::public_api::{broadcast_stream, create_public_api},
+};
+
+fn certificate_body(certificate: &Certificate) -> Value {
+    json!({
+        "id": certificate.id.to_string(),
+        "prevId": certificate.prev_id.to_string(),
+        "sourceSubnetId": certificate.source_subnet_id.to_string(),
+        "stateRoot": format!("0x{}", hex::encode(certificate.state_root)),
+        "txRootHash": format!("0x{}", hex::encode(certificate.tx_root_hash)),
+        "receiptsRootHash": format!("0x{}", hex::encode(certificate.receipts_root_hash)),
+        "messagesRoot": format!("0x{}", hex::encode(certificate.messages_root)),
+        "targetSubnets": certificate
+            .target_subnets
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        "verifier": certificate.verifier,
+        "proof": format!("0x{}", hex::encode(&certificate.proof)),
+        "signature": format!("0x{}", hex::encode(&certificate.signature)),
+    })
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn query_delivered_certificates_and_positions() {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store: Arc<ValidatorStore> = create_validator_store(
+        certificates.clone(),
+        futures::future::ready(fullnode_store.clone()),
+    )
+    .await;
+
+    let (api_context, _events) = create_public_api(
+        storage_client(vec![]),
+        broadcast_stream(),
+        futures::future::ready(validator_store.clone()),
+    )
+    .await;
+
+    // Wait for server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let endpoint = api_context.rest_endpoint.clone();
+    let head = certificates.last().unwrap();
+
+    let response = reqwest::get(format!(
+        "{endpoint}/v1/certificates/{}",
+        head.certificate.id
+    ))
+    .await
+    .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["certificate"]["id"], head.certificate.id.to_string());
+    assert_eq!(
+        body["position"],
+        *head.proof_of_delivery.delivery_position.position
+    );
+
+    let response = reqwest::get(format!("{endpoint}/v1/certificates/{CERTIFICATE_ID_9}"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let response = reqwest::get(format!("{endpoint}/v1/certificates/0x1234"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    let body: Value = reqwest::get(format!("{endpoint}/v1/positions"))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let position = &body[SOURCE_SUBNET_ID_1.to_string()];
+    assert_eq!(position["certificateId"], head.certificate.id.to_string());
+    assert_eq!(
+        position["position"],
+        *head.proof_of_delivery.delivery_position.position
+    );
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn submit_certificates_through_rest() {
+    let delivered = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_1], 1);
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store: Arc<ValidatorStore> = create_validator_store(
+        delivered.clone(),
+        futures::future::ready(fullnode_store.clone()),
+    )
+    .await;
+
+    let (api_context, events) = create_public_api(
+        storage_client(vec![]),
+        broadcast_stream(),
+        futures::future::ready(validator_store.clone()),
+    )
+    .await;
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+
+    let store = validator_store.clone();
+    tokio::spawn(async move {
+        let mut events = Box::pin(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::CertificateSubmitted {
+                certificate,
+                sender,
+            } = event
+            {
+                _ = sender.send(
+                    store
+                        .insert_pending_certificate(&certificate)
+                        .map(|pending_id| {
+                            pending_id.map_or(PendingResult::AwaitPrecedence, |pending_id| {
+                                PendingResult::InPending(pending_id)
+                            })
+                        })
+                        .map_err(Into::into),
+                );
+            }
+        }
+    });
+
+    // Wait for server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+    let endpoint = api_context.rest_endpoint.clone();
+    let submit = |body: Value| {
+        let request = client
+            .post(format!("{endpoint}/v1/certificates"))
+            .json(&body);
+        async move {
+            let response = request.send().await.unwrap();
+            let status = response.status();
+
+            (status, response.json::<Value>().await.unwrap())
+        }
+    };
+
+    let (status, body) = submit(certificate_body(&certificates[1].certificate)).await;
+    assert_eq!(status, reqwest::StatusCode::ACCEPTED);
+    assert_eq!(body["result"], "precedence_pool");
+    assert_eq!(
+        body["prevCertificateId"],
+        certificates[0].certificate.id.to_string()
+    );
+
+    let (status, body) = submit(certificate_body(&certificates[0].certificate)).await;
+    assert_eq!(status, reqwest::StatusCode::ACCEPTED);
+    assert_eq!(body["result"], "accepted");
+
+    let (status, body) = submit(certificate_body(&delivered[0].certificate)).await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert_eq!(body["result"], "already_delivered");
+
+    let mut invalid_certificate = certificates[0].certificate.clone();
+    invalid_certificate.id = CERTIFICATE_ID_9;
+    let (status, body) = submit(certificate_body(&invalid_certificate)).await;
+    assert_eq!(status, reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["result"], "invalid_id");
+
+    let mut malformed = certificate_body(&certificates[0].certificate);
+    malformed["sourceSubnetId"] = json!("0x12");
+    let (status, body) = submit(malformed).await;
+    assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("subnet id"));
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn submissions_are_unavailable_while_the_node_is_not_synced() {
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store: Arc<ValidatorStore> =
+        create_validator_store(vec![], futures::future::ready(fullnode_store.clone())).await;
+
+    let (api_context, events) = create_public_api(
+        storage_client(vec![]),
+        broadcast_stream(),
+        futures::future::ready(validator_store),
+    )
+    .await;
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1);
+
+    tokio::spawn(async move {
+        let mut events = Box::pin(events);
+        while let Some(event) = events.next().await {
+            if let RuntimeEvent::CertificateSubmitted { sender, .. } = event {
+                _ = sender.send(Err(RuntimeError::NotSynced("SYNC_STATE_SYNCING")));
+            }
+        }
+    });
+
+    // Wait for server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/v1/certificates", api_context.rest_endpoint))
+        .json(&certificate_body(&certificates[0].certificate))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+
+    let body: Value = response.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("not synced"));
+}
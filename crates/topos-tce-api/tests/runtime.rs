@@ -49,7 +49,8 @@ async fn runtime_can_dispatch_a_cert(
                     target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
                     positions: Vec::new()
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                ..Default::default()
             }.into()
         };
 
@@ -129,7 +130,8 @@ async fn can_catchup_with_old_certs(
                     target_subnet_ids: vec![TARGET_SUBNET_ID_1.into()],
                     positions: Vec::new()
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                ..Default::default()
             }.into()
         };
 
@@ -254,7 +256,8 @@ async fn can_catchup_with_old_certs_with_position(
                         }
                     ]
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                ..Default::default()
             }.into()
         };
 
@@ -492,7 +495,8 @@ async fn can_query_graphql_endpoint_for_certificates(
                         }
                     ]
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                ..Default::default()
             }.into()
         };
 
@@ -0,0 +1,269 @@
+use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa};
+use rstest::rstest;
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
+use test_log::test;
+use tokio::sync::broadcast;
+use tonic::transport::{channel, ClientTlsConfig, Identity};
+use topos_core::api::grpc::tce::v1::{
+    api_service_client::ApiServiceClient, GetLastPendingCertificatesRequest,
+};
+use topos_tce_api::{Runtime, RuntimeClient, RuntimeContext, TlsConfig};
+use topos_tce_storage::StorageClient;
+use topos_test_sdk::{networking::get_available_addr, storage::create_validator_store};
+
+/// Certificates signed by a test CA, written as PEM files
+struct TestPki {
+    directory: PathBuf,
+    ca: Certificate,
+}
+
+impl TestPki {
+    fn new(name: &str) -> Self {
+        let directory =
+            std::env::temp_dir().join(format!("topos-tce-api-tls-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let mut params = CertificateParams::new(Vec::new());
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = Certificate::from_params(params).unwrap();
+        std::fs::write(directory.join("ca.pem"), ca.serialize_pem().unwrap()).unwrap();
+
+        Self { directory, ca }
+    }
+
+    fn ca_path(&self) -> PathBuf {
+        self.directory.join("ca.pem")
+    }
+
+    fn ca_pem(&self) -> Vec<u8> {
+        std::fs::read(self.ca_path()).unwrap()
+    }
+
+    /// Issues a certificate for `localhost`, returns the paths of the certificate and its key
+    fn issue(&self, name: &str) -> (PathBuf, PathBuf) {
+        let certificate =
+            Certificate::from_params(CertificateParams::new(vec!["localhost".to_string()]))
+                .unwrap();
+
+        let certificate_path = self.directory.join(format!("{name}.pem"));
+        let key_path = self.directory.join(format!("{name}.key"));
+        std::fs::write(
+            &certificate_path,
+            certificate.serialize_pem_with_signer(&self.ca).unwrap(),
+        )
+        .unwrap();
+        std::fs::write(&key_path, certificate.serialize_private_key_pem()).unwrap();
+
+        (certificate_path, key_path)
+    }
+}
+
+impl Drop for TestPki {
+    fn drop(&mut self) {
+        _ = std::fs::remove_dir_all(&self.directory);
+    }
+}
+
+struct Api {
+    grpc_addr: SocketAddr,
+    graphql_addr: SocketAddr,
+    rest_addr: SocketAddr,
+    _client: RuntimeClient,
+    _context: RuntimeContext,
+}
+
+async fn launch_api(tls: TlsConfig) -> Api {
+    let grpc_addr = get_available_addr();
+    let graphql_addr = get_available_addr();
+    let rest_addr = get_available_addr();
+    let metrics_addr = get_available_addr();
+
+    let store = create_validator_store::default().await;
+    let (_, broadcast_stream) = broadcast::channel(10);
+    let (client, _events, context) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(StorageClient::new(store.clone()))
+        .store(store)
+        .serve_grpc_addr(grpc_addr)
+        .serve_graphql_addr(graphql_addr)
+        .serve_rest_addr(rest_addr)
+        .serve_metrics_addr(metrics_addr)
+        .with_tls(Some(tls))
+        .build_and_launch()
+        .await;
+
+    // Wait for servers to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    Api {
+        grpc_addr,
+        graphql_addr,
+        rest_addr,
+        _client: client,
+        _context: context,
+    }
+}
+
+async fn connect(
+    addr: SocketAddr,
+    tls: Option<ClientTlsConfig>,
+) -> Result<ApiServiceClient<channel::Channel>, tonic::transport::Error> {
+    let endpoint = match tls {
+        Some(tls) => channel::Endpoint::from_shared(format!("https://localhost:{}", addr.port()))?
+            .tls_config(tls.domain_name("localhost"))?,
+        None => channel::Endpoint::from_shared(format!("http://{addr}"))?,
+    };
+
+    Ok(ApiServiceClient::new(endpoint.connect().await?))
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn grpc_api_is_served_over_tls() {
+    let pki = TestPki::new("server");
+    let (certificate, key) = pki.issue("server");
+    let tls = TlsConfig::from_pem_files(&certificate, &key, None).unwrap();
+    let Api { grpc_addr, .. } = launch_api(tls).await;
+
+    let client_tls = ClientTlsConfig::new()
+        .ca_certificate(tonic::transport::Certificate::from_pem(pki.ca_pem()));
+    let mut client = connect(grpc_addr, Some(client_tls)).await.unwrap();
+    assert!(client
+        .get_last_pending_certificates(GetLastPendingCertificatesRequest::default())
+        .await
+        .is_ok());
+
+    // Plaintext clients can't talk to the server anymore
+    let plaintext = match connect(grpc_addr, None).await {
+        Ok(mut client) => client
+            .get_last_pending_certificates(GetLastPendingCertificatesRequest::default())
+            .await
+            .is_ok(),
+        Err(_) => false,
+    };
+    assert!(!plaintext);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn grpc_api_authenticates_clients_with_their_certificate() {
+    let pki = TestPki::new("mtls");
+    let (certificate, key) = pki.issue("server");
+    let (client_certificate, client_key) = pki.issue("client");
+    let tls = TlsConfig::from_pem_files(&certificate, &key, Some(&pki.ca_path())).unwrap();
+    let Api { grpc_addr, .. } = launch_api(tls).await;
+
+    let client_tls = ClientTlsConfig::new()
+        .ca_certificate(tonic::transport::Certificate::from_pem(pki.ca_pem()));
+
+    let anonymous = match connect(grpc_addr, Some(client_tls.clone())).await {
+        Ok(mut client) => client
+            .get_last_pending_certificates(GetLastPendingCertificatesRequest::default())
+            .await
+            .is_ok(),
+        Err(_) => false,
+    };
+    assert!(!anonymous);
+
+    let identity = Identity::from_pem(
+        std::fs::read(client_certificate).unwrap(),
+        std::fs::read(client_key).unwrap(),
+    );
+    let mut client = connect(grpc_addr, Some(client_tls.identity(identity)))
+        .await
+        .unwrap();
+    assert!(client
+        .get_last_pending_certificates(GetLastPendingCertificatesRequest::default())
+        .await
+        .is_ok());
+}
+
+/// HTTP client trusting the test CA, presenting the given identity if set
+fn http_client(pki: &TestPki, identity: Option<(PathBuf, PathBuf)>) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder()
+        .use_rustls_tls()
+        .add_root_certificate(reqwest::Certificate::from_pem(&pki.ca_pem()).unwrap());
+
+    if let Some((certificate, key)) = identity {
+        let mut pem = std::fs::read(key).unwrap();
+        pem.extend(std::fs::read(certificate).unwrap());
+        builder = builder.identity(reqwest::Identity::from_pem(&pem).unwrap());
+    }
+
+    builder.build().unwrap()
+}
+
+/// Whether the GraphQL and REST servers answer the client
+async fn http_apis_answer(client: &reqwest::Client, scheme: &str, api: &Api) -> [bool; 2] {
+    let graphql = client
+        .get(format!(
+            "{scheme}://localhost:{}/health",
+            api.graphql_addr.port()
+        ))
+        .send()
+        .await
+        .is_ok_and(|response| response.status().is_success());
+    let rest = client
+        .get(format!(
+            "{scheme}://localhost:{}/v1/positions",
+            api.rest_addr.port()
+        ))
+        .send()
+        .await
+        .is_ok_and(|response| response.status().is_success());
+
+    [graphql, rest]
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn http_apis_are_served_over_tls() {
+    let pki = TestPki::new("http");
+    let (certificate, key) = pki.issue("server");
+    let tls = TlsConfig::from_pem_files(&certificate, &key, None).unwrap();
+    let api = launch_api(tls).await;
+
+    let client = http_client(&pki, None);
+    assert_eq!(http_apis_answer(&client, "https", &api).await, [true, true]);
+
+    // Plaintext clients can't talk to the servers anymore
+    assert_eq!(
+        http_apis_answer(&reqwest::Client::new(), "http", &api).await,
+        [false, false]
+    );
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(4))]
+#[test(tokio::test)]
+async fn http_apis_authenticate_clients_with_their_certificate() {
+    let pki = TestPki::new("http-mtls");
+    let (certificate, key) = pki.issue("server");
+    let client_identity = pki.issue("client");
+    let tls = TlsConfig::from_pem_files(&certificate, &key, Some(&pki.ca_path())).unwrap();
+    let api = launch_api(tls).await;
+
+    let anonymous = http_client(&pki, None);
+    assert_eq!(
+        http_apis_answer(&anonymous, "https", &api).await,
+        [false, false]
+    );
+
+    let authenticated = http_client(&pki, Some(client_identity));
+    assert_eq!(
+        http_apis_answer(&authenticated, "https", &api).await,
+        [true, true]
+    );
+}
+
+#[test]
+fn invalid_pem_files_are_rejected_when_loaded() {
+    let pki = TestPki::new("invalid");
+    let (certificate, _) = pki.issue("server");
+
+    // The certificate holds no private key
+    assert!(TlsConfig::from_pem_files(&certificate, &certificate, None).is_err());
+}
@@ -1,16 +1,16 @@
 use crate::sampler::SubscriptionsView;
 use std::sync::Arc;
-use std::{collections::HashSet, time};
+use std::{collections::HashMap, time};
 use tce_transport::ProtocolEvents;
 use tokio::sync::mpsc;
 use topos_core::{
     types::{
         stream::{CertificateSourceStreamPosition, Position},
-        CertificateDelivered, ProofOfDelivery, Ready, ValidatorId,
+        CertificateDelivered, ProofOfDelivery, ValidatorId,
     },
-    uci::Certificate,
+    uci::{Certificate, CertificateId},
 };
-use topos_crypto::messages::MessageSigner;
+use topos_crypto::messages::{MessageSigner, Signature};
 use topos_metrics::DOUBLE_ECHO_BROADCAST_FINISHED_TOTAL;
 use tracing::{debug, info, warn};
 mod status;
@@ -29,10 +29,32 @@ pub struct BroadcastState {
     message_signer: Arc<MessageSigner>,
     event_sender: mpsc::Sender<ProtocolEvents>,
     delivery_time: time::Instant,
-    readies: HashSet<Ready>,
+    /// Signed Ready messages received, kept to build the proof of delivery
+    readies: HashMap<ValidatorId, Signature>,
     pub(crate) expected_position: Option<Position>,
 }
 
+/// Progress of the broadcast of a certificate, as exposed to the operators of the node
+#[derive(Debug, Clone)]
+pub struct BroadcastStateSnapshot {
+    pub certificate_id: CertificateId,
+    pub status: Status,
+    /// Whether the broadcast waits for the delivery of the previous certificate to start
+    pub awaiting_previous_certificate: bool,
+    pub network_size: usize,
+    pub echo_threshold: usize,
+    pub ready_threshold: usize,
+    pub delivery_threshold: usize,
+    /// Validators from which no Echo has been received yet
+    pub missing_echoes: Vec<ValidatorId>,
+    /// Validators from which no Ready has been received yet
+    pub missing_readies: Vec<ValidatorId>,
+    /// Position expected for the certificate in its source stream, once the broadcast started
+    pub expected_position: Option<Position>,
+    /// Time elapsed since the broadcast started
+    pub elapsed: time::Duration,
+}
+
 impl BroadcastState {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -57,7 +79,7 @@ impl BroadcastState {
             message_signer,
             event_sender,
             delivery_time: time::Instant::now(),
-            readies: HashSet::new(),
+            readies: HashMap::new(),
             expected_position: None,
         };
 
@@ -92,14 +114,31 @@ impl BroadcastState {
                 readies: self
                     .readies
                     .iter()
-                    .cloned()
-                    .map(|r| (r, "signature".to_string()))
+                    .map(|(validator_id, signature)| {
+                        (validator_id.to_string(), signature.to_string())
+                    })
                     .collect(),
                 threshold: self.delivery_threshold as u64,
             },
         }
     }
 
+    pub fn snapshot(&self) -> BroadcastStateSnapshot {
+        BroadcastStateSnapshot {
+            certificate_id: self.certificate.id,
+            status: self.status,
+            awaiting_previous_certificate: false,
+            network_size: self.subscriptions_view.network_size,
+            echo_threshold: self.echo_threshold,
+            ready_threshold: self.ready_threshold,
+            delivery_threshold: self.delivery_threshold,
+            missing_echoes: self.subscriptions_view.echo.iter().copied().collect(),
+            missing_readies: self.subscriptions_view.ready.iter().copied().collect(),
+            expected_position: self.expected_position,
+            elapsed: self.delivery_time.elapsed(),
+        }
+    }
+
     pub fn apply_echo(&mut self, validator_id: ValidatorId) -> Option<Status> {
         if self.subscriptions_view.echo.remove(&validator_id) {
             self.update_status()
@@ -108,9 +147,13 @@ impl BroadcastState {
         }
     }
 
-    pub fn apply_ready(&mut self, validator_id: ValidatorId) -> Option<Status> {
+    pub fn apply_ready(
+        &mut self,
+        validator_id: ValidatorId,
+        signature: Signature,
+    ) -> Option<Status> {
         if self.subscriptions_view.ready.remove(&validator_id) {
-            self.readies.insert(validator_id.to_string());
+            self.readies.insert(validator_id, signature);
             self.update_status()
         } else {
             None
@@ -15,6 +15,7 @@
 
 use crate::TaskStatus;
 use crate::{DoubleEchoCommand, SubscriptionsView};
+use broadcast_state::BroadcastStateSnapshot;
 use std::collections::HashSet;
 use std::sync::Arc;
 use tce_transport::{ProtocolEvents, ReliableBroadcastParams};
@@ -142,6 +143,10 @@ impl DoubleEcho {
 
                         DoubleEchoCommand::Broadcast { need_gossip, cert } => self.broadcast(cert, need_gossip).await,
 
+                        DoubleEchoCommand::GetBroadcastState { certificate_id, sender } => self.get_broadcast_state(certificate_id, sender).await,
+
+                        DoubleEchoCommand::Evict { certificate_ids } => self.evict(certificate_ids).await,
+
                         command if self.subscriptions.is_some() => {
                             match command {
                                 DoubleEchoCommand::Echo { certificate_id, validator_id, signature } => {
@@ -191,8 +196,16 @@ impl DoubleEcho {
                 }
 
                 Some((certificate_id, status)) = task_completion.recv() => {
-                    if let TaskStatus::Success = status {
-                        self.delivered_certificates.insert(certificate_id);
+                    match status {
+                        TaskStatus::Success => {
+                            self.delivered_certificates.insert(certificate_id);
+                        }
+                        TaskStatus::Failure => {
+                            _ = self
+                                .event_sender
+                                .send(ProtocolEvents::BroadcastFailed { certificate_id })
+                                .await;
+                        }
                     }
                 }
 
@@ -247,6 +260,29 @@ impl DoubleEcho {
         }
     }
 
+    /// Ask the TaskManager for the state of the broadcast of a certificate
+    pub async fn get_broadcast_state(
+        &self,
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Option<BroadcastStateSnapshot>>,
+    ) {
+        _ = self
+            .task_manager_message_sender
+            .send(DoubleEchoCommand::GetBroadcastState {
+                certificate_id,
+                sender,
+            })
+            .await;
+    }
+
+    /// Ask the TaskManager to stop the broadcast of evicted certificates
+    pub async fn evict(&self, certificate_ids: Vec<CertificateId>) {
+        _ = self
+            .task_manager_message_sender
+            .send(DoubleEchoCommand::Evict { certificate_ids })
+            .await;
+    }
+
     /// Build initial delivery state
     async fn delivery_state_for_new_cert(
         &mut self,
@@ -45,6 +45,8 @@ use tokio_stream::wrappers::ReceiverStream;
 use topos_core::types::ValidatorId;
 use topos_core::uci::{Certificate, CertificateId};
 use topos_crypto::messages::{MessageSigner, Signature};
+
+use crate::double_echo::broadcast_state::BroadcastStateSnapshot;
 use topos_metrics::DOUBLE_ECHO_COMMAND_CHANNEL_CAPACITY_TOTAL;
 use topos_tce_storage::types::CertificateDeliveredWithPositions;
 use topos_tce_storage::validator::ValidatorStore;
@@ -81,7 +83,7 @@ pub struct ReliableBroadcastConfig {
     pub message_signer: Arc<MessageSigner>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum DoubleEchoCommand {
     /// Entry point for new certificate to submit as initial sender
     Broadcast {
@@ -102,6 +104,15 @@ pub enum DoubleEchoCommand {
         certificate_id: CertificateId,
         signature: Signature,
     },
+
+    /// Ask for the state of the broadcast of a certificate, `None` if it isn't broadcast
+    GetBroadcastState {
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Option<BroadcastStateSnapshot>>,
+    },
+
+    /// Stop the broadcast of certificates evicted from the pending pool
+    Evict { certificate_ids: Vec<CertificateId> },
 }
 
 /// Thread safe client to the protocol aggregate
@@ -184,6 +195,39 @@ impl ReliableBroadcastClient {
         Ok(())
     }
 
+    /// Returns the state of the ongoing broadcast of a certificate
+    ///
+    /// Returns `Ok(None)` if the certificate isn't being broadcast, either because it is
+    /// unknown or already delivered.
+    pub async fn get_broadcast_state(
+        &self,
+        certificate_id: CertificateId,
+    ) -> Result<Option<BroadcastStateSnapshot>, Errors> {
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(DoubleEchoCommand::GetBroadcastState {
+                certificate_id,
+                sender,
+            })
+            .await
+            .map_err(Box::new)?;
+
+        Ok(receiver.await?)
+    }
+
+    /// Stops the broadcast of certificates evicted from the pending pool
+    pub async fn evict_certificates(
+        &self,
+        certificate_ids: Vec<CertificateId>,
+    ) -> Result<(), Errors> {
+        self.command_sender
+            .send(DoubleEchoCommand::Evict { certificate_ids })
+            .await
+            .map_err(Box::new)?;
+
+        Ok(())
+    }
+
     pub async fn shutdown(&self) -> Result<(), Errors> {
         debug!("Shutting down reliable broadcast client");
         let (double_echo_sender, double_echo_receiver) = oneshot::channel();
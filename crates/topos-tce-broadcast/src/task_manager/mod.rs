@@ -6,7 +6,7 @@ use std::future::IntoFuture;
 use std::pin::Pin;
 use std::sync::Arc;
 use tce_transport::{ProtocolEvents, ReliableBroadcastParams};
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc::error::SendError, oneshot};
 use tokio::{spawn, sync::mpsc};
 use topos_core::types::ValidatorId;
 use topos_core::uci::CertificateId;
@@ -21,7 +21,7 @@ use tracing::warn;
 
 pub mod task;
 
-use crate::double_echo::broadcast_state::BroadcastState;
+use crate::double_echo::broadcast_state::{BroadcastState, BroadcastStateSnapshot};
 use crate::sampler::SubscriptionsView;
 use crate::DoubleEchoCommand;
 use crate::TaskStatus;
@@ -145,15 +145,25 @@ impl TaskManager {
                                 std::collections::hash_map::Entry::Occupied(_) => {},
                             }
                         }
+                        DoubleEchoCommand::GetBroadcastState { certificate_id, sender } => {
+                            self.get_broadcast_state(certificate_id, sender).await;
+                        }
+                        DoubleEchoCommand::Evict { certificate_ids } => {
+                            self.evict(certificate_ids);
+                        }
                     }
                 }
 
 
                 Some((certificate_id, status)) = self.running_tasks.next() => {
-                    if let TaskStatus::Success = status {
-                        self.tasks.remove(&certificate_id);
-                        DOUBLE_ECHO_ACTIVE_TASKS_COUNT.dec();
-                        let _ = self.task_completion_sender.send((certificate_id, status)).await;
+                    // A failed task is forgotten as well, so that its certificate can be broadcast
+                    // again
+                    let delivered = matches!(status, TaskStatus::Success);
+                    self.tasks.remove(&certificate_id);
+                    DOUBLE_ECHO_ACTIVE_TASKS_COUNT.dec();
+                    let _ = self.task_completion_sender.send((certificate_id, status)).await;
+
+                    if delivered {
                         if let Some(task) = self.precedence.remove(&certificate_id) {
                             if let Some(context) = self.tasks.get(&task.certificate_id) {
 
@@ -185,6 +195,55 @@ impl TaskManager {
         }
     }
 
+    /// Answer with the state of a task, which is either waiting for its previous
+    /// certificate or running
+    async fn get_broadcast_state(
+        &self,
+        certificate_id: CertificateId,
+        sender: oneshot::Sender<Option<BroadcastStateSnapshot>>,
+    ) {
+        if let Some(task) = self
+            .precedence
+            .values()
+            .find(|task| task.certificate_id == certificate_id)
+        {
+            let mut snapshot = task.broadcast_state.snapshot();
+            snapshot.awaiting_previous_certificate = true;
+
+            _ = sender.send(Some(snapshot));
+        } else if let Some(task_context) = self.tasks.get(&certificate_id) {
+            // The task answers once the messages received before are processed
+            if let Err(SendError(DoubleEchoCommand::GetBroadcastState { sender, .. })) =
+                task_context
+                    .sink
+                    .send(DoubleEchoCommand::GetBroadcastState {
+                        certificate_id,
+                        sender,
+                    })
+                    .await
+            {
+                _ = sender.send(None);
+            }
+        } else {
+            _ = sender.send(None);
+        }
+    }
+
+    /// Stop the tasks of evicted certificates, the waiting ones are dropped while the running
+    /// ones are shut down and reported as failed on completion
+    fn evict(&mut self, certificate_ids: Vec<CertificateId>) {
+        self.precedence
+            .retain(|_, task| !certificate_ids.contains(&task.certificate_id));
+
+        for certificate_id in certificate_ids {
+            self.buffered_messages.remove(&certificate_id);
+
+            if let Some(task_context) = self.tasks.remove(&certificate_id) {
+                _ = task_context.shutdown_sender.try_send(());
+            }
+        }
+    }
+
     fn start_task(
         running_tasks: &RunningTasks,
         task: Task,
@@ -114,8 +114,8 @@ impl IntoFuture for Task {
 
                                 }
                             }
-                            DoubleEchoCommand::Ready { validator_id, .. } => {
-                                if let Some(Status::DeliveredWithReadySent) = self.broadcast_state.apply_ready(validator_id) {
+                            DoubleEchoCommand::Ready { validator_id, signature, .. } => {
+                                if let Some(Status::DeliveredWithReadySent) = self.broadcast_state.apply_ready(validator_id, signature) {
                                     match self.persist().await {
                                         Ok(delivered) => {
                                             _ = self.broadcast_sender.send(delivered);
@@ -129,6 +129,9 @@ impl IntoFuture for Task {
                                     }
                                 }
                             }
+                            DoubleEchoCommand::GetBroadcastState { sender, .. } => {
+                                _ = sender.send(Some(self.broadcast_state.snapshot()));
+                            }
                             _ => {}
                         }
                     }
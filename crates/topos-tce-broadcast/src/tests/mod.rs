@@ -1,3 +1,4 @@
+use crate::double_echo::broadcast_state::Status;
 use crate::double_echo::*;
 use crate::*;
 use rstest::*;
@@ -254,3 +255,63 @@ async fn trigger_ready_when_reached_enough_ready(#[case] params: TceParams) {
         Some(ProtocolEvents::Ready { .. })
     ));
 }
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(4))]
+async fn expose_the_state_of_a_broadcast() {
+    let params = small_config();
+    let nb_peers = params.nb_peers;
+    let echo_threshold = params.broadcast_params.echo_threshold;
+    let (mut double_echo, mut ctx) = create_context(params).await;
+
+    let dummy_cert =
+        Certificate::new_with_default_fields(PREV_CERTIFICATE_ID, SOURCE_SUBNET_ID_1, &[])
+            .expect("Dummy certificate");
+
+    let (sender, receiver) = oneshot::channel();
+    double_echo.get_broadcast_state(dummy_cert.id, sender).await;
+    assert!(receiver.await.unwrap().is_none());
+
+    double_echo.broadcast(dummy_cert.clone(), true).await;
+    reach_echo_threshold(&mut double_echo, &dummy_cert).await;
+
+    assert!(matches!(
+        ctx.event_receiver.recv().await,
+        Some(ProtocolEvents::Broadcast { certificate_id }) if certificate_id == dummy_cert.id
+    ));
+
+    let (sender, receiver) = oneshot::channel();
+    double_echo.get_broadcast_state(dummy_cert.id, sender).await;
+    let state = receiver.await.unwrap().expect("Broadcast state");
+
+    assert_eq!(state.certificate_id, dummy_cert.id);
+    assert_eq!(state.status, Status::ReadySent);
+    assert!(!state.awaiting_previous_certificate);
+    assert_eq!(state.network_size, nb_peers);
+    assert_eq!(state.missing_echoes.len(), nb_peers - echo_threshold);
+    assert_eq!(state.missing_readies.len(), nb_peers);
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(4))]
+async fn stop_the_broadcast_of_an_evicted_certificate() {
+    let (mut double_echo, _ctx) = create_context(small_config()).await;
+
+    let dummy_cert =
+        Certificate::new_with_default_fields(PREV_CERTIFICATE_ID, SOURCE_SUBNET_ID_1, &[])
+            .expect("Dummy certificate");
+
+    double_echo.broadcast(dummy_cert.clone(), true).await;
+
+    let (sender, receiver) = oneshot::channel();
+    double_echo.get_broadcast_state(dummy_cert.id, sender).await;
+    assert!(receiver.await.unwrap().is_some());
+
+    double_echo.evict(vec![dummy_cert.id]).await;
+
+    let (sender, receiver) = oneshot::channel();
+    double_echo.get_broadcast_state(dummy_cert.id, sender).await;
+    assert!(receiver.await.unwrap().is_none());
+}
@@ -1,4 +1,4 @@
-use crate::{Error, TceProxyEvent};
+use crate::{ApiKey, Error, TceClientTls, TceProxyEvent};
 use base64ct::{Base64, Encoding};
 use futures::stream::FuturesUnordered;
 use opentelemetry::trace::FutureExt;
@@ -144,6 +144,8 @@ pub struct TceClientBuilder {
     tce_endpoint: Option<String>,
     subnet_id: Option<SubnetId>,
     tce_proxy_event_sender: Option<mpsc::Sender<TceProxyEvent>>,
+    tls: TceClientTls,
+    api_key: ApiKey,
 }
 
 impl TceClientBuilder {
@@ -152,6 +154,18 @@ impl TceClientBuilder {
         self
     }
 
+    /// Sets the TLS settings used if the endpoint is `https`
+    pub fn set_tls(mut self, tls: TceClientTls) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Sets the API key presented to the TCE node
+    pub fn set_api_key(mut self, api_key: ApiKey) -> Self {
+        self.api_key = api_key;
+        self
+    }
+
     pub fn set_subnet_id(mut self, subnet_id: SubnetId) -> Self {
         self.subnet_id = Some(subnet_id);
         self
@@ -185,17 +199,22 @@ impl TceClientBuilder {
             .ok_or(Error::InvalidTceEndpoint)?
             .clone();
         // Connect to tce node service using backoff strategy
-        let mut tce_grpc_client =
-            match crate::connect_to_tce_service_with_retry(tce_endpoint.clone()).await {
-                Ok(client) => {
-                    info!("Connected to the TCE service at {}", &tce_endpoint);
-                    client
-                }
-                Err(e) => {
-                    error!("Unable to connect to tce client: {}", e);
-                    return Err(e);
-                }
-            };
+        let mut tce_grpc_client = match crate::connect_to_tce_service_with_retry(
+            tce_endpoint.clone(),
+            &self.tls,
+            &self.api_key,
+        )
+        .await
+        {
+            Ok(client) => {
+                info!("Connected to the TCE service at {}", &tce_endpoint);
+                client
+            }
+            Err(e) => {
+                error!("Unable to connect to tce client: {}", e);
+                return Err(e);
+            }
+        };
 
         // Channel used to initiate watch_certificates_request::Command that will be sent to the TCE through stream
         let (outbound_stream_command_sender, mut outbound_stream_command_receiver) =
@@ -327,6 +346,8 @@ impl TceClientBuilder {
             .ok_or(Error::InvalidTceEndpoint)?
             .clone();
 
+        let submission_event_sender = self.tce_proxy_event_sender.clone();
+
         tokio::spawn(async move {
             let mut certificate_to_send = FuturesUnordered::new();
             info!(
@@ -335,8 +356,17 @@ impl TceClientBuilder {
             );
             loop {
                 tokio::select! {
-                    Some(_) = certificate_to_send.next() => {
-                        continue;
+                    Some((certificate_id, result)) = certificate_to_send.next() => {
+                        // Notify the application of the outcome of the submission
+                        if let Some(sender) = &submission_event_sender {
+                            let event = match result {
+                                Ok(_) => TceProxyEvent::CertificateSubmitted { certificate_id },
+                                Err(_) => TceProxyEvent::CertificateSubmissionFailed { certificate_id },
+                            };
+                            if let Err(e) = sender.send(event).await {
+                                error!("Unable to send certificate submission event: {e}");
+                            }
+                        }
                     }
                     Some(sender) = shutdown.recv() => {
                         info!("Shutdown tce proxy command received...");
@@ -378,22 +408,35 @@ impl TceClientBuilder {
                                         .with_context(context_backoff.clone())
                                         .instrument(Span::current())
                                         .await
-                                        .map(|_response| {
-                                            info!("Successfully submitted the Certificate {} (previous: {}) to the TCE at {}",
-                                                &cert_id, &previous_cert_id, &tce_endpoint);
-                                        })
                                         .map_err(|e| {
                                             error!("Failed to submit the Certificate to the TCE at {}, will retry: {e}", &tce_endpoint);
                                             new_tce_proxy_backoff_err(e)
                                         })
+                                        .and_then(|response| {
+                                            let result = response.into_inner().result();
+                                            if result.is_rejection() {
+                                                // Submitting the certificate again won't change the outcome
+                                                error!("The TCE at {} rejected the Certificate {}: {}",
+                                                    &tce_endpoint, &cert_id, result.as_str_name());
+                                                return Err(backoff::Error::permanent(tonic::Status::failed_precondition(
+                                                    result.as_str_name(),
+                                                )));
+                                            }
+
+                                            info!("Successfully submitted the Certificate {} (previous: {}) to the TCE at {}: {}",
+                                                &cert_id, &previous_cert_id, &tce_endpoint, result.as_str_name());
+                                            Ok(())
+                                        })
                                     };
 
-                                    backoff::future::retry(backoff::ExponentialBackoff::default(), op)
+                                    let result = backoff::future::retry(backoff::ExponentialBackoff::default(), op)
                                         .await
                                         .map_err(|e| {
                                             error!("Failed to submit certificate to the TCE: {e}");
                                            e
-                                        })
+                                        });
+
+                                    (cert_id, result)
                                 }
                                 .with_context(context)
                                 .instrument(span));
@@ -409,7 +452,8 @@ impl TceClientBuilder {
                                             watch_certificates_request::OpenStream {
                                                 target_checkpoint:
                                                     Some(target_checkpoint.into()),
-                                                source_checkpoint: None
+                                                source_checkpoint: None,
+                                                ..Default::default()
                                             }.into(),
                                     )
                                     .await
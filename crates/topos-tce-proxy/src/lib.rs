@@ -5,13 +5,31 @@ pub mod client;
 pub mod worker;
 
 use opentelemetry::Context;
-use tonic::transport::channel;
+use std::{fmt::Debug, path::Path, time::Duration};
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::{interceptor::InterceptedService, Interceptor};
+use tonic::transport::{channel, ClientTlsConfig, Identity};
+use tonic_health::pb::{
+    health_check_response::ServingStatus, health_client::HealthClient, HealthCheckRequest,
+};
 use topos_core::api::grpc::checkpoints::TargetStreamPosition;
 use topos_core::{
     api::grpc::tce::v1::api_service_client::ApiServiceClient,
-    uci::{Certificate, SubnetId},
+    uci::{Certificate, CertificateId, SubnetId},
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+/// Name of the gRPC API service of the TCE, used for health checks
+const TCE_API_SERVICE_NAME: &str = "topos.tce.v1.APIService";
+
+/// Delay after which a TCE not answering a health check is considered unhealthy
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Header carrying the API key expected by the TCE nodes restricting their public APIs
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// gRPC client of the API of a TCE node, presenting the API key if any
+pub(crate) type TceApiClient = ApiServiceClient<InterceptedService<channel::Channel, ApiKey>>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -29,6 +47,10 @@ pub enum Error {
     InvalidChannelError,
     #[error("Invalid tce endpoint error")]
     InvalidTceEndpoint,
+    #[error("Invalid API key, it must only contain visible ASCII characters")]
+    InvalidApiKey,
+    #[error("None of the tce endpoints {0:?} is healthy")]
+    NoHealthyTceEndpoint(Vec<String>),
     #[error("Invalid subnet id error")]
     InvalidSubnetId,
     #[error("Invalid certificate error")]
@@ -75,34 +97,146 @@ pub enum TceProxyEvent {
     },
     /// Failed watching certificates channel. Requires a restart of the sequencer tce proxy to recover.
     WatchCertificatesChannelFailed,
+    /// Certificate of the subnet accepted by the TCE
+    CertificateSubmitted { certificate_id: CertificateId },
+    /// Certificate of the subnet which couldn't be submitted to the TCE
+    CertificateSubmissionFailed { certificate_id: CertificateId },
+    /// Latest delivered certificate of the subnet, polled from the TCE
+    SourceHeadUpdated {
+        certificate_id: CertificateId,
+        position: u64,
+    },
+}
+
+/// TLS settings of the connections to the TCE nodes, applied to the `https` endpoints
+#[derive(Clone, Default)]
+pub struct TceClientTls {
+    /// PEM encoded CA verifying the TCE nodes, the native roots are used if not set
+    pub ca_certificate: Option<Vec<u8>>,
+    /// PEM encoded certificate and private key presented to the TCE nodes which authenticate
+    /// their clients
+    pub identity: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TceClientTls {
+    /// Loads the PEM encoded CA and client identity if set
+    pub fn from_pem_files(
+        ca_certificate: Option<&Path>,
+        identity: Option<(&Path, &Path)>,
+    ) -> Result<Self, std::io::Error> {
+        Ok(Self {
+            ca_certificate: ca_certificate.map(std::fs::read).transpose()?,
+            identity: identity
+                .map(|(certificate, key)| {
+                    Ok::<_, std::io::Error>((std::fs::read(certificate)?, std::fs::read(key)?))
+                })
+                .transpose()?,
+        })
+    }
+
+    /// Endpoint of a TCE node, using TLS if its scheme is `https`
+    fn endpoint(&self, endpoint: &str) -> Result<channel::Endpoint, tonic::transport::Error> {
+        let channel = channel::Endpoint::from_shared(endpoint.to_string())?;
+        if channel.uri().scheme_str() != Some("https") {
+            return Ok(channel);
+        }
+
+        let mut config = ClientTlsConfig::new();
+        if let Some(ca_certificate) = &self.ca_certificate {
+            config = config.ca_certificate(tonic::transport::Certificate::from_pem(ca_certificate));
+        }
+        if let Some((certificate, key)) = &self.identity {
+            config = config.identity(Identity::from_pem(certificate, key));
+        }
+
+        channel.tls_config(config)
+    }
+}
+
+impl Debug for TceClientTls {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never log the private key
+        f.debug_struct("TceClientTls")
+            .field("custom_ca", &self.ca_certificate.is_some())
+            .field("client_authentication", &self.identity.is_some())
+            .finish()
+    }
+}
+
+/// API key presented to the TCE nodes restricting the access to their public APIs,
+/// no key is presented if not set
+#[derive(Clone, Default)]
+pub struct ApiKey(Option<MetadataValue<Ascii>>);
+
+impl ApiKey {
+    pub fn new(api_key: Option<&str>) -> Result<Self, Error> {
+        api_key
+            .map(|api_key| MetadataValue::try_from(api_key).map_err(|_| Error::InvalidApiKey))
+            .transpose()
+            .map(Self)
+    }
+}
+
+impl Interceptor for ApiKey {
+    fn call(
+        &mut self,
+        mut request: tonic::Request<()>,
+    ) -> Result<tonic::Request<()>, tonic::Status> {
+        if let Some(api_key) = &self.0 {
+            request
+                .metadata_mut()
+                .insert(API_KEY_HEADER, api_key.clone());
+        }
+
+        Ok(request)
+    }
+}
+
+impl Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never log the key itself
+        f.debug_tuple("ApiKey").field(&self.0.is_some()).finish()
+    }
+}
+
+/// Latest certificates of the source subnet known by the TCE
+#[derive(Debug, Clone, Default)]
+pub struct SourceHead {
+    /// Latest delivered certificate, along with its position in the source stream
+    pub delivered: Option<(Certificate, u64)>,
+    /// Latest certificate of the pending pool, following the delivered one
+    pub pending: Option<Certificate>,
 }
 
 /// Configuration data for the TCE proxy, used to configure the `TceProxyWorker`.
 pub struct TceProxyConfig {
     /// The [`SubnetId`] this config handles certificate proxying for.
     pub subnet_id: SubnetId,
-    /// The GRPC endpoint where the Sequencer is expecting to find a TCE node.
-    pub tce_endpoint: String,
+    /// The GRPC endpoints where the Sequencer is expecting to find a TCE node, by order of preference.
+    pub tce_endpoints: Vec<String>,
     /// The positions in the index of the known Certificates.
     pub positions: Vec<TargetStreamPosition>,
+    /// TLS settings of the connections to the `https` endpoints
+    pub tls: TceClientTls,
+    /// API key presented to the TCE nodes
+    pub api_key: ApiKey,
 }
 
 async fn connect_to_tce_service_with_retry(
     endpoint: String,
-) -> Result<ApiServiceClient<tonic::transport::channel::Channel>, Error> {
+    tls: &TceClientTls,
+    api_key: &ApiKey,
+) -> Result<TceApiClient, Error> {
     info!(
         "Connecting to the TCE at {} using the exponential backoff strategy...",
         endpoint
     );
     let op = || async {
-        let channel = channel::Endpoint::from_shared(endpoint.clone())?
-            .connect()
-            .await
-            .map_err(|e| {
-                error!("Failed to connect to the TCE at {}: {e}", &endpoint);
-                e
-            })?;
-        Ok(ApiServiceClient::new(channel))
+        let channel = tls.endpoint(&endpoint)?.connect().await.map_err(|e| {
+            error!("Failed to connect to the TCE at {}: {e}", &endpoint);
+            e
+        })?;
+        Ok(ApiServiceClient::with_interceptor(channel, api_key.clone()))
     };
     backoff::future::retry(backoff::ExponentialBackoff::default(), op)
         .await
@@ -111,3 +245,59 @@ async fn connect_to_tce_service_with_retry(
             Error::TonicTransportError { source: e }
         })
 }
+
+/// Check that the TCE at the given endpoint is serving its gRPC API
+pub async fn is_tce_healthy(endpoint: &str, tls: &TceClientTls) -> bool {
+    let check = async {
+        let channel = tls.endpoint(endpoint)?.connect().await?;
+        let response = HealthClient::new(channel)
+            .check(HealthCheckRequest {
+                service: TCE_API_SERVICE_NAME.to_string(),
+            })
+            .await?;
+
+        Ok::<_, Error>(response.into_inner().status == ServingStatus::Serving as i32)
+    };
+
+    match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check).await {
+        Ok(Ok(healthy)) => healthy,
+        Ok(Err(e)) => {
+            warn!("Health check of the TCE at {endpoint} failed: {e}");
+            false
+        }
+        Err(_) => {
+            warn!("Health check of the TCE at {endpoint} timed out");
+            false
+        }
+    }
+}
+
+/// Select the first healthy TCE endpoint, by order of preference,
+/// using the exponential backoff strategy while none of them is healthy
+async fn select_healthy_tce_endpoint(
+    endpoints: &[String],
+    tls: &TceClientTls,
+) -> Result<String, Error> {
+    if endpoints.is_empty() {
+        return Err(Error::InvalidTceEndpoint);
+    }
+
+    let op = || async {
+        for endpoint in endpoints {
+            if is_tce_healthy(endpoint, tls).await {
+                return Ok(endpoint.clone());
+            }
+        }
+
+        Err(backoff::Error::transient(Error::NoHealthyTceEndpoint(
+            endpoints.to_vec(),
+        )))
+    };
+
+    backoff::future::retry(backoff::ExponentialBackoff::default(), op)
+        .await
+        .map_err(|e| {
+            error!("Unable to find a healthy TCE: {e}");
+            e
+        })
+}
@@ -1,11 +1,17 @@
-use crate::{client::TceClientBuilder, Error, TceProxyCommand, TceProxyConfig, TceProxyEvent};
+use crate::{
+    client::TceClientBuilder, Error, SourceHead, TceProxyCommand, TceProxyConfig, TceProxyEvent,
+};
 use opentelemetry::trace::FutureExt;
+use std::time::Duration;
 use tokio::sync::{mpsc, oneshot};
 use tokio_stream::StreamExt;
 use topos_core::uci::Certificate;
-use tracing::{error, info, info_span, Instrument, Span};
+use tracing::{error, info, info_span, warn, Instrument, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
+/// Interval between two polls of the latest delivered certificate of the subnet
+const SOURCE_HEAD_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Proxy with the TCE
 ///
 /// Performs two tasks:
@@ -14,14 +20,19 @@ use tracing_opentelemetry::OpenTelemetrySpanExt;
 pub struct TceProxyWorker {
     /// The [`TceProxyConfig`] used to setup this worker.
     pub config: TceProxyConfig,
+    /// The endpoint of the TCE this worker is connected to, selected among the healthy ones.
+    pub tce_endpoint: String,
     commands: mpsc::Sender<TceProxyCommand>,
     events: mpsc::Receiver<TceProxyEvent>,
 }
 
 impl TceProxyWorker {
     /// Construct a new [`TceProxyWorker`] with a 128 items deep channel to send commands to and receive events from a TCE node on the given subnet.
-    /// The worker holds a [`crate::client::TceClient`]
-    pub async fn new(config: TceProxyConfig) -> Result<(Self, Option<(Certificate, u64)>), Error> {
+    /// The worker holds a [`crate::client::TceClient`] connected to the first healthy TCE endpoint of the config.
+    pub async fn new(config: TceProxyConfig) -> Result<(Self, SourceHead), Error> {
+        let tce_endpoint =
+            crate::select_healthy_tce_endpoint(&config.tce_endpoints, &config.tls).await?;
+
         let (command_sender, mut command_rcv) = mpsc::channel::<TceProxyCommand>(128);
         let (evt_sender, evt_rcv) = mpsc::channel::<TceProxyEvent>(128);
         let (tce_client_shutdown_channel, shutdown_receiver) =
@@ -29,22 +40,25 @@ impl TceProxyWorker {
 
         let (mut tce_client, mut receiving_certificate_stream) = TceClientBuilder::default()
             .set_subnet_id(config.subnet_id)
-            .set_tce_endpoint(&config.tce_endpoint)
+            .set_tce_endpoint(&tce_endpoint)
+            .set_tls(config.tls.clone())
+            .set_api_key(config.api_key.clone())
             .set_proxy_event_sender(evt_sender.clone())
             .build_and_launch(shutdown_receiver)
             .await?;
 
         tce_client.open_stream(config.positions.clone()).await?;
 
-        // Get pending certificates from the TCE node. Source head certificate
-        // is latest pending certificate for this subnet
-        let source_last_pending_certificate: Option<(Certificate, u64)> = match tce_client
+        // Get pending certificates from the TCE node, the latest one of the subnet
+        // follows its latest delivered certificate
+        let source_last_pending_certificate: Option<Certificate> = match tce_client
             .get_last_pending_certificates(vec![tce_client.get_subnet_id()])
             .await
         {
             Ok(mut pending_certificates) => pending_certificates
                 .remove(&tce_client.get_subnet_id())
-                .unwrap_or_default(),
+                .unwrap_or_default()
+                .map(|(certificate, _index)| certificate),
             Err(e) => {
                 error!("Unable to retrieve latest pending certificate {e}");
                 return Err(e);
@@ -71,18 +85,9 @@ impl TceProxyWorker {
             source_last_delivered_certificate
         );
 
-        let source_last_certificate = if source_last_pending_certificate.is_none() {
-            // There are no pending certificates on the TCE
-            // Block height to get next from subnet is position +1
-            source_last_delivered_certificate
-        } else {
-            // Last generated is pending certificate
-            // Block height to get next from subnet is position of the last delivered certificate + index of the pending certificate
-            let delivered_certificate_position = source_last_delivered_certificate
-                .map(|(_cert, position)| position)
-                .unwrap_or_default();
-            source_last_pending_certificate
-                .map(|(cert, index)| (cert, delivered_certificate_position + index))
+        let source_head = SourceHead {
+            delivered: source_last_delivered_certificate,
+            pending: source_last_pending_certificate,
         };
 
         tokio::spawn(async move {
@@ -90,8 +95,28 @@ impl TceProxyWorker {
                 "Starting the TCE proxy connected to the TCE at {}",
                 tce_client.get_tce_endpoint()
             );
+            let mut source_head_poll = tokio::time::interval(SOURCE_HEAD_POLL_INTERVAL);
             loop {
                 tokio::select! {
+                    // Poll the latest delivered certificate of the subnet
+                    _ = source_head_poll.tick() => {
+                        match tce_client.get_source_head().await {
+                            Ok((certificate, position)) => {
+                                if let Err(e) = evt_sender.send(TceProxyEvent::SourceHeadUpdated {
+                                    certificate_id: certificate.id,
+                                    position,
+                                })
+                                .await {
+                                    error!("Unable to send SourceHeadUpdated event {e}");
+                                }
+                            }
+                            Err(Error::SourceHeadEmpty { .. }) => {}
+                            Err(e) => {
+                                warn!("Unable to poll the source head certificate: {e}");
+                            }
+                        }
+                    }
+
                     // process TCE proxy commands received from application
                     Some(cmd) = command_rcv.recv() => {
                         match cmd {
@@ -145,14 +170,15 @@ impl TceProxyWorker {
             );
         });
 
-        // Save channels and handles, return latest tce known certificate
+        // Save channels and handles, return latest tce known certificates
         Ok((
             Self {
                 commands: command_sender,
                 events: evt_rcv,
                 config,
+                tce_endpoint,
             },
-            source_last_certificate,
+            source_head,
         ))
     }
 
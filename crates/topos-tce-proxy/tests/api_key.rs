@@ -0,0 +1,72 @@
+use rstest::rstest;
+use std::{collections::HashSet, time::Duration};
+use test_log::test;
+use tokio::sync::{broadcast, mpsc};
+use topos_tce_api::{ApiAccess, ApiKeys, Runtime};
+use topos_tce_proxy::{client::TceClientBuilder, ApiKey, Error};
+use topos_tce_storage::StorageClient;
+use topos_test_sdk::{
+    constants::SOURCE_SUBNET_ID_1, networking::get_available_addr, storage::create_validator_store,
+};
+
+const API_KEY: &str = "secret-api-key";
+
+#[rstest]
+#[timeout(Duration::from_secs(10))]
+#[test(tokio::test)]
+async fn tce_client_presents_its_api_key() {
+    let grpc_addr = get_available_addr();
+    let store = create_validator_store::default().await;
+    let (_, broadcast_stream) = broadcast::channel(10);
+    let (_client, _events, _context) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(StorageClient::new(store.clone()))
+        .store(store)
+        .serve_grpc_addr(grpc_addr)
+        .serve_graphql_addr(get_available_addr())
+        .serve_rest_addr(get_available_addr())
+        .serve_metrics_addr(get_available_addr())
+        .with_api_access(ApiAccess {
+            api_keys: Some(ApiKeys::new(HashSet::from([API_KEY.to_string()]))),
+            ..Default::default()
+        })
+        .build_and_launch()
+        .await;
+
+    // Wait for servers to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let endpoint = format!("http://{grpc_addr}");
+
+    let anonymous = TceClientBuilder::default()
+        .set_subnet_id(SOURCE_SUBNET_ID_1)
+        .set_tce_endpoint(&endpoint)
+        .build_and_launch(mpsc::channel(1).1)
+        .await;
+    assert!(matches!(
+        anonymous,
+        Err(Error::TonicStatusError { source }) if source.code() == tonic::Code::Unauthenticated
+    ));
+
+    let (mut tce_client, _certificates) = TceClientBuilder::default()
+        .set_subnet_id(SOURCE_SUBNET_ID_1)
+        .set_tce_endpoint(&endpoint)
+        .set_api_key(ApiKey::new(Some(API_KEY)).unwrap())
+        .build_and_launch(mpsc::channel(1).1)
+        .await
+        .unwrap();
+
+    assert!(tce_client
+        .get_last_pending_certificates(vec![SOURCE_SUBNET_ID_1])
+        .await
+        .is_ok());
+}
+
+#[test]
+fn api_key_must_be_a_valid_header_value() {
+    assert!(matches!(
+        ApiKey::new(Some("invalid\nkey")),
+        Err(Error::InvalidApiKey)
+    ));
+    assert!(ApiKey::new(None).is_ok());
+}
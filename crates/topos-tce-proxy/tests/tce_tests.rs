@@ -23,7 +23,7 @@ use topos_core::uci::SUBNET_ID_LENGTH;
 use topos_tce_proxy::client::{TceClient, TceClientBuilder};
 use topos_tce_proxy::worker::TceProxyWorker;
 use topos_tce_proxy::{TceProxyCommand, TceProxyConfig, TceProxyEvent};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info};
 
 use topos_test_sdk::{
     certificates::create_certificate_chain,
@@ -59,6 +59,8 @@ async fn test_tce_submit_certificate(
                 verifier: 0,
                 proof: Some(StarkProof { value: Vec::new() }),
                 signature: Some(Default::default()),
+                messages_root: [0u8; 32].to_vec(),
+                block_range: None,
             }),
         })
         .await
@@ -96,7 +98,8 @@ async fn test_tce_watch_certificates(
                 target_subnet_ids: vec![ subnet_id_instream ],
                 positions: Vec::new()
             }),
-            source_checkpoint: None
+            source_checkpoint: None,
+            ..Default::default()
         }.into()
     };
 
@@ -176,6 +179,8 @@ async fn test_tce_get_source_head_certificate(
         verifier: 0,
         proof: Some(StarkProof { value: Vec::new() }),
         signature: Some(Default::default()),
+        messages_root: [0u8; 32].to_vec(),
+        block_range: None,
     };
     let expected_response = GetSourceHeadResponse {
         certificate: Some(expected_default_genesis_certificate.clone()),
@@ -199,6 +204,8 @@ async fn test_tce_get_source_head_certificate(
         verifier: 0,
         proof: Some(StarkProof { value: Vec::new() }),
         signature: Some(Default::default()),
+        messages_root: [0u8; 32].to_vec(),
+        block_range: None,
     };
 
     match context
@@ -434,7 +441,8 @@ async fn test_tce_open_stream_with_checkpoint(
     let in_stream = async_stream::stream! {
         yield watch_certificates_request::OpenStream {
             target_checkpoint: Some(target_checkpoint),
-            source_checkpoint: None
+            source_checkpoint: None,
+            ..Default::default()
         }.into()
     };
 
@@ -537,6 +545,33 @@ fn input_certificates() -> Vec<CertificateDelivered> {
     certificates
 }
 
+#[rstest]
+#[test(tokio::test)]
+async fn test_tce_proxy_failover_to_healthy_endpoint(
+    #[future] start_node: TceContext,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut context = start_node.await;
+
+    let unreachable_endpoint = "http://127.0.0.1:1".to_string();
+    assert!(!topos_tce_proxy::is_tce_healthy(&unreachable_endpoint, &Default::default()).await);
+    assert!(topos_tce_proxy::is_tce_healthy(&context.api_entrypoint, &Default::default()).await);
+
+    let (tce_proxy_worker, _source_head_certificate) = TceProxyWorker::new(TceProxyConfig {
+        subnet_id: SOURCE_SUBNET_ID_1,
+        tce_endpoints: vec![unreachable_endpoint, context.api_entrypoint.clone()],
+        positions: Vec::new(),
+        tls: Default::default(),
+        api_key: Default::default(),
+    })
+    .await?;
+
+    assert_eq!(tce_proxy_worker.tce_endpoint, context.api_entrypoint);
+
+    tce_proxy_worker.shutdown().await?;
+    context.shutdown().await?;
+    Ok(())
+}
+
 #[rstest]
 #[test(tokio::test)]
 async fn test_tce_proxy_submit_certificate(
@@ -559,28 +594,22 @@ async fn test_tce_proxy_submit_certificate(
     let (tce_proxy_worker, _source_head_certificate_id) =
         match TceProxyWorker::new(TceProxyConfig {
             subnet_id: source_subnet_id,
-            tce_endpoint: context.api_entrypoint.clone(),
+            tce_endpoints: vec![context.api_entrypoint.clone()],
             positions: target_subnet_stream_positions,
+            tls: Default::default(),
+            api_key: Default::default(),
         })
         .await
         {
-            Ok((tce_proxy_worker, mut source_head_certificate)) => {
-                if let Some((cert, _position)) = &mut source_head_certificate {
-                    if cert.id == CertificateId::default() {
-                        warn!(
-                            "Tce has not provided source head certificate, starting from subnet \
-                             genesis block..."
-                        );
-                        source_head_certificate = None;
-                    }
-                }
-
+            Ok((tce_proxy_worker, source_head)) => {
                 info!(
                     "TCE proxy client is starting for the source subnet {:?} from the head {:?}",
-                    source_subnet_id, source_head_certificate
+                    source_subnet_id, source_head
                 );
-                let source_head_certificate_id =
-                    source_head_certificate.map(|(cert, position)| (cert.id, position));
+                let source_head_certificate_id = source_head
+                    .delivered
+                    .map(|(cert, _position)| cert.id)
+                    .filter(|id| *id != CertificateId::default());
                 (tce_proxy_worker, source_head_certificate_id)
             }
             Err(e) => {
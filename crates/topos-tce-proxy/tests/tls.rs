@@ -0,0 +1,111 @@
+use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa};
+use rstest::rstest;
+use std::{net::SocketAddr, time::Duration};
+use test_log::test;
+use tonic::transport::{Identity, Server, ServerTlsConfig};
+use tonic_health::ServingStatus;
+use topos_tce_proxy::{is_tce_healthy, TceClientTls};
+use topos_test_sdk::networking::get_available_addr;
+
+/// PEM encoded certificates signed by a test CA
+struct TestPki {
+    ca: Certificate,
+}
+
+impl TestPki {
+    fn new() -> Self {
+        let mut params = CertificateParams::new(Vec::new());
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+
+        Self {
+            ca: Certificate::from_params(params).unwrap(),
+        }
+    }
+
+    fn ca_pem(&self) -> Vec<u8> {
+        self.ca.serialize_pem().unwrap().into_bytes()
+    }
+
+    /// Issues a certificate for `localhost`, returns the certificate and its key
+    fn issue(&self) -> (Vec<u8>, Vec<u8>) {
+        let certificate =
+            Certificate::from_params(CertificateParams::new(vec!["localhost".to_string()]))
+                .unwrap();
+
+        (
+            certificate
+                .serialize_pem_with_signer(&self.ca)
+                .unwrap()
+                .into_bytes(),
+            certificate.serialize_private_key_pem().into_bytes(),
+        )
+    }
+}
+
+/// Serves the health service of a TCE over TLS, returns its `https` endpoint
+async fn serve_tce_health(tls: ServerTlsConfig) -> String {
+    let addr: SocketAddr = get_available_addr();
+    let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
+    health_reporter
+        .set_service_status("topos.tce.v1.APIService", ServingStatus::Serving)
+        .await;
+
+    let server = Server::builder()
+        .tls_config(tls)
+        .unwrap()
+        .add_service(health_service)
+        .serve(addr);
+    tokio::spawn(server);
+
+    // Wait for the server to boot
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    format!("https://localhost:{}", addr.port())
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[test(tokio::test)]
+async fn tce_served_over_tls_is_verified_with_a_custom_ca() {
+    let pki = TestPki::new();
+    let (certificate, key) = pki.issue();
+    let endpoint =
+        serve_tce_health(ServerTlsConfig::new().identity(Identity::from_pem(certificate, key)))
+            .await;
+
+    let tls = TceClientTls {
+        ca_certificate: Some(pki.ca_pem()),
+        identity: None,
+    };
+    assert!(is_tce_healthy(&endpoint, &tls).await);
+
+    // The test CA isn't part of the native roots
+    assert!(!is_tce_healthy(&endpoint, &TceClientTls::default()).await);
+}
+
+#[rstest]
+#[timeout(Duration::from_secs(15))]
+#[test(tokio::test)]
+async fn tce_authenticating_its_clients_accepts_the_client_identity() {
+    let pki = TestPki::new();
+    let (certificate, key) = pki.issue();
+    let (client_certificate, client_key) = pki.issue();
+    let endpoint = serve_tce_health(
+        ServerTlsConfig::new()
+            .identity(Identity::from_pem(certificate, key))
+            .client_ca_root(tonic::transport::Certificate::from_pem(pki.ca_pem())),
+    )
+    .await;
+
+    let anonymous = TceClientTls {
+        ca_certificate: Some(pki.ca_pem()),
+        identity: None,
+    };
+    assert!(!is_tce_healthy(&endpoint, &anonymous).await);
+
+    let authenticated = TceClientTls {
+        identity: Some((client_certificate, client_key)),
+        ..anonymous
+    };
+    assert!(is_tce_healthy(&endpoint, &authenticated).await);
+}
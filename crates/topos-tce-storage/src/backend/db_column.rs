@@ -1,32 +1,42 @@
-use std::{borrow::Borrow, marker::PhantomData, sync::Arc};
+use std::{borrow::Borrow, fmt, marker::PhantomData, path::Path, sync::Arc};
 
-#[cfg(test)]
-use std::path::Path;
-
-#[cfg(test)]
+#[cfg(all(test, feature = "rocksdb"))]
 use rocksdb::ColumnFamilyDescriptor;
-use rocksdb::{
-    BoundColumnFamily, DBRawIteratorWithThreadMode, DBWithThreadMode, Direction, IteratorMode,
-    MultiThreaded, ReadOptions, WriteBatch,
-};
 
 use bincode::Options;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::errors::InternalStorageError;
 
-use super::{iterator::ColumnIterator, map::Map, RocksDB};
+use super::{
+    iterator::ColumnIterator, map::Map, IteratorMode, KeyValueColumn, StorageBackend, WriteBatch,
+};
 
-/// A DBColumn represents a CF structure
-#[derive(Clone, Debug)]
+/// A DBColumn represents a typed column of a [`StorageBackend`]
 pub struct DBColumn<K, V> {
-    pub(crate) rocksdb: RocksDB,
+    column: Arc<dyn KeyValueColumn>,
     _phantom: PhantomData<fn(K) -> V>,
-    cf: &'static str,
+}
+
+impl<K, V> Clone for DBColumn<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            column: self.column.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for DBColumn<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DBColumn")
+            .field("column", &self.column.name())
+            .finish()
+    }
 }
 
 impl<K, V> DBColumn<K, V> {
-    #[cfg(test)]
+    #[cfg(all(test, feature = "rocksdb"))]
     #[allow(dead_code)]
     pub fn open<P: AsRef<Path>>(
         path: P,
@@ -50,26 +60,30 @@ impl<K, V> DBColumn<K, V> {
             )
         };
 
-        Ok(Self {
-            rocksdb,
-            _phantom: PhantomData,
-            cf: column,
-        })
+        Ok(Self::reopen(&rocksdb, column))
     }
 
-    pub fn reopen(db: &RocksDB, column: &'static str) -> Self {
+    pub fn reopen(backend: &impl StorageBackend, column: &'static str) -> Self {
         Self {
-            rocksdb: db.clone(),
+            column: backend.column(column),
             _phantom: PhantomData,
-            cf: column,
         }
     }
 
-    /// Returns the CF of the DBColumn, used to build queries.
-    fn cf(&self) -> Result<Arc<BoundColumnFamily<'_>>, InternalStorageError> {
-        self.rocksdb
-            .cf_handle(self.cf)
-            .ok_or(InternalStorageError::InvalidColumnFamily(self.cf))
+    /// Create a checkpoint at `path` of the whole backend holding the column
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.column.checkpoint(path)
+    }
+
+    /// Compact the column, reclaiming the space of the deleted and overwritten keys
+    pub(crate) fn compact(&self) -> Result<(), InternalStorageError> {
+        self.column.compact()
+    }
+
+    /// Returns an estimation of the number of keys of the column, cheap to compute
+    /// but not exact as it doesn't account for the overwritten and deleted keys
+    pub(crate) fn estimate_num_keys(&self) -> Result<u64, InternalStorageError> {
+        self.column.estimate_num_keys()
     }
 }
 
@@ -82,15 +96,11 @@ where
     ///
     /// Key are fixed length bincode serialized.
     pub(crate) fn insert(&self, key: &K, value: &V) -> Result<(), InternalStorageError> {
-        let cf = self.cf()?;
-
         let key_buf = be_fix_int_ser(key)?;
 
         let value_buf = bincode::serialize(value)?;
 
-        self.rocksdb.put_cf(&cf, key_buf, value_buf)?;
-
-        Ok(())
+        self.column.insert(key_buf, value_buf)
     }
 
     /// Delete a record from the storage by passing a Key
@@ -99,9 +109,7 @@ where
     pub(crate) fn delete(&self, key: &K) -> Result<(), InternalStorageError> {
         let key_buf = be_fix_int_ser(key)?;
 
-        self.rocksdb.delete_cf(&self.cf()?, key_buf)?;
-
-        Ok(())
+        self.column.delete(&key_buf)
     }
 
     /// Get a record from the storage by passing a Key
@@ -110,13 +118,11 @@ where
     pub(crate) fn get(&self, key: &K) -> Result<Option<V>, InternalStorageError> {
         let key_buf = be_fix_int_ser(key)?;
 
-        self.rocksdb
-            .get_pinned_cf(&self.cf()?, key_buf)?
-            .map_or(Ok(None), |v| {
-                bincode::deserialize::<V>(&v)
-                    .map(|r| Some(r))
-                    .map_err(|_| InternalStorageError::UnableToDeserializeValue)
-            })
+        self.column.get(&key_buf)?.map_or(Ok(None), |v| {
+            bincode::deserialize::<V>(&v)
+                .map(|r| Some(r))
+                .map_err(|_| InternalStorageError::UnableToDeserializeValue)
+        })
     }
 
     pub(crate) fn multi_insert(
@@ -132,14 +138,8 @@ where
         let keys: Result<Vec<_>, InternalStorageError> =
             keys.iter().map(|k| be_fix_int_ser(k)).collect();
 
-        let results: Result<Vec<_>, InternalStorageError> = self
-            .rocksdb
-            .batched_multi_get_cf_opt(&self.cf()?, keys?, false, &ReadOptions::default())
-            .into_iter()
-            .map(|r| r.map_err(InternalStorageError::RocksDBError))
-            .collect();
-
-        results?
+        self.column
+            .multi_get(keys?)?
             .into_iter()
             .map(|e| match e {
                 Some(v) => bincode::deserialize(&v)
@@ -155,23 +155,23 @@ where
         let key_buf = be_fix_int_ser(key)?;
         let value_buf = bincode::serialize(&value)?;
 
-        Ok(self.rocksdb.merge_cf(&self.cf()?, key_buf, value_buf)?)
+        self.column.merge(key_buf, value_buf)
     }
 
     pub(crate) fn batch(&self) -> DBBatch {
-        DBBatch::new(&self.rocksdb)
+        DBBatch::new(&self.column)
     }
 }
 
 pub(crate) struct DBBatch {
-    rocksdb: Arc<DBWithThreadMode<MultiThreaded>>,
+    column: Arc<dyn KeyValueColumn>,
     batch: WriteBatch,
 }
 
 impl DBBatch {
-    fn new(rocksdb: &Arc<DBWithThreadMode<MultiThreaded>>) -> Self {
+    fn new(column: &Arc<dyn KeyValueColumn>) -> Self {
         Self {
-            rocksdb: rocksdb.clone(),
+            column: column.clone(),
             batch: WriteBatch::default(),
         }
     }
@@ -187,24 +187,45 @@ impl DBBatch {
         Key: Borrow<K>,
         Value: Borrow<V>,
     {
-        check_cross_batch(&self.rocksdb, &db.rocksdb)?;
+        check_cross_batch(self.column.as_ref(), db.column.as_ref())?;
 
         values
             .into_iter()
             .try_for_each::<_, Result<(), InternalStorageError>>(|(k, v)| {
                 let key_buffer = be_fix_int_ser(k.borrow())?;
                 let value_buffer = bincode::serialize(v.borrow())?;
-                self.batch.put_cf(&db.cf()?, key_buffer, value_buffer);
+                self.batch
+                    .inserts
+                    .push((db.column.name(), key_buffer, value_buffer));
                 Ok(())
             })?;
 
         Ok(self)
     }
 
-    pub(crate) fn write(self) -> Result<(), InternalStorageError> {
-        self.rocksdb.write(self.batch)?;
+    pub(crate) fn delete_batch<K, V, Key>(
+        mut self,
+        db: &DBColumn<K, V>,
+        keys: impl IntoIterator<Item = Key>,
+    ) -> Result<Self, InternalStorageError>
+    where
+        K: Serialize + std::fmt::Debug,
+        Key: Borrow<K>,
+    {
+        check_cross_batch(self.column.as_ref(), db.column.as_ref())?;
 
-        Ok(())
+        keys.into_iter()
+            .try_for_each::<_, Result<(), InternalStorageError>>(|k| {
+                let key_buffer = be_fix_int_ser(k.borrow())?;
+                self.batch.deletes.push((db.column.name(), key_buffer));
+                Ok(())
+            })?;
+
+        Ok(self)
+    }
+
+    pub(crate) fn write(self) -> Result<(), InternalStorageError> {
+        self.column.write_batch(self.batch)
     }
 }
 
@@ -216,43 +237,23 @@ where
     type Iterator = ColumnIterator<'a, K, V>;
 
     fn iter(&'a self) -> Result<Self::Iterator, InternalStorageError> {
-        let mut raw_iterator = self.rocksdb.raw_iterator_cf(&self.cf()?);
-        raw_iterator.seek_to_first();
-
-        Ok(ColumnIterator::new(raw_iterator))
+        self.iter_with_mode(IteratorMode::Start)
     }
 
     fn iter_with_mode(
         &'a self,
-        mode: IteratorMode<'_>,
+        mode: IteratorMode,
     ) -> Result<Self::Iterator, InternalStorageError> {
-        let mut raw_iterator = self.rocksdb.raw_iterator_cf(&self.cf()?);
-
-        let direction = match mode {
-            IteratorMode::Start => {
-                raw_iterator.seek_to_first();
-                Direction::Forward
-            }
-            IteratorMode::End => {
-                raw_iterator.seek_to_last();
-                Direction::Forward
-            }
-            _ => unimplemented!(),
-        };
-
-        Ok(ColumnIterator::new_with_direction(raw_iterator, direction))
+        Ok(ColumnIterator::new(self.column.iter(mode)?))
     }
 
     fn prefix_iter<P: Serialize>(
         &'a self,
         prefix: &P,
     ) -> Result<Self::Iterator, InternalStorageError> {
-        let iterator = self
-            .rocksdb
-            .prefix_iterator_cf(&self.cf()?, be_fix_int_ser(prefix)?)
-            .into();
-
-        Ok(ColumnIterator::new(iterator))
+        Ok(ColumnIterator::new(
+            self.column.prefix_iter(&be_fix_int_ser(prefix)?, None)?,
+        ))
     }
 
     fn prefix_iter_at<P: Serialize, I: Serialize>(
@@ -260,18 +261,15 @@ where
         prefix: &P,
         index: &I,
     ) -> Result<Self::Iterator, InternalStorageError> {
-        let mut iterator: DBRawIteratorWithThreadMode<_> = self
-            .rocksdb
-            .prefix_iterator_cf(&self.cf()?, be_fix_int_ser(prefix)?)
-            .into();
-
-        iterator.seek(be_fix_int_ser(index)?);
-        Ok(ColumnIterator::new(iterator))
+        Ok(ColumnIterator::new(self.column.prefix_iter(
+            &be_fix_int_ser(prefix)?,
+            Some(&be_fix_int_ser(index)?),
+        )?))
     }
 }
 
 /// Serialize a value using a fix length serialize and a big endian endianness
-fn be_fix_int_ser<S>(t: &S) -> Result<Vec<u8>, InternalStorageError>
+pub(crate) fn be_fix_int_ser<S>(t: &S) -> Result<Vec<u8>, InternalStorageError>
 where
     S: Serialize + ?Sized,
 {
@@ -281,8 +279,11 @@ where
         .serialize(t)?)
 }
 
-fn check_cross_batch(base: &RocksDB, current: &RocksDB) -> Result<(), InternalStorageError> {
-    if !Arc::ptr_eq(base, current) {
+fn check_cross_batch(
+    base: &dyn KeyValueColumn,
+    current: &dyn KeyValueColumn,
+) -> Result<(), InternalStorageError> {
+    if base.backend_id() != current.backend_id() {
         return Err(InternalStorageError::ConcurrentDBBatchDetected);
     }
 
@@ -0,0 +1,42 @@
+use std::marker::PhantomData;
+
+use bincode::Options;
+use serde::de::DeserializeOwned;
+
+use super::RawIterator;
+
+pub struct ColumnIterator<'a, K, V> {
+    iterator: RawIterator<'a>,
+    _phantom: PhantomData<(K, V)>,
+}
+
+impl<'a, K, V> ColumnIterator<'a, K, V> {
+    /// Creates a new ColumnIterator deserializing the pairs of a [`RawIterator`]
+    pub(crate) fn new(iterator: RawIterator<'a>) -> Self {
+        Self {
+            iterator,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for ColumnIterator<'a, K, V>
+where
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iterator.next()?;
+
+        let config = bincode::DefaultOptions::new()
+            .with_big_endian()
+            .with_fixint_encoding();
+
+        let key = config.deserialize(&key).ok();
+        let value = bincode::deserialize(&value).ok();
+
+        key.and_then(|k| value.map(|v| (k, v)))
+    }
+}
@@ -1,8 +1,9 @@
-use rocksdb::IteratorMode;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::errors::InternalStorageError;
 
+use super::IteratorMode;
+
 pub trait Map<'a, K, V>
 where
     K: Serialize + DeserializeOwned + ?Sized,
@@ -14,10 +15,8 @@ where
     fn iter(&'a self) -> Result<Self::Iterator, InternalStorageError>;
 
     /// Returns an Iterator over the whole CF with mode configured
-    fn iter_with_mode(
-        &'a self,
-        mode: IteratorMode<'_>,
-    ) -> Result<Self::Iterator, InternalStorageError>;
+    fn iter_with_mode(&'a self, mode: IteratorMode)
+        -> Result<Self::Iterator, InternalStorageError>;
 
     /// Returns a prefixed Iterator over the CF
     fn prefix_iter<P: Serialize>(
@@ -0,0 +1,175 @@
+//! In-memory storage backend
+//!
+//! Columns are ordered maps living in memory, nothing is persisted on disk.
+//! Iterators work on a snapshot of the column taken at their creation, so that
+//! the column can be written while being iterated.
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Bound,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+use crate::errors::InternalStorageError;
+
+use super::{IteratorMode, KeyValueColumn, RawIterator, StorageBackend, WriteBatch};
+
+type Column = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// In-memory database holding named columns
+///
+/// Clones share the same columns.
+#[derive(Clone, Default)]
+pub(crate) struct MemoryDB {
+    columns: Arc<RwLock<HashMap<&'static str, Column>>>,
+}
+
+impl StorageBackend for MemoryDB {
+    fn column(&self, name: &'static str) -> Arc<dyn KeyValueColumn> {
+        Arc::new(MemoryColumn {
+            db: self.clone(),
+            name,
+        })
+    }
+}
+
+struct MemoryColumn {
+    db: MemoryDB,
+    name: &'static str,
+}
+
+impl MemoryColumn {
+    /// Collect the pairs of the column starting with `prefix`, from the key `start`
+    fn snapshot(&self, start: Bound<&[u8]>, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.db
+            .columns
+            .read()
+            .unwrap()
+            .get(self.name)
+            .map(|column| {
+                column
+                    .range::<[u8], _>((start, Bound::Unbounded))
+                    .take_while(|(key, _)| key.starts_with(prefix))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl KeyValueColumn for MemoryColumn {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn backend_id(&self) -> usize {
+        Arc::as_ptr(&self.db.columns) as usize
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, InternalStorageError> {
+        Ok(self
+            .db
+            .columns
+            .read()
+            .unwrap()
+            .get(self.name)
+            .and_then(|column| column.get(key).cloned()))
+    }
+
+    fn multi_get(&self, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>, InternalStorageError> {
+        let columns = self.db.columns.read().unwrap();
+        let column = columns.get(self.name);
+
+        Ok(keys
+            .iter()
+            .map(|key| column.and_then(|column| column.get(key).cloned()))
+            .collect())
+    }
+
+    fn insert(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), InternalStorageError> {
+        self.db
+            .columns
+            .write()
+            .unwrap()
+            .entry(self.name)
+            .or_default()
+            .insert(key, value);
+
+        Ok(())
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), InternalStorageError> {
+        if let Some(column) = self.db.columns.write().unwrap().get_mut(self.name) {
+            column.remove(key);
+        }
+
+        Ok(())
+    }
+
+    fn merge(&self, _key: Vec<u8>, _value: Vec<u8>) -> Result<(), InternalStorageError> {
+        Err(InternalStorageError::UnsupportedOperation("merge"))
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<(), InternalStorageError> {
+        let mut columns = self.db.columns.write().unwrap();
+        for (name, key, value) in batch.inserts {
+            columns.entry(name).or_default().insert(key, value);
+        }
+        for (name, key) in batch.deletes {
+            if let Some(column) = columns.get_mut(name) {
+                column.remove(&key);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn iter(&self, mode: IteratorMode) -> Result<RawIterator<'_>, InternalStorageError> {
+        let pairs = match mode {
+            IteratorMode::Start => self.snapshot(Bound::Unbounded, &[]),
+            IteratorMode::End => self
+                .db
+                .columns
+                .read()
+                .unwrap()
+                .get(self.name)
+                .and_then(|column| column.last_key_value())
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .into_iter()
+                .collect(),
+        };
+
+        Ok(Box::new(pairs.into_iter()))
+    }
+
+    fn prefix_iter(
+        &self,
+        prefix: &[u8],
+        from: Option<&[u8]>,
+    ) -> Result<RawIterator<'_>, InternalStorageError> {
+        let start = from.filter(|from| *from > prefix).unwrap_or(prefix);
+
+        Ok(Box::new(
+            self.snapshot(Bound::Included(start), prefix).into_iter(),
+        ))
+    }
+
+    fn checkpoint(&self, _path: &Path) -> Result<(), InternalStorageError> {
+        Err(InternalStorageError::UnsupportedOperation("checkpoint"))
+    }
+
+    fn compact(&self) -> Result<(), InternalStorageError> {
+        // Deleted keys are dropped right away, there is nothing to reclaim
+        Ok(())
+    }
+
+    fn estimate_num_keys(&self) -> Result<u64, InternalStorageError> {
+        Ok(self
+            .db
+            .columns
+            .read()
+            .unwrap()
+            .get(self.name)
+            .map_or(0, |column| column.len() as u64))
+    }
+}
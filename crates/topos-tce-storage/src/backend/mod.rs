@@ -0,0 +1,90 @@
+//! Storage backends of the tables
+//!
+//! The tables are made of [`DBColumn`](struct@db_column::DBColumn)s, typed views over
+//! the [`KeyValueColumn`]s of a [`StorageBackend`]. Keys and values are serialized by the
+//! [`DBColumn`](struct@db_column::DBColumn), a backend only deals with bytes.
+//!
+//! RocksDB is the default backend, an in-memory one is available with the `inmemory`
+//! feature. Another engine can be plugged in by implementing [`StorageBackend`] and
+//! [`KeyValueColumn`], without touching the stores.
+use std::{path::Path, sync::Arc};
+
+use crate::errors::InternalStorageError;
+
+pub(crate) mod db_column;
+pub(crate) mod iterator;
+pub(crate) mod map;
+#[cfg(feature = "inmemory")]
+pub(crate) mod memory;
+
+/// Iterator over the raw key/value pairs of a [`KeyValueColumn`]
+pub type RawIterator<'a> = Box<dyn Iterator<Item = (Vec<u8>, Vec<u8>)> + 'a>;
+
+/// Starting point of an iteration over a whole column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IteratorMode {
+    /// Iterate from the first key
+    Start,
+    /// Only yield the last key
+    End,
+}
+
+/// Insertions and deletions spanning several columns of one backend, written atomically
+///
+/// The deletions are applied after the insertions.
+#[derive(Debug, Default)]
+pub struct WriteBatch {
+    pub(crate) inserts: Vec<(&'static str, Vec<u8>, Vec<u8>)>,
+    pub(crate) deletes: Vec<(&'static str, Vec<u8>)>,
+}
+
+/// Storage engine holding the named columns of some tables
+pub trait StorageBackend {
+    /// Returns the column of the given name
+    fn column(&self, name: &'static str) -> Arc<dyn KeyValueColumn>;
+}
+
+/// Key/value column of a [`StorageBackend`], keys and values being already serialized
+///
+/// Keys are ordered bytewise, which the prefix iterations rely on.
+pub trait KeyValueColumn: Send + Sync {
+    /// Name of the column in its backend
+    fn name(&self) -> &'static str;
+
+    /// Identifier of the backend holding the column, a [`WriteBatch`] can only span
+    /// the columns of one backend
+    fn backend_id(&self) -> usize;
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, InternalStorageError>;
+
+    fn multi_get(&self, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>, InternalStorageError>;
+
+    fn insert(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), InternalStorageError>;
+
+    fn delete(&self, key: &[u8]) -> Result<(), InternalStorageError>;
+
+    fn merge(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), InternalStorageError>;
+
+    /// Atomically write a batch whose operations target columns of the same backend
+    fn write_batch(&self, batch: WriteBatch) -> Result<(), InternalStorageError>;
+
+    /// Returns an Iterator over the whole column
+    fn iter(&self, mode: IteratorMode) -> Result<RawIterator<'_>, InternalStorageError>;
+
+    /// Returns an Iterator over the keys starting with `prefix`, from the key `from` if set
+    fn prefix_iter(
+        &self,
+        prefix: &[u8],
+        from: Option<&[u8]>,
+    ) -> Result<RawIterator<'_>, InternalStorageError>;
+
+    /// Create a consistent copy of the whole backend holding the column at `path`, which
+    /// must not exist yet
+    fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError>;
+
+    /// Compact the whole column, reclaiming the space of the deleted and overwritten keys
+    fn compact(&self) -> Result<(), InternalStorageError>;
+
+    /// Returns an estimation of the number of keys of the column, without iterating over it
+    fn estimate_num_keys(&self) -> Result<u64, InternalStorageError>;
+}
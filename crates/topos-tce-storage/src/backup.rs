@@ -0,0 +1,105 @@
+//! Backup and restore of the storage
+//!
+//! A backup is taken while the node is running using
+//! [`ValidatorStore::backup`](fn@crate::validator::ValidatorStore::backup). It is a directory
+//! holding a RocksDB checkpoint of every database opened under the storage path
+//! (`perpetual`, `index`, `pending`, `validators` and `epochs`) and a [`BackupManifest`]
+//! describing the delivered state of the backup.
+//!
+//! Checkpoints are made of hard links to the immutable files of the databases when the
+//! backup is on the same filesystem, taking one is cheap even for a large storage.
+//!
+//! A backup is restored with [`restore`] while the node is stopped, it copies the databases
+//! under the storage path of the node, which then starts from the delivered state of the
+//! backup instead of synchronizing it from its peers.
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constant::paths,
+    errors::{BackupError, StorageError},
+    SourceHead,
+};
+
+/// Name of the manifest file of a backup
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Description of the delivered state held by a backup
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupManifest {
+    /// Number of certificates delivered at the time of the backup
+    pub delivered_certificates: usize,
+    /// Source head of every subnet known at the time of the backup
+    pub source_heads: Vec<SourceHead>,
+}
+
+impl BackupManifest {
+    /// Read the manifest of the backup at the given path
+    ///
+    /// The manifest is written once every checkpoint is taken, a backup without one is
+    /// incomplete.
+    pub fn read(backup_path: &Path) -> Result<Self, StorageError> {
+        let path = backup_path.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Err(BackupError::MissingManifest(path).into());
+        }
+
+        let file = File::open(path).map_err(BackupError::Io)?;
+
+        Ok(serde_json::from_reader(BufReader::new(file)).map_err(BackupError::InvalidManifest)?)
+    }
+
+    pub(crate) fn write(&self, backup_path: &Path) -> Result<(), StorageError> {
+        let file = File::create(backup_path.join(MANIFEST_FILE)).map_err(BackupError::Io)?;
+
+        Ok(serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .map_err(BackupError::InvalidManifest)?)
+    }
+}
+
+/// Restore the backup at `backup_path` as the storage at `path`
+///
+/// The node using the storage must be stopped. None of the databases of the backup can
+/// already exist under `path`.
+pub fn restore(backup_path: &Path, path: &Path) -> Result<BackupManifest, StorageError> {
+    let manifest = BackupManifest::read(backup_path)?;
+
+    if let Some(database) = paths::DATABASES
+        .iter()
+        .map(|database| path.join(database))
+        .find(|database| database.exists())
+    {
+        return Err(BackupError::DatabaseAlreadyExists(database).into());
+    }
+
+    for database in paths::DATABASES {
+        let source = backup_path.join(database);
+        if source.exists() {
+            copy_dir(&source, &path.join(database)).map_err(BackupError::Io)?;
+        }
+    }
+
+    Ok(manifest)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
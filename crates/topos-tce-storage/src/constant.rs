@@ -3,6 +3,8 @@ pub(crate) mod cfs {
     pub(crate) const STREAMS: &str = "streams";
     pub(crate) const EPOCH_CHAIN: &str = "epoch_chain";
     pub(crate) const UNVERIFIED: &str = "unverified";
+    pub(crate) const PROOFS: &str = "proofs";
+    pub(crate) const PRUNED_STREAMS: &str = "pruned_streams";
 
     pub(crate) const PENDING_POOL: &str = "pending_pool";
     pub(crate) const PENDING_POOL_INDEX: &str = "pending_pool_index";
@@ -19,3 +21,14 @@ pub(crate) mod cfs {
     pub(crate) const EPOCH_SUMMARY: &str = "epoch_summary";
     pub(crate) const BROADCAST_STATES: &str = "broadcast_states";
 }
+
+/// Directories of the databases opened under the storage path
+pub(crate) mod paths {
+    pub(crate) const PERPETUAL: &str = "perpetual";
+    pub(crate) const INDEX: &str = "index";
+    pub(crate) const PENDING: &str = "pending";
+    pub(crate) const VALIDATORS: &str = "validators";
+    pub(crate) const EPOCHS: &str = "epochs";
+
+    pub(crate) const DATABASES: [&str; 5] = [PERPETUAL, INDEX, PENDING, VALIDATORS, EPOCHS];
+}
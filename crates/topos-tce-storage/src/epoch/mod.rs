@@ -1,10 +1,10 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::{collections::HashMap, sync::RwLock};
 
 use arc_swap::ArcSwap;
 
-use crate::errors::StorageError;
+use crate::errors::{InternalStorageError, StorageError};
 use crate::types::{EpochId, Validators};
 
 pub use self::tables::EpochValidatorsTables;
@@ -14,17 +14,28 @@ mod tables;
 
 /// Epoch contextualized data - can be purged at some point
 pub struct ValidatorPerEpochStore {
-    #[allow(unused)]
     epoch_id: EpochId,
-    #[allow(unused)]
     validators: RwLock<Validators>,
     #[allow(unused)]
     tables: ValidatorPerEpochTables,
 }
 
 impl ValidatorPerEpochStore {
+    #[cfg(feature = "rocksdb")]
     pub fn new(epoch_id: EpochId, path: PathBuf) -> Result<ArcSwap<Self>, StorageError> {
-        let tables: ValidatorPerEpochTables = ValidatorPerEpochTables::open(epoch_id, path);
+        Self::with_tables(epoch_id, ValidatorPerEpochTables::open(epoch_id, path))
+    }
+
+    /// Create the store of the given epoch in memory, nothing is persisted on disk
+    #[cfg(feature = "inmemory")]
+    pub fn new_in_memory(epoch_id: EpochId) -> Result<ArcSwap<Self>, StorageError> {
+        Self::with_tables(epoch_id, ValidatorPerEpochTables::open_in_memory())
+    }
+
+    fn with_tables(
+        epoch_id: EpochId,
+        tables: ValidatorPerEpochTables,
+    ) -> Result<ArcSwap<Self>, StorageError> {
         let store = ArcSwap::from(Arc::new(Self {
             epoch_id,
             validators: RwLock::new(Vec::new()),
@@ -33,6 +44,31 @@ impl ValidatorPerEpochStore {
 
         Ok(store)
     }
+
+    /// Returns the id of the epoch of the store
+    pub fn epoch_id(&self) -> EpochId {
+        self.epoch_id
+    }
+
+    /// Returns the validator set of the epoch, empty as long as none is recorded
+    pub fn validators(&self) -> Validators {
+        self.validators
+            .read()
+            .map(|validators| validators.clone())
+            .unwrap_or_default()
+    }
+
+    /// Record the validator set of the epoch
+    pub fn set_validators(&self, validators: Validators) {
+        if let Ok(mut current) = self.validators.write() {
+            *current = validators;
+        }
+    }
+
+    /// Create a checkpoint of the tables of the epoch under the given path
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.tables.checkpoint(self.epoch_id, path)
+    }
 }
 pub struct EpochValidatorsStore {
     #[allow(unused)]
@@ -42,8 +78,18 @@ pub struct EpochValidatorsStore {
 }
 
 impl EpochValidatorsStore {
+    #[cfg(feature = "rocksdb")]
     pub fn new(path: PathBuf) -> Result<Arc<Self>, StorageError> {
-        let tables = EpochValidatorsTables::open(path);
+        Self::with_tables(EpochValidatorsTables::open(path))
+    }
+
+    /// Create the store in memory, nothing is persisted on disk
+    #[cfg(feature = "inmemory")]
+    pub fn new_in_memory() -> Result<Arc<Self>, StorageError> {
+        Self::with_tables(EpochValidatorsTables::open_in_memory())
+    }
+
+    fn with_tables(tables: EpochValidatorsTables) -> Result<Arc<Self>, StorageError> {
         let store = Arc::new(Self {
             tables,
             caches: RwLock::new(HashMap::new()),
@@ -51,4 +97,9 @@ impl EpochValidatorsStore {
 
         Ok(store)
     }
+
+    /// Create a checkpoint of the validators tables under the given path
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.tables.checkpoint(path)
+    }
 }
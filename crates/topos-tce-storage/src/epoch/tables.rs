@@ -1,15 +1,21 @@
+use std::path::Path;
+#[cfg(feature = "rocksdb")]
 use std::{fs::create_dir_all, path::PathBuf};
 
+#[cfg(feature = "rocksdb")]
 use rocksdb::ColumnFamilyDescriptor;
 use topos_core::uci::CertificateId;
+#[cfg(feature = "rocksdb")]
 use tracing::warn;
 
+#[cfg(feature = "inmemory")]
+use crate::backend::memory::MemoryDB;
+#[cfg(feature = "rocksdb")]
+use crate::rocks::db::{default_options, init_db, init_with_cfs};
 use crate::{
-    constant::cfs,
-    rocks::{
-        db::{default_options, init_db, init_with_cfs},
-        db_column::DBColumn,
-    },
+    backend::{db_column::DBColumn, StorageBackend},
+    constant::{cfs, paths},
+    errors::InternalStorageError,
     types::{BroadcastState, EpochId, Validators, VerifiedCheckpointSummary},
 };
 
@@ -19,16 +25,32 @@ pub struct EpochValidatorsTables {
 }
 
 impl EpochValidatorsTables {
+    #[cfg(feature = "rocksdb")]
     pub(crate) fn open(mut path: PathBuf) -> Self {
-        path.push("validators");
+        path.push(paths::VALIDATORS);
         let mut options = rocksdb::Options::default();
         options.create_if_missing(true);
         let db = init_db(&path, options).unwrap_or_else(|_| panic!("Cannot open DB at {:?}", path));
 
+        Self::from_backend(&db)
+    }
+
+    #[cfg(feature = "inmemory")]
+    pub(crate) fn open_in_memory() -> Self {
+        Self::from_backend(&MemoryDB::default())
+    }
+
+    fn from_backend(backend: &impl StorageBackend) -> Self {
         Self {
-            validators_map: DBColumn::reopen(&db, cfs::VALIDATORS),
+            validators_map: DBColumn::reopen(backend, cfs::VALIDATORS),
         }
     }
+
+    /// Create a checkpoint of the [`EpochValidatorsTables`] under the given path
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.validators_map
+            .checkpoint(&path.join(paths::VALIDATORS))
+    }
 }
 
 /// Epoch contextualized data - can be purged at some point
@@ -42,8 +64,9 @@ pub struct ValidatorPerEpochTables {
 }
 
 impl ValidatorPerEpochTables {
+    #[cfg(feature = "rocksdb")]
     pub(crate) fn open(epoch_id: EpochId, mut path: PathBuf) -> Self {
-        path.push("epochs");
+        path.push(paths::EPOCHS);
         path.push(epoch_id.to_string());
         if !path.exists() {
             warn!("Path {:?} does not exist, creating it", path);
@@ -57,12 +80,32 @@ impl ValidatorPerEpochTables {
         let db = init_with_cfs(&path, default_options(), cfs)
             .unwrap_or_else(|_| panic!("Cannot open DB at {:?}", path));
 
+        Self::from_backend(&db)
+    }
+
+    #[cfg(feature = "inmemory")]
+    pub(crate) fn open_in_memory() -> Self {
+        Self::from_backend(&MemoryDB::default())
+    }
+
+    fn from_backend(backend: &impl StorageBackend) -> Self {
         Self {
-            epoch_summary: DBColumn::reopen(&db, cfs::EPOCH_SUMMARY),
-            broadcast_states: DBColumn::reopen(&db, cfs::BROADCAST_STATES),
+            epoch_summary: DBColumn::reopen(backend, cfs::EPOCH_SUMMARY),
+            broadcast_states: DBColumn::reopen(backend, cfs::BROADCAST_STATES),
             validators: Vec::new(),
         }
     }
+
+    /// Create a checkpoint of the [`ValidatorPerEpochTables`] of the given epoch under the
+    /// given path, whose epochs directory must already exist
+    pub(crate) fn checkpoint(
+        &self,
+        epoch_id: EpochId,
+        path: &Path,
+    ) -> Result<(), InternalStorageError> {
+        self.epoch_summary
+            .checkpoint(&path.join(paths::EPOCHS).join(epoch_id.to_string()))
+    }
 }
 
 #[allow(unused)]
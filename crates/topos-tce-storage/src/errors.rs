@@ -1,7 +1,9 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 use topos_core::{
-    types::stream::PositionError,
+    types::stream::{Position, PositionError},
     uci::{CertificateId, SubnetId, SUBNET_ID_LENGTH},
 };
 
@@ -10,6 +12,9 @@ pub enum InternalStorageError {
     #[error("The certificate already exists")]
     CertificateAlreadyExists,
 
+    #[error("The certificate is already pending")]
+    CertificateAlreadyPending,
+
     #[error("Unable to find a certificate: {0:?}")]
     CertificateNotFound(CertificateId),
 
@@ -33,6 +38,9 @@ pub enum InternalStorageError {
     #[error(transparent)]
     Bincode(#[from] Box<bincode::ErrorKind>),
 
+    #[error("Operation not supported by the storage backend: {0}")]
+    UnsupportedOperation(&'static str),
+
     #[error("A concurrent DBBatch has been detected")]
     ConcurrentDBBatchDetected,
 
@@ -62,4 +70,55 @@ pub enum StorageError {
 
     #[error("Unable to execute shutdown on the storage service: {0}")]
     ShutdownCommunication(mpsc::error::SendError<oneshot::Sender<()>>),
+
+    #[error(transparent)]
+    Backup(#[from] BackupError),
+
+    #[error(transparent)]
+    Pruned(#[from] PruningError),
+}
+
+#[derive(Debug, Error)]
+pub enum PruningError {
+    #[error("Certificate {0} has been pruned, only its proof of delivery is kept")]
+    CertificatePruned(CertificateId),
+
+    #[error(
+        "Position {position} of the source stream of {subnet_id} has been pruned, the stream \
+         starts at position {first_available}"
+    )]
+    PositionPruned {
+        subnet_id: SubnetId,
+        position: Position,
+        first_available: Position,
+    },
+
+    #[error(
+        "Position {position} of the target stream of {target_subnet_id} from {source_subnet_id} \
+         has been pruned, the stream starts at position {first_available}"
+    )]
+    TargetPositionPruned {
+        target_subnet_id: SubnetId,
+        source_subnet_id: SubnetId,
+        position: Position,
+        first_available: Position,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("Backup path {0} already exists")]
+    BackupAlreadyExists(PathBuf),
+
+    #[error("Unable to restore over the existing database {0}")]
+    DatabaseAlreadyExists(PathBuf),
+
+    #[error("Missing backup manifest {0}, the backup is incomplete")]
+    MissingManifest(PathBuf),
+
+    #[error("Invalid backup manifest: {0}")]
+    InvalidManifest(#[from] serde_json::Error),
+
+    #[error("Unable to access the backup files: {0}")]
+    Io(#[from] std::io::Error),
 }
@@ -1,23 +1,29 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::Arc,
+};
 
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
+use tokio::sync::RwLock;
 
 use topos_core::{
     types::{
         stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition, Position},
-        CertificateDelivered,
+        CertificateDelivered, ProofOfDelivery,
     },
     uci::{CertificateId, SubnetId},
 };
 use tracing::{error, info};
 
 use crate::{
+    backend::map::Map,
     epoch::{EpochValidatorsStore, ValidatorPerEpochStore},
-    errors::{InternalStorageError, StorageError},
+    errors::{InternalStorageError, PruningError, StorageError},
     index::IndexTables,
-    rocks::{map::Map, TargetSourceListKey},
     store::{ReadStore, WriteStore},
+    types::{EpochId, TargetSourceListKey, Validators},
     validator::ValidatorPerpetualTables,
     CertificatePositions, SourceHead,
 };
@@ -41,9 +47,10 @@ pub mod locking;
 pub struct FullNodeStore {
     certificate_lock_guards: LockGuards<CertificateId>,
     subnet_lock_guards: LockGuards<SubnetId>,
-    #[allow(unused)]
+    /// Held shared while delivering certificates and exclusively while taking a backup, so
+    /// that a backup never contains a partially delivered certificate
+    pub(crate) backup_guard: RwLock<()>,
     epoch_store: ArcSwap<ValidatorPerEpochStore>,
-    #[allow(unused)]
     validators_store: Arc<EpochValidatorsStore>,
     pub(crate) perpetual_tables: Arc<ValidatorPerpetualTables>,
     pub(crate) index_tables: Arc<IndexTables>,
@@ -59,17 +66,255 @@ impl FullNodeStore {
         Ok(Arc::new(Self {
             certificate_lock_guards: LockGuards::new(),
             subnet_lock_guards: LockGuards::new(),
+            backup_guard: RwLock::new(()),
             epoch_store,
             validators_store,
             perpetual_tables,
             index_tables,
         }))
     }
-}
 
-#[async_trait]
-impl WriteStore for FullNodeStore {
-    async fn insert_certificate_delivered(
+    /// Open a [`FullNodeStore`] whose tables and stores all live in memory, nothing is
+    /// persisted on disk
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory() -> Result<Arc<Self>, StorageError> {
+        Self::open(
+            ValidatorPerEpochStore::new_in_memory(0)?,
+            EpochValidatorsStore::new_in_memory()?,
+            Arc::new(ValidatorPerpetualTables::open_in_memory()),
+            Arc::new(IndexTables::open_in_memory()),
+        )
+    }
+
+    /// Returns the id of the current epoch
+    pub fn current_epoch(&self) -> EpochId {
+        self.epoch_store.load().epoch_id()
+    }
+
+    /// Returns the validator set of the current epoch
+    pub fn current_validators(&self) -> Validators {
+        self.epoch_store.load().validators()
+    }
+
+    /// Record the validator set of the current epoch
+    pub fn set_current_validators(&self, validators: Validators) {
+        self.epoch_store.load().set_validators(validators)
+    }
+
+    /// Create a checkpoint of the tables and stores under the given path
+    ///
+    /// The caller is responsible for holding the `backup_guard` exclusively.
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), StorageError> {
+        self.perpetual_tables.checkpoint(path)?;
+        self.index_tables.checkpoint(path)?;
+        self.validators_store.checkpoint(path)?;
+        self.epoch_store.load().checkpoint(path)?;
+
+        Ok(())
+    }
+
+    /// Compact the tables holding the delivered certificates and their indexes
+    pub(crate) fn compact(&self) -> Result<(), StorageError> {
+        self.perpetual_tables.compact()?;
+        self.index_tables.compact()?;
+
+        Ok(())
+    }
+
+    /// Returns an estimation of the number of delivered certificates, including the pruned
+    /// ones still holding their proof
+    ///
+    /// Unlike [`ReadStore::count_certificates_delivered`], the tables are not iterated.
+    pub fn estimate_certificates_delivered(&self) -> Result<u64, StorageError> {
+        Ok(self.perpetual_tables.certificates.estimate_num_keys()?
+            + self.perpetual_tables.proofs.estimate_num_keys()?)
+    }
+
+    /// Maximum number of certificates pruned in one write batch
+    const PRUNING_BATCH_SIZE: usize = 1000;
+
+    /// Returns the first position of the source stream of a subnet that has not been pruned
+    pub fn first_available_position(&self, subnet_id: &SubnetId) -> Result<Position, StorageError> {
+        Ok(self
+            .perpetual_tables
+            .pruned_streams
+            .get(subnet_id)?
+            .unwrap_or(Position::ZERO))
+    }
+
+    /// Returns the proof of delivery of a certificate, even if the certificate has been pruned
+    pub fn get_proof_of_delivery(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<ProofOfDelivery>, StorageError> {
+        if let Some(certificate) = self.perpetual_tables.certificates.get(certificate_id)? {
+            return Ok(Some(certificate.proof_of_delivery));
+        }
+
+        Ok(self.perpetual_tables.proofs.get(certificate_id)?)
+    }
+
+    /// Prune every source stream down to its last `retained_certificates` certificates
+    ///
+    /// The certificates and stream entries below the retained range are removed along with
+    /// their leftover unverified proofs. The proof of delivery of a pruned certificate is
+    /// kept, and so is the source head of every stream as at least one certificate is
+    /// retained. Returns the number of certificates pruned.
+    pub async fn prune(&self, retained_certificates: u64) -> Result<usize, StorageError> {
+        let _backup_guard = self.backup_guard.read().await;
+        let retained_certificates = retained_certificates.max(1);
+
+        let heads: Vec<(SubnetId, Position)> = self
+            .index_tables
+            .source_list
+            .iter()?
+            .map(|(subnet_id, (_, position))| (subnet_id, position))
+            .collect();
+
+        let mut pruned = 0;
+        for (subnet_id, head) in heads {
+            let until = (*head + 1).saturating_sub(retained_certificates).into();
+            pruned += self.prune_source_stream(subnet_id, until).await?;
+        }
+
+        Ok(pruned)
+    }
+
+    /// Prune the source stream of a subnet up to the position `until`, excluded
+    async fn prune_source_stream(
+        &self,
+        subnet_id: SubnetId,
+        until: Position,
+    ) -> Result<usize, StorageError> {
+        let subnet_lock = self.subnet_lock_guards.get_lock(subnet_id).await;
+        let _subnet_guard = subnet_lock.lock().await;
+
+        if until <= self.first_available_position(&subnet_id)? {
+            return Ok(0);
+        }
+
+        let entries: Vec<(CertificateSourceStreamPosition, CertificateId)> = self
+            .perpetual_tables
+            .streams
+            .prefix_iter(&subnet_id)?
+            .take_while(|(position, _)| position.position < until)
+            .collect();
+
+        for chunk in entries.chunks(Self::PRUNING_BATCH_SIZE) {
+            let positions: Vec<_> = chunk.iter().map(|(position, _)| position).collect();
+            let certificate_ids: Vec<_> = chunk.iter().map(|(_, id)| *id).collect();
+
+            let pruned: Vec<CertificateDelivered> = self
+                .perpetual_tables
+                .certificates
+                .multi_get(&certificate_ids[..])?
+                .into_iter()
+                .flatten()
+                .collect();
+
+            // The index lives in another database, it is pruned first so that an interrupted
+            // round is resumed from the source stream
+            self.prune_target_streams(subnet_id, &pruned)?;
+
+            let proofs: Vec<(CertificateId, ProofOfDelivery)> = pruned
+                .into_iter()
+                .map(|delivered| (delivered.certificate.id, delivered.proof_of_delivery))
+                .collect();
+
+            let first_available = positions
+                .last()
+                .map(|position| position.position.increment())
+                .transpose()
+                .map_err(|error| InternalStorageError::PositionError(error, subnet_id.into()))?
+                .unwrap_or(until);
+
+            self.perpetual_tables
+                .proofs
+                .batch()
+                .insert_batch(&self.perpetual_tables.proofs, proofs)?
+                .insert_batch(
+                    &self.perpetual_tables.pruned_streams,
+                    [(&subnet_id, &first_available)],
+                )?
+                .delete_batch(&self.perpetual_tables.certificates, &certificate_ids)?
+                .delete_batch(&self.perpetual_tables.unverified, &certificate_ids)?
+                .delete_batch(&self.perpetual_tables.streams, positions)?
+                .write()?;
+        }
+
+        if !entries.is_empty() {
+            info!(
+                "Pruned {} certificates of the source stream of {}, stream now starts at {}",
+                entries.len(),
+                subnet_id,
+                until
+            );
+        }
+
+        Ok(entries.len())
+    }
+
+    /// Remove the pruned certificates of a source stream from the target streams
+    ///
+    /// The pruned certificates being the first ones of the source stream, their entries are
+    /// the first ones of every target stream fed by the source stream.
+    fn prune_target_streams(
+        &self,
+        source_subnet_id: SubnetId,
+        pruned: &[CertificateDelivered],
+    ) -> Result<(), StorageError> {
+        let pruned_ids: HashSet<CertificateId> = pruned
+            .iter()
+            .map(|delivered| delivered.certificate.id)
+            .collect();
+        let target_subnet_ids: HashSet<SubnetId> = pruned
+            .iter()
+            .flat_map(|delivered| delivered.certificate.target_subnets.iter().copied())
+            .collect();
+
+        let mut batch = self.index_tables.target_streams.batch();
+        for target_subnet_id in target_subnet_ids {
+            let positions: Vec<CertificateTargetStreamPosition> = self
+                .index_tables
+                .target_streams
+                .prefix_iter(&TargetSourceListKey(target_subnet_id, source_subnet_id))?
+                .take_while(|(_, certificate_id)| pruned_ids.contains(certificate_id))
+                .map(|(position, _)| position)
+                .collect();
+
+            batch = batch.delete_batch(&self.index_tables.target_streams, positions)?;
+        }
+        batch.write()?;
+
+        Ok(())
+    }
+
+    /// Compact the columns emptied by the pruning, reclaiming the space of the pruned
+    /// certificates
+    ///
+    /// This is a blocking operation.
+    pub(crate) fn compact_pruned(&self) -> Result<(), StorageError> {
+        self.perpetual_tables.certificates.compact()?;
+        self.perpetual_tables.streams.compact()?;
+        self.perpetual_tables.unverified.compact()?;
+        self.index_tables.target_streams.compact()?;
+
+        Ok(())
+    }
+
+    /// Returns an error if the certificate has been pruned
+    fn check_not_pruned(&self, certificate_id: &CertificateId) -> Result<(), StorageError> {
+        if self.perpetual_tables.proofs.get(certificate_id)?.is_some() {
+            return Err(PruningError::CertificatePruned(*certificate_id).into());
+        }
+
+        Ok(())
+    }
+
+    /// Persist a delivered certificate and update the streams accordingly
+    ///
+    /// The caller is responsible for holding the `backup_guard`.
+    pub(crate) async fn deliver_certificate(
         &self,
         certificate: &CertificateDelivered,
     ) -> Result<CertificatePositions, StorageError> {
@@ -93,6 +338,17 @@ impl WriteStore for FullNodeStore {
         let mut batch = self.perpetual_tables.certificates.batch();
         let mut index_batch = self.index_tables.target_streams.batch();
 
+        // Check position already pruned
+        let first_available = self.first_available_position(&subnet_id)?;
+        if expected_position.position < first_available {
+            return Err(PruningError::PositionPruned {
+                subnet_id,
+                position: expected_position.position,
+                first_available,
+            }
+            .into());
+        }
+
         // Check position already taken
         if let Some(delivered_at_position) =
             self.perpetual_tables.streams.get(&expected_position)?
@@ -160,27 +416,19 @@ impl WriteStore for FullNodeStore {
             .collect();
 
         for target_subnet_id in &certificate.certificate.target_subnets {
-            let target = match self
+            // The head of the target stream is kept aside, its first entries can be pruned
+            let position = match self
                 .index_tables
-                .target_streams
-                .prefix_iter(&TargetSourceListKey(*target_subnet_id, subnet_id))?
-                .last()
+                .target_source_list
+                .get(&TargetSourceListKey(*target_subnet_id, subnet_id))?
             {
-                None => CertificateTargetStreamPosition::new(
-                    *target_subnet_id,
-                    subnet_id,
-                    Position::ZERO,
-                ),
-                Some((mut target_stream_position, _)) => {
-                    target_stream_position.position = target_stream_position
-                        .position
-                        .increment()
-                        .map_err(|error| {
-                        InternalStorageError::PositionError(error, subnet_id.into())
-                    })?;
-                    target_stream_position
-                }
+                None => Position::ZERO,
+                Some(head) => head.increment().map_err(|error| {
+                    InternalStorageError::PositionError(error, subnet_id.into())
+                })?,
             };
+            let target =
+                CertificateTargetStreamPosition::new(*target_subnet_id, subnet_id, position);
 
             target_subnet_stream_positions.insert(*target_subnet_id, target);
 
@@ -214,6 +462,18 @@ impl WriteStore for FullNodeStore {
             source: expected_position,
         })
     }
+}
+
+#[async_trait]
+impl WriteStore for FullNodeStore {
+    async fn insert_certificate_delivered(
+        &self,
+        certificate: &CertificateDelivered,
+    ) -> Result<CertificatePositions, StorageError> {
+        let _backup_guard = self.backup_guard.read().await;
+
+        self.deliver_certificate(certificate).await
+    }
 
     async fn insert_certificates_delivered(
         &self,
@@ -228,7 +488,8 @@ impl WriteStore for FullNodeStore {
 
 impl ReadStore for FullNodeStore {
     fn count_certificates_delivered(&self) -> Result<usize, StorageError> {
-        Ok(self.perpetual_tables.certificates.iter()?.count())
+        Ok(self.perpetual_tables.certificates.iter()?.count()
+            + self.perpetual_tables.proofs.iter()?.count())
     }
 
     fn get_source_head(&self, subnet_id: &SubnetId) -> Result<Option<SourceHead>, StorageError> {
@@ -247,7 +508,10 @@ impl ReadStore for FullNodeStore {
         &self,
         certificate_id: &CertificateId,
     ) -> Result<Option<CertificateDelivered>, StorageError> {
-        Ok(self.perpetual_tables.certificates.get(certificate_id)?)
+        match self.perpetual_tables.certificates.get(certificate_id)? {
+            None => self.check_not_pruned(certificate_id).map(|_| None),
+            certificate => Ok(certificate),
+        }
     }
 
     fn get_certificates(
@@ -295,7 +559,18 @@ impl ReadStore for FullNodeStore {
         from: CertificateSourceStreamPosition,
         limit: usize,
     ) -> Result<Vec<(CertificateDelivered, CertificateSourceStreamPosition)>, StorageError> {
-        let starting_position = from.position;
+        let first_available = self.first_available_position(&from.subnet_id)?;
+        if from.position < first_available {
+            return Err(PruningError::PositionPruned {
+                subnet_id: from.subnet_id,
+                position: from.position,
+                first_available,
+            }
+            .into());
+        }
+
+        // The stream entries before the first available position have been pruned
+        let starting_position = Position::from(*from.position - *first_available);
         let x: Vec<(CertificateId, CertificateSourceStreamPosition)> = self
             .perpetual_tables
             .streams
@@ -337,16 +612,31 @@ impl ReadStore for FullNodeStore {
         let certs_with_positions: Vec<(CertificateId, CertificateTargetStreamPosition)> = self
             .index_tables
             .target_streams
-            .prefix_iter(&prefix)?
-            .skip(starting_position.try_into().map_err(|_| {
-                StorageError::InternalStorage(InternalStorageError::InvalidQueryArgument(
-                    "Unable to parse Position",
-                ))
-            })?)
+            .prefix_iter_at(&prefix, &position)?
             .take(limit)
             .map(|(k, v)| (v, k))
             .collect();
 
+        // The first entries of a target stream are removed along with the pruned certificates
+        let first_available = match certs_with_positions.first() {
+            Some((_, first)) => first.position,
+            None => match self.index_tables.target_source_list.get(&prefix)? {
+                Some(head) => head.increment().map_err(|error| {
+                    InternalStorageError::PositionError(error, position.source_subnet_id.into())
+                })?,
+                None => Position::ZERO,
+            },
+        };
+        if starting_position < first_available {
+            return Err(PruningError::TargetPositionPruned {
+                target_subnet_id: position.target_subnet_id,
+                source_subnet_id: position.source_subnet_id,
+                position: starting_position,
+                first_available,
+            }
+            .into());
+        }
+
         let certificate_ids: Vec<_> = certs_with_positions
             .iter()
             .map(|(k, _)| k)
@@ -358,6 +648,17 @@ impl ReadStore for FullNodeStore {
             .certificates
             .multi_get(&certificate_ids[..])?;
 
+        if let Some(missing) =
+            certificate_ids
+                .iter()
+                .zip(&certificates)
+                .find_map(|(certificate_id, certificate)| {
+                    certificate.is_none().then_some(certificate_id)
+                })
+        {
+            self.check_not_pruned(missing)?;
+        }
+
         Ok(certs_with_positions
             .into_iter()
             .zip(certificates)
@@ -1,19 +1,27 @@
+use std::path::Path;
+#[cfg(feature = "rocksdb")]
 use std::{fs::create_dir_all, path::PathBuf};
 
+#[cfg(feature = "rocksdb")]
 use rocksdb::ColumnFamilyDescriptor;
 use topos_core::{
     types::stream::Position,
     uci::{CertificateId, SubnetId},
 };
+#[cfg(feature = "rocksdb")]
 use tracing::warn;
 
+#[cfg(feature = "inmemory")]
+use crate::backend::memory::MemoryDB;
+#[cfg(feature = "rocksdb")]
+use crate::rocks::{
+    constants,
+    db::{default_options, init_with_cfs},
+};
 use crate::{
-    constant::cfs,
-    rocks::{
-        constants,
-        db::{default_options, init_with_cfs},
-        db_column::DBColumn,
-    },
+    backend::{db_column::DBColumn, StorageBackend},
+    constant::{cfs, paths},
+    errors::InternalStorageError,
     types::{TargetSourceListColumn, TargetStreamsColumn},
 };
 
@@ -27,8 +35,9 @@ pub struct IndexTables {
 }
 
 impl IndexTables {
+    #[cfg(feature = "rocksdb")]
     pub fn open(mut path: PathBuf) -> Self {
-        path.push("index");
+        path.push(paths::INDEX);
         if !path.exists() {
             warn!("Path {:?} does not exist, creating it", path);
             create_dir_all(&path).expect("Cannot create IndexTables directory");
@@ -51,14 +60,37 @@ impl IndexTables {
         let db = init_with_cfs(&path, default_options(), cfs)
             .unwrap_or_else(|_| panic!("Cannot open DB at {:?}", path));
 
+        Self::from_backend(&db)
+    }
+
+    /// Open the [`IndexTables`] in memory, nothing is persisted on disk.
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory() -> Self {
+        Self::from_backend(&MemoryDB::default())
+    }
+
+    fn from_backend(backend: &impl StorageBackend) -> Self {
         Self {
-            target_streams: DBColumn::reopen(&db, cfs::TARGET_STREAMS),
-            target_source_list: DBColumn::reopen(&db, cfs::TARGET_SOURCE_LIST),
-            source_list: DBColumn::reopen(&db, cfs::SOURCE_LIST),
+            target_streams: DBColumn::reopen(backend, cfs::TARGET_STREAMS),
+            target_source_list: DBColumn::reopen(backend, cfs::TARGET_SOURCE_LIST),
+            source_list: DBColumn::reopen(backend, cfs::SOURCE_LIST),
             source_list_per_target: DBColumn::reopen(
-                &db,
+                backend,
                 cfs::DELIVERED_CERTIFICATES_PER_SOURCE_FOR_TARGET,
             ),
         }
     }
+
+    /// Create a checkpoint of the [`IndexTables`] under the given path
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.target_streams.checkpoint(&path.join(paths::INDEX))
+    }
+
+    /// Compact every column of the [`IndexTables`]
+    pub(crate) fn compact(&self) -> Result<(), InternalStorageError> {
+        self.target_streams.compact()?;
+        self.target_source_list.compact()?;
+        self.source_list.compact()?;
+        self.source_list_per_target.compact()
+    }
 }
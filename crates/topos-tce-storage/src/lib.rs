@@ -49,6 +49,9 @@
 //!
 //! When using the storage layer, be aware of the following:
 //! - The storage layer uses [rocksdb](https://rocksdb.org/) as the backend, which means don't need an external service, as `rocksdb` is an embedded key-value store.
+//! - The stores can also be opened in memory, see [`FullNodeStore::open_in_memory`](fn@fullnode::FullNodeStore::open_in_memory), nothing is then persisted on disk.
+//! - A consistent backup of a running node can be taken and restored on another node, see [`backup`](module@backup).
+//! - A node can be run in pruned mode, only keeping the most recent delivered certificates, see [`pruning`](module@pruning).
 //! - The storage layer uses [`Arc`](struct@std::sync::Arc) to share the stores between threads. It also means that a `store` is only instantiated once.
 //! - Some storage methods are batching multiple writes into a single transaction.
 //!
@@ -84,12 +87,15 @@ use topos_core::{
 };
 
 // v2
+pub(crate) mod backend;
+pub mod backup;
 pub mod constant;
 /// Epoch related store
 pub mod epoch;
 /// Fullnode store
 pub mod fullnode;
 pub mod index;
+pub mod pruning;
 pub mod types;
 pub mod validator;
 
@@ -0,0 +1,110 @@
+//! Storage modes and pruning of the delivered certificates
+//!
+//! An [`Archive`](StorageMode::Archive) node keeps every delivered certificate forever.
+//! A [`Pruned`](StorageMode::Pruned) node only keeps the most recent certificates of every
+//! source stream, the older ones are removed by a [`Pruner`] running in the background,
+//! from the source stream as well as from the target streams, the pruned columns being
+//! compacted after every round.
+//!
+//! The proof of delivery of a pruned certificate is kept, and so is the source head of
+//! every stream. Queries reaching a pruned certificate or a pruned range of a stream fail
+//! with a [`PruningError`](enum@crate::errors::PruningError) instead of returning partial
+//! results.
+use std::{future::IntoFuture, sync::Arc, time::Duration};
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::fullnode::FullNodeStore;
+
+/// Retention policy of the delivered certificates
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StorageMode {
+    /// Keep every delivered certificate
+    #[default]
+    Archive,
+    /// Only keep the last `retained_certificates` certificates of every source stream
+    Pruned { retained_certificates: u64 },
+}
+
+/// Background task pruning the [`FullNodeStore`] at a fixed interval
+pub struct Pruner {
+    store: Arc<FullNodeStore>,
+    retained_certificates: u64,
+    interval: Duration,
+    shutdown: CancellationToken,
+}
+
+impl Pruner {
+    /// Default interval between two pruning rounds
+    pub const INTERVAL: Duration = Duration::from_secs(60);
+
+    /// Returns the [`Pruner`] matching the storage mode, if any
+    pub fn new(
+        mode: StorageMode,
+        store: Arc<FullNodeStore>,
+        shutdown: CancellationToken,
+    ) -> Option<Self> {
+        match mode {
+            StorageMode::Archive => None,
+            StorageMode::Pruned {
+                retained_certificates,
+            } => Some(Self {
+                store,
+                retained_certificates,
+                interval: Self::INTERVAL,
+                shutdown,
+            }),
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+
+        self
+    }
+
+    /// Reclaim the space of the certificates pruned by a round, the deletions only being
+    /// tombstones until the columns are compacted
+    async fn compact(&self) {
+        let store = self.store.clone();
+        match tokio::task::spawn_blocking(move || store.compact_pruned()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => error!("Unable to compact the pruned storage: {}", error),
+            Err(error) => error!("Compaction of the pruned storage panicked: {}", error),
+        }
+    }
+}
+
+impl IntoFuture for Pruner {
+    type Output = ();
+
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        async move {
+            let mut interval = tokio::time::interval(self.interval);
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        match self.store.prune(self.retained_certificates).await {
+                            Ok(0) => {}
+                            Ok(pruned) => {
+                                info!("Pruning round done, {} certificates pruned", pruned);
+                                self.compact().await;
+                            }
+                            Err(error) => error!("Pruning round failed: {}", error),
+                        }
+                    }
+
+                    _ = self.shutdown.cancelled() => { break; }
+                }
+            }
+
+            info!("Shutting down the Pruner...");
+        }
+        .boxed()
+    }
+}
@@ -1,10 +1,6 @@
 use self::db::RocksDB;
 
+pub(crate) mod column;
 pub(crate) mod constants;
 pub(crate) mod db;
-pub(crate) mod db_column;
 pub(crate) mod iterator;
-pub(crate) mod map;
-pub(crate) mod types;
-
-pub(crate) use types::*;
@@ -0,0 +1,142 @@
+use std::{path::Path, sync::Arc};
+
+use rocksdb::{
+    checkpoint::Checkpoint, BoundColumnFamily, DBRawIteratorWithThreadMode, Direction, ReadOptions,
+};
+
+use crate::{
+    backend::{IteratorMode, KeyValueColumn, RawIterator, StorageBackend, WriteBatch},
+    errors::InternalStorageError,
+};
+
+use super::{iterator::RocksIterator, RocksDB};
+
+/// A column family of a RocksDB database
+pub(crate) struct RocksColumn {
+    rocksdb: RocksDB,
+    cf: &'static str,
+}
+
+impl RocksColumn {
+    /// Returns the CF of the RocksColumn, used to build queries.
+    fn cf(&self) -> Result<Arc<BoundColumnFamily<'_>>, InternalStorageError> {
+        cf_handle(&self.rocksdb, self.cf)
+    }
+}
+
+fn cf_handle<'a>(
+    rocksdb: &'a RocksDB,
+    cf: &'static str,
+) -> Result<Arc<BoundColumnFamily<'a>>, InternalStorageError> {
+    rocksdb
+        .cf_handle(cf)
+        .ok_or(InternalStorageError::InvalidColumnFamily(cf))
+}
+
+impl StorageBackend for RocksDB {
+    fn column(&self, name: &'static str) -> Arc<dyn KeyValueColumn> {
+        Arc::new(RocksColumn {
+            rocksdb: self.clone(),
+            cf: name,
+        })
+    }
+}
+
+impl KeyValueColumn for RocksColumn {
+    fn name(&self) -> &'static str {
+        self.cf
+    }
+
+    fn backend_id(&self) -> usize {
+        Arc::as_ptr(&self.rocksdb) as usize
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, InternalStorageError> {
+        Ok(self
+            .rocksdb
+            .get_pinned_cf(&self.cf()?, key)?
+            .map(|value| value.to_vec()))
+    }
+
+    fn multi_get(&self, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>, InternalStorageError> {
+        self.rocksdb
+            .batched_multi_get_cf_opt(&self.cf()?, keys, false, &ReadOptions::default())
+            .into_iter()
+            .map(|r| {
+                r.map(|value| value.map(|value| value.to_vec()))
+                    .map_err(InternalStorageError::RocksDBError)
+            })
+            .collect()
+    }
+
+    fn insert(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), InternalStorageError> {
+        Ok(self.rocksdb.put_cf(&self.cf()?, key, value)?)
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), InternalStorageError> {
+        Ok(self.rocksdb.delete_cf(&self.cf()?, key)?)
+    }
+
+    fn merge(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), InternalStorageError> {
+        Ok(self.rocksdb.merge_cf(&self.cf()?, key, value)?)
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<(), InternalStorageError> {
+        let mut write_batch = rocksdb::WriteBatch::default();
+        for (cf, key, value) in batch.inserts {
+            write_batch.put_cf(&cf_handle(&self.rocksdb, cf)?, key, value);
+        }
+        for (cf, key) in batch.deletes {
+            write_batch.delete_cf(&cf_handle(&self.rocksdb, cf)?, key);
+        }
+
+        Ok(self.rocksdb.write(write_batch)?)
+    }
+
+    fn iter(&self, mode: IteratorMode) -> Result<RawIterator<'_>, InternalStorageError> {
+        let mut raw_iterator = self.rocksdb.raw_iterator_cf(&self.cf()?);
+
+        match mode {
+            IteratorMode::Start => raw_iterator.seek_to_first(),
+            IteratorMode::End => raw_iterator.seek_to_last(),
+        }
+
+        Ok(Box::new(RocksIterator::new_with_direction(
+            raw_iterator,
+            Direction::Forward,
+        )))
+    }
+
+    fn prefix_iter(
+        &self,
+        prefix: &[u8],
+        from: Option<&[u8]>,
+    ) -> Result<RawIterator<'_>, InternalStorageError> {
+        let mut iterator: DBRawIteratorWithThreadMode<_> =
+            self.rocksdb.prefix_iterator_cf(&self.cf()?, prefix).into();
+
+        if let Some(from) = from {
+            iterator.seek(from);
+        }
+
+        Ok(Box::new(RocksIterator::new(iterator)))
+    }
+
+    fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        Ok(Checkpoint::new(&*self.rocksdb)?.create_checkpoint(path)?)
+    }
+
+    fn compact(&self) -> Result<(), InternalStorageError> {
+        self.rocksdb
+            .compact_range_cf(&self.cf()?, None::<&[u8]>, None::<&[u8]>);
+
+        Ok(())
+    }
+
+    fn estimate_num_keys(&self) -> Result<u64, InternalStorageError> {
+        Ok(self
+            .rocksdb
+            .property_int_value_cf(&self.cf()?, rocksdb::properties::ESTIMATE_NUM_KEYS)?
+            .unwrap_or_default())
+    }
+}
@@ -1,58 +1,46 @@
-use std::marker::PhantomData;
-
-use bincode::Options;
 use rocksdb::{DBRawIteratorWithThreadMode, DBWithThreadMode, Direction, MultiThreaded};
-use serde::de::DeserializeOwned;
 
-pub struct ColumnIterator<'a, K, V> {
+/// Iterator over the raw key/value pairs of a RocksDB column family
+pub(crate) struct RocksIterator<'a> {
     iterator: DBRawIteratorWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>,
     direction: Direction,
-    _phantom: PhantomData<(K, V)>,
 }
 
-impl<'a, K, V> ColumnIterator<'a, K, V> {
-    /// Creates a new ColumnIterator base on a DBRawIteratorWithThreadMode
-    pub fn new(iterator: DBRawIteratorWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>) -> Self {
+impl<'a> RocksIterator<'a> {
+    pub(crate) fn new(
+        iterator: DBRawIteratorWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>,
+    ) -> Self {
         Self::new_with_direction(iterator, Direction::Forward)
     }
 
-    pub fn new_with_direction(
+    pub(crate) fn new_with_direction(
         iterator: DBRawIteratorWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>,
         direction: Direction,
     ) -> Self {
         Self {
             iterator,
             direction,
-            _phantom: PhantomData,
         }
     }
 }
 
-impl<'a, K, V> Iterator for ColumnIterator<'a, K, V>
-where
-    K: DeserializeOwned,
-    V: DeserializeOwned,
-{
-    type Item = (K, V);
+impl<'a> Iterator for RocksIterator<'a> {
+    type Item = (Vec<u8>, Vec<u8>);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.iterator.valid() {
-            let config = bincode::DefaultOptions::new()
-                .with_big_endian()
-                .with_fixint_encoding();
-
-            let key = self.iterator.key().and_then(|k| config.deserialize(k).ok());
-            let value = self
+            let item = self
                 .iterator
-                .value()
-                .and_then(|v| bincode::deserialize(v).ok());
+                .key()
+                .zip(self.iterator.value())
+                .map(|(key, value)| (key.to_vec(), value.to_vec()));
 
             match self.direction {
                 Direction::Forward => self.iterator.next(),
                 Direction::Reverse => self.iterator.prev(),
             }
 
-            key.and_then(|k| value.map(|v| (k, v)))
+            item
         } else {
             None
         }
@@ -0,0 +1,138 @@
+use rstest::rstest;
+use test_log::test;
+
+use crate::backend::{
+    db_column::DBColumn, map::Map, memory::MemoryDB, IteratorMode, StorageBackend,
+};
+use crate::errors::InternalStorageError;
+use crate::rocks::constants;
+
+use super::support::{database_name, rocks_db};
+
+fn check_column_operations(
+    backend: &impl StorageBackend,
+    first: &'static str,
+    second: &'static str,
+) {
+    let column: DBColumn<(u64, u64), String> = DBColumn::reopen(backend, first);
+    let other_column: DBColumn<u64, u64> = DBColumn::reopen(backend, second);
+
+    column.insert(&(1, 1), &"one".to_string()).unwrap();
+    assert_eq!(column.get(&(1, 1)).unwrap(), Some("one".to_string()));
+    assert_eq!(column.get(&(1, 2)).unwrap(), None);
+
+    column.delete(&(1, 1)).unwrap();
+    assert_eq!(column.get(&(1, 1)).unwrap(), None);
+
+    // A batch spans several columns of the backend
+    column
+        .batch()
+        .insert_batch(
+            &column,
+            [
+                ((2u64, 1u64), "two".to_string()),
+                ((1, 2), "three".to_string()),
+                ((1, 1), "four".to_string()),
+            ],
+        )
+        .unwrap()
+        .insert_batch(&other_column, [(1u64, 10u64), (2, 20)])
+        .unwrap()
+        .write()
+        .unwrap();
+
+    assert_eq!(
+        other_column.multi_get(&[2, 3, 1]).unwrap(),
+        vec![Some(20), None, Some(10)]
+    );
+
+    // Keys are ordered
+    assert_eq!(
+        column
+            .iter()
+            .unwrap()
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>(),
+        vec![(1, 1), (1, 2), (2, 1)]
+    );
+    assert_eq!(
+        column
+            .iter_with_mode(IteratorMode::End)
+            .unwrap()
+            .collect::<Vec<_>>(),
+        vec![((2, 1), "two".to_string())]
+    );
+    assert_eq!(
+        column
+            .prefix_iter(&1u64)
+            .unwrap()
+            .next()
+            .map(|(key, _)| key),
+        Some((1, 1))
+    );
+    assert_eq!(
+        column
+            .prefix_iter_at(&1u64, &(1u64, 2u64))
+            .unwrap()
+            .next()
+            .map(|(key, _)| key),
+        Some((1, 2))
+    );
+}
+
+#[rstest]
+#[test]
+fn rocksdb_backend_column_operations(database_name: &'static str) {
+    check_column_operations(
+        &rocks_db(database_name),
+        constants::CERTIFICATES,
+        constants::PENDING_CERTIFICATES,
+    );
+}
+
+#[test]
+fn in_memory_backend_column_operations() {
+    check_column_operations(&MemoryDB::default(), "first", "second");
+}
+
+#[test]
+fn in_memory_backend_bounds_prefix_iterations() {
+    let column: DBColumn<(u64, u64), u64> = DBColumn::reopen(&MemoryDB::default(), "column");
+    column
+        .multi_insert([((1, 1), 11), ((1, 2), 12), ((2, 1), 21)])
+        .unwrap();
+
+    assert_eq!(
+        column.prefix_iter(&1u64).unwrap().collect::<Vec<_>>(),
+        vec![((1, 1), 11), ((1, 2), 12)]
+    );
+    assert_eq!(
+        column
+            .prefix_iter_at(&1u64, &(1u64, 2u64))
+            .unwrap()
+            .collect::<Vec<_>>(),
+        vec![((1, 2), 12)]
+    );
+    assert!(column.prefix_iter(&3u64).unwrap().next().is_none());
+}
+
+#[test]
+fn batch_cannot_span_backends() {
+    let column: DBColumn<u64, u64> = DBColumn::reopen(&MemoryDB::default(), "column");
+    let other_column: DBColumn<u64, u64> = DBColumn::reopen(&MemoryDB::default(), "column");
+
+    assert!(matches!(
+        column.batch().insert_batch(&other_column, [(1u64, 1u64)]),
+        Err(InternalStorageError::ConcurrentDBBatchDetected)
+    ));
+}
+
+#[test]
+fn in_memory_backend_does_not_support_merge() {
+    let column: DBColumn<u64, u64> = DBColumn::reopen(&MemoryDB::default(), "column");
+
+    assert!(matches!(
+        column.merge(&1, 1),
+        Err(InternalStorageError::UnsupportedOperation("merge"))
+    ));
+}
@@ -0,0 +1,136 @@
+use std::{fs, path::PathBuf, sync::Arc, thread};
+
+use rstest::rstest;
+use test_log::test;
+use topos_core::uci::Certificate;
+use topos_test_sdk::{certificates::create_certificate_chain, constants::*};
+
+use crate::{
+    backup::{restore, BackupManifest, MANIFEST_FILE},
+    errors::{BackupError, InternalStorageError, StorageError},
+    store::{ReadStore, WriteStore},
+    validator::ValidatorStore,
+};
+
+use super::support::{in_memory_store, open_store};
+
+fn folder(name: &str) -> PathBuf {
+    topos_test_sdk::storage::create_folder(&format!(
+        "{}_{name}",
+        thread::current().name().unwrap().replace("::", "_")
+    ))
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn backup_can_be_restored() {
+    let store = open_store(folder("source"));
+    let backup_path = folder("backup");
+    let restored_path = folder("restored");
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 5);
+    store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+    let pending_certificate = Certificate::new_with_default_fields(
+        certificates[4].certificate.id,
+        SOURCE_SUBNET_ID_1,
+        &[],
+    )
+    .unwrap();
+    store
+        .insert_pending_certificate(&pending_certificate)
+        .unwrap();
+
+    let manifest = store.backup(&backup_path).await.unwrap();
+    assert_eq!(manifest.delivered_certificates, 5);
+    assert_eq!(manifest.source_heads.len(), 1);
+    assert_eq!(
+        manifest.source_heads[0].certificate_id,
+        certificates[4].certificate.id
+    );
+
+    // Deliveries after the backup are not part of it
+    let next_certificates = create_certificate_chain(SOURCE_SUBNET_ID_2, &[TARGET_SUBNET_ID_1], 2);
+    store
+        .insert_certificates_delivered(&next_certificates)
+        .await
+        .unwrap();
+
+    let restored_manifest = restore(&backup_path, &restored_path).unwrap();
+    assert_eq!(restored_manifest.delivered_certificates, 5);
+
+    let restored_store = open_store(restored_path);
+    assert_eq!(restored_store.count_certificates_delivered().unwrap(), 5);
+    assert_eq!(restored_store.count_pending_certificates().unwrap(), 1);
+    assert_eq!(
+        restored_store
+            .get_source_head(&SOURCE_SUBNET_ID_1)
+            .unwrap()
+            .map(|head| head.certificate_id),
+        Some(certificates[4].certificate.id)
+    );
+    assert!(restored_store
+        .get_source_head(&SOURCE_SUBNET_ID_2)
+        .unwrap()
+        .is_none());
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn backup_does_not_overwrite_existing_path() {
+    let store = open_store(folder("source"));
+    let backup_path = folder("backup");
+    fs::create_dir_all(&backup_path).unwrap();
+
+    assert!(matches!(
+        store.backup(&backup_path).await,
+        Err(StorageError::Backup(BackupError::BackupAlreadyExists(_)))
+    ));
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn restore_does_not_overwrite_existing_database() {
+    let source_path = folder("source");
+    let store = open_store(source_path.clone());
+    let backup_path = folder("backup");
+
+    store.backup(&backup_path).await.unwrap();
+
+    assert!(matches!(
+        restore(&backup_path, &source_path),
+        Err(StorageError::Backup(BackupError::DatabaseAlreadyExists(_)))
+    ));
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn restore_requires_a_manifest() {
+    let store = open_store(folder("source"));
+    let backup_path = folder("backup");
+
+    store.backup(&backup_path).await.unwrap();
+    fs::remove_file(backup_path.join(MANIFEST_FILE)).unwrap();
+
+    assert!(matches!(
+        BackupManifest::read(&backup_path),
+        Err(StorageError::Backup(BackupError::MissingManifest(_)))
+    ));
+    assert!(matches!(
+        restore(&backup_path, &folder("restored")),
+        Err(StorageError::Backup(BackupError::MissingManifest(_)))
+    ));
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn in_memory_store_cannot_be_backed_up(in_memory_store: Arc<ValidatorStore>) {
+    assert!(matches!(
+        in_memory_store.backup(&folder("backup")).await,
+        Err(StorageError::InternalStorage(
+            InternalStorageError::UnsupportedOperation("checkpoint")
+        ))
+    ));
+}
@@ -5,7 +5,7 @@ use topos_core::uci::Certificate;
 use topos_test_sdk::certificates::create_certificate_at_position;
 use topos_test_sdk::constants::SOURCE_SUBNET_ID_1;
 
-use crate::rocks::map::Map;
+use crate::backend::map::Map;
 use crate::tests::{PREV_CERTIFICATE_ID, SOURCE_STORAGE_SUBNET_ID};
 use crate::types::{CertificatesColumn, PendingCertificatesColumn, StreamsColumn};
 use crate::Position;
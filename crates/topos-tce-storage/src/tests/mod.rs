@@ -10,19 +10,22 @@ use topos_core::{
 };
 
 use crate::{
-    rocks::map::Map,
+    backend::map::Map,
     store::{ReadStore, WriteStore},
     validator::ValidatorStore,
 };
 
-use self::support::store;
+use self::support::{in_memory_store, store};
 
 use topos_test_sdk::certificates::create_certificate_chain;
 use topos_test_sdk::constants::*;
 
+mod backends;
+mod backup;
 mod db_columns;
 mod pending_certificates;
 mod position;
+mod pruning;
 mod rocks;
 pub(crate) mod support;
 
@@ -588,3 +591,103 @@ async fn fetch_source_subnet_certificates_in_order(store: Arc<ValidatorStore>) {
         prev = cert.id;
     }
 }
+
+#[rstest]
+#[test(tokio::test)]
+async fn in_memory_stores_are_independent() {
+    let store = in_memory_store();
+    let other_store = in_memory_store();
+
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 5);
+    store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+
+    let pending_certificate = Certificate::new_with_default_fields(
+        certificates[4].certificate.id,
+        SOURCE_SUBNET_ID_1,
+        &[],
+    )
+    .unwrap();
+    let pending_id = store
+        .insert_pending_certificate(&pending_certificate)
+        .unwrap();
+    assert!(pending_id.is_some());
+
+    assert_eq!(store.count_certificates_delivered().unwrap(), 5);
+    assert_eq!(store.count_pending_certificates().unwrap(), 1);
+    assert_eq!(
+        store
+            .get_source_head(&SOURCE_SUBNET_ID_1)
+            .unwrap()
+            .map(|head| head.certificate_id),
+        Some(certificates[4].certificate.id)
+    );
+    assert_eq!(
+        store
+            .get_target_source_subnet_list(&TARGET_SUBNET_ID_1)
+            .unwrap(),
+        vec![SOURCE_SUBNET_ID_1]
+    );
+
+    assert_eq!(other_store.count_certificates_delivered().unwrap(), 0);
+    assert_eq!(other_store.count_pending_certificates().unwrap(), 0);
+    assert!(other_store
+        .get_source_head(&SOURCE_SUBNET_ID_1)
+        .unwrap()
+        .is_none());
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn checkpoint_diff_starts_after_the_known_position(store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 150);
+    store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+
+    let diff = store.get_checkpoint_diff(vec![], 100).unwrap();
+    let proofs = diff.get(&SOURCE_SUBNET_ID_1).unwrap();
+    assert_eq!(proofs.len(), 100);
+    assert_eq!(proofs[0].certificate_id, certificates[0].certificate.id);
+
+    let diff = store
+        .get_checkpoint_diff(vec![certificates[99].proof_of_delivery.clone()], 100)
+        .unwrap();
+    let proofs = diff.get(&SOURCE_SUBNET_ID_1).unwrap();
+    assert_eq!(
+        proofs
+            .iter()
+            .map(|proof| proof.certificate_id)
+            .collect::<Vec<_>>(),
+        certificates[100..]
+            .iter()
+            .map(|certificate| certificate.certificate.id)
+            .collect::<Vec<_>>()
+    );
+
+    // The requester pages through the stream with smaller diffs
+    let diff = store
+        .get_checkpoint_diff(vec![certificates[9].proof_of_delivery.clone()], 10)
+        .unwrap();
+    let proofs = diff.get(&SOURCE_SUBNET_ID_1).unwrap();
+    assert_eq!(
+        proofs
+            .iter()
+            .map(|proof| proof.certificate_id)
+            .collect::<Vec<_>>(),
+        certificates[10..20]
+            .iter()
+            .map(|certificate| certificate.certificate.id)
+            .collect::<Vec<_>>()
+    );
+
+    let diff = store
+        .get_checkpoint_diff(vec![certificates[149].proof_of_delivery.clone()], 100)
+        .unwrap();
+    assert!(diff
+        .get(&SOURCE_SUBNET_ID_1)
+        .map_or(true, |proofs| proofs.is_empty()));
+}
@@ -8,7 +8,11 @@ use topos_test_sdk::{
 };
 
 use super::support::store;
-use crate::{store::WriteStore, validator::ValidatorStore};
+use crate::{
+    errors::{InternalStorageError, StorageError},
+    store::WriteStore,
+    validator::ValidatorStore,
+};
 
 #[rstest]
 fn adding_genesis_pending_certificate(store: Arc<ValidatorStore>) {
@@ -59,6 +63,16 @@ async fn adding_pending_certificate_with_precedence_check_fail(store: Arc<Valida
         .get(&certificate.prev_id)
         .unwrap()
         .is_some());
+    assert_eq!(
+        store
+            .get_precedence_pool_certificate(&certificate.id)
+            .unwrap(),
+        Some(certificate.clone())
+    );
+    assert!(store
+        .get_precedence_pool_certificate(&certificate.prev_id)
+        .unwrap()
+        .is_none());
     store
         .insert_certificate_delivered(&initial_certificate_delivered)
         .await
@@ -86,3 +100,121 @@ async fn adding_pending_certificate_already_delivered(store: Arc<ValidatorStore>
         .insert_pending_certificate(&initial_certificate_delivered.certificate)
         .is_err());
 }
+
+#[rstest]
+fn adding_pending_certificate_twice(store: Arc<ValidatorStore>) {
+    let certificate = Certificate::new_with_default_fields(
+        INITIAL_CERTIFICATE_ID,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+    let child = Certificate::new_with_default_fields(
+        certificate.id,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+
+    assert!(store
+        .insert_pending_certificate(&certificate)
+        .unwrap()
+        .is_some());
+    assert!(store.insert_pending_certificate(&child).unwrap().is_none());
+
+    assert!(matches!(
+        store.insert_pending_certificate(&certificate),
+        Err(StorageError::InternalStorage(
+            InternalStorageError::CertificateAlreadyPending
+        ))
+    ));
+    assert!(matches!(
+        store.insert_pending_certificate(&child),
+        Err(StorageError::InternalStorage(
+            InternalStorageError::CertificateAlreadyPending
+        ))
+    ));
+    assert_eq!(store.count_pending_certificates().unwrap(), 1);
+}
+
+#[rstest]
+fn concurrent_submissions_are_inserted_once(store: Arc<ValidatorStore>) {
+    let certificate = Certificate::new_with_default_fields(
+        INITIAL_CERTIFICATE_ID,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+
+    let inserted = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| scope.spawn(|| store.insert_pending_certificate(&certificate)))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|result| match result {
+                Ok(pending_id) => pending_id.is_some(),
+                Err(StorageError::InternalStorage(
+                    InternalStorageError::CertificateAlreadyPending,
+                )) => false,
+                Err(error) => panic!("Unexpected error: {error}"),
+            })
+            .count()
+    });
+
+    assert_eq!(inserted, 1);
+    assert_eq!(store.count_pending_certificates().unwrap(), 1);
+}
+
+#[rstest]
+fn evicting_pending_certificate(store: Arc<ValidatorStore>) {
+    let certificate = Certificate::new_with_default_fields(
+        INITIAL_CERTIFICATE_ID,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+
+    let child = Certificate::new_with_default_fields(
+        certificate.id,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+    let grandchild =
+        Certificate::new_with_default_fields(child.id, SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1])
+            .unwrap();
+
+    let pending_id = store
+        .insert_pending_certificate(&certificate)
+        .unwrap()
+        .unwrap();
+    assert!(store.insert_pending_certificate(&child).unwrap().is_none());
+    assert!(store
+        .insert_pending_certificate(&grandchild)
+        .unwrap()
+        .is_none());
+
+    // The certificates waiting for the evicted one are evicted as well
+    assert_eq!(
+        store.evict_pending_certificate(&certificate.id).unwrap(),
+        Some((pending_id, vec![child.id, grandchild.id]))
+    );
+    assert!(store.get_pending_id(&certificate.id).unwrap().is_none());
+    assert!(store
+        .get_pending_certificate(&pending_id)
+        .unwrap()
+        .is_none());
+    assert_eq!(store.count_pending_certificates().unwrap(), 0);
+    assert_eq!(store.count_precedence_pool_certificates().unwrap(), 0);
+
+    // Evicting a certificate which is not pending is a no-op
+    assert!(store
+        .evict_pending_certificate(&certificate.id)
+        .unwrap()
+        .is_none());
+
+    store.compact().unwrap();
+}
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use rstest::rstest;
+use test_log::test;
+use tokio_util::sync::CancellationToken;
+use topos_core::{
+    types::{
+        stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition, Position},
+        CertificateDelivered, ProofOfDelivery,
+    },
+    uci::Certificate,
+};
+use topos_test_sdk::{certificates::create_certificate_chain, constants::*};
+
+use crate::{
+    errors::{PruningError, StorageError},
+    pruning::{Pruner, StorageMode},
+    store::{ReadStore, WriteStore},
+    validator::ValidatorStore,
+};
+
+use super::support::{in_memory_store, store};
+
+async fn prune_keeps_the_last_certificates_of_streams(store: Arc<ValidatorStore>) {
+    let fullnode_store = store.get_fullnode_store();
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 10);
+    store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+    store
+        .insert_unverified_proofs(vec![certificates[1].proof_of_delivery.clone()])
+        .unwrap();
+
+    assert_eq!(fullnode_store.prune(3).await.unwrap(), 7);
+    assert_eq!(fullnode_store.prune(3).await.unwrap(), 0);
+
+    // Pruned certificates are still counted as delivered and their proofs are kept
+    assert_eq!(store.count_certificates_delivered().unwrap(), 10);
+    assert_eq!(
+        fullnode_store
+            .get_proof_of_delivery(&certificates[0].certificate.id)
+            .unwrap(),
+        Some(certificates[0].proof_of_delivery.clone())
+    );
+    assert!(store
+        .get_unverified_proof(&certificates[1].certificate.id)
+        .unwrap()
+        .is_none());
+
+    assert!(matches!(
+        store.get_certificate(&certificates[0].certificate.id),
+        Err(StorageError::Pruned(PruningError::CertificatePruned(id)))
+            if id == certificates[0].certificate.id
+    ));
+    assert_eq!(
+        store
+            .get_certificate(&certificates[9].certificate.id)
+            .unwrap(),
+        Some(certificates[9].clone())
+    );
+    assert_eq!(
+        store
+            .get_source_head(&SOURCE_SUBNET_ID_1)
+            .unwrap()
+            .map(|head| head.certificate_id),
+        Some(certificates[9].certificate.id)
+    );
+
+    assert!(matches!(
+        store.get_source_stream_certificates_from_position(
+            CertificateSourceStreamPosition::new(SOURCE_SUBNET_ID_1, Position::ZERO),
+            10
+        ),
+        Err(StorageError::Pruned(PruningError::PositionPruned {
+            first_available,
+            ..
+        })) if *first_available == 7
+    ));
+    let retained: Vec<_> = store
+        .get_source_stream_certificates_from_position(
+            CertificateSourceStreamPosition::new(SOURCE_SUBNET_ID_1, 7),
+            10,
+        )
+        .unwrap()
+        .into_iter()
+        .map(|(certificate, _)| certificate)
+        .collect();
+    assert_eq!(retained, certificates[7..]);
+
+    // The target streams are pruned along with the source stream
+    assert!(matches!(
+        store.get_target_stream_certificates_from_position(
+            CertificateTargetStreamPosition::new(
+                TARGET_SUBNET_ID_1,
+                SOURCE_SUBNET_ID_1,
+                Position::ZERO
+            ),
+            10
+        ),
+        Err(StorageError::Pruned(PruningError::TargetPositionPruned {
+            first_available,
+            ..
+        })) if *first_available == 7
+    ));
+    let retained: Vec<_> = store
+        .get_target_stream_certificates_from_position(
+            CertificateTargetStreamPosition::new(TARGET_SUBNET_ID_1, SOURCE_SUBNET_ID_1, 7),
+            10,
+        )
+        .unwrap()
+        .into_iter()
+        .map(|(certificate, _)| certificate)
+        .collect();
+    assert_eq!(retained, certificates[7..]);
+
+    // Pruned positions can't be delivered again, the stream goes on from its head
+    assert!(matches!(
+        store.insert_certificate_delivered(&certificates[0]).await,
+        Err(StorageError::Pruned(PruningError::PositionPruned { .. }))
+    ));
+
+    let next = Certificate::new_with_default_fields(
+        certificates[9].certificate.id,
+        SOURCE_SUBNET_ID_1,
+        &[TARGET_SUBNET_ID_1],
+    )
+    .unwrap();
+    let next = CertificateDelivered {
+        proof_of_delivery: ProofOfDelivery {
+            certificate_id: next.id,
+            delivery_position: CertificateSourceStreamPosition::new(SOURCE_SUBNET_ID_1, 10),
+            readies: vec![],
+            threshold: 0,
+        },
+        certificate: next,
+    };
+    let positions = store.insert_certificate_delivered(&next).await.unwrap();
+    assert_eq!(store.count_certificates_delivered().unwrap(), 11);
+    assert_eq!(
+        positions
+            .targets
+            .get(&TARGET_SUBNET_ID_1)
+            .map(|target| *target.position),
+        Some(10)
+    );
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn prune_rocksdb_store(store: Arc<ValidatorStore>) {
+    prune_keeps_the_last_certificates_of_streams(store).await;
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn prune_in_memory_store(in_memory_store: Arc<ValidatorStore>) {
+    prune_keeps_the_last_certificates_of_streams(in_memory_store).await;
+}
+
+#[rstest]
+#[test(tokio::test)]
+async fn prune_keeps_at_least_the_source_head(in_memory_store: Arc<ValidatorStore>) {
+    let certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 3);
+    in_memory_store
+        .insert_certificates_delivered(&certificates)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        in_memory_store.get_fullnode_store().prune(0).await.unwrap(),
+        2
+    );
+    assert_eq!(
+        in_memory_store
+            .get_certificate(&certificates[2].certificate.id)
+            .unwrap(),
+        Some(certificates[2].clone())
+    );
+}
+
+#[rstest]
+#[test]
+fn pruner_only_runs_in_pruned_mode(in_memory_store: Arc<ValidatorStore>) {
+    assert!(Pruner::new(
+        StorageMode::Archive,
+        in_memory_store.get_fullnode_store(),
+        CancellationToken::new()
+    )
+    .is_none());
+    assert!(Pruner::new(
+        StorageMode::Pruned {
+            retained_certificates: 10
+        },
+        in_memory_store.get_fullnode_store(),
+        CancellationToken::new()
+    )
+    .is_some());
+}
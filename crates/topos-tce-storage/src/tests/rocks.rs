@@ -2,7 +2,7 @@ use std::thread;
 
 use rstest::rstest;
 
-use crate::rocks::db_column::DBColumn;
+use crate::backend::db_column::DBColumn;
 use crate::tests::support::database_name;
 use crate::tests::support::rocks_db;
 
@@ -1,6 +1,7 @@
 use rstest::fixture;
 
-use crate::rocks::{constants, db_column::DBColumn};
+use crate::backend::db_column::DBColumn;
+use crate::rocks::constants;
 use crate::types::{
     CertificatesColumn, PendingCertificatesColumn, StreamsColumn, TargetSourceListColumn,
     TargetStreamsColumn,
@@ -24,7 +24,7 @@ use self::folder::created_folder;
 pub(crate) mod columns;
 pub(crate) mod folder;
 
-pub(crate) static DB: Lazy<Mutex<HashMap<&'static str, Arc<RocksDB>>>> =
+pub(crate) static DB: Lazy<Mutex<HashMap<&'static str, RocksDB>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
 #[fixture]
@@ -39,7 +39,10 @@ pub(crate) fn database_name() -> &'static str {
 
 #[fixture]
 pub(crate) fn store() -> Arc<ValidatorStore> {
-    let temp_dir = create_folder::default();
+    open_store(create_folder::default())
+}
+
+pub(crate) fn open_store(temp_dir: PathBuf) -> Arc<ValidatorStore> {
     let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(temp_dir.clone()));
     let index_tables = Arc::new(IndexTables::open(temp_dir.clone()));
 
@@ -61,7 +64,14 @@ pub(crate) fn store() -> Arc<ValidatorStore> {
 }
 
 #[fixture]
-pub(crate) fn rocks_db(database_name: &'static str) -> Arc<RocksDB> {
+pub(crate) fn in_memory_store() -> Arc<ValidatorStore> {
+    let store = FullNodeStore::open_in_memory().expect("Unable to create full node store");
+
+    ValidatorStore::open_in_memory(store).unwrap()
+}
+
+#[fixture]
+pub(crate) fn rocks_db(database_name: &'static str) -> RocksDB {
     let mut dbs = DB.lock().unwrap();
 
     dbs.entry(database_name)
@@ -72,7 +82,7 @@ pub(crate) fn rocks_db(database_name: &'static str) -> Arc<RocksDB> {
             options.create_if_missing(true);
             options.create_missing_column_families(true);
 
-            Arc::new(init_db(&path, options).unwrap())
+            init_db(&path, options).unwrap()
         })
         .clone()
 }
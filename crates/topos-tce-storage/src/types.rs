@@ -1,16 +1,14 @@
+use serde::{Deserialize, Serialize};
 use topos_core::{
     api::grpc::checkpoints::SourceStreamPosition,
     types::{
         stream::{CertificateSourceStreamPosition, CertificateTargetStreamPosition, Position},
         CertificateDelivered, Ready, Signature,
     },
-    uci::{Certificate, CertificateId},
+    uci::{Certificate, CertificateId, SubnetId},
 };
 
-use crate::{
-    rocks::{db_column::DBColumn, TargetSourceListKey},
-    CertificatePositions, PendingCertificateId,
-};
+use crate::{backend::db_column::DBColumn, CertificatePositions, PendingCertificateId};
 
 pub type Echo = String;
 
@@ -32,6 +30,14 @@ pub(crate) type TargetStreamsColumn = DBColumn<CertificateTargetStreamPosition,
 /// Keeps position for particular target subnet id <- source subnet id column in TargetStreamsColumn
 pub(crate) type TargetSourceListColumn = DBColumn<TargetSourceListKey, Position>;
 
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct TargetSourceListKey(
+    // Target subnet id
+    pub(crate) SubnetId,
+    // Source subnet id
+    pub(crate) SubnetId,
+);
+
 #[derive(Debug, Clone)]
 pub enum PendingResult {
     AlreadyDelivered,
@@ -16,8 +16,9 @@
 //!
 use std::{
     collections::HashMap,
-    path::PathBuf,
-    sync::{atomic::Ordering, Arc},
+    fs::create_dir_all,
+    path::{Path, PathBuf},
+    sync::{atomic::Ordering, Arc, Mutex},
 };
 
 use async_trait::async_trait;
@@ -32,9 +33,11 @@ use topos_core::{
 use tracing::{debug, info, instrument};
 
 use crate::{
-    errors::{InternalStorageError, StorageError},
+    backend::map::Map,
+    backup::BackupManifest,
+    constant::paths,
+    errors::{BackupError, InternalStorageError, StorageError},
     fullnode::FullNodeStore,
-    rocks::map::Map,
     store::{ReadStore, WriteStore},
     CertificatePositions, CertificateTargetStreamPosition, PendingCertificateId, SourceHead,
 };
@@ -59,23 +62,85 @@ mod tables;
 pub struct ValidatorStore {
     pub(crate) pending_tables: ValidatorPendingTables,
     pub(crate) fullnode_store: Arc<FullNodeStore>,
+    /// Held while a certificate is checked and inserted in the pending pools, so that a
+    /// certificate submitted concurrently is only inserted once
+    pending_insertion_lock: Mutex<()>,
 }
 
 impl ValidatorStore {
     /// Open a [`ValidatorStore`] at the given `path` and using the given [`FullNodeStore`]
+    #[cfg(feature = "rocksdb")]
     pub fn open(
         path: PathBuf,
         fullnode_store: Arc<FullNodeStore>,
     ) -> Result<Arc<Self>, StorageError> {
-        let pending_tables: ValidatorPendingTables = ValidatorPendingTables::open(path);
+        Self::with_pending_tables(ValidatorPendingTables::open(path), fullnode_store)
+    }
+
+    /// Open a [`ValidatorStore`] keeping its pending pools in memory and using the given
+    /// [`FullNodeStore`]
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory(fullnode_store: Arc<FullNodeStore>) -> Result<Arc<Self>, StorageError> {
+        Self::with_pending_tables(ValidatorPendingTables::open_in_memory(), fullnode_store)
+    }
+
+    fn with_pending_tables(
+        pending_tables: ValidatorPendingTables,
+        fullnode_store: Arc<FullNodeStore>,
+    ) -> Result<Arc<Self>, StorageError> {
         let store = Arc::new(Self {
             pending_tables,
             fullnode_store,
+            pending_insertion_lock: Mutex::new(()),
         });
 
         Ok(store)
     }
 
+    /// Take a consistent backup of the storage at the given `path`, while the node keeps running
+    ///
+    /// The backup is made of checkpoints of every database along with a [`BackupManifest`]
+    /// written last, see [`backup`](module@crate::backup) to restore it.
+    /// Deliveries are paused while the checkpoints are taken.
+    pub async fn backup(&self, path: &Path) -> Result<BackupManifest, StorageError> {
+        if path.exists() {
+            return Err(BackupError::BackupAlreadyExists(path.to_path_buf()).into());
+        }
+        create_dir_all(path.join(paths::EPOCHS)).map_err(BackupError::Io)?;
+
+        let manifest = {
+            let _backup_guard = self.fullnode_store.backup_guard.write().await;
+
+            self.fullnode_store.checkpoint(path)?;
+            self.pending_tables.checkpoint(path)?;
+
+            BackupManifest {
+                delivered_certificates: self.count_certificates_delivered()?,
+                source_heads: self.get_checkpoint()?.into_values().collect(),
+            }
+        };
+
+        manifest.write(path)?;
+        info!(
+            "Backup of {} delivered certificates taken at {}",
+            manifest.delivered_certificates,
+            path.display()
+        );
+
+        Ok(manifest)
+    }
+
+    /// Compact the storage, reclaiming the space of the pruned and delivered certificates
+    ///
+    /// This is a blocking operation which can take a while on a large storage.
+    pub fn compact(&self) -> Result<(), StorageError> {
+        self.fullnode_store.compact()?;
+        self.pending_tables.compact()?;
+        info!("Storage compacted");
+
+        Ok(())
+    }
+
     /// Returns the [`FullNodeStore`] used by the [`ValidatorStore`]
     pub fn get_fullnode_store(&self) -> Arc<FullNodeStore> {
         self.fullnode_store.clone()
@@ -91,6 +156,18 @@ impl ValidatorStore {
         Ok(self.pending_tables.precedence_pool.iter()?.count())
     }
 
+    /// Returns an estimation of the number of certificates in the pending pool,
+    /// without iterating over it
+    pub fn estimate_pending_certificates(&self) -> Result<u64, StorageError> {
+        Ok(self.pending_tables.pending_pool.estimate_num_keys()?)
+    }
+
+    /// Returns an estimation of the number of certificates in the precedence pool,
+    /// without iterating over it
+    pub fn estimate_precedence_pool_certificates(&self) -> Result<u64, StorageError> {
+        Ok(self.pending_tables.precedence_pool.estimate_num_keys()?)
+    }
+
     /// Try to return the [`PendingCertificateId`] for a [`CertificateId`]
     ///
     /// Return `Ok(None)` if the `certificate_id` is not found.
@@ -111,6 +188,24 @@ impl ValidatorStore {
         Ok(self.pending_tables.pending_pool.get(pending_id)?)
     }
 
+    /// Try to return the [`Certificate`] waiting in the precedence pool for the delivery of its
+    /// previous certificate
+    ///
+    /// Return `Ok(None)` if the `certificate_id` is not in the precedence pool.
+    // TODO: The precedence pool is indexed by the previous certificate id, so we need to iterate
+    // over it to find a certificate
+    pub fn get_precedence_pool_certificate(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<Certificate>, StorageError> {
+        Ok(self
+            .pending_tables
+            .precedence_pool
+            .iter()?
+            .map(|(_, certificate)| certificate)
+            .find(|certificate| certificate.id == *certificate_id))
+    }
+
     /// Returns the entire pending_pool
     pub fn get_pending_certificates(
         &self,
@@ -118,6 +213,41 @@ impl ValidatorStore {
         Ok(self.pending_tables.pending_pool.iter()?.collect())
     }
 
+    /// Remove a certificate from the pending pool, it won't be broadcast unless submitted again
+    ///
+    /// The certificates of the precedence pool which depend on it can't be delivered anymore,
+    /// they are removed as well and returned along with the pending id of the certificate.
+    ///
+    /// Return `Ok(None)` if the `certificate_id` is not in the pending pool.
+    pub fn evict_pending_certificate(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<(PendingCertificateId, Vec<CertificateId>)>, StorageError> {
+        let Some(pending_id) = self.pending_tables.pending_pool_index.get(certificate_id)? else {
+            return Ok(None);
+        };
+
+        // The precedence pool is indexed by the previous certificate id
+        let mut previous_id = *certificate_id;
+        let mut previous_ids = Vec::new();
+        let mut dependents = Vec::new();
+        while let Some(dependent) = self.pending_tables.precedence_pool.get(&previous_id)? {
+            previous_ids.push(previous_id);
+            dependents.push(dependent.id);
+            previous_id = dependent.id;
+        }
+
+        self.pending_tables
+            .pending_pool
+            .batch()
+            .delete_batch(&self.pending_tables.pending_pool, [pending_id])?
+            .delete_batch(&self.pending_tables.pending_pool_index, [certificate_id])?
+            .delete_batch(&self.pending_tables.precedence_pool, previous_ids)?
+            .write()?;
+
+        Ok(Some((pending_id, dependents)))
+    }
+
     // TODO: Performance issue on this one as we iter over all the pending certificates
     // We need to improve how we request the pending certificates.
     pub fn get_pending_certificates_for_subnets(
@@ -190,6 +320,11 @@ impl ValidatorStore {
         &self,
         certificate: &Certificate,
     ) -> Result<Option<PendingCertificateId>, StorageError> {
+        let _insertion_guard = self
+            .pending_insertion_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
         if self.get_certificate(&certificate.id)?.is_some() {
             return Err(StorageError::InternalStorage(
                 InternalStorageError::CertificateAlreadyExists,
@@ -202,16 +337,36 @@ impl ValidatorStore {
                 .get_certificate(&certificate.prev_id)?
                 .is_some();
 
+        let already_pending = if prev_delivered {
+            self.get_pending_id(&certificate.id)?.is_some()
+        } else {
+            self.pending_tables
+                .precedence_pool
+                .get(&certificate.prev_id)?
+                .is_some_and(|pending| pending.id == certificate.id)
+        };
+
+        if already_pending {
+            return Err(StorageError::InternalStorage(
+                InternalStorageError::CertificateAlreadyPending,
+            ));
+        }
+
         if prev_delivered {
             let id = self
                 .pending_tables
                 .next_pending_id
                 .fetch_add(1, Ordering::Relaxed);
 
-            self.pending_tables.pending_pool.insert(&id, certificate)?;
             self.pending_tables
-                .pending_pool_index
-                .insert(&certificate.id, &id)?;
+                .pending_pool
+                .batch()
+                .insert_batch(&self.pending_tables.pending_pool, [(&id, certificate)])?
+                .insert_batch(
+                    &self.pending_tables.pending_pool_index,
+                    [(&certificate.id, &id)],
+                )?
+                .write()?;
 
             Ok(Some(id))
         } else {
@@ -295,9 +450,15 @@ impl ValidatorStore {
             .get(certificate_id)?)
     }
 
+    /// Returns, for every local subnet, up to `limit` proofs of delivery of the certificates
+    /// following the positions of `from`
+    ///
+    /// The requester pages through the source streams by asking again from the last
+    /// position returned.
     pub fn get_checkpoint_diff(
         &self,
         from: Vec<ProofOfDelivery>,
+        limit: usize,
     ) -> Result<HashMap<SubnetId, Vec<ProofOfDelivery>>, StorageError> {
         // Parse the from in order to extract the different position per subnets
         let mut from_positions: HashMap<SubnetId, Vec<ProofOfDelivery>> = from
@@ -323,11 +484,15 @@ impl ValidatorStore {
                 if local_position <= position.delivery_position.position {
                     continue;
                 }
+                // Start right after the position already known by the requester
                 self.fullnode_store
                     .perpetual_tables
                     .streams
-                    .prefix_iter_at(&subnet, &position)?
-                    .take(100)
+                    .prefix_iter_at(&subnet, &position.delivery_position)?
+                    .skip_while(|(stream_position, _)| {
+                        stream_position.position <= position.delivery_position.position
+                    })
+                    .take(limit)
                     .map(|(_, v)| v)
                     .collect()
             } else {
@@ -335,7 +500,7 @@ impl ValidatorStore {
                     .perpetual_tables
                     .streams
                     .prefix_iter(&subnet)?
-                    .take(100)
+                    .take(limit)
                     .map(|(_, v)| v)
                     .collect()
             };
@@ -454,10 +619,11 @@ impl WriteStore for ValidatorStore {
         &self,
         certificate: &CertificateDelivered,
     ) -> Result<CertificatePositions, StorageError> {
-        let position = self
-            .fullnode_store
-            .insert_certificate_delivered(certificate)
-            .await?;
+        // The pending pool is updated under the same guard so that a backup never
+        // holds a delivered certificate that is still pending
+        let _backup_guard = self.fullnode_store.backup_guard.read().await;
+
+        let position = self.fullnode_store.deliver_certificate(certificate).await?;
 
         if let Ok(Some(pending_id)) = self
             .pending_tables
@@ -472,7 +638,13 @@ impl WriteStore for ValidatorStore {
             .precedence_pool
             .get(&certificate.certificate.id)
         {
-            self.insert_pending_certificate(&certificate)?;
+            match self.insert_pending_certificate(&certificate) {
+                Ok(_)
+                | Err(StorageError::InternalStorage(
+                    InternalStorageError::CertificateAlreadyPending,
+                )) => {}
+                Err(error) => return Err(error),
+            }
         }
 
         Ok(position)
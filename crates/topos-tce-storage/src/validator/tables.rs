@@ -1,19 +1,27 @@
-use std::{fs::create_dir_all, path::PathBuf, sync::atomic::AtomicU64};
+#[cfg(feature = "rocksdb")]
+use std::{fs::create_dir_all, path::PathBuf};
+use std::{path::Path, sync::atomic::AtomicU64};
 
+#[cfg(feature = "rocksdb")]
 use rocksdb::ColumnFamilyDescriptor;
 use topos_core::{
-    types::ProofOfDelivery,
-    uci::{Certificate, CertificateId},
+    types::{stream::Position, ProofOfDelivery},
+    uci::{Certificate, CertificateId, SubnetId},
 };
+#[cfg(feature = "rocksdb")]
 use tracing::warn;
 
+#[cfg(feature = "inmemory")]
+use crate::backend::memory::MemoryDB;
+#[cfg(feature = "rocksdb")]
+use crate::rocks::{
+    constants,
+    db::{default_options, init_with_cfs},
+};
 use crate::{
-    constant::cfs,
-    rocks::{
-        constants,
-        db::{default_options, init_with_cfs},
-        db_column::DBColumn,
-    },
+    backend::{db_column::DBColumn, StorageBackend},
+    constant::{cfs, paths},
+    errors::InternalStorageError,
     types::{CertificatesColumn, EpochId, EpochSummary, PendingCertificatesColumn, StreamsColumn},
     PendingCertificateId,
 };
@@ -57,8 +65,9 @@ pub struct ValidatorPendingTables {
 
 impl ValidatorPendingTables {
     /// Open the [`ValidatorPendingTables`] at the given path.
+    #[cfg(feature = "rocksdb")]
     pub fn open(mut path: PathBuf) -> Self {
-        path.push("pending");
+        path.push(paths::PENDING);
         if !path.exists() {
             warn!("Path {:?} does not exist, creating it", path);
             create_dir_all(&path).expect("Cannot create ValidatorPendingTables directory");
@@ -72,14 +81,36 @@ impl ValidatorPendingTables {
         let db = init_with_cfs(&path, default_options(), cfs)
             .unwrap_or_else(|_| panic!("Cannot open DB at {:?}", path));
 
+        Self::from_backend(&db)
+    }
+
+    /// Open the [`ValidatorPendingTables`] in memory, nothing is persisted on disk.
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory() -> Self {
+        Self::from_backend(&MemoryDB::default())
+    }
+
+    fn from_backend(backend: &impl StorageBackend) -> Self {
         Self {
             // TODO: Fetch it from the storage
             next_pending_id: AtomicU64::new(0),
-            pending_pool: DBColumn::reopen(&db, cfs::PENDING_POOL),
-            pending_pool_index: DBColumn::reopen(&db, cfs::PENDING_POOL_INDEX),
-            precedence_pool: DBColumn::reopen(&db, cfs::PRECEDENCE_POOL),
+            pending_pool: DBColumn::reopen(backend, cfs::PENDING_POOL),
+            pending_pool_index: DBColumn::reopen(backend, cfs::PENDING_POOL_INDEX),
+            precedence_pool: DBColumn::reopen(backend, cfs::PRECEDENCE_POOL),
         }
     }
+
+    /// Create a checkpoint of the [`ValidatorPendingTables`] under the given path
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.pending_pool.checkpoint(&path.join(paths::PENDING))
+    }
+
+    /// Compact every column of the [`ValidatorPendingTables`]
+    pub(crate) fn compact(&self) -> Result<(), InternalStorageError> {
+        self.pending_pool.compact()?;
+        self.pending_pool_index.compact()?;
+        self.precedence_pool.compact()
+    }
 }
 
 /// Data that shouldn't be purged at all.
@@ -90,11 +121,16 @@ pub struct ValidatorPerpetualTables {
     #[allow(unused)]
     epoch_chain: DBColumn<EpochId, EpochSummary>,
     pub(crate) unverified: DBColumn<CertificateId, ProofOfDelivery>,
+    /// Proofs of delivery of the certificates that have been pruned
+    pub(crate) proofs: DBColumn<CertificateId, ProofOfDelivery>,
+    /// First position of every source stream that has not been pruned
+    pub(crate) pruned_streams: DBColumn<SubnetId, Position>,
 }
 
 impl ValidatorPerpetualTables {
+    #[cfg(feature = "rocksdb")]
     pub fn open(mut path: PathBuf) -> Self {
-        path.push("perpetual");
+        path.push(paths::PERPETUAL);
         if !path.exists() {
             warn!("Path {:?} does not exist, creating it", path);
             create_dir_all(&path).expect("Cannot create ValidatorPerpetualTables directory");
@@ -109,17 +145,46 @@ impl ValidatorPerpetualTables {
             ColumnFamilyDescriptor::new(cfs::STREAMS, options_stream),
             ColumnFamilyDescriptor::new(cfs::EPOCH_CHAIN, default_options()),
             ColumnFamilyDescriptor::new(cfs::UNVERIFIED, default_options()),
+            ColumnFamilyDescriptor::new(cfs::PROOFS, default_options()),
+            ColumnFamilyDescriptor::new(cfs::PRUNED_STREAMS, default_options()),
         ];
 
         let db = init_with_cfs(&path, default_options(), cfs).unwrap_or_else(|e| {
             panic!("Cannot open DB at {:?} => error {:?}", path, e);
         });
 
+        Self::from_backend(&db)
+    }
+
+    /// Open the [`ValidatorPerpetualTables`] in memory, nothing is persisted on disk.
+    #[cfg(feature = "inmemory")]
+    pub fn open_in_memory() -> Self {
+        Self::from_backend(&MemoryDB::default())
+    }
+
+    fn from_backend(backend: &impl StorageBackend) -> Self {
         Self {
-            certificates: DBColumn::reopen(&db, cfs::CERTIFICATES),
-            streams: DBColumn::reopen(&db, cfs::STREAMS),
-            epoch_chain: DBColumn::reopen(&db, cfs::EPOCH_CHAIN),
-            unverified: DBColumn::reopen(&db, cfs::UNVERIFIED),
+            certificates: DBColumn::reopen(backend, cfs::CERTIFICATES),
+            streams: DBColumn::reopen(backend, cfs::STREAMS),
+            epoch_chain: DBColumn::reopen(backend, cfs::EPOCH_CHAIN),
+            unverified: DBColumn::reopen(backend, cfs::UNVERIFIED),
+            proofs: DBColumn::reopen(backend, cfs::PROOFS),
+            pruned_streams: DBColumn::reopen(backend, cfs::PRUNED_STREAMS),
         }
     }
+
+    /// Create a checkpoint of the [`ValidatorPerpetualTables`] under the given path
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), InternalStorageError> {
+        self.certificates.checkpoint(&path.join(paths::PERPETUAL))
+    }
+
+    /// Compact every column of the [`ValidatorPerpetualTables`]
+    pub(crate) fn compact(&self) -> Result<(), InternalStorageError> {
+        self.certificates.compact()?;
+        self.streams.compact()?;
+        self.epoch_chain.compact()?;
+        self.unverified.compact()?;
+        self.proofs.compact()?;
+        self.pruned_streams.compact()
+    }
 }
@@ -1,8 +1,9 @@
-use std::{future::IntoFuture, sync::Arc};
+use std::{collections::HashSet, future::IntoFuture, sync::Arc};
 
 use tokio::{spawn, sync::mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
+use topos_core::types::ValidatorId;
 use topos_p2p::NetworkClient;
 use topos_tce_storage::validator::ValidatorStore;
 
@@ -10,6 +11,7 @@ use crate::{
     checkpoints_collector::{
         CheckpointSynchronizer, CheckpointsCollectorConfig, CheckpointsCollectorError,
     },
+    client::SynchronizerClient,
     Synchronizer, SynchronizerError, SynchronizerEvent,
 };
 
@@ -17,8 +19,24 @@ pub struct SynchronizerBuilder {
     network_client: Option<NetworkClient>,
     store: Option<Arc<ValidatorStore>>,
     sync_interval_seconds: u64,
+    /// Bootstrap an empty store from the snapshot of a peer (default: false)
+    snapshot_bootstrap: bool,
+    /// Validators signing the Ready messages of the proofs of delivery (default: none)
+    validators: HashSet<ValidatorId>,
+    /// Number of valid Ready messages expected in a proof of delivery (default: 0)
+    delivery_threshold: usize,
+    /// Number of proofs of delivery asked for each subnet in a checkpoint diff (default: 100)
+    checkpoint_diff_size: u64,
+    /// Number of certificates asked for in each fetch request (default: 50)
+    fetch_window_size: usize,
+    /// Maximum number of fetch requests in flight (default: 8)
+    max_concurrent_requests: usize,
+    /// Number of peers confirming the node holds their certificates (default: 3)
+    synced_quorum: usize,
     /// Size of the channel producing events (default: 100)
     event_channel_size: usize,
+    /// Size of the channel receiving commands (default: 10)
+    command_channel_size: usize,
     /// CancellationToken used to trigger shutdown of the Synchronizer
     shutdown: Option<CancellationToken>,
 }
@@ -29,7 +47,15 @@ impl Default for SynchronizerBuilder {
             network_client: None,
             store: None,
             sync_interval_seconds: 1,
+            snapshot_bootstrap: false,
+            validators: HashSet::new(),
+            delivery_threshold: 0,
+            checkpoint_diff_size: 100,
+            fetch_window_size: 50,
+            max_concurrent_requests: 8,
+            synced_quorum: 3,
             event_channel_size: 100,
+            command_channel_size: 10,
             shutdown: None,
         }
     }
@@ -50,10 +76,20 @@ impl SynchronizerBuilder {
         let (sync_events, checkpoints_collector_stream) = mpsc::channel(self.event_channel_size);
 
         let checkpoints_collector_stream = ReceiverStream::new(checkpoints_collector_stream);
+        let (commands, commands_recv) = mpsc::channel(self.command_channel_size);
 
         spawn(
             CheckpointSynchronizer {
-                config: CheckpointsCollectorConfig::default(),
+                config: CheckpointsCollectorConfig {
+                    snapshot_bootstrap: self.snapshot_bootstrap,
+                    validators: self.validators,
+                    delivery_threshold: self.delivery_threshold,
+                    checkpoint_diff_size: self.checkpoint_diff_size,
+                    fetch_window_size: self.fetch_window_size,
+                    max_concurrent_requests: self.max_concurrent_requests,
+                    synced_quorum: self.synced_quorum,
+                    ..Default::default()
+                },
                 network: if let Some(network) = self.network_client {
                     network
                 } else {
@@ -72,6 +108,8 @@ impl SynchronizerBuilder {
                 current_request_id: None,
                 shutdown: shutdown.child_token(),
                 events: sync_events,
+                commands: commands_recv,
+                status: Default::default(),
             }
             .into_future(),
         );
@@ -81,6 +119,7 @@ impl SynchronizerBuilder {
                 shutdown,
                 events,
                 checkpoints_collector_stream,
+                client: SynchronizerClient { commands },
             },
             ReceiverStream::new(events_recv),
         ))
@@ -106,6 +145,54 @@ impl SynchronizerBuilder {
         self
     }
 
+    pub fn with_snapshot_bootstrap(mut self, snapshot_bootstrap: bool) -> Self {
+        self.snapshot_bootstrap = snapshot_bootstrap;
+
+        self
+    }
+
+    /// Validators and threshold against which the proofs of delivery of a snapshot are
+    /// verified, a snapshot is never trusted without them
+    pub fn with_validators(
+        mut self,
+        validators: HashSet<ValidatorId>,
+        delivery_threshold: usize,
+    ) -> Self {
+        self.validators = validators;
+        self.delivery_threshold = delivery_threshold;
+
+        self
+    }
+
+    /// Number of proofs of delivery asked for each subnet in a checkpoint diff, bounded by
+    /// [`SynchronizerService::MAX_CHECKPOINT_DIFF_SIZE`](crate::SynchronizerService::MAX_CHECKPOINT_DIFF_SIZE)
+    /// on the peers
+    pub fn with_checkpoint_diff_size(mut self, checkpoint_diff_size: u64) -> Self {
+        self.checkpoint_diff_size = checkpoint_diff_size.max(1);
+
+        self
+    }
+
+    pub fn with_fetch_window_size(mut self, fetch_window_size: usize) -> Self {
+        self.fetch_window_size = fetch_window_size.max(1);
+
+        self
+    }
+
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+
+        self
+    }
+
+    /// Number of distinct peers which must have nothing more to deliver before the node
+    /// reports itself as synced, bounded by the number of connected peers
+    pub fn with_synced_quorum(mut self, synced_quorum: usize) -> Self {
+        self.synced_quorum = synced_quorum.max(1);
+
+        self
+    }
+
     pub fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
         self.shutdown = Some(shutdown);
 
@@ -1,15 +1,53 @@
+use std::collections::HashSet;
+
+use topos_core::types::ValidatorId;
+
 pub struct CheckpointsCollectorConfig {
     pub(crate) sync_interval_seconds: u64,
+    /// Bootstrap an empty store from the snapshot of a peer before syncing checkpoints
+    pub(crate) snapshot_bootstrap: bool,
+    /// Number of certificates asked for in each snapshot chunk
+    pub(crate) snapshot_chunk_size: u64,
+    /// Validators allowed to sign the Ready messages of the proofs of delivery of a snapshot
+    pub(crate) validators: HashSet<ValidatorId>,
+    /// Number of valid Ready messages a proof of delivery of a snapshot must hold
+    pub(crate) delivery_threshold: usize,
+    /// Number of proofs of delivery asked for each subnet in a checkpoint diff
+    pub(crate) checkpoint_diff_size: u64,
+    /// Number of certificates asked for in each fetch request
+    pub(crate) fetch_window_size: usize,
+    /// Maximum number of fetch requests in flight across every subnet
+    pub(crate) max_concurrent_requests: usize,
+    /// Number of attempts of a fetch request, each one targeting a random peer
+    pub(crate) max_fetch_attempts: usize,
+    /// Number of distinct peers which must have nothing more to deliver before the node
+    /// is synced, bounded by the number of connected peers
+    pub(crate) synced_quorum: usize,
 }
 
 impl CheckpointsCollectorConfig {
     const SYNC_INTERVAL_SECONDS: u64 = 10;
+    const SNAPSHOT_CHUNK_SIZE: u64 = 100;
+    const CHECKPOINT_DIFF_SIZE: u64 = 100;
+    const FETCH_WINDOW_SIZE: usize = 50;
+    const MAX_CONCURRENT_REQUESTS: usize = 8;
+    const MAX_FETCH_ATTEMPTS: usize = 3;
+    const SYNCED_QUORUM: usize = 3;
 }
 
 impl Default for CheckpointsCollectorConfig {
     fn default() -> Self {
         Self {
             sync_interval_seconds: Self::SYNC_INTERVAL_SECONDS,
+            snapshot_bootstrap: false,
+            snapshot_chunk_size: Self::SNAPSHOT_CHUNK_SIZE,
+            validators: HashSet::new(),
+            delivery_threshold: 0,
+            checkpoint_diff_size: Self::CHECKPOINT_DIFF_SIZE,
+            fetch_window_size: Self::FETCH_WINDOW_SIZE,
+            max_concurrent_requests: Self::MAX_CONCURRENT_REQUESTS,
+            max_fetch_attempts: Self::MAX_FETCH_ATTEMPTS,
+            synced_quorum: Self::SYNCED_QUORUM,
         }
     }
 }
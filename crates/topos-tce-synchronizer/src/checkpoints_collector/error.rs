@@ -1,5 +1,6 @@
 use thiserror::Error;
 use tokio::sync::oneshot::error::RecvError;
+use topos_p2p::PeerId;
 
 #[derive(Error, Debug)]
 pub enum CheckpointsCollectorError {
@@ -23,4 +24,7 @@ pub enum CheckpointsCollectorError {
 
     #[error("Unable to start the CheckpointsCollector: No store provided")]
     NoStore,
+
+    #[error("Unable to synchronize from peer {0}: {1}")]
+    ResyncFailed(PeerId, String),
 }
@@ -5,8 +5,11 @@ use std::{
     sync::Arc,
 };
 
-use futures::{future::BoxFuture, FutureExt};
-use tokio::sync::mpsc;
+use futures::{
+    future::{join_all, BoxFuture},
+    stream, FutureExt, StreamExt,
+};
+use tokio::sync::{mpsc, oneshot, Semaphore};
 use tokio_util::sync::CancellationToken;
 use tonic::Status;
 use topos_core::{
@@ -24,20 +27,31 @@ use topos_core::{
     uci::{Certificate, CertificateId, SubnetId},
 };
 
-use topos_p2p::{error::P2PError, NetworkClient, PeerId};
+use topos_metrics::{
+    SYNCHRONIZER_CERTIFICATES_SYNCHRONIZED_TOTAL, SYNCHRONIZER_FETCH_REQUEST_FAILED_TOTAL,
+    SYNCHRONIZER_FETCH_REQUEST_LATENCY, SYNCHRONIZER_MISSING_CERTIFICATES,
+};
+use topos_p2p::{
+    error::{CommandExecutionError, P2PError},
+    NetworkClient, PeerId,
+};
 use topos_tce_storage::{errors::StorageError, store::ReadStore, validator::ValidatorStore};
-use tracing::{debug, error, warn};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 mod config;
 mod error;
+mod snapshot;
 #[cfg(test)]
 mod tests;
 
 pub use config::CheckpointsCollectorConfig;
 pub use error::CheckpointsCollectorError;
 
-use crate::SynchronizerService;
+use crate::{
+    status::{SyncState, SyncStatus},
+    SynchronizerService,
+};
 
 pub struct CheckpointSynchronizer {
     pub(crate) config: CheckpointsCollectorConfig,
@@ -50,8 +64,21 @@ pub struct CheckpointSynchronizer {
 
     pub(crate) shutdown: CancellationToken,
 
-    #[allow(dead_code)]
     pub(crate) events: mpsc::Sender<CheckpointsCollectorEvent>,
+
+    pub(crate) commands: mpsc::Receiver<CheckpointsCollectorCommand>,
+
+    pub(crate) status: SyncStatus,
+}
+
+/// Commands handled by the [`CheckpointSynchronizer`] in between two synchronizations
+#[derive(Debug)]
+pub(crate) enum CheckpointsCollectorCommand {
+    /// Synchronize with the checkpoints of the given peer, fetching the certificates from it
+    ResyncFromPeer {
+        peer: PeerId,
+        sender: oneshot::Sender<Result<usize, CheckpointsCollectorError>>,
+    },
 }
 
 impl IntoFuture for CheckpointSynchronizer {
@@ -65,6 +92,18 @@ impl IntoFuture for CheckpointSynchronizer {
                 self.config.sync_interval_seconds,
             ));
 
+            if self.config.snapshot_bootstrap {
+                tokio::select! {
+                    result = self.bootstrap_from_snapshot() => {
+                        if let Err(error) = result {
+                            warn!("Unable to bootstrap from a snapshot, falling back to checkpoint sync: {}", error);
+                        }
+                    }
+
+                    _ = self.shutdown.cancelled() => { return Ok(()); }
+                }
+            }
+
             loop {
                 tokio::select! {
                     _tick = interval.tick() => {
@@ -72,13 +111,30 @@ impl IntoFuture for CheckpointSynchronizer {
                         // If there is, skip
                         // If there is not,
                         //  1. Ask a random peer for the diff between local and its latest checkpoint
-                        //  2. Validate the PoD diff, if fail, go back to 1
-                        //  3. Based on the diff, check if we already have some of the certs
-                        //      - Fetch every missing certs from one peer
-                        //      - Each certs triggers a precedence check
+                        //  2. Fetch windows of the missing certs from random peers,
+                        //     delivering them in the order of their source stream
+                        //  3. Go back to 1 until a diff brings nothing new
                         if self.current_request_id.is_none() {
                             if let Err(error) = self.initiate_request().await {
                                 warn!("Unsuccessful sync due to: {}", error);
+                                self.synchronization_failed().await;
+                            }
+                        }
+                    }
+
+                    Some(command) = self.commands.recv() => {
+                        match command {
+                            CheckpointsCollectorCommand::ResyncFromPeer { peer, sender } => {
+                                info!("Forced synchronization from {}", peer);
+                                let result = self.synchronize_with(Some(peer)).await;
+                                if let Err(ref error) = result {
+                                    warn!("Unsuccessful sync from {} due to: {}", peer, error);
+                                    self.synchronization_failed().await;
+                                }
+
+                                _ = sender.send(result.map_err(|error| {
+                                    CheckpointsCollectorError::ResyncFailed(peer, error.to_string())
+                                }));
                             }
                         }
                     }
@@ -99,6 +155,9 @@ enum SyncError {
     #[error("Unable to fetch target peer from network layer")]
     UnableToFetchTargetPeer,
 
+    #[error("No known peer to synchronize with")]
+    NoKnownPeer,
+
     #[error("Unable to parse subnet id")]
     // TODO: Check if needed after full merge of grpc over p2p
     #[allow(unused)]
@@ -124,6 +183,27 @@ enum SyncError {
 
     #[error(transparent)]
     Grpc(#[from] Status),
+
+    #[error("Invalid snapshot certificate {0}: {1}")]
+    InvalidSnapshot(CertificateId, &'static str),
+
+    #[error("No validator set to verify the proofs of delivery of a snapshot against")]
+    UntrustedSnapshot,
+
+    #[error("Snapshot source stream of {0} ended before its head")]
+    IncompleteSnapshot(SubnetId),
+
+    #[error("Peer {0} pruned the beginning of its source streams")]
+    PrunedSnapshot(PeerId),
+
+    #[error("No connected peer served a valid snapshot")]
+    NoSnapshotAvailable,
+
+    #[error("Peer {0} did not return the certificates asked for")]
+    MissingCertificates(PeerId),
+
+    #[error("Invalid certificate {0}: id not matching its content")]
+    InvalidCertificate(CertificateId),
 }
 
 impl CheckpointSynchronizer {
@@ -154,6 +234,7 @@ impl CheckpointSynchronizer {
         let req = CheckpointRequest {
             request_id: Some(request_id),
             checkpoint,
+            limit: self.config.checkpoint_diff_size,
         };
 
         debug!("Asking {} for latest checkpoint", peer);
@@ -182,41 +263,142 @@ impl CheckpointSynchronizer {
         Ok(diff)
     }
 
-    fn insert_unverified_proofs(
+    /// Synchronize the certificates of a checkpoint diff, subnets being synchronized
+    /// concurrently
+    ///
+    /// Returns the number of certificates synchronized, an error is only returned if none
+    /// could be.
+    async fn synchronize_diff(
         &self,
         diff: HashMap<SubnetId, Vec<ProofOfDelivery>>,
-    ) -> Result<Vec<Vec<CertificateId>>, SyncError> {
-        let mut certs: HashSet<CertificateId> = HashSet::new();
-        for (_subnet, proofs) in diff {
-            let len = proofs.len();
-            let unverified_certs = self.store.insert_unverified_proofs(proofs)?;
-
-            debug!("Persist {} unverified proofs", len);
-            certs.extend(&unverified_certs[..]);
+        peer: Option<PeerId>,
+    ) -> Result<usize, SyncError> {
+        let missing = diff.values().map(Vec::len).sum::<usize>();
+        SYNCHRONIZER_MISSING_CERTIFICATES.set(missing as i64);
+
+        let requests = Arc::new(Semaphore::new(self.config.max_concurrent_requests));
+        let results = join_all(diff.into_iter().map(|(subnet_id, proofs)| {
+            self.synchronize_subnet(subnet_id, proofs, peer, requests.clone())
+        }))
+        .await;
+
+        SYNCHRONIZER_MISSING_CERTIFICATES.set(0);
+
+        let mut synchronized = 0;
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok(count) => synchronized += count,
+                Err(error) => {
+                    warn!("Unable to synchronize a subnet: {}", error);
+                    first_error.get_or_insert(error);
+                }
+            }
         }
 
-        // Chunk certs
-        let mut chunked_certs: Vec<Vec<CertificateId>> = vec![];
+        match first_error {
+            Some(error) if synchronized == 0 => Err(error),
+            _ => Ok(synchronized),
+        }
+    }
 
-        let certs = certs.into_iter().collect::<Vec<_>>();
+    /// Synchronize the certificates of one source stream, in the order of its proofs of
+    /// delivery
+    ///
+    /// Windows of certificates are fetched concurrently but delivered one after the other,
+    /// the synchronization of the subnet stops at the first window that can't be fetched.
+    async fn synchronize_subnet(
+        &self,
+        subnet_id: SubnetId,
+        proofs: Vec<ProofOfDelivery>,
+        peer: Option<PeerId>,
+        requests: Arc<Semaphore>,
+    ) -> Result<usize, SyncError> {
+        let len = proofs.len();
+        let certificate_ids = self.store.insert_unverified_proofs(proofs)?;
+        debug!("Persist {} unverified proofs for {}", len, subnet_id);
+
+        let mut windows = stream::iter(certificate_ids.chunks(self.config.fetch_window_size))
+            .map(|certificate_ids| self.fetch_window(certificate_ids, peer, &requests))
+            .buffered(self.config.max_concurrent_requests);
+
+        let mut synchronized = 0;
+        while let Some(certificates) = windows.next().await {
+            if self.shutdown.is_cancelled() {
+                debug!(
+                    "Synchronization of {} interrupted by the shutdown",
+                    subnet_id
+                );
+                break;
+            }
 
-        for certs in certs.chunks(10) {
-            chunked_certs.push(certs.to_vec());
+            for certificate in certificates? {
+                let certificate_id = certificate.id;
+                self.store.synchronize_certificate(certificate).await?;
+                debug!("Certificate {} synchronized", certificate_id);
+
+                synchronized += 1;
+                SYNCHRONIZER_CERTIFICATES_SYNCHRONIZED_TOTAL.inc();
+                SYNCHRONIZER_MISSING_CERTIFICATES.dec();
+                self.emit(CheckpointsCollectorEvent::CertificateSynchronized {
+                    certificate_id,
+                    subnet_id,
+                });
+            }
         }
 
-        Ok(chunked_certs)
+        Ok(synchronized)
     }
 
-    async fn fetch_certificates(
+    /// Fetch a window of certificates from the given peer, or from a random peer which is
+    /// changed on every retry
+    async fn fetch_window(
         &self,
-        certificate_ids: Vec<CertificateId>,
+        certificate_ids: &[CertificateId],
+        peer: Option<PeerId>,
+        requests: &Semaphore,
     ) -> Result<Vec<Certificate>, SyncError> {
-        let target_peer = self
-            .network
-            .random_known_peer()
+        let _permit = requests
+            .acquire()
             .await
-            .map_err(|_| SyncError::UnableToFetchTargetPeer)?;
+            .expect("The requests semaphore is never closed");
+
+        let mut attempt = 1;
+        loop {
+            let target_peer = self.target_peer(peer).await?;
+
+            let timer = SYNCHRONIZER_FETCH_REQUEST_LATENCY.start_timer();
+            let result = self.fetch_certificates(target_peer, certificate_ids).await;
+            timer.observe_duration();
+
+            match result {
+                Ok(certificates) => return Ok(certificates),
+                Err(error) => {
+                    SYNCHRONIZER_FETCH_REQUEST_FAILED_TOTAL.inc();
+                    warn!(
+                        "Fetching certificates from {} failed (attempt {}/{}): {}",
+                        target_peer, attempt, self.config.max_fetch_attempts, error
+                    );
+                    self.emit(CheckpointsCollectorEvent::FetchFailed {
+                        peer: target_peer,
+                        attempt,
+                    });
+
+                    if attempt >= self.config.max_fetch_attempts {
+                        return Err(error);
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
 
+    /// Fetch certificates from a peer, which must return every one of them, in order
+    async fn fetch_certificates(
+        &self,
+        target_peer: PeerId,
+        certificate_ids: &[CertificateId],
+    ) -> Result<Vec<Certificate>, SyncError> {
         let request_id: Option<APIUuid> = Some(Uuid::new_v4().into());
         let req = FetchCertificatesRequest {
             request_id,
@@ -237,46 +419,192 @@ impl CheckpointSynchronizer {
 
         let response = client.fetch_certificates(req).await?.into_inner();
 
-        let certificates: Result<Vec<Certificate>, _> = response
+        let certificates = response
             .certificates
             .into_iter()
             .map(TryInto::try_into)
-            .collect();
+            .collect::<Result<Vec<Certificate>, _>>()?;
+
+        if certificates.len() != certificate_ids.len()
+            || certificates
+                .iter()
+                .zip(certificate_ids)
+                .any(|(certificate, id)| certificate.id != *id)
+        {
+            return Err(SyncError::MissingCertificates(target_peer));
+        }
+
+        if let Some(certificate) = certificates
+            .iter()
+            .find(|certificate| certificate.check_id().is_err())
+        {
+            return Err(SyncError::InvalidCertificate(certificate.id));
+        }
+
+        Ok(certificates)
+    }
+
+    /// Returns the given peer, or a random known one if none is given
+    async fn target_peer(&self, peer: Option<PeerId>) -> Result<PeerId, SyncError> {
+        match peer {
+            Some(peer) => Ok(peer),
+            None => self
+                .network
+                .random_known_peer()
+                .await
+                .map_err(|error| match error {
+                    P2PError::CommandError(CommandExecutionError::NoKnownPeer) => {
+                        SyncError::NoKnownPeer
+                    }
+                    _ => SyncError::UnableToFetchTargetPeer,
+                }),
+        }
+    }
+
+    /// Synchronize with the checkpoints of random peers until nothing is left to catch up
+    async fn initiate_request(&mut self) -> Result<usize, SyncError> {
+        self.synchronize_with(None).await
+    }
 
-        Ok(certificates?)
+    /// Synchronize with the checkpoints of the given peer, or of random peers if none is
+    /// given, until nothing is left to catch up
+    ///
+    /// Returns the number of certificates synchronized.
+    async fn synchronize_with(&mut self, peer: Option<PeerId>) -> Result<usize, SyncError> {
+        let mut total = 0;
+        // Peers having nothing more to deliver since the last round
+        let mut up_to_date_peers = HashSet::new();
+        let mut next_peer = peer;
+        loop {
+            // Stop in between two rounds on shutdown, keeping what is already synchronized
+            if self.shutdown.is_cancelled() {
+                return Ok(total);
+            }
+
+            //  1. Ask a peer for the diff between local and its latest checkpoint
+            let target_peer = match self.target_peer(next_peer).await {
+                // A lone node, e.g. a devnet of a single node, has nobody to catch up with
+                Err(SyncError::NoKnownPeer) if self.status.missing_certificates() == 0 => {
+                    self.status.state = SyncState::Synced;
+                    self.publish_status().await;
+
+                    return Ok(total);
+                }
+                target_peer => target_peer?,
+            };
+
+            let diff = self.ask_for_checkpoint(target_peer).await?;
+            self.status.update_best_known_positions(&diff);
+
+            let certificates = diff.values().map(Vec::len).sum::<usize>();
+            if certificates == 0 {
+                self.status
+                    .update_local_positions(&self.store.get_checkpoint()?);
+
+                // A single peer, lagging behind or lying, can't declare the node synced
+                up_to_date_peers.insert(target_peer);
+                next_peer = self.next_quorum_peer(&up_to_date_peers).await?;
+                if next_peer.is_some() {
+                    continue;
+                }
+                self.status.state = SyncState::Synced;
+                self.publish_status().await;
+
+                return Ok(total);
+            }
+            up_to_date_peers.clear();
+
+            self.emit(CheckpointsCollectorEvent::CheckpointDiffReceived {
+                peer: target_peer,
+                certificates,
+            });
+
+            //  2. Fetch and deliver the certificates of the diff
+            let synchronized = self.synchronize_diff(diff, next_peer).await?;
+            total += synchronized;
+
+            info!(
+                "Certificate Sync: {}/{} certificates synchronized from the checkpoint of {}",
+                synchronized, certificates, target_peer
+            );
+            self.emit(CheckpointsCollectorEvent::RoundCompleted { synchronized });
+
+            self.status
+                .update_local_positions(&self.store.get_checkpoint()?);
+            self.status.state = if synchronized == 0 {
+                SyncState::Stalled
+            } else {
+                SyncState::Syncing
+            };
+            self.publish_status().await;
+
+            //  3. Ask for the next diff while the previous one made progress
+            if synchronized == 0 {
+                return Ok(total);
+            }
+        }
     }
 
-    async fn initiate_request(&mut self) -> Result<(), SyncError> {
-        //  1. Ask a random peer for the diff between local and its latest checkpoint
-        let target_peer = self
+    /// Returns a connected peer to ask for its checkpoint, `None` once enough peers have
+    /// nothing more to deliver for the node to be synced
+    async fn next_quorum_peer(
+        &self,
+        up_to_date_peers: &HashSet<PeerId>,
+    ) -> Result<Option<PeerId>, SyncError> {
+        let connected_peers = self
             .network
-            .random_known_peer()
+            .connected_peers()
             .await
             .map_err(|_| SyncError::UnableToFetchTargetPeer)?;
+        let quorum = self.config.synced_quorum.min(connected_peers.len());
+        if up_to_date_peers.len() >= quorum {
+            return Ok(None);
+        }
 
-        let diff = self.ask_for_checkpoint(target_peer).await?;
+        Ok(connected_peers
+            .into_iter()
+            .find(|peer| !up_to_date_peers.contains(peer)))
+    }
 
-        let certificates_to_catchup = self.insert_unverified_proofs(diff)?;
+    /// Publish a progress event, dropped if the consumer lags behind
+    fn emit(&self, event: CheckpointsCollectorEvent) {
+        _ = self.events.try_send(event);
+    }
 
-        for certificates in certificates_to_catchup {
-            let certificates = self.fetch_certificates(certificates).await?;
+    /// Publish the synchronization status, unlike progress events it is never dropped
+    async fn publish_status(&self) {
+        self.status.record_metrics();
+        _ = self
+            .events
+            .send(CheckpointsCollectorEvent::StatusUpdated(
+                self.status.clone(),
+            ))
+            .await;
+    }
 
-            // TODO: verify every certificates
-            for certificate in certificates {
-                let store = self.store.clone();
-                tokio::spawn(async move {
-                    // Validate
-                    // Check precedence
-                    let certificate_id = certificate.id;
-                    match store.synchronize_certificate(certificate).await {
-                        Ok(_) => debug!("Certificate {} synchronized", certificate_id),
-                        Err(e) => error!("Failed to sync because of: {:?}", e),
-                    }
-                });
-            }
+    /// A node can't be synced while some certificates known by a peer are missing
+    async fn synchronization_failed(&mut self) {
+        if self.status.state != SyncState::Synced || self.status.missing_certificates() > 0 {
+            self.status.state = SyncState::Stalled;
+            self.publish_status().await;
         }
-        Ok(())
     }
 }
 
-pub enum CheckpointsCollectorEvent {}
+/// Progress of the synchronization of the checkpoints
+#[derive(Debug, Clone)]
+pub enum CheckpointsCollectorEvent {
+    /// A peer returned the proofs of delivery of certificates missing locally
+    CheckpointDiffReceived { peer: PeerId, certificates: usize },
+    /// A certificate has been fetched, verified and delivered
+    CertificateSynchronized {
+        certificate_id: CertificateId,
+        subnet_id: SubnetId,
+    },
+    /// A request for a window of certificates failed
+    FetchFailed { peer: PeerId, attempt: usize },
+    /// Every certificate of a checkpoint diff has been processed
+    RoundCompleted { synchronized: usize },
+    /// The synchronization status changed
+    StatusUpdated(SyncStatus),
+}
@@ -0,0 +1,280 @@
+//! Bootstrap of an empty node from the snapshot of a peer
+//!
+//! Instead of synchronizing the delivered certificates checkpoint by checkpoint, an empty
+//! node can ask a peer for its snapshot: the proof of delivery of the head of every source
+//! stream. Each source stream is then downloaded in chunks up to its head and verified
+//! against the proofs of delivery before being stored. Once done, the regular checkpoint
+//! synchronization takes over from the delivered state of the snapshot.
+//!
+//! A proof of delivery is only trusted if it holds enough Ready messages signed by the
+//! known validators, the threshold being the local one and never the one of the peer.
+//!
+//! The connected peers are tried one after the other until one serves a valid snapshot,
+//! peers having pruned the beginning of their source streams are skipped.
+use std::{collections::HashSet, str::FromStr};
+
+use topos_core::{
+    api::grpc::{
+        shared::v1::Uuid as APIUuid,
+        tce::v1::{
+            synchronizer_service_client::SynchronizerServiceClient,
+            synchronizer_service_server::SynchronizerServiceServer, SnapshotChunkRequest,
+            SnapshotRequest,
+        },
+    },
+    types::{stream::Position, CertificateDelivered, ProofOfDelivery, ValidatorId},
+    uci::{CertificateId, SubnetId, INITIAL_CERTIFICATE_ID},
+};
+use topos_crypto::messages::{verify_signature, Signature};
+use topos_p2p::PeerId;
+use topos_tce_storage::store::{ReadStore, WriteStore};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::{CheckpointSynchronizer, SyncError};
+use crate::SynchronizerService;
+
+impl CheckpointSynchronizer {
+    /// Fill an empty store with the snapshot of the first connected peer able to serve it
+    ///
+    /// Returns the number of certificates synchronized, nothing is done if the store
+    /// already holds delivered certificates.
+    pub(super) async fn bootstrap_from_snapshot(&self) -> Result<usize, SyncError> {
+        if self.store.count_certificates_delivered()? != 0 {
+            return Ok(0);
+        }
+
+        if self.config.validators.is_empty() || self.config.delivery_threshold == 0 {
+            return Err(SyncError::UntrustedSnapshot);
+        }
+
+        let peers = self
+            .network
+            .connected_peers()
+            .await
+            .map_err(|_| SyncError::UnableToFetchTargetPeer)?;
+
+        for peer in peers {
+            match self.bootstrap_from_peer(peer).await {
+                Ok(synchronized) => {
+                    info!(
+                        "Bootstrapped from the snapshot of {}: {} certificates synchronized",
+                        peer, synchronized
+                    );
+
+                    return Ok(synchronized);
+                }
+                Err(error) => warn!("Unable to bootstrap from the snapshot of {peer}: {error}"),
+            }
+        }
+
+        Err(SyncError::NoSnapshotAvailable)
+    }
+
+    async fn bootstrap_from_peer(&self, target_peer: PeerId) -> Result<usize, SyncError> {
+        let heads = self.ask_for_snapshot(target_peer).await?;
+
+        let mut subnets = HashSet::new();
+        for head in &heads {
+            if !subnets.insert(head.delivery_position.subnet_id) {
+                return Err(SyncError::InvalidSnapshot(
+                    head.certificate_id,
+                    "duplicated head",
+                ));
+            }
+            verify_proof_of_delivery(
+                head,
+                &self.config.validators,
+                self.config.delivery_threshold,
+            )?;
+        }
+
+        let mut synchronized = 0;
+        for head in heads {
+            synchronized += self.download_source_stream(target_peer, head).await?;
+        }
+
+        Ok(synchronized)
+    }
+
+    async fn ask_for_snapshot(&self, peer: PeerId) -> Result<Vec<ProofOfDelivery>, SyncError> {
+        let request_id: APIUuid = Uuid::new_v4().into();
+
+        debug!("Asking {} for its snapshot", peer);
+        let mut client: SynchronizerServiceClient<_> = self
+            .network
+            .new_grpc_client::<SynchronizerServiceClient<_>, SynchronizerServiceServer<SynchronizerService>>(peer)
+            .await?;
+
+        let response = client
+            .fetch_snapshot(SnapshotRequest {
+                request_id: Some(request_id),
+            })
+            .await?
+            .into_inner();
+
+        if response.pruned {
+            return Err(SyncError::PrunedSnapshot(peer));
+        }
+
+        Ok(response
+            .heads
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Download, verify and store the source stream of a subnet up to the given head
+    async fn download_source_stream(
+        &self,
+        peer: PeerId,
+        head: ProofOfDelivery,
+    ) -> Result<usize, SyncError> {
+        let subnet_id = head.delivery_position.subnet_id;
+        let head_position = *head.delivery_position.position;
+
+        let mut client: SynchronizerServiceClient<_> = self
+            .network
+            .new_grpc_client::<SynchronizerServiceClient<_>, SynchronizerServiceServer<SynchronizerService>>(peer)
+            .await?;
+
+        let mut position: u64 = 0;
+        let mut previous: CertificateId = INITIAL_CERTIFICATE_ID;
+
+        while position <= head_position {
+            let request_id: APIUuid = Uuid::new_v4().into();
+            debug!(
+                "Asking {} for {}:{} snapshot chunk",
+                peer, subnet_id, position
+            );
+
+            let response = client
+                .fetch_snapshot_chunk(SnapshotChunkRequest {
+                    request_id: Some(request_id),
+                    source_subnet_id: Some(subnet_id.into()),
+                    from_position: position,
+                    limit: self.config.snapshot_chunk_size,
+                })
+                .await?
+                .into_inner();
+
+            if response.certificates.is_empty() {
+                return Err(SyncError::IncompleteSnapshot(subnet_id));
+            }
+
+            let mut certificates = Vec::with_capacity(response.certificates.len());
+            for certificate in response.certificates {
+                if position > head_position {
+                    break;
+                }
+
+                let certificate: CertificateDelivered = certificate.try_into()?;
+                verify_delivered_certificate(
+                    &certificate,
+                    subnet_id,
+                    position.into(),
+                    previous,
+                    &self.config.validators,
+                    self.config.delivery_threshold,
+                )?;
+
+                if position == head_position && certificate.certificate.id != head.certificate_id {
+                    return Err(SyncError::InvalidSnapshot(
+                        certificate.certificate.id,
+                        "does not match the head of the source stream",
+                    ));
+                }
+
+                previous = certificate.certificate.id;
+                position += 1;
+                certificates.push(certificate);
+            }
+
+            self.store
+                .insert_certificates_delivered(&certificates)
+                .await?;
+        }
+
+        Ok(position as usize)
+    }
+}
+
+/// Check that a proof of delivery holds Ready messages of enough distinct validators, each
+/// one signed by its validator
+///
+/// The threshold of the proof is ignored as it is set by the peer.
+pub(super) fn verify_proof_of_delivery(
+    proof: &ProofOfDelivery,
+    validators: &HashSet<ValidatorId>,
+    threshold: usize,
+) -> Result<(), SyncError> {
+    let signers = proof
+        .readies
+        .iter()
+        .filter_map(|(ready, signature)| {
+            let validator_id = ValidatorId::from_str(ready).ok()?;
+            let signature = Signature::from_str(signature).ok()?;
+
+            if !validators.contains(&validator_id) {
+                return None;
+            }
+
+            let mut payload = Vec::new();
+            payload.extend_from_slice(proof.certificate_id.as_array());
+            payload.extend_from_slice(validator_id.as_bytes());
+
+            verify_signature(signature, &payload, validator_id.address())
+                .ok()
+                .map(|_| validator_id)
+        })
+        .collect::<HashSet<_>>();
+
+    if signers.len() < threshold {
+        return Err(SyncError::InvalidSnapshot(
+            proof.certificate_id,
+            "not enough valid Ready messages in the proof of delivery",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check that a certificate of a snapshot is the expected one of its source stream and is
+/// proven delivered
+///
+/// The first certificate of a source stream must follow the `INITIAL_CERTIFICATE_ID`.
+pub(super) fn verify_delivered_certificate(
+    delivered: &CertificateDelivered,
+    subnet_id: SubnetId,
+    position: Position,
+    previous: CertificateId,
+    validators: &HashSet<ValidatorId>,
+    threshold: usize,
+) -> Result<(), SyncError> {
+    let certificate = &delivered.certificate;
+    let proof = &delivered.proof_of_delivery;
+
+    let error = |reason| Err(SyncError::InvalidSnapshot(certificate.id, reason));
+
+    if certificate.source_subnet_id != subnet_id || proof.delivery_position.subnet_id != subnet_id {
+        return error("unexpected source subnet");
+    }
+
+    if proof.certificate_id != certificate.id {
+        return error("proof of delivery of another certificate");
+    }
+
+    if proof.delivery_position.position != position {
+        return error("unexpected position in the source stream");
+    }
+
+    if certificate.prev_id != previous {
+        return error("not following the previous certificate of the source stream");
+    }
+
+    if certificate.check_id().is_err() {
+        return error("id not matching the content of the certificate");
+    }
+
+    verify_proof_of_delivery(proof, validators, threshold)
+}
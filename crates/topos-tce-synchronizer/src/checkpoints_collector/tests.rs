@@ -1,28 +1,89 @@
-use std::time::Duration;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use rstest::rstest;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use topos_core::{
     api::grpc::tce::v1::{
         synchronizer_service_client::SynchronizerServiceClient,
         synchronizer_service_server::SynchronizerServiceServer, CheckpointMapFieldEntry,
-        CheckpointRequest, CheckpointResponse, FetchCertificatesRequest,
+        CheckpointRequest, CheckpointResponse, FetchCertificatesRequest, SnapshotChunkRequest,
+        SnapshotRequest,
+    },
+    types::{
+        stream::{CertificateSourceStreamPosition, Position},
+        CertificateDelivered, ValidatorId,
     },
-    types::CertificateDelivered,
+    uci::INITIAL_CERTIFICATE_ID,
 };
+use topos_crypto::messages::MessageSigner;
 
-use topos_p2p::GrpcRouter;
+use topos_p2p::{
+    error::{CommandExecutionError, P2PError},
+    utils::GrpcOverP2P,
+    Command, GrpcRouter, NetworkClient, PeerId,
+};
+use topos_tce_storage::{store::ReadStore, SourceHead};
 use topos_test_sdk::{
     certificates::create_certificate_chain,
+    constants::{SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2, TARGET_SUBNET_ID_1},
     storage::{create_fullnode_store, create_validator_store},
     tce::{create_network, NodeConfig},
 };
 
 use uuid::Uuid;
 
-use crate::SynchronizerService;
+use super::{
+    snapshot::verify_delivered_certificate, CheckpointSynchronizer, CheckpointsCollectorConfig,
+    CheckpointsCollectorEvent, SyncError,
+};
+use crate::{SubnetSyncStatus, SyncState, SyncStatus, SynchronizerService};
 
 mod integration;
 
+/// Validators signing the proofs of delivery of the test certificates
+fn validators(count: u8) -> Vec<MessageSigner> {
+    validators_from(1, count)
+}
+
+fn validators_from(first_seed: u8, count: u8) -> Vec<MessageSigner> {
+    (first_seed..first_seed + count)
+        .map(|seed| MessageSigner::new(&[seed; 32]).unwrap())
+        .collect()
+}
+
+fn validator_ids(signers: &[MessageSigner]) -> HashSet<ValidatorId> {
+    signers
+        .iter()
+        .map(|signer| signer.public_address.into())
+        .collect()
+}
+
+/// Fill the proofs of delivery with the Ready messages of the given validators
+fn sign_readies(certificates: &mut [CertificateDelivered], signers: &[MessageSigner]) {
+    for certificate in certificates {
+        let proof = &mut certificate.proof_of_delivery;
+        proof.threshold = signers.len() as u64;
+        proof.readies = signers
+            .iter()
+            .map(|signer| {
+                let validator_id: ValidatorId = signer.public_address.into();
+                let mut payload = Vec::new();
+                payload.extend_from_slice(proof.certificate_id.as_array());
+                payload.extend_from_slice(validator_id.as_bytes());
+
+                (
+                    validator_id.to_string(),
+                    signer.sign_message(&payload).unwrap().to_string(),
+                )
+            })
+            .collect();
+    }
+}
+
 #[test]
 fn encode() {
     use topos_core::api::grpc::shared::v1::Uuid as APIUuid;
@@ -30,6 +91,7 @@ fn encode() {
     let req = CheckpointRequest {
         request_id: Some(request_id),
         checkpoint: vec![],
+        limit: 100,
     };
 
     let x: Vec<u8> = req.clone().into();
@@ -121,6 +183,455 @@ async fn check_fetch_certificates() {
     assert_eq!(res.certificates, expected);
 }
 
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(10))]
+async fn fetch_snapshot_in_chunks() {
+    let certificates: Vec<CertificateDelivered> =
+        create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 5);
+
+    let boot_node = NodeConfig::from_seed(1);
+    let cluster = create_network(5, certificates.clone()).await;
+    let boot_node = cluster
+        .get(&boot_node.keypair.public().to_peer_id())
+        .unwrap()
+        .node_config
+        .clone();
+
+    let cfg = NodeConfig {
+        seed: 6,
+        minimum_cluster_size: 3,
+        ..Default::default()
+    };
+
+    let (client, _, _) = cfg.bootstrap(&[boot_node.clone()], None).await.unwrap();
+
+    use topos_core::api::grpc::shared::v1::Uuid as APIUuid;
+
+    let mut client: SynchronizerServiceClient<_> = client
+        .new_grpc_client::<SynchronizerServiceClient<_>, SynchronizerServiceServer<SynchronizerService>>(boot_node.keypair.public().to_peer_id())
+        .await
+        .unwrap();
+
+    let request_id: APIUuid = Uuid::new_v4().into();
+    let res = client
+        .fetch_snapshot(SnapshotRequest {
+            request_id: Some(request_id),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        res.heads,
+        vec![certificates[4].proof_of_delivery.clone().into()]
+    );
+    assert!(!res.pruned);
+
+    let request_id: APIUuid = Uuid::new_v4().into();
+    let res = client
+        .fetch_snapshot_chunk(SnapshotChunkRequest {
+            request_id: Some(request_id),
+            source_subnet_id: Some(SOURCE_SUBNET_ID_1.into()),
+            from_position: 1,
+            limit: 3,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let expected = certificates[1..4]
+        .iter()
+        .cloned()
+        .map(Into::into)
+        .collect::<Vec<topos_core::api::grpc::tce::v1::CertificateDelivered>>();
+
+    assert_eq!(res.certificates, expected);
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(10))]
+async fn bootstrap_from_snapshot() {
+    let signers = validators(3);
+    let mut certificates: Vec<CertificateDelivered> =
+        create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 15);
+    sign_readies(&mut certificates, &signers);
+
+    let boot_node = NodeConfig::from_seed(1);
+    let cluster = create_network(5, certificates.clone()).await;
+    let boot_node = cluster
+        .get(&boot_node.keypair.public().to_peer_id())
+        .unwrap()
+        .node_config
+        .clone();
+
+    let cfg = NodeConfig {
+        seed: 6,
+        minimum_cluster_size: 3,
+        ..Default::default()
+    };
+
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store =
+        create_validator_store(vec![], futures::future::ready(fullnode_store.clone())).await;
+
+    let (network, _, _) = cfg.bootstrap(&[boot_node.clone()], None).await.unwrap();
+    let (events, _) = mpsc::channel(1);
+
+    // A snapshot is never trusted without a validator set to verify it against
+    let untrusting = CheckpointSynchronizer {
+        config: CheckpointsCollectorConfig {
+            snapshot_bootstrap: true,
+            ..Default::default()
+        },
+        network: network.clone(),
+        store: validator_store.clone(),
+        current_request_id: None,
+        shutdown: CancellationToken::new(),
+        events: events.clone(),
+        commands: mpsc::channel(1).1,
+        status: Default::default(),
+    };
+    assert!(matches!(
+        untrusting.bootstrap_from_snapshot().await,
+        Err(SyncError::UntrustedSnapshot)
+    ));
+    assert_eq!(validator_store.count_certificates_delivered().unwrap(), 0);
+
+    let synchronizer = CheckpointSynchronizer {
+        config: CheckpointsCollectorConfig {
+            snapshot_bootstrap: true,
+            snapshot_chunk_size: 4,
+            validators: validator_ids(&signers),
+            delivery_threshold: 3,
+            ..Default::default()
+        },
+        network,
+        store: validator_store.clone(),
+        current_request_id: None,
+        shutdown: CancellationToken::new(),
+        events,
+        commands: mpsc::channel(1).1,
+        status: Default::default(),
+    };
+
+    assert_eq!(synchronizer.bootstrap_from_snapshot().await.unwrap(), 15);
+    assert_eq!(validator_store.count_certificates_delivered().unwrap(), 15);
+    assert_eq!(
+        validator_store
+            .get_source_head(&SOURCE_SUBNET_ID_1)
+            .unwrap()
+            .map(|head| head.certificate_id),
+        Some(certificates[14].certificate.id)
+    );
+
+    // A store already holding delivered certificates is not bootstrapped again
+    assert_eq!(synchronizer.bootstrap_from_snapshot().await.unwrap(), 0);
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(30))]
+async fn synchronize_checkpoints_in_windows() {
+    // More certificates than a single checkpoint diff holds
+    let mut certificates: Vec<CertificateDelivered> =
+        create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 120);
+    certificates.extend(create_certificate_chain(
+        SOURCE_SUBNET_ID_2,
+        &[TARGET_SUBNET_ID_1],
+        15,
+    ));
+
+    let boot_node = NodeConfig::from_seed(1);
+    let cluster = create_network(5, certificates.clone()).await;
+    let boot_node = cluster
+        .get(&boot_node.keypair.public().to_peer_id())
+        .unwrap()
+        .node_config
+        .clone();
+
+    let cfg = NodeConfig {
+        seed: 6,
+        minimum_cluster_size: 3,
+        ..Default::default()
+    };
+
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store =
+        create_validator_store(vec![], futures::future::ready(fullnode_store.clone())).await;
+
+    let (network, _, _) = cfg.bootstrap(&[boot_node.clone()], None).await.unwrap();
+    let (events, mut events_recv) = mpsc::channel(1000);
+
+    let mut synchronizer = CheckpointSynchronizer {
+        config: CheckpointsCollectorConfig {
+            fetch_window_size: 7,
+            max_concurrent_requests: 3,
+            ..Default::default()
+        },
+        network,
+        store: validator_store.clone(),
+        current_request_id: None,
+        shutdown: CancellationToken::new(),
+        events,
+        commands: mpsc::channel(1).1,
+        status: Default::default(),
+    };
+
+    synchronizer.initiate_request().await.unwrap();
+
+    assert_eq!(validator_store.count_certificates_delivered().unwrap(), 135);
+    for subnet_id in [SOURCE_SUBNET_ID_1, SOURCE_SUBNET_ID_2] {
+        let expected = certificates
+            .iter()
+            .filter(|certificate| certificate.certificate.source_subnet_id == subnet_id)
+            .map(|certificate| certificate.certificate.id)
+            .collect::<Vec<_>>();
+        let synchronized = validator_store
+            .get_source_stream_certificates_from_position(
+                CertificateSourceStreamPosition::new(subnet_id, Position::ZERO),
+                1000,
+            )
+            .unwrap()
+            .into_iter()
+            .map(|(certificate, _)| certificate.certificate.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(synchronized, expected);
+    }
+
+    let mut rounds = 0;
+    let mut synchronized = 0;
+    while let Ok(event) = events_recv.try_recv() {
+        match event {
+            CheckpointsCollectorEvent::RoundCompleted { .. } => rounds += 1,
+            CheckpointsCollectorEvent::CertificateSynchronized { .. } => synchronized += 1,
+            _ => {}
+        }
+    }
+    assert_eq!(rounds, 2);
+    assert_eq!(synchronized, 135);
+
+    assert_eq!(synchronizer.status.state, SyncState::Synced);
+    assert_eq!(synchronizer.status.missing_certificates(), 0);
+    assert_eq!(
+        synchronizer.status.subnets.get(&SOURCE_SUBNET_ID_1),
+        Some(&SubnetSyncStatus {
+            local_position: Some(119u64.into()),
+            best_known_position: Some(119u64.into()),
+        })
+    );
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(10))]
+async fn lone_node_is_synced() {
+    let (sender, mut commands) = mpsc::channel(1);
+    tokio::spawn(async move {
+        while let Some(command) = commands.recv().await {
+            if let Command::RandomKnownPeer { sender } = command {
+                _ = sender.send(Err(P2PError::CommandError(
+                    CommandExecutionError::NoKnownPeer,
+                )));
+            }
+        }
+    });
+    let network = NetworkClient {
+        retry_ttl: 10,
+        local_peer_id: PeerId::random(),
+        sender: sender.clone(),
+        grpc_over_p2p: GrpcOverP2P::new(sender),
+        shutdown_channel: mpsc::channel(1).0,
+    };
+
+    let fullnode_store = create_fullnode_store(vec![]).await;
+    let validator_store =
+        create_validator_store(vec![], futures::future::ready(fullnode_store)).await;
+    let (events, _) = mpsc::channel(10);
+
+    let mut synchronizer = CheckpointSynchronizer {
+        config: Default::default(),
+        network,
+        store: validator_store,
+        current_request_id: None,
+        shutdown: CancellationToken::new(),
+        events,
+        commands: mpsc::channel(1).1,
+        status: Default::default(),
+    };
+
+    assert_eq!(synchronizer.initiate_request().await.unwrap(), 0);
+    assert_eq!(synchronizer.status.state, SyncState::Synced);
+}
+
+#[test]
+fn sync_status_tracks_the_best_known_positions() {
+    let certificates: Vec<CertificateDelivered> =
+        create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 5);
+
+    let mut status = SyncStatus::default();
+    status.update_best_known_positions(&HashMap::from([(
+        SOURCE_SUBNET_ID_1,
+        certificates
+            .iter()
+            .map(|certificate| certificate.proof_of_delivery.clone())
+            .collect(),
+    )]));
+    assert_eq!(status.missing_certificates(), 5);
+
+    status.update_local_positions(&HashMap::from([(
+        SOURCE_SUBNET_ID_1,
+        SourceHead {
+            certificate_id: certificates[2].certificate.id,
+            subnet_id: SOURCE_SUBNET_ID_1,
+            position: 2u64.into(),
+        },
+    )]));
+    assert_eq!(status.missing_certificates(), 2);
+
+    // A local head beyond what peers reported is the best known position
+    status.update_local_positions(&HashMap::from([(
+        SOURCE_SUBNET_ID_2,
+        SourceHead {
+            certificate_id: certificates[0].certificate.id,
+            subnet_id: SOURCE_SUBNET_ID_2,
+            position: Position::ZERO,
+        },
+    )]));
+    assert_eq!(status.missing_certificates(), 2);
+    assert_eq!(
+        status.subnets.get(&SOURCE_SUBNET_ID_2),
+        Some(&SubnetSyncStatus {
+            local_position: Some(Position::ZERO),
+            best_known_position: Some(Position::ZERO),
+        })
+    );
+}
+
+#[test]
+fn verify_snapshot_certificates() {
+    let signers = validators(3);
+    let validators = validator_ids(&signers);
+    let mut certificates: Vec<CertificateDelivered> =
+        create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 2);
+    sign_readies(&mut certificates, &signers);
+    let first_id = certificates[0].certificate.id;
+
+    let verify = |certificate: &CertificateDelivered, subnet_id, position, previous| {
+        verify_delivered_certificate(certificate, subnet_id, position, previous, &validators, 3)
+    };
+
+    assert!(verify(
+        &certificates[0],
+        SOURCE_SUBNET_ID_1,
+        Position::ZERO,
+        INITIAL_CERTIFICATE_ID
+    )
+    .is_ok());
+    assert!(verify(&certificates[1], SOURCE_SUBNET_ID_1, 1u64.into(), first_id).is_ok());
+
+    let invalid =
+        |result: Result<(), SyncError>| matches!(result, Err(SyncError::InvalidSnapshot(_, _)));
+
+    // Unexpected source subnet
+    assert!(invalid(verify(
+        &certificates[0],
+        SOURCE_SUBNET_ID_2,
+        Position::ZERO,
+        INITIAL_CERTIFICATE_ID
+    )));
+
+    // Unexpected position
+    assert!(invalid(verify(
+        &certificates[1],
+        SOURCE_SUBNET_ID_1,
+        2u64.into(),
+        first_id
+    )));
+
+    // Broken precedence chain
+    assert!(invalid(verify(
+        &certificates[1],
+        SOURCE_SUBNET_ID_1,
+        1u64.into(),
+        certificates[1].certificate.id
+    )));
+
+    // First certificate of the stream not following the initial certificate
+    assert!(invalid(verify(
+        &certificates[1],
+        SOURCE_SUBNET_ID_1,
+        Position::ZERO,
+        INITIAL_CERTIFICATE_ID
+    )));
+
+    // Content not matching the certificate id
+    let mut tampered = certificates[0].clone();
+    tampered.certificate.state_root[0] ^= 0xff;
+    assert!(invalid(verify(
+        &tampered,
+        SOURCE_SUBNET_ID_1,
+        Position::ZERO,
+        INITIAL_CERTIFICATE_ID
+    )));
+
+    // The threshold of the proof is the one of the peer, the local one applies
+    let mut unproven = certificates[0].clone();
+    unproven.proof_of_delivery.threshold = 1;
+    unproven.proof_of_delivery.readies.truncate(2);
+    assert!(invalid(verify(
+        &unproven,
+        SOURCE_SUBNET_ID_1,
+        Position::ZERO,
+        INITIAL_CERTIFICATE_ID
+    )));
+
+    // Duplicated Ready messages only count once
+    let mut duplicated = certificates[0].clone();
+    let ready = duplicated.proof_of_delivery.readies[0].clone();
+    duplicated.proof_of_delivery.readies = vec![ready; 3];
+    assert!(invalid(verify(
+        &duplicated,
+        SOURCE_SUBNET_ID_1,
+        Position::ZERO,
+        INITIAL_CERTIFICATE_ID
+    )));
+
+    // Made up signatures
+    let mut forged = certificates[0].clone();
+    for (_, signature) in &mut forged.proof_of_delivery.readies {
+        *signature = "signature".into();
+    }
+    assert!(invalid(verify(
+        &forged,
+        SOURCE_SUBNET_ID_1,
+        Position::ZERO,
+        INITIAL_CERTIFICATE_ID
+    )));
+
+    // Ready messages of a certificate replayed for another one
+    let mut replayed = certificates[1].clone();
+    replayed.proof_of_delivery.readies = certificates[0].proof_of_delivery.readies.clone();
+    assert!(invalid(verify(
+        &replayed,
+        SOURCE_SUBNET_ID_1,
+        1u64.into(),
+        first_id
+    )));
+
+    // Ready messages signed by unknown validators
+    let mut unknown = certificates[0].clone();
+    sign_readies(std::slice::from_mut(&mut unknown), &validators_from(10, 3));
+    assert!(invalid(verify(
+        &unknown,
+        SOURCE_SUBNET_ID_1,
+        Position::ZERO,
+        INITIAL_CERTIFICATE_ID
+    )));
+}
+
 #[test]
 fn sync_unordered_certificates() {}
 
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use tonic::Request;
 use topos_core::api::grpc::tce::v1::{
     synchronizer_service_server::SynchronizerService, CheckpointRequest, FetchCertificatesRequest,
+    SnapshotChunkRequest, SnapshotRequest,
 };
 
 struct MockSynchronizerServer {}
@@ -25,4 +26,20 @@ impl SynchronizerService for MockSynchronizerServer {
     > {
         todo!()
     }
+
+    async fn fetch_snapshot(
+        &self,
+        _request: Request<SnapshotRequest>,
+    ) -> Result<tonic::Response<topos_core::api::grpc::tce::v1::SnapshotResponse>, tonic::Status>
+    {
+        todo!()
+    }
+
+    async fn fetch_snapshot_chunk(
+        &self,
+        _request: Request<SnapshotChunkRequest>,
+    ) -> Result<tonic::Response<topos_core::api::grpc::tce::v1::SnapshotChunkResponse>, tonic::Status>
+    {
+        todo!()
+    }
 }
@@ -0,0 +1,26 @@
+use tokio::sync::{mpsc, oneshot};
+use topos_p2p::PeerId;
+
+use crate::{checkpoints_collector::CheckpointsCollectorCommand, SynchronizerError};
+
+/// Client used to send commands to a running [`Synchronizer`](crate::Synchronizer)
+#[derive(Clone, Debug)]
+pub struct SynchronizerClient {
+    pub(crate) commands: mpsc::Sender<CheckpointsCollectorCommand>,
+}
+
+impl SynchronizerClient {
+    /// Synchronize the delivered certificates with the checkpoints of the given peer
+    ///
+    /// Returns the number of certificates synchronized.
+    pub async fn resync_from_peer(&self, peer: PeerId) -> Result<usize, SynchronizerError> {
+        let (sender, receiver) = oneshot::channel();
+
+        self.commands
+            .send(CheckpointsCollectorCommand::ResyncFromPeer { peer, sender })
+            .await
+            .map_err(|_| SynchronizerError::CommandCommunication)?;
+
+        Ok(receiver.await??)
+    }
+}
@@ -1,7 +1,7 @@
 use std::{future::IntoFuture, sync::Arc};
 
 use builder::SynchronizerBuilder;
-use checkpoints_collector::{CheckpointsCollectorError, CheckpointsCollectorEvent};
+use checkpoints_collector::CheckpointsCollectorError;
 use futures::{future::BoxFuture, FutureExt};
 use thiserror::Error;
 use tokio::sync::{
@@ -12,6 +12,12 @@ use tokio_stream::StreamExt;
 
 mod builder;
 mod checkpoints_collector;
+mod client;
+mod status;
+
+pub use checkpoints_collector::CheckpointsCollectorEvent;
+pub use client::SynchronizerClient;
+pub use status::{SubnetSyncStatus, SyncState, SyncStatus};
 
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
@@ -23,19 +29,22 @@ use topos_core::{
             synchronizer_service_server::SynchronizerService as GrpcSynchronizerService,
             CheckpointMapFieldEntry, CheckpointRequest, CheckpointResponse,
             FetchCertificatesRequest, FetchCertificatesResponse, ProofOfDelivery, SignedReady,
+            SnapshotChunkRequest, SnapshotChunkResponse, SnapshotRequest, SnapshotResponse,
         },
     },
-    uci::CertificateId,
+    types::stream::{CertificateSourceStreamPosition, Position},
+    uci::{CertificateId, SubnetId},
 };
 use topos_tce_storage::{store::ReadStore, validator::ValidatorStore};
 use tracing::{error, info, warn};
 
 pub struct Synchronizer {
     pub(crate) shutdown: CancellationToken,
-    #[allow(dead_code)]
     pub(crate) events: mpsc::Sender<SynchronizerEvent>,
 
     pub(crate) checkpoints_collector_stream: ReceiverStream<CheckpointsCollectorEvent>,
+
+    pub(crate) client: SynchronizerClient,
 }
 
 impl IntoFuture for Synchronizer {
@@ -51,7 +60,13 @@ impl IntoFuture for Synchronizer {
                         break None
                     }
 
-                    _checkpoint_event = self.checkpoints_collector_stream.next() => {}
+                    Some(event) = self.checkpoints_collector_stream.next() => {
+                        if let CheckpointsCollectorEvent::StatusUpdated(status) = event {
+                            _ = self.events.send(SynchronizerEvent::StatusUpdated(status)).await;
+                        } else {
+                            _ = self.events.try_send(SynchronizerEvent::CheckpointsCollector(event));
+                        }
+                    }
                 }
             };
 
@@ -71,6 +86,11 @@ impl Synchronizer {
     pub fn builder() -> SynchronizerBuilder {
         SynchronizerBuilder::default()
     }
+
+    /// Returns a client to send commands to the running synchronizer
+    pub fn client(&self) -> SynchronizerClient {
+        self.client.clone()
+    }
 }
 
 #[derive(Error, Debug)]
@@ -95,15 +115,34 @@ pub enum SynchronizerError {
 
     #[error("No network protocol receiver set")]
     NoProtocolReceiver,
+
+    #[error("Unable to send a command to the CheckpointsCollector")]
+    CommandCommunication,
 }
 
-pub enum SynchronizerEvent {}
+#[derive(Debug, Clone)]
+pub enum SynchronizerEvent {
+    /// Progress of the synchronization of the checkpoints
+    CheckpointsCollector(CheckpointsCollectorEvent),
+    /// The synchronization status of the node changed
+    StatusUpdated(SyncStatus),
+}
 
 #[derive(Clone)]
 pub struct SynchronizerService {
     pub validator_store: Arc<ValidatorStore>,
 }
 
+impl SynchronizerService {
+    /// Maximum number of certificates served in one snapshot chunk
+    pub const MAX_SNAPSHOT_CHUNK_SIZE: usize = 100;
+    /// Number of proofs of delivery returned for each subnet in a checkpoint diff when the
+    /// request doesn't set a limit
+    pub const DEFAULT_CHECKPOINT_DIFF_SIZE: usize = 100;
+    /// Maximum number of proofs of delivery returned for each subnet in a checkpoint diff
+    pub const MAX_CHECKPOINT_DIFF_SIZE: usize = 1000;
+}
+
 #[async_trait::async_trait]
 impl GrpcSynchronizerService for SynchronizerService {
     async fn fetch_certificates(
@@ -160,7 +199,12 @@ impl GrpcSynchronizerService for SynchronizerService {
             Ok(value) => value,
         };
 
-        let diff = if let Ok(diff) = self.validator_store.get_checkpoint_diff(res) {
+        let limit = match usize::try_from(request.limit).unwrap_or(usize::MAX) {
+            0 => Self::DEFAULT_CHECKPOINT_DIFF_SIZE,
+            limit => limit.min(Self::MAX_CHECKPOINT_DIFF_SIZE),
+        };
+
+        let diff = if let Ok(diff) = self.validator_store.get_checkpoint_diff(res, limit) {
             diff.into_iter()
                 .map(|(key, value)| {
                     let v: Vec<_> = value
@@ -196,4 +240,77 @@ impl GrpcSynchronizerService for SynchronizerService {
 
         Ok(Response::new(response))
     }
+
+    async fn fetch_snapshot(
+        &self,
+        request: Request<SnapshotRequest>,
+    ) -> Result<Response<SnapshotResponse>, Status> {
+        let request = request.into_inner();
+
+        let checkpoint = self
+            .validator_store
+            .get_checkpoint()
+            .map_err(|_| Status::internal("Unable to read the checkpoint"))?;
+
+        let fullnode_store = self.validator_store.get_fullnode_store();
+        let mut pruned = false;
+        for subnet_id in checkpoint.keys() {
+            pruned |= fullnode_store
+                .first_available_position(subnet_id)
+                .map_err(|_| Status::internal("Unable to read the pruned source streams"))?
+                != Position::ZERO;
+        }
+
+        let certificate_ids = checkpoint
+            .into_values()
+            .map(|head| head.certificate_id)
+            .collect::<Vec<_>>();
+
+        let heads = self
+            .validator_store
+            .get_certificates(&certificate_ids[..])
+            .map_err(|_| Status::internal("Unable to read the source heads"))?
+            .into_iter()
+            .flatten()
+            .map(|delivered_certificate| delivered_certificate.proof_of_delivery.into())
+            .collect();
+
+        Ok(Response::new(SnapshotResponse {
+            request_id: request.request_id,
+            heads,
+            pruned,
+        }))
+    }
+
+    async fn fetch_snapshot_chunk(
+        &self,
+        request: Request<SnapshotChunkRequest>,
+    ) -> Result<Response<SnapshotChunkResponse>, Status> {
+        let request = request.into_inner();
+        let subnet_id: SubnetId = request
+            .source_subnet_id
+            .ok_or_else(|| Status::invalid_argument("Missing source subnet id"))?
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Unable to parse source subnet id"))?;
+
+        let limit = usize::try_from(request.limit)
+            .unwrap_or(usize::MAX)
+            .min(Self::MAX_SNAPSHOT_CHUNK_SIZE);
+
+        let certificates = self
+            .validator_store
+            .get_source_stream_certificates_from_position(
+                CertificateSourceStreamPosition::new(subnet_id, request.from_position),
+                limit,
+            )
+            .map_err(|_| Status::internal("Unable to read the source stream"))?
+            .into_iter()
+            .map(|(delivered_certificate, _)| delivered_certificate.into())
+            .collect();
+
+        Ok(Response::new(SnapshotChunkResponse {
+            request_id: request.request_id,
+            certificates,
+        }))
+    }
 }
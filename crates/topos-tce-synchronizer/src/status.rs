@@ -0,0 +1,155 @@
+//! Synchronization status of the node compared to its peers
+//!
+//! The checkpoints synchronizer keeps, for every source stream, the position of the local
+//! head and the highest position a peer reported as delivered in a checkpoint diff. The
+//! [`SyncState`] is derived from the outcome of the synchronization rounds, the node being
+//! synced only once a quorum of peers has nothing more to deliver.
+use std::collections::HashMap;
+
+use topos_core::{
+    api::grpc::tce::v1 as grpc,
+    types::{stream::Position, ProofOfDelivery},
+    uci::SubnetId,
+};
+use topos_metrics::{
+    SYNCHRONIZER_BEST_KNOWN_POSITION, SYNCHRONIZER_LOCAL_POSITION, SYNCHRONIZER_STATE,
+};
+use topos_tce_storage::SourceHead;
+
+/// State of the synchronization of the delivered certificates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncState {
+    /// Catching up with the certificates delivered by the peers
+    #[default]
+    Syncing,
+    /// Every certificate known to be delivered by a peer is delivered locally
+    Synced,
+    /// Behind the peers without making any progress
+    Stalled,
+}
+
+/// Local and best known positions of a source stream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubnetSyncStatus {
+    /// Position of the local source head, `None` if nothing is delivered yet
+    pub local_position: Option<Position>,
+    /// Highest position known to be delivered by a peer
+    pub best_known_position: Option<Position>,
+}
+
+impl SubnetSyncStatus {
+    /// Number of certificates known to be delivered by a peer but not locally
+    pub fn missing_certificates(&self) -> u64 {
+        match (self.local_position, self.best_known_position) {
+            (_, None) => 0,
+            (None, Some(best_known)) => *best_known + 1,
+            (Some(local), Some(best_known)) => best_known.saturating_sub(*local),
+        }
+    }
+}
+
+/// Synchronization status of the node
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncStatus {
+    pub state: SyncState,
+    pub subnets: HashMap<SubnetId, SubnetSyncStatus>,
+}
+
+impl SyncStatus {
+    /// Number of certificates known to be delivered by a peer but not locally
+    pub fn missing_certificates(&self) -> u64 {
+        self.subnets
+            .values()
+            .map(SubnetSyncStatus::missing_certificates)
+            .sum()
+    }
+
+    /// Update the local positions with the source heads of the store
+    pub(crate) fn update_local_positions(&mut self, checkpoint: &HashMap<SubnetId, SourceHead>) {
+        for (subnet_id, head) in checkpoint {
+            let status = self.subnets.entry(*subnet_id).or_default();
+            status.local_position = Some(head.position);
+            if status
+                .best_known_position
+                .map_or(true, |best_known| *best_known < *head.position)
+            {
+                status.best_known_position = Some(head.position);
+            }
+        }
+    }
+
+    /// Update the best known positions with the checkpoint diff of a peer
+    pub(crate) fn update_best_known_positions(
+        &mut self,
+        diff: &HashMap<SubnetId, Vec<ProofOfDelivery>>,
+    ) {
+        for (subnet_id, proofs) in diff {
+            let Some(position) = proofs
+                .iter()
+                .map(|proof| proof.delivery_position.position)
+                .max_by_key(|position| **position)
+            else {
+                continue;
+            };
+
+            let status = self.subnets.entry(*subnet_id).or_default();
+            if status
+                .best_known_position
+                .map_or(true, |best_known| *best_known < *position)
+            {
+                status.best_known_position = Some(position);
+            }
+        }
+    }
+
+    /// Expose the status as metrics
+    pub(crate) fn record_metrics(&self) {
+        SYNCHRONIZER_STATE.set(match self.state {
+            SyncState::Syncing => 0,
+            SyncState::Synced => 1,
+            SyncState::Stalled => 2,
+        });
+
+        for (subnet_id, status) in &self.subnets {
+            let subnet_id = subnet_id.to_string();
+            if let Some(position) = status.local_position {
+                SYNCHRONIZER_LOCAL_POSITION
+                    .with_label_values(&[&subnet_id])
+                    .set(*position as i64);
+            }
+            if let Some(position) = status.best_known_position {
+                SYNCHRONIZER_BEST_KNOWN_POSITION
+                    .with_label_values(&[&subnet_id])
+                    .set(*position as i64);
+            }
+        }
+    }
+}
+
+impl From<SyncState> for grpc::SyncState {
+    fn from(value: SyncState) -> Self {
+        match value {
+            SyncState::Syncing => grpc::SyncState::Syncing,
+            SyncState::Synced => grpc::SyncState::Synced,
+            SyncState::Stalled => grpc::SyncState::Stalled,
+        }
+    }
+}
+
+impl From<SyncStatus> for grpc::SyncStatus {
+    fn from(value: SyncStatus) -> Self {
+        grpc::SyncStatus {
+            state: grpc::SyncState::from(value.state).into(),
+            subnets: value
+                .subnets
+                .into_iter()
+                .map(|(subnet_id, status)| grpc::SubnetSyncStatus {
+                    subnet_id: Some(subnet_id.into()),
+                    local_position: status.local_position.map(|position| *position),
+                    best_known_position: status.best_known_position.map(|position| *position),
+                    missing_certificates: status.missing_certificates(),
+                })
+                .collect(),
+        }
+    }
+}
@@ -3,8 +3,10 @@
 //!
 use crate::events::Events;
 use futures::{Stream, StreamExt};
+use lru::LruCache;
 use prometheus::HistogramTimer;
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 use tce_transport::ProtocolEvents;
 use tokio::sync::mpsc;
@@ -19,12 +21,13 @@ use topos_tce_gatekeeper::GatekeeperClient;
 use topos_tce_storage::types::CertificateDeliveredWithPositions;
 use topos_tce_storage::validator::ValidatorStore;
 use topos_tce_storage::StorageClient;
-use topos_tce_synchronizer::SynchronizerEvent;
+use topos_tce_synchronizer::{SynchronizerClient, SynchronizerEvent};
 use tracing::{error, info, warn};
 
 mod api;
 mod network;
 pub(crate) mod protocol;
+mod synchronizer;
 
 /// Top-level transducer main app context & driver (alike)
 ///
@@ -42,8 +45,16 @@ pub struct AppContext {
     pub api_client: ApiClient,
     pub pending_storage: StorageClient,
     pub gatekeeper: GatekeeperClient,
+    /// Client of the synchronizer, `None` if the node doesn't run one
+    pub synchronizer: Option<SynchronizerClient>,
+    /// Number of certificates the node can miss, compared to its peers, while still
+    /// accepting the submission of new certificates
+    pub max_missing_certificates: u64,
 
     pub delivery_latency: HashMap<CertificateId, HistogramTimer>,
+    /// Certificates whose broadcast failed, until they are submitted again or delivered,
+    /// the oldest ones being forgotten first
+    pub failed_certificates: LruCache<CertificateId, ()>,
 
     pub validator_store: Arc<ValidatorStore>,
 }
@@ -54,7 +65,14 @@ impl AppContext {
     const DUMMY_INITIAL_CERTIFICATE_ID: CertificateId =
         CertificateId::from_array([0u8; topos_core::uci::CERTIFICATE_ID_LENGTH]);
 
+    /// Number of failed broadcasts remembered to report the status of their certificate
+    const MAX_FAILED_CERTIFICATES: usize = 10_000;
+
+    /// Number of certificates the node can miss by default while accepting submissions
+    pub const DEFAULT_MAX_MISSING_CERTIFICATES: u64 = 10;
+
     /// Factory
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         is_validator: bool,
         pending_storage: StorageClient,
@@ -62,6 +80,7 @@ impl AppContext {
         network_client: NetworkClient,
         api_client: ApiClient,
         gatekeeper: GatekeeperClient,
+        synchronizer: Option<SynchronizerClient>,
         validator_store: Arc<ValidatorStore>,
     ) -> (Self, mpsc::Receiver<Events>) {
         let (events, receiver) = mpsc::channel(100);
@@ -74,13 +93,26 @@ impl AppContext {
                 api_client,
                 pending_storage,
                 gatekeeper,
+                synchronizer,
+                max_missing_certificates: Self::DEFAULT_MAX_MISSING_CERTIFICATES,
                 delivery_latency: Default::default(),
+                failed_certificates: LruCache::new(
+                    NonZeroUsize::new(Self::MAX_FAILED_CERTIFICATES)
+                        .expect("The number of failed certificates is not zero"),
+                ),
                 validator_store,
             },
             receiver,
         )
     }
 
+    /// Set the number of certificates the node can miss while accepting submissions
+    pub fn with_max_missing_certificates(mut self, max_missing_certificates: u64) -> Self {
+        self.max_missing_certificates = max_missing_certificates;
+
+        self
+    }
+
     /// Main processing loop
     #[allow(clippy::too_many_arguments)]
     pub async fn run(
@@ -98,6 +130,7 @@ impl AppContext {
                 Some(delivery) = broadcast_stream.next() => {
                     let certificate_id = delivery.0.certificate.id;
                     CERTIFICATE_DELIVERED_TOTAL.inc();
+                    self.failed_certificates.pop(&certificate_id);
 
                     if let Some(timer) = self.delivery_latency.remove(&certificate_id) {
                         let duration = timer.stop_and_record();
@@ -121,7 +154,8 @@ impl AppContext {
                 }
 
                 // Synchronizer events
-                Some(_event) = synchronizer_stream.next() => {
+                Some(event) = synchronizer_stream.next() => {
+                    self.on_synchronizer_event(event).await;
                 }
 
                 // Shutdown signal
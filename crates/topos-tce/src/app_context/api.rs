@@ -1,9 +1,11 @@
 use crate::AppContext;
 use std::collections::HashMap;
+use topos_core::api::grpc::tce::v1 as grpc;
 use topos_core::uci::{Certificate, SubnetId};
 use topos_metrics::CERTIFICATE_DELIVERY_LATENCY;
 use topos_tce_api::RuntimeError;
 use topos_tce_api::RuntimeEvent as ApiEvent;
+use topos_tce_broadcast::double_echo::broadcast_state::{BroadcastStateSnapshot, Status};
 use topos_tce_storage::errors::{InternalStorageError, StorageError};
 use topos_tce_storage::types::PendingResult;
 use tracing::debug;
@@ -16,8 +18,40 @@ impl AppContext {
                 certificate,
                 sender,
             } => {
+                // A node far behind its peers would broadcast a certificate whose predecessors
+                // it doesn't know yet, it can only be resubmitted once the node caught up.
+                // A few missing certificates, e.g. while a sync round is stalled, are tolerated.
+                if let Some((status, missing_certificates)) = self
+                    .api_client
+                    .sync_status()
+                    .await
+                    .map(|status| {
+                        let missing_certificates = status
+                            .subnets
+                            .iter()
+                            .map(|subnet| subnet.missing_certificates)
+                            .sum::<u64>();
+
+                        (status, missing_certificates)
+                    })
+                    .filter(|(_, missing_certificates)| {
+                        *missing_certificates > self.max_missing_certificates
+                    })
+                {
+                    warn!(
+                        "Certificate {} rejected while the node is missing {} certificates ({})",
+                        certificate.id,
+                        missing_certificates,
+                        status.state().as_str_name()
+                    );
+                    _ = sender.send(Err(RuntimeError::NotSynced(status.state().as_str_name())));
+
+                    return;
+                }
+
                 self.delivery_latency
                     .insert(certificate.id, CERTIFICATE_DELIVERY_LATENCY.start_timer());
+                self.failed_certificates.pop(&certificate.id);
 
                 _ = match self
                     .validator_store
@@ -47,6 +81,18 @@ impl AppContext {
                         );
                         sender.send(Ok(PendingResult::AlreadyDelivered))
                     }
+                    Err(
+                        error @ StorageError::InternalStorage(
+                            InternalStorageError::CertificateAlreadyPending,
+                        ),
+                    ) => {
+                        debug!(
+                            "Certificate {} is already pending, broadcasting it again",
+                            certificate.id
+                        );
+
+                        sender.send(Err(error.into()))
+                    }
                     Err(error) => {
                         error!(
                             "Unable to insert pending certificate {}: {}",
@@ -100,6 +146,8 @@ impl AppContext {
                             state_root: Default::default(),
                             tx_root_hash: Default::default(),
                             receipts_root_hash: Default::default(),
+                            messages_root: Default::default(),
+                            block_range: None,
                             target_subnets: vec![],
                             verifier: 0,
                             id: AppContext::DUMMY_INITIAL_CERTIFICATE_ID,
@@ -151,6 +199,84 @@ impl AppContext {
 
                 _ = sender.send(Ok(last_pending_certificates));
             }
+
+            ApiEvent::ResyncFromPeer { peer_id, sender } => {
+                let Some(synchronizer) = self.synchronizer.clone() else {
+                    _ = sender.send(Err(RuntimeError::UnableToResync(
+                        peer_id,
+                        "the synchronizer is not running".to_string(),
+                    )));
+
+                    return;
+                };
+
+                // The synchronization can take a while, don't block the other events
+                tokio::spawn(async move {
+                    _ =
+                        sender.send(synchronizer.resync_from_peer(peer_id).await.map_err(
+                            |error| RuntimeError::UnableToResync(peer_id, error.to_string()),
+                        ));
+                });
+            }
+
+            ApiEvent::GetBroadcastState {
+                certificate_id,
+                sender,
+            } => {
+                let tce_cli = self.tce_cli.clone();
+                let failed = self.failed_certificates.contains(&certificate_id);
+                tokio::spawn(async move {
+                    _ = sender.send(
+                        tce_cli
+                            .get_broadcast_state(certificate_id)
+                            .await
+                            .map(|state| match state {
+                                Some(state) => Some(broadcast_state_to_grpc(state)),
+                                None if failed => Some(grpc::BroadcastState {
+                                    certificate_id: Some(certificate_id.into()),
+                                    status: grpc::BroadcastStatus::Failed.into(),
+                                    ..Default::default()
+                                }),
+                                None => None,
+                            })
+                            .map_err(|error| {
+                                RuntimeError::UnableToGetBroadcastState(
+                                    certificate_id,
+                                    error.to_string(),
+                                )
+                            }),
+                    );
+                });
+            }
+
+            ApiEvent::EvictCertificates { certificate_ids } => {
+                if let Err(error) = self.tce_cli.evict_certificates(certificate_ids).await {
+                    error!("Unable to stop the broadcast of evicted certificates: {error}");
+                }
+            }
+        }
+    }
+}
+
+fn broadcast_state_to_grpc(state: BroadcastStateSnapshot) -> grpc::BroadcastState {
+    grpc::BroadcastState {
+        certificate_id: Some(state.certificate_id.into()),
+        status: match state.status {
+            Status::Pending => grpc::BroadcastStatus::Pending,
+            Status::EchoSent => grpc::BroadcastStatus::EchoSent,
+            Status::ReadySent => grpc::BroadcastStatus::ReadySent,
+            Status::DeliveredWithReadySent => grpc::BroadcastStatus::DeliveredWithReadySent,
+            Status::Delivered => grpc::BroadcastStatus::Delivered,
         }
+        .into(),
+        awaiting_previous_certificate: state.awaiting_previous_certificate,
+        network_size: state.network_size as u64,
+        echo_threshold: state.echo_threshold as u64,
+        ready_threshold: state.ready_threshold as u64,
+        delivery_threshold: state.delivery_threshold as u64,
+        missing_echoes: state.missing_echoes.into_iter().map(Into::into).collect(),
+        missing_readies: state.missing_readies.into_iter().map(Into::into).collect(),
+        position: state.expected_position.map(|position| *position),
+        elapsed_ms: state.elapsed.as_millis() as u64,
     }
 }
@@ -84,7 +84,8 @@ impl AppContext {
                 }
             }
             ProtocolEvents::BroadcastFailed { certificate_id } => {
-                warn!("Broadcast failed for certificate {certificate_id}")
+                warn!("Broadcast failed for certificate {certificate_id}");
+                self.failed_certificates.put(certificate_id, ());
             }
             ProtocolEvents::AlreadyDelivered { certificate_id } => {
                 info!("Certificate {certificate_id} already delivered")
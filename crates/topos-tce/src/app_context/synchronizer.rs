@@ -0,0 +1,23 @@
+use crate::AppContext;
+use topos_tce_synchronizer::{SyncState, SynchronizerEvent};
+use tracing::{info, warn};
+
+impl AppContext {
+    pub async fn on_synchronizer_event(&mut self, event: SynchronizerEvent) {
+        if let SynchronizerEvent::StatusUpdated(status) = event {
+            match status.state {
+                SyncState::Stalled => warn!(
+                    "Synchronization stalled, {} certificates behind the peers",
+                    status.missing_certificates()
+                ),
+                SyncState::Syncing => info!(
+                    "Synchronizing, {} certificates behind the peers",
+                    status.missing_certificates()
+                ),
+                SyncState::Synced => {}
+            }
+
+            self.api_client.set_sync_status(status.into()).await;
+        }
+    }
+}
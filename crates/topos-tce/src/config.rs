@@ -5,9 +5,12 @@ use std::time::Duration;
 
 use tce_transport::ReliableBroadcastParams;
 use topos_core::types::ValidatorId;
+use topos_core::uci::SubnetId;
 use topos_p2p::{Multiaddr, PeerId};
+use topos_tce_storage::pruning::StorageMode;
 
 pub use crate::AppContext;
+pub use topos_tce_api::{ApiAccess, ApiKeys, LogFilterHandle, RateLimit, TlsConfig};
 
 #[derive(Debug)]
 pub enum AuthKey {
@@ -24,12 +27,46 @@ pub struct TceConfiguration {
     pub validators: HashSet<ValidatorId>,
     pub api_addr: SocketAddr,
     pub graphql_api_addr: SocketAddr,
+    /// Address of the REST gateway, the gateway is disabled if not set
+    pub rest_api_addr: Option<SocketAddr>,
     pub metrics_api_addr: SocketAddr,
     pub tce_addr: String,
     pub tce_local_port: u16,
+    /// UDP port of the QUIC transport, QUIC is disabled if not set
+    pub tce_quic_local_port: Option<u16>,
     pub storage: StorageConfiguration,
+    /// Retention policy of the delivered certificates
+    pub storage_mode: StorageMode,
     pub network_bootstrap_timeout: Duration,
     pub minimum_cluster_size: usize,
+    /// Maximum number of concurrent requests served on a single gRPC-over-p2p stream
+    pub max_concurrent_grpc_requests_per_stream: usize,
+    /// Number of certificates the node can miss, compared to its peers, while still
+    /// accepting the submission of new certificates
+    pub max_missing_certificates: u64,
+    /// Bootstrap an empty storage from the snapshot of a peer before syncing checkpoints
+    pub sync_from_snapshot: bool,
+    /// Gating mode, only the allowed peers and the proven validators can connect if set
+    pub allowed_peers: Option<HashSet<PeerId>>,
+    /// Sentries of a validator, or validators protected by a sentry, always receiving our gossip
+    pub sentry_peers: Vec<(PeerId, Multiaddr)>,
+    /// Token of the admin gRPC service, the service is disabled if not set
+    pub admin_token: Option<String>,
+    /// Address of the admin gRPC service, served apart from the public APIs
+    pub admin_api_addr: SocketAddr,
+    /// Source subnets from which certificates can be submitted, every subnet is accepted if not set
+    pub allowed_source_subnets: Option<HashSet<SubnetId>>,
+    /// Authentication, rate limits and query limits of the public APIs
+    pub api_access: ApiAccess,
+    /// PEM file of the certificate chain of the public APIs, served over TLS if set with the key
+    pub tls_certificate: Option<PathBuf>,
+    /// PEM file of the private key of the public APIs
+    pub tls_key: Option<PathBuf>,
+    /// PEM file of the CA authenticating the clients of the public APIs over TLS, the clients
+    /// don't present a certificate if not set
+    pub tls_client_ca: Option<PathBuf>,
+    /// Handle used by the admin gRPC service to change the log filter
+    pub log_filter: Option<LogFilterHandle>,
     pub version: &'static str,
 }
 
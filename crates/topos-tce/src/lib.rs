@@ -12,9 +12,12 @@ use tokio::{
 };
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_util::sync::CancellationToken;
-use topos_core::api::grpc::tce::v1::synchronizer_service_server::SynchronizerServiceServer;
+use topos_core::api::grpc::tce::v1::{
+    synchronizer_service_server::SynchronizerServiceServer, NodeRole,
+};
 use topos_crypto::{messages::MessageSigner, validator_id::ValidatorId};
 use topos_p2p::{
+    config::{GatingConfig, LimitsConfig},
     utils::{local_key_pair, local_key_pair_from_slice},
     GrpcContext, GrpcRouter, Multiaddr,
 };
@@ -23,6 +26,7 @@ use topos_tce_storage::{
     epoch::{EpochValidatorsStore, ValidatorPerEpochStore},
     fullnode::FullNodeStore,
     index::IndexTables,
+    pruning::Pruner,
     store::ReadStore,
     validator::{ValidatorPerpetualTables, ValidatorStore},
     StorageClient,
@@ -35,10 +39,11 @@ pub mod config;
 pub mod events;
 #[cfg(test)]
 mod tests;
+mod validator_proof;
 
 pub use app_context::AppContext;
 
-use crate::config::{AuthKey, StorageConfiguration};
+use crate::config::{AuthKey, StorageConfiguration, TlsConfig};
 
 // TODO: Estimate on the max broadcast throughput, could need to be override by config
 const BROADCAST_CHANNEL_SIZE: usize = 10_000;
@@ -76,43 +81,76 @@ pub async fn run(
 
     let addr: Multiaddr = format!("/ip4/0.0.0.0/tcp/{}", config.tce_local_port).parse()?;
 
+    let quic_addrs: Option<(Multiaddr, Multiaddr)> = match config.tce_quic_local_port {
+        Some(port) => Some((
+            format!("/ip4/0.0.0.0/udp/{port}/quic-v1").parse()?,
+            format!("{}/udp/{port}/quic-v1", config.tce_addr).parse()?,
+        )),
+        None => None,
+    };
+
     let mut boot_peers = config.boot_peers.clone();
     // Remove myself from the bootnode list
     boot_peers.retain(|(p, _)| *p != peer_id);
     let is_validator = config.validators.contains(&validator_id);
 
     debug!("Starting the Storage");
-    let path = if let StorageConfiguration::RocksDB(Some(ref path)) = config.storage {
-        path
-    } else {
-        return Err(Box::new(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!("Unsupported storage type {:?}", config.storage),
-        )));
+    let (fullnode_store, validator_store) = match config.storage {
+        StorageConfiguration::RAM => {
+            warn!("Storage kept in memory, nothing will survive a restart");
+            let fullnode_store =
+                FullNodeStore::open_in_memory().expect("Unable to create full node store");
+            let validator_store = ValidatorStore::open_in_memory(fullnode_store.clone())
+                .expect("Unable to create validator store");
+
+            (fullnode_store, validator_store)
+        }
+        StorageConfiguration::RocksDB(Some(ref path)) => {
+            let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(path.clone()));
+            let index_tables = Arc::new(IndexTables::open(path.clone()));
+
+            let validators_store = EpochValidatorsStore::new(path.clone())
+                .expect("Unable to create EpochValidators store");
+
+            let epoch_store = ValidatorPerEpochStore::new(0, path.clone())
+                .expect("Unable to create Per epoch store");
+
+            let fullnode_store = FullNodeStore::open(
+                epoch_store,
+                validators_store,
+                perpetual_tables,
+                index_tables,
+            )
+            .expect("Unable to create full node store");
+
+            let validator_store = ValidatorStore::open(path.clone(), fullnode_store.clone())
+                .expect("Unable to create validator store");
+
+            (fullnode_store, validator_store)
+        }
+        StorageConfiguration::RocksDB(None) => {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Unsupported storage type {:?}", config.storage),
+            )));
+        }
     };
 
-    let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(path.clone()));
-    let index_tables = Arc::new(IndexTables::open(path.clone()));
-
-    let validators_store =
-        EpochValidatorsStore::new(path.clone()).expect("Unable to create EpochValidators store");
-
-    let epoch_store =
-        ValidatorPerEpochStore::new(0, path.clone()).expect("Unable to create Per epoch store");
-
-    let fullnode_store = FullNodeStore::open(
-        epoch_store,
-        validators_store,
-        perpetual_tables,
-        index_tables,
-    )
-    .expect("Unable to create full node store");
-
-    let validator_store = ValidatorStore::open(path.clone(), fullnode_store.clone())
-        .expect("Unable to create validator store");
+    if let Some(pruner) = Pruner::new(
+        config.storage_mode,
+        fullnode_store.clone(),
+        shutdown.0.child_token(),
+    ) {
+        info!("Storage pruned, {:?}", config.storage_mode);
+        spawn(pruner.into_future());
+    }
+
+    let limits = LimitsConfig::default().with_max_concurrent_grpc_requests_per_stream(
+        config.max_concurrent_grpc_requests_per_stream,
+    );
 
     let grpc_context = GrpcContext::default().with_router(
-        GrpcRouter::new(tonic::transport::Server::builder()).add_service(
+        GrpcRouter::new_with_limits(tonic::transport::Server::builder(), &limits).add_service(
             SynchronizerServiceServer::new(SynchronizerService {
                 validator_store: validator_store.clone(),
             }),
@@ -140,15 +178,44 @@ pub async fn run(
         certificates_synced, pending_certificates, precedence_pool_certificates
     );
 
-    let (network_client, event_stream, unbootstrapped_runtime) = topos_p2p::network::builder()
+    let mut network_builder = topos_p2p::network::builder()
         .peer_key(key)
         .listen_addr(addr)
         .minimum_cluster_size(config.minimum_cluster_size)
         .exposed_addresses(external_addr)
         .known_peers(&boot_peers)
-        .grpc_context(grpc_context)
-        .build()
-        .await?;
+        .explicit_peers(&config.sentry_peers)
+        .limits(limits)
+        .grpc_context(grpc_context);
+
+    if is_validator {
+        network_builder = network_builder.peer_proof_signer(
+            validator_proof::validator_proof_signer(message_signer.clone(), peer_id),
+        );
+    }
+
+    if let Some(allowed_peers) = config.allowed_peers.clone() {
+        info!(
+            "Gating mode enabled with {} allowed peers",
+            allowed_peers.len()
+        );
+        network_builder = network_builder.gating(
+            GatingConfig::default()
+                .with_allowed_peers(allowed_peers)
+                .with_proof_verifier(validator_proof::validator_proof_verifier(
+                    fullnode_store.clone(),
+                    config.validators.clone(),
+                )),
+        );
+    }
+
+    if let Some((quic_addr, quic_external_addr)) = quic_addrs {
+        network_builder = network_builder
+            .quic_listen_addr(quic_addr)
+            .quic_exposed_addresses(quic_external_addr);
+    }
+
+    let (network_client, event_stream, unbootstrapped_runtime) = network_builder.build().await?;
 
     debug!("Starting the p2p network");
     let network_runtime = tokio::time::timeout(
@@ -193,22 +260,61 @@ pub async fn run(
             .with_shutdown(shutdown.0.child_token())
             .with_store(validator_store.clone())
             .with_network_client(network_client.clone())
+            .with_snapshot_bootstrap(config.sync_from_snapshot)
+            .with_validators(
+                config.validators.clone(),
+                config.tce_params.delivery_threshold,
+            )
             .build()?;
 
+    let synchronizer_client = synchronizer_runtime.client();
     spawn(synchronizer_runtime.into_future());
     debug!("Synchronizer started");
 
+    let api_tls = match (&config.tls_certificate, &config.tls_key) {
+        (Some(certificate), Some(key)) => Some(TlsConfig::from_pem_files(
+            certificate,
+            key,
+            config.tls_client_ca.as_deref(),
+        )?),
+        (None, None) if config.tls_client_ca.is_none() => None,
+        _ => {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Serving the APIs over TLS requires both a certificate and a key",
+            )))
+        }
+    };
+
     debug!("Starting gRPC api");
-    let (api_client, api_stream, _ctx) = topos_tce_api::Runtime::builder()
+    let mut api_builder = topos_tce_api::Runtime::builder()
         .with_peer_id(peer_id.to_string())
+        .with_validator_id(public_address)
+        .with_role(if is_validator {
+            NodeRole::Validator
+        } else {
+            NodeRole::FullNode
+        })
+        .with_version(config.version.to_string())
+        .with_network_client(network_client.clone())
         .with_broadcast_stream(broadcast_receiver.resubscribe())
         .serve_grpc_addr(config.api_addr)
         .serve_graphql_addr(config.graphql_api_addr)
         .serve_metrics_addr(config.metrics_api_addr)
+        .with_admin_token(config.admin_token.clone())
+        .serve_admin_addr(config.admin_api_addr)
+        .with_log_filter(config.log_filter.clone())
+        .with_allowed_source_subnets(config.allowed_source_subnets.clone())
+        .with_api_access(config.api_access.clone())
+        .with_tls(api_tls)
         .store(validator_store.clone())
-        .storage(storage_client.clone())
-        .build_and_launch()
-        .await;
+        .storage(storage_client.clone());
+
+    if let Some(rest_api_addr) = config.rest_api_addr {
+        api_builder = api_builder.serve_rest_addr(rest_api_addr);
+    }
+
+    let (api_client, api_stream, _ctx) = api_builder.build_and_launch().await;
     debug!("gRPC api started");
 
     // setup transport-tce-storage-api connector
@@ -219,10 +325,12 @@ pub async fn run(
         network_client,
         api_client,
         gatekeeper_client,
+        Some(synchronizer_client),
         validator_store,
     );
 
     app_context
+        .with_max_missing_certificates(config.max_missing_certificates)
         .run(
             event_stream,
             tce_stream,
@@ -5,8 +5,12 @@ use prost::Message;
 use rstest::rstest;
 use test_log::test;
 use tokio::sync::{mpsc, oneshot};
-use topos_core::api::grpc::tce::v1::{double_echo_request, DoubleEchoRequest, Echo, Gossip, Ready};
+use topos_core::api::grpc::tce::v1::{
+    double_echo_request, DoubleEchoRequest, Echo, Gossip, Ready, SubnetSyncStatus, SyncState,
+    SyncStatus,
+};
 use topos_crypto::{messages::MessageSigner, validator_id::ValidatorId};
+use topos_tce_api::RuntimeError;
 use topos_tce_storage::{store::WriteStore, types::PendingResult};
 use topos_test_sdk::{
     certificates::create_certificate_chain,
@@ -105,3 +109,52 @@ async fn handle_certificate_already_delivered(
 
     assert!(matches!(response, Ok(Ok(PendingResult::AlreadyDelivered))));
 }
+
+#[rstest]
+#[case::stalled_with_a_few_missing_certificates(SyncState::Stalled, 3, true)]
+#[case::syncing_far_behind(SyncState::Syncing, 11, false)]
+#[test(tokio::test)]
+async fn handle_new_certificate_while_not_synced(
+    #[case] state: SyncState,
+    #[case] missing_certificates: u64,
+    #[case] accepted: bool,
+    #[future] setup_test: (
+        AppContext,
+        mpsc::Receiver<topos_p2p::Command>,
+        Arc<MessageSigner>,
+    ),
+) {
+    let (context, _p2p_receiver, _message_signer) = setup_test.await;
+    let mut context = context.with_max_missing_certificates(10);
+    let mut certificates = create_certificate_chain(SOURCE_SUBNET_ID_1, &[TARGET_SUBNET_ID_1], 1);
+    let certificate = certificates.pop().unwrap().certificate;
+
+    context
+        .api_client
+        .set_sync_status(SyncStatus {
+            state: state.into(),
+            subnets: vec![SubnetSyncStatus {
+                subnet_id: Some(SOURCE_SUBNET_ID_1.into()),
+                missing_certificates,
+                ..Default::default()
+            }],
+        })
+        .await;
+
+    let (sender, receiver) = oneshot::channel();
+
+    context
+        .on_api_event(topos_tce_api::RuntimeEvent::CertificateSubmitted {
+            certificate: Box::new(certificate),
+            sender,
+        })
+        .await;
+
+    let response = receiver.await.unwrap();
+
+    if accepted {
+        assert!(matches!(response, Ok(PendingResult::InPending(_))));
+    } else {
+        assert!(matches!(response, Err(RuntimeError::NotSynced(_))));
+    }
+}
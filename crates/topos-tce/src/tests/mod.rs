@@ -27,6 +27,7 @@ use crate::AppContext;
 
 mod api;
 mod network;
+mod validator_proof;
 
 #[rstest]
 #[tokio::test]
@@ -144,6 +145,7 @@ pub async fn setup_test(
         network_client,
         api_client,
         gatekeeper_client,
+        None,
         validator_store,
     );
 
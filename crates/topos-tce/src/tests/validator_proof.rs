@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use libp2p::PeerId;
+use rstest::rstest;
+use topos_crypto::{messages::MessageSigner, validator_id::ValidatorId};
+use topos_test_sdk::storage::create_fullnode_store;
+
+use crate::validator_proof::{
+    create_validator_proof, validator_proof_verifier, verify_validator_proof,
+};
+
+const CHALLENGE: [u8; 32] = [7u8; 32];
+
+#[rstest]
+fn valid_proof_from_validator() {
+    let message_signer = MessageSigner::new(&[5u8; 32]).unwrap();
+    let validator_id: ValidatorId = message_signer.public_address.into();
+    let peer_id = PeerId::random();
+
+    let proof = create_validator_proof(&message_signer, &peer_id, &CHALLENGE).unwrap();
+
+    assert!(verify_validator_proof(
+        &HashSet::from([validator_id]),
+        &peer_id,
+        &CHALLENGE,
+        &proof
+    ));
+}
+
+#[rstest]
+fn proof_from_unknown_validator() {
+    let message_signer = MessageSigner::new(&[5u8; 32]).unwrap();
+    let other_validator: ValidatorId = MessageSigner::new(&[6u8; 32])
+        .unwrap()
+        .public_address
+        .into();
+    let peer_id = PeerId::random();
+
+    let proof = create_validator_proof(&message_signer, &peer_id, &CHALLENGE).unwrap();
+
+    assert!(!verify_validator_proof(
+        &HashSet::from([other_validator]),
+        &peer_id,
+        &CHALLENGE,
+        &proof
+    ));
+}
+
+#[rstest]
+fn proof_for_another_peer() {
+    let message_signer = MessageSigner::new(&[5u8; 32]).unwrap();
+    let validator_id: ValidatorId = message_signer.public_address.into();
+
+    let proof = create_validator_proof(&message_signer, &PeerId::random(), &CHALLENGE).unwrap();
+
+    assert!(!verify_validator_proof(
+        &HashSet::from([validator_id]),
+        &PeerId::random(),
+        &CHALLENGE,
+        &proof
+    ));
+    assert!(!verify_validator_proof(
+        &HashSet::from([validator_id]),
+        &PeerId::random(),
+        &CHALLENGE,
+        "rust-libp2p/0.43.0"
+    ));
+}
+
+#[rstest]
+fn proof_replayed_for_another_challenge() {
+    let message_signer = MessageSigner::new(&[5u8; 32]).unwrap();
+    let validator_id: ValidatorId = message_signer.public_address.into();
+    let peer_id = PeerId::random();
+
+    let proof = create_validator_proof(&message_signer, &peer_id, &CHALLENGE).unwrap();
+
+    assert!(!verify_validator_proof(
+        &HashSet::from([validator_id]),
+        &peer_id,
+        &[8u8; 32],
+        &proof
+    ));
+}
+
+#[rstest]
+#[tokio::test]
+async fn proof_verified_against_the_current_epoch_validators() {
+    let genesis_signer = MessageSigner::new(&[5u8; 32]).unwrap();
+    let genesis_validator: ValidatorId = genesis_signer.public_address.into();
+    let epoch_signer = MessageSigner::new(&[6u8; 32]).unwrap();
+    let epoch_validator: ValidatorId = epoch_signer.public_address.into();
+    let peer_id = PeerId::random();
+
+    let store = create_fullnode_store::default().await;
+    let verifier = validator_proof_verifier(store.clone(), HashSet::from([genesis_validator]));
+
+    let genesis_proof = create_validator_proof(&genesis_signer, &peer_id, &CHALLENGE).unwrap();
+    let epoch_proof = create_validator_proof(&epoch_signer, &peer_id, &CHALLENGE).unwrap();
+
+    // The genesis validators are used as long as the epoch doesn't record its own set
+    assert!(verifier(&peer_id, &CHALLENGE, &genesis_proof));
+    assert!(!verifier(&peer_id, &CHALLENGE, &epoch_proof));
+
+    store.set_current_validators(vec![epoch_validator.to_string()]);
+
+    assert!(!verifier(&peer_id, &CHALLENGE, &genesis_proof));
+    assert!(verifier(&peer_id, &CHALLENGE, &epoch_proof));
+}
@@ -0,0 +1,91 @@
+//! Proof binding the p2p identity of a node to its [`ValidatorId`].
+//!
+//! The proof is the signature by the validator key of the node's `PeerId` and of a
+//! random challenge drawn by the remote peer. It is exchanged over the dedicated
+//! peer proof protocol and allows validators running in gating mode to accept
+//! connections from the members of the validator set of the current epoch, a proof
+//! being only valid for the challenge it answers.
+
+use std::{collections::HashSet, str::FromStr, sync::Arc};
+
+use topos_crypto::{
+    messages::{MessageSigner, Signature},
+    validator_id::ValidatorId,
+};
+use topos_p2p::{
+    config::{PeerProofSigner, PeerProofVerifier},
+    PeerId,
+};
+use topos_tce_storage::fullnode::FullNodeStore;
+
+const PROOF_DOMAIN: &[u8] = b"topos-validator-proof/";
+
+fn proof_payload(peer_id: &PeerId, challenge: &[u8]) -> Vec<u8> {
+    [PROOF_DOMAIN, &peer_id.to_bytes(), challenge].concat()
+}
+
+pub(crate) fn create_validator_proof(
+    message_signer: &MessageSigner,
+    peer_id: &PeerId,
+    challenge: &[u8],
+) -> Result<String, Box<dyn std::error::Error>> {
+    let signature = message_signer.sign_message(&proof_payload(peer_id, challenge))?;
+
+    Ok(signature.to_string())
+}
+
+/// Check that the proof was produced by a member of the validator set for the given
+/// peer and challenge
+pub(crate) fn verify_validator_proof(
+    validators: &HashSet<ValidatorId>,
+    peer_id: &PeerId,
+    challenge: &[u8],
+    proof: &str,
+) -> bool {
+    Signature::from_str(proof)
+        .ok()
+        .and_then(|signature| signature.recover(proof_payload(peer_id, challenge)).ok())
+        .map(|address| validators.contains(&ValidatorId::from(address)))
+        .unwrap_or(false)
+}
+
+pub(crate) fn validator_proof_signer(
+    message_signer: Arc<MessageSigner>,
+    peer_id: PeerId,
+) -> PeerProofSigner {
+    Arc::new(move |challenge| create_validator_proof(&message_signer, &peer_id, challenge).ok())
+}
+
+/// Validator set of the current epoch, the genesis validators being used as long as
+/// the epoch doesn't record its own set
+pub(crate) fn current_validators(
+    store: &FullNodeStore,
+    genesis_validators: &HashSet<ValidatorId>,
+) -> HashSet<ValidatorId> {
+    let validators: HashSet<ValidatorId> = store
+        .current_validators()
+        .iter()
+        .filter_map(|validator| ValidatorId::from_str(validator).ok())
+        .collect();
+
+    if validators.is_empty() {
+        genesis_validators.clone()
+    } else {
+        validators
+    }
+}
+
+/// The validator set is read on every verification to follow the epoch changes
+pub(crate) fn validator_proof_verifier(
+    store: Arc<FullNodeStore>,
+    genesis_validators: HashSet<ValidatorId>,
+) -> PeerProofVerifier {
+    Arc::new(move |peer_id, challenge, proof| {
+        verify_validator_proof(
+            &current_validators(&store, &genesis_validators),
+            peer_id,
+            challenge,
+            proof,
+        )
+    })
+}
@@ -1,2 +1,4 @@
+pub mod subnet;
+
 pub const TEST_VALIDATOR_KEY: &str =
     "11eddfae7abe45531b3f18342c8062969323a7131d3043f1a33c40df74803cc7";
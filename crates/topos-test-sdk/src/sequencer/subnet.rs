@@ -0,0 +1,194 @@
+use async_trait::async_trait;
+use ethers::types::{TransactionReceipt, TxHash, H256};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+use topos_core::api::grpc::checkpoints::TargetStreamPosition;
+use topos_core::uci::{Certificate, CertificateId, SubnetId};
+use topos_sequencer_subnet_client::{
+    BlockInfo, BlockStream, Error, PushResult, Subnet, SubnetEvent,
+};
+
+/// In-memory subnet producing blocks on demand and holding the pushed certificates
+///
+/// The subnet starts with its genesis block. New blocks are produced with
+/// [`MockSubnet::produce_block`] and the latest ones can be replaced by a
+/// competing branch with [`MockSubnet::reorg`].
+pub struct MockSubnet {
+    state: Mutex<MockSubnetState>,
+    new_blocks: broadcast::Sender<BlockInfo>,
+}
+
+#[derive(Default)]
+struct MockSubnetState {
+    blocks: Vec<BlockInfo>,
+    /// Number of reorgs, making the hashes of the competing branches differ
+    reorgs: u64,
+    pushed_certificates: Vec<(Certificate, u64)>,
+    push_results: VecDeque<PushResult>,
+}
+
+impl Default for MockSubnet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockSubnet {
+    pub fn new() -> Self {
+        let (new_blocks, _) = broadcast::channel(256);
+        let subnet = Self {
+            state: Mutex::new(MockSubnetState::default()),
+            new_blocks,
+        };
+        subnet.produce_block(Vec::new());
+
+        subnet
+    }
+
+    /// Produce a new block emitting the given events and notify the subscribers
+    pub fn produce_block(&self, events: Vec<SubnetEvent>) -> BlockInfo {
+        let mut state = self.state.lock().unwrap();
+        let number = state.blocks.len() as u64;
+        let parent_hash = state
+            .blocks
+            .last()
+            .map(|block| block.hash.clone())
+            .unwrap_or_else(|| block_hash(0, 0));
+
+        let mut root = [0u8; 32];
+        root[..8].copy_from_slice(&number.to_be_bytes());
+        root[8..16].copy_from_slice(&state.reorgs.to_be_bytes());
+
+        let block = BlockInfo {
+            hash: block_hash(number + 1, state.reorgs),
+            parent_hash,
+            number,
+            state_root: root,
+            tx_root_hash: root,
+            receipts_root_hash: root,
+            events,
+        };
+        state.blocks.push(block.clone());
+        _ = self.new_blocks.send(block.clone());
+
+        block
+    }
+
+    /// Drop the latest `depth` blocks so that the next produced blocks form a competing branch
+    pub fn reorg(&self, depth: u64) {
+        let mut state = self.state.lock().unwrap();
+        let height = state.blocks.len().saturating_sub(depth as usize).max(1);
+        state.blocks.truncate(height);
+        state.reorgs += 1;
+    }
+
+    /// Number of open block subscriptions
+    pub fn subscribers(&self) -> usize {
+        self.new_blocks.receiver_count()
+    }
+
+    /// Certificates pushed to the subnet with their position, by order of push
+    pub fn pushed_certificates(&self) -> Vec<(Certificate, u64)> {
+        self.state.lock().unwrap().pushed_certificates.clone()
+    }
+}
+
+fn block_hash(number: u64, reorgs: u64) -> String {
+    format!("0x{number:032x}{reorgs:032x}")
+}
+
+#[async_trait]
+impl Subnet for MockSubnet {
+    async fn subscribe_blocks(&self) -> Result<BlockStream, Error> {
+        let receiver = self.new_blocks.subscribe();
+
+        Ok(Box::pin(futures::stream::unfold(
+            receiver,
+            |mut receiver| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(block) => return Some((Ok(block), receiver)),
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        )))
+    }
+
+    async fn get_subnet_block_number(&self) -> Result<u64, Error> {
+        Ok(self.state.lock().unwrap().blocks.len() as u64 - 1)
+    }
+
+    async fn get_finalized_block(&self, block_number: u64) -> Result<BlockInfo, Error> {
+        self.state
+            .lock()
+            .unwrap()
+            .blocks
+            .get(block_number as usize)
+            .cloned()
+            .ok_or(Error::BlockNotAvailable(block_number))
+    }
+
+    async fn get_checkpoints(
+        &self,
+        target_subnet_id: &SubnetId,
+    ) -> Result<Vec<TargetStreamPosition>, Error> {
+        let state = self.state.lock().unwrap();
+        let mut checkpoints: HashMap<SubnetId, (CertificateId, u64)> = HashMap::new();
+        for (certificate, position) in &state.pushed_certificates {
+            let checkpoint = checkpoints
+                .entry(certificate.source_subnet_id)
+                .or_insert((certificate.id, *position));
+            if *position >= checkpoint.1 {
+                *checkpoint = (certificate.id, *position);
+            }
+        }
+
+        Ok(checkpoints
+            .into_iter()
+            .map(
+                |(source_subnet_id, (certificate_id, position))| TargetStreamPosition {
+                    target_subnet_id: *target_subnet_id,
+                    source_subnet_id,
+                    certificate_id: Some(certificate_id),
+                    position,
+                },
+            )
+            .collect())
+    }
+
+    async fn push_certificate(
+        &self,
+        cert: &Certificate,
+        cert_position: u64,
+    ) -> Result<Option<TxHash>, Error> {
+        let mut state = self.state.lock().unwrap();
+        if state
+            .pushed_certificates
+            .iter()
+            .any(|(certificate, _)| certificate.id == cert.id)
+        {
+            return Ok(None);
+        }
+
+        let tx_hash = H256::from(*cert.id.as_array());
+        let receipt = TransactionReceipt {
+            transaction_hash: tx_hash,
+            block_number: Some((state.blocks.len() as u64 - 1).into()),
+            status: Some(1u64.into()),
+            ..Default::default()
+        };
+        state
+            .pushed_certificates
+            .push((cert.clone(), cert_position));
+        state.push_results.push_back((cert.id, Ok(receipt)));
+
+        Ok(Some(tx_hash))
+    }
+
+    async fn next_push_result(&self) -> Option<PushResult> {
+        self.state.lock().unwrap().push_results.pop_front()
+    }
+}
@@ -10,9 +10,7 @@ use std::{
 
 use topos_core::types::CertificateDelivered;
 use topos_tce_storage::{
-    epoch::EpochValidatorsStore, epoch::ValidatorPerEpochStore, fullnode::FullNodeStore,
-    index::IndexTables, store::WriteStore, validator::ValidatorPerpetualTables,
-    validator::ValidatorStore, StorageClient,
+    fullnode::FullNodeStore, store::WriteStore, validator::ValidatorStore, StorageClient,
 };
 
 #[fixture]
@@ -52,11 +50,10 @@ pub async fn create_validator_store(
     certificates: Vec<CertificateDelivered>,
     #[future] create_fullnode_store: Arc<FullNodeStore>,
 ) -> Arc<ValidatorStore> {
-    let temp_dir = create_folder::default();
     let fullnode_store = create_fullnode_store.await;
 
     let store =
-        ValidatorStore::open(temp_dir, fullnode_store).expect("Unable to create validator store");
+        ValidatorStore::open_in_memory(fullnode_store).expect("Unable to create validator store");
 
     store
         .insert_certificates_delivered(&certificates)
@@ -69,29 +66,11 @@ pub async fn create_validator_store(
 pub async fn create_validator_store_with_fullnode(
     fullnode_store: Arc<FullNodeStore>,
 ) -> Arc<ValidatorStore> {
-    ValidatorStore::open(create_folder::default(), fullnode_store)
-        .expect("Unable to create validator store")
+    ValidatorStore::open_in_memory(fullnode_store).expect("Unable to create validator store")
 }
 #[fixture(certificates = Vec::new())]
 pub async fn create_fullnode_store(certificates: Vec<CertificateDelivered>) -> Arc<FullNodeStore> {
-    let temp_dir = create_folder::default();
-
-    let perpetual_tables = Arc::new(ValidatorPerpetualTables::open(temp_dir.clone()));
-    let index_tables = Arc::new(IndexTables::open(temp_dir.clone()));
-
-    let validators_store = EpochValidatorsStore::new(temp_dir.clone())
-        .expect("Unable to create EpochValidators store");
-
-    let epoch_store =
-        ValidatorPerEpochStore::new(0, temp_dir).expect("Unable to create Per epoch store");
-
-    let store = FullNodeStore::open(
-        epoch_store,
-        validators_store,
-        perpetual_tables,
-        index_tables,
-    )
-    .expect("Unable to create full node store");
+    let store = FullNodeStore::open_in_memory().expect("Unable to create full node store");
 
     store
         .insert_certificates_delivered(&certificates[..])
@@ -26,6 +26,7 @@ use topos_core::api::grpc::tce::v1::{
 };
 use topos_core::api::grpc::tce::v1::{
     CheckpointRequest, CheckpointResponse, FetchCertificatesRequest, FetchCertificatesResponse,
+    SnapshotChunkRequest, SnapshotChunkResponse, SnapshotRequest, SnapshotResponse,
 };
 use topos_core::api::grpc::tce::v1::{StatusRequest, StatusResponse};
 use topos_core::types::CertificateDelivered;
@@ -187,6 +188,20 @@ impl GrpcSynchronizerService for DummyService {
     ) -> Result<Response<CheckpointResponse>, Status> {
         Err(Status::unimplemented("fetch_checkpoint"))
     }
+
+    async fn fetch_snapshot(
+        &self,
+        _request: Request<SnapshotRequest>,
+    ) -> Result<Response<SnapshotResponse>, Status> {
+        Err(Status::unimplemented("fetch_snapshot"))
+    }
+
+    async fn fetch_snapshot_chunk(
+        &self,
+        _request: Request<SnapshotChunkRequest>,
+    ) -> Result<Response<SnapshotChunkResponse>, Status> {
+        Err(Status::unimplemented("fetch_snapshot_chunk"))
+    }
 }
 
 pub fn create_dummy_router() -> Router {
@@ -254,7 +269,7 @@ pub async fn start_node(
 
     let (gatekeeper_client, gatekeeper_join_handle) = create_gatekeeper().await.unwrap();
 
-    let (synchronizer_stream, synchronizer_join_handle) = create_synchronizer(
+    let (synchronizer_client, synchronizer_stream, synchronizer_join_handle) = create_synchronizer(
         gatekeeper_client.clone(),
         network_client.clone(),
         validator_store.clone(),
@@ -268,6 +283,7 @@ pub async fn start_node(
         network_client,
         api_context.client,
         gatekeeper_client,
+        Some(synchronizer_client),
         validator_store,
     );
 
@@ -24,6 +24,7 @@ use crate::PORT_MAPPING;
 
 pub struct PublicApiContext {
     pub entrypoint: String,
+    pub rest_endpoint: String,
     pub client: RuntimeClient,
     pub api_client: ApiServiceClient<Channel>,
     pub console_client: ConsoleServiceClient<Channel>,
@@ -47,6 +48,7 @@ pub async fn create_public_api(
     let store = create_validator_store.await;
     let grpc_addr = get_available_addr();
     let graphql_addr = get_available_addr();
+    let rest_addr = get_available_addr();
     let metrics_addr = get_available_addr();
 
     let api_port = grpc_addr.port();
@@ -55,12 +57,14 @@ pub async fn create_public_api(
     warn!("API endpoint: {}", api_endpoint);
     warn!("gRPC endpoint: {}", grpc_addr);
     warn!("GraphQL endpoint: {}", graphql_addr);
+    warn!("REST endpoint: {}", rest_addr);
     warn!("Metrics endpoint: {}", metrics_addr);
     warn!("PORT MAPPING: {:?}", PORT_MAPPING.lock().unwrap());
     let (client, stream, ctx) = topos_tce_api::Runtime::builder()
         .with_broadcast_stream(broadcast_stream)
         .serve_grpc_addr(grpc_addr)
         .serve_graphql_addr(graphql_addr)
+        .serve_rest_addr(rest_addr)
         .serve_metrics_addr(metrics_addr)
         .store(store)
         .storage(storage_client)
@@ -80,6 +84,7 @@ pub async fn create_public_api(
 
     let context = PublicApiContext {
         entrypoint: api_endpoint,
+        rest_endpoint: format!("http://{rest_addr}"),
         client,
         api_client,
         console_client,
@@ -7,6 +7,7 @@ use tokio_util::sync::CancellationToken;
 use topos_p2p::NetworkClient;
 use topos_tce_gatekeeper::GatekeeperClient;
 use topos_tce_storage::validator::ValidatorStore;
+use topos_tce_synchronizer::SynchronizerClient;
 use topos_tce_synchronizer::SynchronizerError;
 use topos_tce_synchronizer::SynchronizerEvent;
 
@@ -15,6 +16,7 @@ pub async fn create_synchronizer(
     network_client: NetworkClient,
     store: Arc<ValidatorStore>,
 ) -> (
+    SynchronizerClient,
     impl Stream<Item = SynchronizerEvent>,
     JoinHandle<Result<(), SynchronizerError>>,
 ) {
@@ -27,7 +29,12 @@ pub async fn create_synchronizer(
             .build()
             .expect("Can't create the Synchronizer");
 
+    let synchronizer_client = synchronizer_runtime.client();
     let synchronizer_join_handle = spawn(synchronizer_runtime.into_future());
 
-    (synchronizer_stream, synchronizer_join_handle)
+    (
+        synchronizer_client,
+        synchronizer_stream,
+        synchronizer_join_handle,
+    )
 }
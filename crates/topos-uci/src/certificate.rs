@@ -3,6 +3,13 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::fmt::Debug;
 
+/// Inclusive range of the subnet blocks certified by a certificate
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BlockRange {
+    pub start: u64,
+    pub end: u64,
+}
+
 /// Certificate - main exchange item
 #[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Certificate {
@@ -12,6 +19,12 @@ pub struct Certificate {
     pub state_root: StateRoot,
     pub tx_root_hash: TxRootHash,
     pub receipts_root_hash: ReceiptsRootHash,
+    /// Root of the cross-subnet messages sent by the certified blocks
+    #[serde(default)]
+    pub messages_root: MessagesRoot,
+    /// Subnet blocks certified, unset by the sequencers which don't record it
+    #[serde(default)]
+    pub block_range: Option<BlockRange>,
     pub target_subnets: Vec<SubnetId>,
     pub verifier: u32,
     pub proof: StarkProof,
@@ -42,6 +55,11 @@ impl Debug for Certificate {
                 "receipts_root_hash",
                 &("0x".to_string() + &hex::encode(self.receipts_root_hash)),
             )
+            .field(
+                "messages_root",
+                &("0x".to_string() + &hex::encode(self.messages_root)),
+            )
+            .field("block_range", &self.block_range)
             .field(
                 "target_subnets",
                 &self
@@ -68,6 +86,8 @@ impl Certificate {
         state_root: StateRoot,
         tx_root_hash: TxRootHash,
         receipts_root_hash: ReceiptsRootHash,
+        messages_root: MessagesRoot,
+        block_range: Option<BlockRange>,
         target_subnets: &[SubnetId],
         verifier: u32,
         proof: Vec<u8>,
@@ -79,6 +99,8 @@ impl Certificate {
             state_root,
             tx_root_hash,
             receipts_root_hash,
+            messages_root,
+            block_range,
             target_subnets: target_subnets.into(),
             verifier,
             proof,
@@ -101,6 +123,8 @@ impl Certificate {
             state_root: Default::default(),
             tx_root_hash: Default::default(),
             receipts_root_hash: Default::default(),
+            messages_root: Default::default(),
+            block_range: None,
             target_subnets: target_subnets.into(),
             verifier: 0,
             proof: Default::default(),
@@ -121,6 +145,18 @@ impl Certificate {
         Ok(())
     }
 
+    /// Checks that the id of the certificate matches its content
+    pub fn check_id(&self) -> Result<(), Error> {
+        if Self::calculate_cert_id(self)? != *self.id.as_array() {
+            return Err(Error::ValidationError(format!(
+                "certificate id {} does not match its content",
+                self.id
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Signs the hash of the certificate payload
     pub fn update_signature(&mut self, private_key: &[u8]) -> Result<(), Error> {
         self.signature =
@@ -138,6 +174,7 @@ impl Certificate {
         buffer.extend_from_slice(self.state_root.as_ref());
         buffer.extend_from_slice(self.tx_root_hash.as_ref());
         buffer.extend_from_slice(self.receipts_root_hash.as_ref());
+        self.extend_with_extensions(&mut buffer);
         for target_subnet in &self.target_subnets {
             buffer.extend_from_slice(target_subnet.as_array().as_ref());
         }
@@ -146,6 +183,19 @@ impl Certificate {
         buffer
     }
 
+    // Fields added after the initial format are only part of the id and of the signed
+    // payload when they are set, so that certificates of the sequencers which don't
+    // fill them keep their id
+    fn extend_with_extensions(&self, buffer: &mut Vec<u8>) {
+        if self.messages_root != MessagesRoot::default() {
+            buffer.extend_from_slice(self.messages_root.as_ref());
+        }
+        if let Some(block_range) = self.block_range {
+            buffer.extend_from_slice(&block_range.start.to_be_bytes());
+            buffer.extend_from_slice(&block_range.end.to_be_bytes());
+        }
+    }
+
     // To get unique id, calculate certificate id of certificate object using keccak256,
     // excluding cert_id and signature fields
     fn calculate_cert_id(certificate: &Certificate) -> Result<[u8; CERTIFICATE_ID_LENGTH], Error> {
@@ -155,6 +205,7 @@ impl Certificate {
         buffer.extend_from_slice(certificate.state_root.as_ref());
         buffer.extend_from_slice(certificate.tx_root_hash.as_ref());
         buffer.extend_from_slice(certificate.receipts_root_hash.as_ref());
+        certificate.extend_with_extensions(&mut buffer);
         for target_subnet in &certificate.target_subnets {
             buffer.extend_from_slice(target_subnet.as_array().as_ref());
         }
@@ -174,6 +225,7 @@ mod tests {
     const STATE_ROOT: StateRoot = [4u8; 32];
     const TX_ROOT_HASH: TxRootHash = [5u8; 32];
     const RECEIPTS_ROOT_HASH: ReceiptsRootHash = [6u8; 32];
+    const MESSAGES_ROOT: MessagesRoot = [7u8; 32];
     const PRIVATE_TEST_KEY: &str =
         "5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133";
 
@@ -188,6 +240,8 @@ mod tests {
             STATE_ROOT,
             TX_ROOT_HASH,
             RECEIPTS_ROOT_HASH,
+            MESSAGES_ROOT,
+            Some(BlockRange { start: 8, end: 9 }),
             &[TARGET_SUBNET_ID],
             2,
             Default::default(),
@@ -212,6 +266,52 @@ mod tests {
         .expect("valid signature check")
     }
 
+    #[test]
+    fn certificate_id_matches_content() {
+        let private_test_key = hex::decode(PRIVATE_TEST_KEY).unwrap();
+        let mut dummy_cert = generate_dummy_cert(&private_test_key);
+
+        assert!(dummy_cert.check_id().is_ok());
+
+        dummy_cert.state_root[0] = 0xff;
+
+        assert!(dummy_cert.check_id().is_err());
+
+        let mut dummy_cert = generate_dummy_cert(&private_test_key);
+        dummy_cert.messages_root[0] = 0xff;
+
+        assert!(dummy_cert.check_id().is_err());
+
+        let mut dummy_cert = generate_dummy_cert(&private_test_key);
+        dummy_cert.block_range = Some(BlockRange { start: 7, end: 9 });
+
+        assert!(dummy_cert.check_id().is_err());
+    }
+
+    #[test]
+    fn certificate_without_extensions_keeps_its_id() {
+        let private_test_key = hex::decode(PRIVATE_TEST_KEY).unwrap();
+        let mut certificate = generate_dummy_cert(&private_test_key);
+        certificate.messages_root = MessagesRoot::default();
+        certificate.block_range = None;
+
+        // Id computed by the sequencers predating the messages root and the block range
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(certificate.prev_id.as_array().as_ref());
+        buffer.extend_from_slice(certificate.source_subnet_id.as_array().as_ref());
+        buffer.extend_from_slice(&certificate.state_root);
+        buffer.extend_from_slice(&certificate.tx_root_hash);
+        buffer.extend_from_slice(&certificate.receipts_root_hash);
+        for target_subnet in &certificate.target_subnets {
+            buffer.extend_from_slice(target_subnet.as_array().as_ref());
+        }
+        buffer.extend_from_slice(&certificate.verifier.to_be_bytes());
+        buffer.extend_from_slice(&certificate.proof);
+        certificate.id = topos_crypto::hash::calculate_hash(&buffer).into();
+
+        assert!(certificate.check_id().is_ok());
+    }
+
     #[test]
     #[should_panic]
     fn signature_verification_failed_corrupt_data() {
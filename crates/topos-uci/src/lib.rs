@@ -2,7 +2,7 @@
 //!
 //! Data structures to support Certificates' exchange
 
-pub use certificate::Certificate;
+pub use certificate::{BlockRange, Certificate};
 pub use certificate_id::CertificateId;
 pub use subnet_id::SubnetId;
 
@@ -26,6 +26,7 @@ pub type Amount = ethereum_types::U256;
 pub type StateRoot = [u8; 32];
 pub type TxRootHash = [u8; 32];
 pub type ReceiptsRootHash = [u8; 32];
+pub type MessagesRoot = [u8; 32];
 
 /// Heavily checked on the gossip, so not abstracted
 const DUMMY_FROST_VERIF_DELAY: time::Duration = time::Duration::from_millis(0);
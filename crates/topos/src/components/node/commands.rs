@@ -3,11 +3,15 @@ use std::path::PathBuf;
 use clap::{Args, Subcommand};
 use serde::Serialize;
 
+mod admin;
 mod init;
+mod restore;
 mod status;
 mod up;
 
+pub(crate) use admin::{Admin, AdminCommands};
 pub(crate) use init::Init;
+pub(crate) use restore::Restore;
 pub(crate) use status::Status;
 pub(crate) use up::Up;
 
@@ -15,6 +19,11 @@ pub(crate) use up::Up;
 pub(crate) struct NodeArgument {
     #[clap(short, long, default_value = "http://[::1]:1340")]
     pub(crate) node: String,
+
+    /// API key expected by the node if it restricts the access to its public APIs
+    #[arg(long, env = "TOPOS_NODE_API_KEY", hide_env_values = true)]
+    #[serde(skip)]
+    pub(crate) api_key: Option<String>,
 }
 
 /// Utility to manage your nodes in the Topos network
@@ -42,6 +51,8 @@ pub(crate) enum NodeCommands {
     Up(Box<Up>),
     Init(Box<Init>),
     Status(Status),
+    Restore(Restore),
+    Admin(Admin),
 }
 
 #[cfg(test)]
@@ -52,5 +63,8 @@ mod tests {
     fn test_run() {
         assert!(NodeCommands::has_subcommand("up"));
         assert!(NodeCommands::has_subcommand("init"));
+        assert!(AdminCommands::has_subcommand("backup"));
+        assert!(NodeCommands::has_subcommand("restore"));
+        assert!(NodeCommands::has_subcommand("admin"));
     }
 }
@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+#[derive(Args, Debug, Serialize)]
+#[command(about = "Operate a running node through its admin service")]
+pub(crate) struct Admin {
+    /// Endpoint of the admin service of the node, served apart from the public gRPC API
+    #[clap(short, long, default_value = "http://127.0.0.1:1341")]
+    pub(crate) node: String,
+
+    /// Token of the admin service, as configured on the node
+    #[arg(long, env = "TOPOS_NODE_ADMIN_TOKEN", hide_env_values = true)]
+    #[serde(skip)]
+    pub(crate) token: String,
+
+    #[command(subcommand)]
+    pub(crate) command: AdminCommands,
+}
+
+#[derive(Subcommand, Debug, Serialize)]
+pub(crate) enum AdminCommands {
+    /// List the certificates of the pending pool
+    ListPending {
+        /// Only list the certificates of this subnet
+        #[arg(long)]
+        subnet_id: Option<String>,
+    },
+    /// Remove a certificate from the pending pool
+    Evict { certificate_id: String },
+    /// Synchronize the delivered certificates with the checkpoints of a peer
+    Resync {
+        /// PeerId of the peer to synchronize from
+        #[arg(long)]
+        peer: String,
+    },
+    /// Dump the state of the broadcast of a certificate
+    BroadcastState { certificate_id: String },
+    /// Replace the log filter of the node, using the syntax of `RUST_LOG`
+    LogFilter { filter: String },
+    /// Trigger a compaction of the storage
+    Compact,
+    /// Take a backup of the storage
+    Backup {
+        /// Path of the backup on the filesystem of the node, it must not exist yet
+        #[arg(long)]
+        path: PathBuf,
+    },
+}
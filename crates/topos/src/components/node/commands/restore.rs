@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+#[derive(Args, Debug, Serialize)]
+#[command(about = "Restore a backup as the storage of a stopped node")]
+pub(crate) struct Restore {
+    /// Name to identify your node
+    #[arg(long, env = "TOPOS_NODE_NAME", default_value = "default")]
+    pub(crate) name: Option<String>,
+
+    /// Path of the backup to restore
+    #[arg(long)]
+    pub(crate) from: PathBuf,
+}
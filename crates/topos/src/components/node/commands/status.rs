@@ -10,4 +10,8 @@ pub(crate) struct Status {
 
     #[arg(long)]
     pub(crate) sample: bool,
+
+    /// Print the status as JSON
+    #[arg(long)]
+    pub(crate) json: bool,
 }
@@ -4,7 +4,7 @@ use opentelemetry::global;
 use opentelemetry::sdk::metrics::controllers::BasicController;
 use std::{
     fs::{create_dir_all, remove_dir_all, OpenOptions},
-    io::Write,
+    io::{Error, ErrorKind, Write},
 };
 use std::{path::Path, sync::Arc};
 use tokio::{
@@ -12,7 +12,12 @@ use tokio::{
     sync::{mpsc, Mutex},
 };
 use tokio_util::sync::CancellationToken;
-use tonic::transport::{Channel, Endpoint};
+use tonic::{
+    metadata::{Ascii, MetadataValue},
+    service::{interceptor::InterceptedService, Interceptor},
+    transport::{Channel, Endpoint},
+    Request, Status,
+};
 use tower::Service;
 use tracing::{error, info};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
@@ -30,15 +35,43 @@ use topos_wallet::SecretManager;
 pub(crate) mod commands;
 pub(crate) mod services;
 
+/// Interceptor adding the API key, if any, to every request
+#[derive(Clone)]
+pub(crate) struct ApiKey(Option<MetadataValue<Ascii>>);
+
+impl Interceptor for ApiKey {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(api_key) = &self.0 {
+            request.metadata_mut().insert("x-api-key", api_key.clone());
+        }
+
+        Ok(request)
+    }
+}
+
+type ConsoleClient = ConsoleServiceClient<InterceptedService<Channel, ApiKey>>;
+
 pub(crate) struct NodeService {
-    pub(crate) console_client: Arc<Mutex<ConsoleServiceClient<Channel>>>,
+    pub(crate) console_client: Arc<Mutex<ConsoleClient>>,
 }
 
 impl NodeService {
-    pub(crate) fn with_grpc_endpoint(endpoint: &str) -> Self {
-        Self {
-            console_client: setup_console_tce_grpc(endpoint),
-        }
+    pub(crate) fn with_grpc_endpoint(endpoint: &str, api_key: Option<&str>) -> Result<Self, Error> {
+        let api_key = api_key
+            .map(MetadataValue::<Ascii>::try_from)
+            .transpose()
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "Invalid API key"))?;
+
+        let channel = Endpoint::from_shared(endpoint.to_string())
+            .map_err(|error| Error::new(ErrorKind::InvalidInput, error))?
+            .connect_lazy();
+
+        Ok(Self {
+            console_client: Arc::new(Mutex::new(ConsoleServiceClient::with_interceptor(
+                channel,
+                ApiKey(api_key),
+            ))),
+        })
     }
 }
 
@@ -177,7 +210,7 @@ pub(crate) async fn handle_command(
 
             // Setup instrumentation if both otlp agent and otlp service name
             // are provided as arguments
-            let basic_controller = setup_tracing(
+            let (basic_controller, log_filter) = setup_tracing(
                 verbose,
                 no_color,
                 cmd_cloned.otlp_agent,
@@ -235,6 +268,7 @@ pub(crate) async fn handle_command(
                     config.tce.clone().unwrap(),
                     keys,
                     genesis,
+                    log_filter,
                     (shutdown_token.clone(), shutdown_sender.clone()),
                 ));
             }
@@ -259,23 +293,61 @@ pub(crate) async fn handle_command(
             Ok(())
         }
         Some(NodeCommands::Status(status)) => {
-            let mut node_service = NodeService::with_grpc_endpoint(&status.node_args.node);
-            let exit_code = i32::from(!(node_service.call(status).await?));
+            let json = status.json;
+            let mut node_service = NodeService::with_grpc_endpoint(
+                &status.node_args.node,
+                status.node_args.api_key.as_deref(),
+            )?;
+            let node_status = node_service.call(status).await?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&node_status)?);
+            } else {
+                print!("{node_status}");
+            }
+
+            let exit_code = i32::from(!node_status.has_active_sample);
             std::process::exit(exit_code);
         }
-        None => Ok(()),
-    }
-}
+        Some(NodeCommands::Admin(admin)) => {
+            let mut admin_service =
+                services::admin::AdminService::with_grpc_endpoint(&admin.node, &admin.token)?;
+            let output = admin_service.call(admin).await?;
+
+            print!("{output}");
 
-fn setup_console_tce_grpc(endpoint: &str) -> Arc<Mutex<ConsoleServiceClient<Channel>>> {
-    match Endpoint::from_shared(endpoint.to_string()) {
-        Ok(endpoint) => Arc::new(Mutex::new(ConsoleServiceClient::new(
-            endpoint.connect_lazy(),
-        ))),
-        Err(e) => {
-            error!("Failure to setup the gRPC API endpoint on {endpoint}: {e}");
-            std::process::exit(1);
+            Ok(())
         }
+        Some(NodeCommands::Restore(cmd)) => {
+            let name = cmd.name.as_ref().expect("No name or default was given");
+            let node_path = home.join("node").join(name);
+
+            if !node_path.join("config.toml").exists() {
+                println!(
+                    "Please run 'topos node init --name {name}' to create a config file first for \
+                     {name}."
+                );
+                std::process::exit(1);
+            }
+
+            let config = NodeConfig::new(&node_path, None);
+            let Some(tce_config) = config.tce else {
+                println!("The node {name} does not run a TCE, there is no storage to restore");
+                std::process::exit(1);
+            };
+
+            let manifest = topos_tce_storage::backup::restore(&cmd.from, &tce_config.db_path)?;
+
+            println!(
+                "Restored {} delivered certificates over {} subnets at {}",
+                manifest.delivered_certificates,
+                manifest.source_heads.len(),
+                tce_config.db_path.display()
+            );
+
+            Ok(())
+        }
+        None => Ok(()),
     }
 }
 
@@ -1,2 +1,3 @@
+pub(crate) mod admin;
 pub(crate) mod process;
 pub(crate) mod status;
@@ -0,0 +1,330 @@
+use std::{
+    fmt::Write,
+    future::Future,
+    io::{Error, ErrorKind},
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::FutureExt;
+use tokio::sync::Mutex;
+use tonic::{
+    metadata::MetadataValue,
+    service::{interceptor::InterceptedService, Interceptor},
+    transport::{Channel, Endpoint},
+    Request, Status,
+};
+use topos_core::{
+    api::grpc::tce::v1::{
+        admin_service_client::AdminServiceClient, BackupRequest, BroadcastState, BroadcastStatus,
+        CompactStorageRequest, EvictPendingCertificateRequest, GetBroadcastStateRequest,
+        ListPendingCertificatesRequest, ResyncFromPeerRequest, SetLogFilterRequest,
+    },
+    uci::{Certificate, CertificateId, SubnetId},
+};
+use tower::Service;
+use tracing::{debug, error};
+
+use crate::components::node::commands::{Admin, AdminCommands};
+
+/// Interceptor adding the admin token to every request
+#[derive(Clone)]
+pub(crate) struct BearerToken(MetadataValue<tonic::metadata::Ascii>);
+
+impl Interceptor for BearerToken {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        request
+            .metadata_mut()
+            .insert("authorization", self.0.clone());
+
+        Ok(request)
+    }
+}
+
+type Client = AdminServiceClient<InterceptedService<Channel, BearerToken>>;
+
+pub(crate) struct AdminService {
+    pub(crate) admin_client: Arc<Mutex<Client>>,
+}
+
+impl AdminService {
+    pub(crate) fn with_grpc_endpoint(endpoint: &str, token: &str) -> Result<Self, Error> {
+        let token = MetadataValue::try_from(format!("Bearer {token}"))
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "Invalid admin token"))?;
+
+        let channel = Endpoint::from_shared(endpoint.to_string())
+            .map_err(|error| Error::new(ErrorKind::InvalidInput, error))?
+            .connect_lazy();
+
+        Ok(Self {
+            admin_client: Arc::new(Mutex::new(AdminServiceClient::with_interceptor(
+                channel,
+                BearerToken(token),
+            ))),
+        })
+    }
+}
+
+fn invalid_input(message: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+fn parse_certificate_id(certificate_id: &str) -> Result<CertificateId, Error> {
+    CertificateId::try_from(certificate_id.as_bytes())
+        .map_err(|error| invalid_input(format!("Invalid certificate id: {error}")))
+}
+
+fn render_broadcast_state(state: &BroadcastState) -> String {
+    let mut rendered = String::new();
+    let status = match state.status() {
+        BroadcastStatus::Pending => "pending",
+        BroadcastStatus::EchoSent => "echo sent",
+        BroadcastStatus::ReadySent => "ready sent",
+        BroadcastStatus::DeliveredWithReadySent => "delivered (ready sent)",
+        BroadcastStatus::Delivered => "delivered",
+        BroadcastStatus::Failed => "failed",
+        BroadcastStatus::Unspecified => "unknown",
+    };
+
+    _ = writeln!(rendered, "Status:              {status}");
+    // Only the status is known once the broadcast is over
+    if matches!(
+        state.status(),
+        BroadcastStatus::Delivered | BroadcastStatus::Failed
+    ) && state.network_size == 0
+    {
+        if let Some(position) = state.position {
+            _ = writeln!(rendered, "Position:            {position}");
+        }
+
+        return rendered;
+    }
+
+    _ = writeln!(
+        rendered,
+        "Awaiting previous:   {}",
+        state.awaiting_previous_certificate
+    );
+    _ = writeln!(rendered, "Network size:        {}", state.network_size);
+    _ = writeln!(
+        rendered,
+        "Thresholds:          echo {}, ready {}, delivery {}",
+        state.echo_threshold, state.ready_threshold, state.delivery_threshold
+    );
+    _ = writeln!(
+        rendered,
+        "Position:            {}",
+        state
+            .position
+            .map_or_else(|| "-".to_string(), |position| position.to_string())
+    );
+    _ = writeln!(rendered, "Elapsed:             {}ms", state.elapsed_ms);
+    _ = writeln!(
+        rendered,
+        "Missing echoes:      {}",
+        state.missing_echoes.len()
+    );
+    for validator_id in &state.missing_echoes {
+        _ = writeln!(rendered, "  {validator_id}");
+    }
+    _ = writeln!(
+        rendered,
+        "Missing readies:     {}",
+        state.missing_readies.len()
+    );
+    for validator_id in &state.missing_readies {
+        _ = writeln!(rendered, "  {validator_id}");
+    }
+
+    rendered
+}
+
+impl Service<Admin> for AdminService {
+    type Response = String;
+
+    type Error = std::io::Error;
+
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, admin: Admin) -> Self::Future {
+        let client = self.admin_client.clone();
+
+        async move {
+            let server_error = |error: Status| {
+                error!("TCE server returned an error: {:?}", error);
+                Error::new(ErrorKind::Other, error)
+            };
+
+            debug!("Sending the admin command {:?}", admin.command);
+            let mut client = client.lock().await;
+            match admin.command {
+                AdminCommands::ListPending { subnet_id } => {
+                    let subnet_id = subnet_id
+                        .map(|subnet_id| SubnetId::from_str(&subnet_id))
+                        .transpose()
+                        .map_err(|error| invalid_input(format!("Invalid subnet id: {error}")))?;
+
+                    let certificates = client
+                        .list_pending_certificates(ListPendingCertificatesRequest {
+                            subnet_id: subnet_id.map(Into::into),
+                        })
+                        .await
+                        .map_err(server_error)?
+                        .into_inner()
+                        .certificates;
+
+                    let mut rendered = format!("{} pending certificates\n", certificates.len());
+                    for pending in certificates {
+                        let Some(Ok(certificate)) = pending.certificate.map(Certificate::try_from)
+                        else {
+                            _ = writeln!(rendered, "{}: invalid certificate", pending.pending_id);
+                            continue;
+                        };
+
+                        _ = writeln!(
+                            rendered,
+                            "{}: {} from {}",
+                            pending.pending_id, certificate.id, certificate.source_subnet_id
+                        );
+                    }
+
+                    Ok(rendered)
+                }
+                AdminCommands::Evict { certificate_id } => {
+                    let certificate_id = parse_certificate_id(&certificate_id)?;
+
+                    let response = client
+                        .evict_pending_certificate(EvictPendingCertificateRequest {
+                            certificate_id: Some(certificate_id.into()),
+                        })
+                        .await
+                        .map_err(server_error)?
+                        .into_inner();
+
+                    Ok(format!(
+                        "Certificate {certificate_id} (pending id {}) evicted from the pending \
+                         pool along with {} dependent certificates\n",
+                        response.pending_id,
+                        response.evicted_dependents.len()
+                    ))
+                }
+                AdminCommands::Resync { peer } => {
+                    let response = client
+                        .resync_from_peer(ResyncFromPeerRequest {
+                            peer_id: peer.clone(),
+                        })
+                        .await
+                        .map_err(server_error)?
+                        .into_inner();
+
+                    Ok(format!(
+                        "{} certificates synchronized from {peer}\n",
+                        response.synchronized_certificates
+                    ))
+                }
+                AdminCommands::BroadcastState { certificate_id } => {
+                    let certificate_id = parse_certificate_id(&certificate_id)?;
+
+                    let state = client
+                        .get_broadcast_state(GetBroadcastStateRequest {
+                            certificate_id: Some(certificate_id.into()),
+                        })
+                        .await
+                        .map_err(server_error)?
+                        .into_inner()
+                        .state
+                        .ok_or_else(|| {
+                            Error::new(ErrorKind::Other, "Missing broadcast state in the response")
+                        })?;
+
+                    Ok(render_broadcast_state(&state))
+                }
+                AdminCommands::LogFilter { filter } => {
+                    let response = client
+                        .set_log_filter(SetLogFilterRequest {
+                            filter: filter.clone(),
+                        })
+                        .await
+                        .map_err(server_error)?
+                        .into_inner();
+
+                    Ok(format!(
+                        "Log filter changed from \"{}\" to \"{filter}\"\n",
+                        response.previous_filter
+                    ))
+                }
+                AdminCommands::Compact => {
+                    client
+                        .compact_storage(CompactStorageRequest {})
+                        .await
+                        .map_err(server_error)?;
+
+                    Ok("Storage compacted\n".to_string())
+                }
+                AdminCommands::Backup { path } => {
+                    let response = client
+                        .backup(BackupRequest {
+                            path: path.to_string_lossy().into_owned(),
+                        })
+                        .await
+                        .map_err(server_error)?
+                        .into_inner();
+
+                    Ok(format!(
+                        "Backup of {} delivered certificates over {} subnets taken at {}\n",
+                        response.delivered_certificates,
+                        response.source_heads.len(),
+                        path.display()
+                    ))
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use topos_core::api::grpc::tce::v1::{BroadcastState, BroadcastStatus};
+
+    use super::render_broadcast_state;
+
+    #[test]
+    fn render_delivered_broadcast_state() {
+        let rendered = render_broadcast_state(&BroadcastState {
+            status: BroadcastStatus::Delivered.into(),
+            position: Some(4),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            rendered,
+            "Status:              delivered\nPosition:            4\n"
+        );
+    }
+
+    #[test]
+    fn render_ongoing_broadcast_state() {
+        let rendered = render_broadcast_state(&BroadcastState {
+            status: BroadcastStatus::EchoSent.into(),
+            network_size: 4,
+            echo_threshold: 3,
+            ready_threshold: 2,
+            delivery_threshold: 3,
+            elapsed_ms: 120,
+            ..Default::default()
+        });
+
+        assert!(rendered.contains("Status:              echo sent"));
+        assert!(rendered.contains("Thresholds:          echo 3, ready 2, delivery 3"));
+        assert!(rendered.contains("Position:            -"));
+        assert!(rendered.contains("Missing echoes:      0"));
+    }
+}
@@ -7,9 +7,10 @@ use std::time::Duration;
 use thiserror::Error;
 use tokio::{spawn, sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
-use topos_p2p::config::NetworkConfig;
+use topos_p2p::config::{LimitsConfig, NetworkConfig};
 use topos_sequencer::SequencerConfiguration;
-use topos_tce::config::{AuthKey, StorageConfiguration, TceConfiguration};
+use topos_tce::config::{AuthKey, LogFilterHandle, StorageConfiguration, TceConfiguration};
+use topos_tce_storage::pruning::StorageMode;
 use topos_tce_transport::ReliableBroadcastParams;
 use topos_wallet::SecretManager;
 use tracing::{debug, error, info};
@@ -22,6 +23,8 @@ pub enum Errors {
     TceFailure,
     #[error("Sequencer error")]
     SequencerFailure,
+    #[error("Invalid TCE configuration: {0}")]
+    InvalidTceConfiguration(crate::config::tce::Error),
     #[error("Edge error: {0}")]
     EdgeTerminated(#[from] std::io::Error),
 }
@@ -56,16 +59,30 @@ pub(crate) fn spawn_sequencer_process(
     keys: &SecretManager,
     shutdown: (CancellationToken, mpsc::Sender<()>),
 ) -> JoinHandle<Result<(), Errors>> {
+    let batching_policy = config.batching_policy();
+    let push_pipeline = config.push_pipeline();
+    let tce_grpc_endpoints = config.tce_grpc_endpoints();
     let config = SequencerConfiguration {
         subnet_id: config.subnet_id,
         public_key: keys.validator_pubkey(),
         subnet_jsonrpc_http: config.subnet_jsonrpc_http,
         subnet_jsonrpc_ws: config.subnet_jsonrpc_ws,
         subnet_contract_address: config.subnet_contract_address,
-        tce_grpc_endpoint: config.tce_grpc_endpoint,
+        tce_grpc_endpoints,
+        tce_ca_certificate: config.tce_ca_certificate,
+        tce_client_certificate: config.tce_client_certificate,
+        tce_client_key: config.tce_client_key,
+        tce_api_key: config.tce_api_key,
         signing_key: keys.validator.clone().unwrap(),
         verifier: 0,
         start_block: config.start_block,
+        batching_policy,
+        finality_depth: config.finality_depth,
+        outbox_path: Some(config.outbox_path),
+        messages_path: Some(config.messages_path),
+        messages_retention: config.messages_retention,
+        api_addr: config.api_addr,
+        push_pipeline,
     };
 
     debug!("Sequencer args: {config:?}");
@@ -81,36 +98,80 @@ pub(crate) fn spawn_tce_process(
     config: TceConfig,
     keys: SecretManager,
     genesis: Genesis,
+    log_filter: LogFilterHandle,
     shutdown: (CancellationToken, mpsc::Sender<()>),
 ) -> JoinHandle<Result<(), Errors>> {
     let validators = genesis.validators().expect("Cannot parse validators");
     let tce_params = ReliableBroadcastParams::new(validators.len());
 
-    let tce_config = TceConfiguration {
-        boot_peers: genesis
-            .boot_peers(Some(topos_p2p::constants::TCE_BOOTNODE_PORT))
-            .into_iter()
-            .chain(config.parse_boot_peers())
-            .collect::<Vec<_>>(),
-        validators,
-        auth_key: keys.network.map(AuthKey::PrivateKey),
-        signing_key: keys.validator.map(AuthKey::PrivateKey),
-        tce_addr: format!("/ip4/{}", config.libp2p_api_addr.ip()),
-        tce_local_port: config.libp2p_api_addr.port(),
-        tce_params,
-        api_addr: config.grpc_api_addr,
-        graphql_api_addr: config.graphql_api_addr,
-        metrics_api_addr: config.metrics_api_addr,
-        storage: StorageConfiguration::RocksDB(Some(config.db_path)),
-        network_bootstrap_timeout: Duration::from_secs(90),
-        minimum_cluster_size: config
-            .minimum_tce_cluster_size
-            .unwrap_or(NetworkConfig::MINIMUM_CLUSTER_SIZE),
-        version: env!("TOPOS_VERSION"),
-    };
-
-    debug!("TCE args: {tce_config:?}");
     spawn(async move {
+        let (api_access, allowed_source_subnets, allowed_peers) = config
+            .parse_api_access()
+            .and_then(|api_access| {
+                Ok((
+                    api_access,
+                    config.parse_allowed_source_subnets()?,
+                    config.parse_allowed_peers()?,
+                ))
+            })
+            .map_err(|e| {
+                error!("Invalid TCE configuration: {e}");
+                Errors::InvalidTceConfiguration(e)
+            })?;
+
+        let tce_config = TceConfiguration {
+            boot_peers: genesis
+                .boot_peers(Some(topos_p2p::constants::TCE_BOOTNODE_PORT))
+                .into_iter()
+                .chain(config.parse_boot_peers())
+                .collect::<Vec<_>>(),
+            validators,
+            auth_key: keys.network.map(AuthKey::PrivateKey),
+            signing_key: keys.validator.map(AuthKey::PrivateKey),
+            tce_addr: format!("/ip4/{}", config.libp2p_api_addr.ip()),
+            tce_local_port: config.libp2p_api_addr.port(),
+            tce_quic_local_port: config.libp2p_quic_addr.map(|addr| addr.port()),
+            tce_params,
+            api_addr: config.grpc_api_addr,
+            graphql_api_addr: config.graphql_api_addr,
+            rest_api_addr: config.rest_api_addr,
+            metrics_api_addr: config.metrics_api_addr,
+            storage: if config.in_memory_storage {
+                StorageConfiguration::RAM
+            } else {
+                StorageConfiguration::RocksDB(Some(config.db_path.clone()))
+            },
+            storage_mode: match config.pruning_retained_certificates {
+                Some(retained_certificates) => StorageMode::Pruned {
+                    retained_certificates,
+                },
+                None => StorageMode::Archive,
+            },
+            network_bootstrap_timeout: Duration::from_secs(90),
+            minimum_cluster_size: config
+                .minimum_tce_cluster_size
+                .unwrap_or(NetworkConfig::MINIMUM_CLUSTER_SIZE),
+            max_concurrent_grpc_requests_per_stream: config
+                .max_concurrent_grpc_requests_per_stream
+                .unwrap_or(LimitsConfig::MAX_CONCURRENT_GRPC_REQUESTS_PER_STREAM),
+            sync_from_snapshot: config.sync_from_snapshot,
+            max_missing_certificates: config
+                .max_missing_certificates
+                .unwrap_or(topos_tce::AppContext::DEFAULT_MAX_MISSING_CERTIFICATES),
+            allowed_peers,
+            sentry_peers: config.parse_sentry_peers(),
+            admin_token: config.admin_token.clone(),
+            admin_api_addr: config.admin_api_addr,
+            allowed_source_subnets,
+            api_access,
+            tls_certificate: config.tls_certificate.clone(),
+            tls_key: config.tls_key.clone(),
+            tls_client_ca: config.tls_client_ca.clone(),
+            log_filter: Some(log_filter),
+            version: env!("TOPOS_VERSION"),
+        };
+
+        debug!("TCE args: {tce_config:?}");
         topos_tce::run(&tce_config, shutdown).await.map_err(|e| {
             error!("TCE process terminated: {e:?}");
             Errors::TceFailure
@@ -1,4 +1,5 @@
 use std::{
+    fmt::Display,
     future::Future,
     io::Error,
     pin::Pin,
@@ -6,14 +7,168 @@ use std::{
 };
 
 use futures::FutureExt;
-use topos_core::api::grpc::tce::v1::StatusRequest;
+use serde::Serialize;
+use topos_core::{
+    api::grpc::tce::v1::{NodeRole, StatusRequest, StatusResponse, SyncState},
+    uci::SubnetId,
+};
 use tower::Service;
 use tracing::{debug, error};
 
 use crate::components::node::{commands::Status, NodeService};
 
+/// Status of a node as rendered by `topos node status`
+#[derive(Debug, Serialize)]
+pub(crate) struct NodeStatus {
+    pub(crate) role: &'static str,
+    pub(crate) validator_id: String,
+    pub(crate) peer_id: String,
+    pub(crate) version: String,
+    pub(crate) epoch: u64,
+    pub(crate) connected_peers: u64,
+    pub(crate) delivered_certificates: u64,
+    pub(crate) pending_certificates: u64,
+    pub(crate) precedence_pool_certificates: u64,
+    pub(crate) active_broadcast_tasks: u64,
+    pub(crate) has_active_sample: bool,
+    pub(crate) sync: Option<SyncStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct SyncStatus {
+    pub(crate) state: &'static str,
+    pub(crate) missing_certificates: u64,
+    pub(crate) subnets: Vec<SubnetSyncStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct SubnetSyncStatus {
+    pub(crate) subnet_id: String,
+    pub(crate) local_position: Option<u64>,
+    pub(crate) best_known_position: Option<u64>,
+    pub(crate) missing_certificates: u64,
+}
+
+impl From<StatusResponse> for NodeStatus {
+    fn from(status: StatusResponse) -> Self {
+        Self {
+            role: match status.role() {
+                NodeRole::Validator => "validator",
+                NodeRole::FullNode => "full node",
+                NodeRole::Unspecified => "unknown",
+            },
+            sync: status.sync_status.map(|sync_status| {
+                let state = match sync_status.state() {
+                    SyncState::Syncing => "syncing",
+                    SyncState::Synced => "synced",
+                    SyncState::Stalled => "stalled",
+                    SyncState::Unspecified => "unknown",
+                };
+
+                let mut subnets: Vec<SubnetSyncStatus> = sync_status
+                    .subnets
+                    .into_iter()
+                    .map(|subnet| SubnetSyncStatus {
+                        subnet_id: subnet
+                            .subnet_id
+                            .map(|subnet_id| {
+                                SubnetId::try_from(subnet_id.clone())
+                                    .map_or_else(|_| subnet_id.to_string(), |id| id.to_string())
+                            })
+                            .unwrap_or_default(),
+                        local_position: subnet.local_position,
+                        best_known_position: subnet.best_known_position,
+                        missing_certificates: subnet.missing_certificates,
+                    })
+                    .collect();
+                subnets.sort_by(|a, b| a.subnet_id.cmp(&b.subnet_id));
+
+                SyncStatus {
+                    state,
+                    missing_certificates: subnets
+                        .iter()
+                        .map(|subnet| subnet.missing_certificates)
+                        .sum(),
+                    subnets,
+                }
+            }),
+            validator_id: status.validator_id,
+            peer_id: status.peer_id,
+            version: status.version,
+            epoch: status.epoch,
+            connected_peers: status.connected_peers,
+            delivered_certificates: status.delivered_certificates,
+            pending_certificates: status.pending_certificates,
+            precedence_pool_certificates: status.precedence_pool_certificates,
+            active_broadcast_tasks: status.active_broadcast_tasks,
+            has_active_sample: status.has_active_sample,
+        }
+    }
+}
+
+impl Display for NodeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Role:                         {}", self.role)?;
+        writeln!(f, "Validator id:                 {}", self.validator_id)?;
+        writeln!(f, "Peer id:                      {}", self.peer_id)?;
+        writeln!(f, "Version:                      {}", self.version)?;
+        writeln!(f, "Epoch:                        {}", self.epoch)?;
+        writeln!(f, "Connected peers:              {}", self.connected_peers)?;
+        writeln!(
+            f,
+            "Active sample:                {}",
+            self.has_active_sample
+        )?;
+        writeln!(
+            f,
+            "Delivered certificates:       {}",
+            self.delivered_certificates
+        )?;
+        writeln!(
+            f,
+            "Pending certificates:         {}",
+            self.pending_certificates
+        )?;
+        writeln!(
+            f,
+            "Precedence pool certificates: {}",
+            self.precedence_pool_certificates
+        )?;
+        writeln!(
+            f,
+            "Active broadcast tasks:       {}",
+            self.active_broadcast_tasks
+        )?;
+
+        let Some(sync) = &self.sync else {
+            return writeln!(f, "Sync state:                   not started");
+        };
+
+        writeln!(
+            f,
+            "Sync state:                   {} ({} certificates behind)",
+            sync.state, sync.missing_certificates
+        )?;
+        for subnet in &sync.subnets {
+            let position = |position: Option<u64>| {
+                position.map_or_else(|| "-".to_string(), |position| position.to_string())
+            };
+
+            writeln!(
+                f,
+                "  {}: {} / {}",
+                subnet.subnet_id,
+                position(subnet.local_position),
+                position(subnet.best_known_position)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Service<Status> for NodeService {
-    type Response = bool;
+    type Response = NodeStatus;
 
     type Error = std::io::Error;
 
@@ -33,7 +188,7 @@ impl Service<Status> for NodeService {
                 Ok(status_response) => {
                     let status = status_response.into_inner();
                     debug!("Successfully fetched the status {:?} from the TCE", status);
-                    Ok(status.has_active_sample)
+                    Ok(status.into())
                 }
                 Err(err) => {
                     error!("TCE server returned an error: {:?}", err);
@@ -44,3 +199,48 @@ impl Service<Status> for NodeService {
         .boxed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use topos_core::api::grpc::tce::v1::{
+        NodeRole, StatusResponse, SubnetSyncStatus, SyncState, SyncStatus,
+    };
+    use topos_test_sdk::constants::SOURCE_SUBNET_ID_1;
+
+    use super::NodeStatus;
+
+    #[test]
+    fn render_node_status() {
+        let status: NodeStatus = StatusResponse {
+            has_active_sample: true,
+            role: NodeRole::Validator.into(),
+            peer_id: "peer".into(),
+            delivered_certificates: 10,
+            sync_status: Some(SyncStatus {
+                state: SyncState::Syncing.into(),
+                subnets: vec![SubnetSyncStatus {
+                    subnet_id: Some(SOURCE_SUBNET_ID_1.into()),
+                    local_position: Some(9),
+                    best_known_position: Some(14),
+                    missing_certificates: 5,
+                }],
+            }),
+            ..Default::default()
+        }
+        .into();
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["role"], "validator");
+        assert_eq!(json["delivered_certificates"], 10);
+        assert_eq!(json["sync"]["state"], "syncing");
+        assert_eq!(json["sync"]["missing_certificates"], 5);
+        assert_eq!(
+            json["sync"]["subnets"][0]["subnet_id"],
+            SOURCE_SUBNET_ID_1.to_string()
+        );
+
+        let rendered = status.to_string();
+        assert!(rendered.contains("Role:                         validator"));
+        assert!(rendered.contains("Sync state:                   syncing (5 certificates behind)"));
+    }
+}
@@ -57,7 +57,7 @@ pub(crate) async fn handle_command(
 
             // Setup instrumentation if both otlp agent and otlp service name
             // are provided as arguments
-            let basic_controller =
+            let (basic_controller, _) =
                 setup_tracing(verbose, false, cmd.otlp_agent, cmd.otlp_service_name)?;
 
             let (shutdown_sender, shutdown_receiver) = mpsc::channel::<oneshot::Sender<()>>(1);
@@ -87,7 +87,8 @@ pub(crate) async fn check_certificate_delivery(
                             target_subnet_ids: vec![[2u8; SUBNET_ID_LENGTH].into()],
                             positions: vec![]
                         }),
-                        source_checkpoint: None
+                        source_checkpoint: None,
+                        ..Default::default()
                     }.into()
                 };
 
@@ -51,6 +51,12 @@ impl NodeConfig {
             config.db_path = home.join(&config.db_path);
         }
 
+        // Make the sequencer outbox and messages paths relative to the folder
+        if let Some(config) = config.sequencer.as_mut() {
+            config.outbox_path = home.join(&config.outbox_path);
+            config.messages_path = home.join(&config.messages_path);
+        }
+
         config
     }
 }
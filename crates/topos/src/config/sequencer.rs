@@ -1,11 +1,13 @@
-use std::path::Path;
-
 use crate::config::Config;
 use figment::{
     providers::{Format, Toml},
     Figment,
 };
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use topos_sequencer::{BatchingPolicy, PushPipelineConfig, DEFAULT_MESSAGES_RETENTION};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
@@ -27,10 +29,24 @@ pub struct SequencerConfig {
     #[serde(default = "default_subnet_contract_address")]
     pub subnet_contract_address: String,
 
-    /// gRPC API endpoint of one TCE process
+    /// gRPC API endpoints of TCE processes, comma separated, by order of preference
+    /// The sequencer fails over to the next healthy endpoint when the current one fails
     #[serde(default = "default_tce_grpc_endpoint")]
     pub tce_grpc_endpoint: String,
 
+    /// PEM file of the CA verifying the `https` TCE endpoints
+    /// Default is to use the native root certificates
+    pub tce_ca_certificate: Option<PathBuf>,
+
+    /// PEM file of the certificate presented to the TCE nodes which authenticate their clients
+    pub tce_client_certificate: Option<PathBuf>,
+
+    /// PEM file of the private key of the certificate presented to the TCE nodes
+    pub tce_client_key: Option<PathBuf>,
+
+    /// API key presented to the TCE nodes restricting the access to their public APIs
+    pub tce_api_key: Option<String>,
+
     /// OTLP agent endpoint, not used if not provided
     pub otlp_agent: Option<String>,
 
@@ -40,6 +56,76 @@ pub struct SequencerConfig {
     /// Start synchronizing from particular block number
     /// Default is to sync from genesis block (0)
     pub start_block: Option<u64>,
+
+    /// Number of blocks covered by a single certificate
+    /// Default is to generate one certificate per block
+    pub certificate_batch_blocks: Option<u64>,
+
+    /// Time window, in seconds, covered by a single certificate
+    pub certificate_batch_window: Option<u64>,
+
+    /// Only generate certificates for the blocks emitting cross-subnet events
+    #[serde(default)]
+    pub certificate_cross_subnet_events_only: bool,
+
+    /// Number of blocks to wait on top of a block before certifying it
+    /// Default is to certify blocks as soon as they are received
+    #[serde(default)]
+    pub finality_depth: u64,
+
+    /// Path of the file keeping track of the certificates until their delivery
+    #[serde(default = "default_outbox_path")]
+    pub outbox_path: PathBuf,
+
+    /// Path of the file retaining the cross-subnet messages of the certificates
+    #[serde(default = "default_messages_path")]
+    pub messages_path: PathBuf,
+
+    /// Number of certificates whose cross-subnet messages are retained
+    #[serde(default = "default_messages_retention")]
+    pub messages_retention: usize,
+
+    /// Socket address of the API serving the cross-subnet messages and their inclusion proofs
+    /// The API is not served if not provided
+    pub api_addr: Option<SocketAddr>,
+
+    /// Maximum number of certificate push transactions waiting to be mined on the subnet
+    #[serde(default = "default_max_pending_pushes")]
+    pub max_pending_pushes: usize,
+
+    /// Push certificates to the subnet with EIP-1559 transactions instead of legacy ones
+    #[serde(default)]
+    pub eip1559_pushes: bool,
+}
+
+impl SequencerConfig {
+    pub fn tce_grpc_endpoints(&self) -> Vec<String> {
+        self.tce_grpc_endpoint
+            .split(',')
+            .map(|endpoint| endpoint.trim().to_string())
+            .filter(|endpoint| !endpoint.is_empty())
+            .collect()
+    }
+
+    pub fn batching_policy(&self) -> BatchingPolicy {
+        if self.certificate_cross_subnet_events_only {
+            BatchingPolicy::CrossSubnetEventsOnly
+        } else if let Some(blocks) = self.certificate_batch_blocks {
+            BatchingPolicy::Blocks(blocks)
+        } else if let Some(window) = self.certificate_batch_window {
+            BatchingPolicy::TimeWindow(Duration::from_secs(window))
+        } else {
+            BatchingPolicy::EveryBlock
+        }
+    }
+
+    pub fn push_pipeline(&self) -> PushPipelineConfig {
+        PushPipelineConfig {
+            max_in_flight: self.max_pending_pushes,
+            eip1559: self.eip1559_pushes,
+            ..Default::default()
+        }
+    }
 }
 
 fn default_subnet_jsonrpc_endpoint() -> String {
@@ -50,6 +136,22 @@ fn default_subnet_contract_address() -> String {
     "0x0000000000000000000000000000000000000000".to_string()
 }
 
+fn default_outbox_path() -> PathBuf {
+    PathBuf::from("./sequencer_outbox.json")
+}
+
+fn default_messages_path() -> PathBuf {
+    PathBuf::from("./sequencer_messages.json")
+}
+
+fn default_messages_retention() -> usize {
+    DEFAULT_MESSAGES_RETENTION
+}
+
+fn default_max_pending_pushes() -> usize {
+    PushPipelineConfig::default().max_in_flight
+}
+
 fn default_tce_grpc_endpoint() -> String {
     "http://[::1]:1340".to_string()
 }
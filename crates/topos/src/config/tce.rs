@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::Path;
 use std::{net::SocketAddr, path::PathBuf};
 
@@ -8,18 +9,81 @@ use figment::{
 use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
+use topos_core::uci::SubnetId;
 use topos_p2p::{Multiaddr, PeerId};
+use topos_tce::config::{ApiAccess, ApiKeys, RateLimit};
 
 const DEFAULT_IP: std::net::Ipv4Addr = std::net::Ipv4Addr::new(0, 0, 0, 0);
 
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No API key found in api-keys, remove it to accept every client")]
+    NoApiKey,
+
+    #[error("Invalid subnet id {0} in allowed-source-subnets")]
+    InvalidSourceSubnet(String),
+
+    #[error("Invalid peer id {0} in allowed-peers")]
+    InvalidAllowedPeer(String),
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct TceConfig {
     /// Storage database path, if not set RAM storage is used
     #[serde(default = "default_db_path")]
     pub db_path: PathBuf,
+    /// Keep the storage in memory instead of using the database path, nothing
+    /// survives a restart of the node
+    #[serde(default)]
+    pub in_memory_storage: bool,
+    /// Number of delivered certificates kept per source stream, older ones are pruned.
+    /// Every certificate is kept (archive mode) if not set
+    pub pruning_retained_certificates: Option<u64>,
+    /// Bootstrap an empty storage from the snapshot of a peer, verified against its proofs
+    /// of delivery, instead of syncing it checkpoint by checkpoint
+    #[serde(default)]
+    pub sync_from_snapshot: bool,
+    /// Number of certificates the node can miss, compared to its peers, while still
+    /// accepting the submission of new certificates
+    pub max_missing_certificates: Option<u64>,
     /// Array of extra boot nodes to connect to
     pub extra_boot_peers: Option<String>,
+    /// Comma separated list of PeerIds allowed to connect, enables the gating mode if set.
+    /// Members of the validator set are allowed by presenting a validator proof.
+    pub allowed_peers: Option<String>,
+    /// Array of sentry peers, always receiving the gossip of this node, same format as
+    /// the extra boot peers
+    pub sentry_peers: Option<String>,
+    /// Token of the admin gRPC service, the service is disabled if not set
+    pub admin_token: Option<String>,
+    /// Admin gRPC service Addr, only reachable from the host by default
+    #[serde(default = "default_admin_api_addr")]
+    pub admin_api_addr: SocketAddr,
+    /// Comma separated list of SubnetIds from which certificates can be submitted,
+    /// every subnet is accepted if not set
+    pub allowed_source_subnets: Option<String>,
+    /// Comma separated list of API keys expected from the clients of the public APIs, sent in
+    /// the `x-api-key` header or as a bearer token. Every client is accepted if not set
+    pub api_keys: Option<String>,
+    /// Number of certificates a client can submit per minute, the client being identified by
+    /// its API key or by its IP address. Unlimited if not set
+    pub submission_rate_limit_per_client: Option<u32>,
+    /// Number of certificates that can be submitted per minute for a source subnet, unlimited
+    /// if not set
+    pub submission_rate_limit_per_subnet: Option<u32>,
+    /// Maximum depth of the GraphQL queries, unlimited if not set
+    pub graphql_max_depth: Option<usize>,
+    /// Maximum complexity of the GraphQL queries, unlimited if not set
+    pub graphql_max_complexity: Option<usize>,
+    /// PEM file of the certificate chain of the gRPC, GraphQL and REST APIs, which are served
+    /// over TLS if set along with the key
+    pub tls_certificate: Option<PathBuf>,
+    /// PEM file of the private key of the gRPC, GraphQL and REST APIs
+    pub tls_key: Option<PathBuf>,
+    /// PEM file of the CA authenticating the clients of the APIs served over TLS (mTLS),
+    /// the clients don't present a certificate if not set
+    pub tls_client_ca: Option<PathBuf>,
     /// Ip for the p2p Multiaddr
     pub tce_ext_host: Option<String>,
     /// Port for the p2p Multiaddr
@@ -28,15 +92,21 @@ pub struct TceConfig {
     pub local_key_seed: Option<String>,
     /// Connection degree for the GossipSub overlay
     pub minimum_tce_cluster_size: Option<usize>,
+    /// Maximum number of concurrent requests served on a single gRPC-over-p2p stream
+    pub max_concurrent_grpc_requests_per_stream: Option<usize>,
     /// gRPC API Addr
     #[serde(default = "default_libp2p_api_addr")]
     pub libp2p_api_addr: SocketAddr,
+    /// QUIC p2p Addr, the QUIC transport is only enabled if set
+    pub libp2p_quic_addr: Option<SocketAddr>,
     /// gRPC API Addr
     #[serde(default = "default_grpc_api_addr")]
     pub grpc_api_addr: SocketAddr,
     /// GraphQL API Addr
     #[serde(default = "default_graphql_api_addr")]
     pub graphql_api_addr: SocketAddr,
+    /// REST gateway Addr, the gateway is only served if set
+    pub rest_api_addr: Option<SocketAddr>,
     /// Metrics server API Addr
     #[serde(default = "default_metrics_api_addr")]
     pub metrics_api_addr: SocketAddr,
@@ -60,6 +130,13 @@ const fn default_grpc_api_addr() -> SocketAddr {
     SocketAddr::V4(std::net::SocketAddrV4::new(DEFAULT_IP, 1340))
 }
 
+const fn default_admin_api_addr() -> SocketAddr {
+    SocketAddr::V4(std::net::SocketAddrV4::new(
+        std::net::Ipv4Addr::LOCALHOST,
+        1341,
+    ))
+}
+
 const fn default_graphql_api_addr() -> SocketAddr {
     SocketAddr::V4(std::net::SocketAddrV4::new(DEFAULT_IP, 4030))
 }
@@ -70,7 +147,81 @@ const fn default_metrics_api_addr() -> SocketAddr {
 
 impl TceConfig {
     pub fn parse_boot_peers(&self) -> Vec<(PeerId, Multiaddr)> {
-        self.extra_boot_peers
+        Self::parse_peers(&self.extra_boot_peers)
+    }
+
+    pub fn parse_sentry_peers(&self) -> Vec<(PeerId, Multiaddr)> {
+        Self::parse_peers(&self.sentry_peers)
+    }
+
+    pub fn parse_allowed_peers(&self) -> Result<Option<HashSet<PeerId>>, Error> {
+        self.allowed_peers
+            .as_ref()
+            .map(|peers| {
+                peers
+                    .split(&[',', ' '])
+                    .filter(|peer| !peer.is_empty())
+                    .map(|peer| {
+                        peer.parse()
+                            .map_err(|_| Error::InvalidAllowedPeer(peer.to_string()))
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
+
+    pub fn parse_allowed_source_subnets(&self) -> Result<Option<HashSet<SubnetId>>, Error> {
+        self.allowed_source_subnets
+            .as_ref()
+            .map(|subnets| {
+                subnets
+                    .split(&[',', ' '])
+                    .filter(|subnet| !subnet.is_empty())
+                    .map(|subnet| {
+                        subnet
+                            .parse()
+                            .map_err(|_| Error::InvalidSourceSubnet(subnet.to_string()))
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
+
+    pub fn parse_api_access(&self) -> Result<ApiAccess, Error> {
+        let api_keys = self
+            .api_keys
+            .as_ref()
+            .map(|keys| {
+                let keys: HashSet<String> = keys
+                    .split(&[',', ' '])
+                    .filter(|key| !key.is_empty())
+                    .map(ToString::to_string)
+                    .collect();
+
+                // An empty set of keys would reject every client
+                if keys.is_empty() {
+                    return Err(Error::NoApiKey);
+                }
+
+                Ok(ApiKeys::new(keys))
+            })
+            .transpose()?;
+
+        Ok(ApiAccess {
+            api_keys,
+            submission_rate_limit_per_client: self
+                .submission_rate_limit_per_client
+                .map(RateLimit::per_minute),
+            submission_rate_limit_per_subnet: self
+                .submission_rate_limit_per_subnet
+                .map(RateLimit::per_minute),
+            graphql_max_depth: self.graphql_max_depth,
+            graphql_max_complexity: self.graphql_max_complexity,
+        })
+    }
+
+    fn parse_peers(peers: &Option<String>) -> Vec<(PeerId, Multiaddr)> {
+        peers
             .clone()
             .unwrap_or_default()
             .split(&[',', ' '])
@@ -5,9 +5,10 @@ use opentelemetry::trace::TracerProvider;
 use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::{SpanExporterBuilder, WithExportConfig};
 use std::time::Duration;
+use topos_tce::config::LogFilterHandle;
 use tracing::Level;
 use tracing_subscriber::{
-    prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
+    prelude::__tracing_subscriber_SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Layer,
 };
 
 fn verbose_to_level(verbose: u8) -> Level {
@@ -60,16 +61,30 @@ fn create_filter(verbose: u8) -> EnvFilter {
 
 // Setup tracing
 // If otlp agent and otlp service name are provided, opentelemetry collection will be used
+// The returned handle replaces the filter of the logs at runtime
 pub(crate) fn setup_tracing(
     verbose: u8,
     no_color: bool,
     otlp_agent: Option<String>,
     otlp_service_name: Option<String>,
-) -> Result<Option<BasicController>, Box<dyn std::error::Error>> {
+) -> Result<(Option<BasicController>, LogFilterHandle), Box<dyn std::error::Error>> {
     let mut layers = Vec::new();
 
     let ansi = !no_color;
 
+    let (log_filter, log_filter_handle) = reload::Layer::new(create_filter(verbose));
+    let log_filter_handle = LogFilterHandle::new(move |directives| {
+        let filter = EnvFilter::try_new(directives).map_err(|error| error.to_string())?;
+        let previous = log_filter_handle
+            .with_current(ToString::to_string)
+            .map_err(|error| error.to_string())?;
+        log_filter_handle
+            .reload(filter)
+            .map_err(|error| error.to_string())?;
+
+        Ok(previous)
+    });
+
     layers.push(
         match std::env::var("TOPOS_LOG_FORMAT")
             .map(|f| f.to_lowercase())
@@ -79,17 +94,17 @@ pub(crate) fn setup_tracing(
             Ok("json") => tracing_subscriber::fmt::layer()
                 .json()
                 .with_ansi(ansi)
-                .with_filter(create_filter(verbose))
+                .with_filter(log_filter)
                 .boxed(),
             Ok("pretty") => tracing_subscriber::fmt::layer()
                 .pretty()
                 .with_ansi(ansi)
-                .with_filter(create_filter(verbose))
+                .with_filter(log_filter)
                 .boxed(),
             _ => tracing_subscriber::fmt::layer()
                 .compact()
                 .with_ansi(ansi)
-                .with_filter(create_filter(verbose))
+                .with_filter(log_filter)
                 .boxed(),
         },
     );
@@ -212,5 +227,5 @@ pub(crate) fn setup_tracing(
 
     tracing_subscriber::registry().with(layers).try_init()?;
 
-    Ok(metrics)
+    Ok((metrics, log_filter_handle))
 }
@@ -141,7 +141,8 @@ async fn cert_delivery() {
                     target_subnet_ids: vec![in_stream_subnet_id.into()],
                     positions: Vec::new()
                 }),
-                source_checkpoint: None
+                source_checkpoint: None,
+                ..Default::default()
             }.into();
         };
 
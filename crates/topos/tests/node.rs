@@ -1,8 +1,13 @@
 mod utils;
 
-use std::process::Command;
+use std::{collections::HashSet, process::Command, time::Duration};
 
 use assert_cmd::prelude::*;
+use rstest::rstest;
+use tokio::sync::broadcast;
+use topos_tce_api::{ApiAccess, ApiKeys, Runtime};
+use topos_tce_storage::StorageClient;
+use topos_test_sdk::{networking::get_available_addr, storage::create_validator_store};
 
 #[test]
 fn help_display() -> Result<(), Box<dyn std::error::Error>> {
@@ -17,3 +22,41 @@ fn help_display() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(20))]
+async fn status_presents_the_api_key() -> Result<(), Box<dyn std::error::Error>> {
+    let grpc_addr = get_available_addr();
+    let store = create_validator_store::default().await;
+    let (_, broadcast_stream) = broadcast::channel(10);
+    let (_client, _events, _context) = Runtime::builder()
+        .with_broadcast_stream(broadcast_stream)
+        .storage(StorageClient::new(store.clone()))
+        .store(store)
+        .serve_grpc_addr(grpc_addr)
+        .serve_graphql_addr(get_available_addr())
+        .serve_rest_addr(get_available_addr())
+        .serve_metrics_addr(get_available_addr())
+        .with_api_access(ApiAccess {
+            api_keys: Some(ApiKeys::new(HashSet::from(["secret-api-key".to_string()]))),
+            ..Default::default()
+        })
+        .build_and_launch()
+        .await;
+
+    let node = format!("http://{grpc_addr}");
+    tokio::task::spawn_blocking(move || {
+        let mut cmd = Command::cargo_bin("topos").unwrap();
+        cmd.args(["node", "status", "--node", &node]);
+        cmd.assert().failure();
+
+        let mut cmd = Command::cargo_bin("topos").unwrap();
+        cmd.args(["node", "status", "--node", &node])
+            .env("TOPOS_NODE_API_KEY", "secret-api-key");
+        cmd.assert().success();
+    })
+    .await?;
+
+    Ok(())
+}